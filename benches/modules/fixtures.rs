@@ -0,0 +1,61 @@
+// Inputs for the scenarios, a child module of `./main.rs`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub struct Fixtures {
+    // This package's own `src/lib.rs`.
+    pub this_crate: PathBuf,
+    // A generated crate with a wide, deep module tree.
+    pub generated: GeneratedCrate,
+}
+
+impl Fixtures {
+    pub fn new() -> Fixtures {
+        Fixtures {
+            this_crate: Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs"),
+            generated: GeneratedCrate::new(8, 3),
+        }
+    }
+}
+
+// A crate in a temporary directory where every module has `width` children,
+// `depth` levels down. Removed on drop.
+pub struct GeneratedCrate {
+    dir: PathBuf,
+}
+
+impl GeneratedCrate {
+    fn new(width: usize, depth: usize) -> GeneratedCrate {
+        let dir = std::env::temp_dir()
+            .join(format!("rust-module-example-bench-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        write_module(&dir.join("src"), "lib.rs", width, depth);
+        GeneratedCrate { dir }
+    }
+
+    pub fn root(&self) -> PathBuf {
+        self.dir.join("src/lib.rs")
+    }
+}
+
+impl Drop for GeneratedCrate {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+// Write `dir/file` declaring `width` children, and the children's files in
+// the directory they're loaded from.
+fn write_module(dir: &Path, file: &str, width: usize, depth: usize) {
+    fs::create_dir_all(dir).unwrap();
+    let mut source = String::from("pub fn f() {}\n");
+    if depth > 0 {
+        for i in 0..width {
+            source.push_str(&format!("pub mod m{i};\nuse m{i}::f as f{i};\n"));
+            let child_dir = if file == "lib.rs" { dir.to_path_buf() } else { dir.join(file.trim_end_matches(".rs")) };
+            write_module(&child_dir, &format!("m{i}.rs"), width, depth - 1);
+        }
+    }
+    fs::write(dir.join(file), source).unwrap();
+}
@@ -0,0 +1,31 @@
+// # Benchmarks
+
+// Cargo builds each benchmark as a crate of its own, like integration tests,
+// and runs them with `cargo bench`. This one is the directory form,
+// `benches/modules/main.rs`, so its child modules can sit next to it:
+// `mod fixtures;` loads `./fixtures.rs`. Had it been `benches/modules.rs`,
+// its children would be `benches/fixtures.rs` and so on, which Cargo would
+// take for more benchmarks.
+
+// The timing is done by the `criterion` crate, which runs each scenario
+// until the measurement is steady and compares it with the last run. It
+// brings its own `main`, so `Cargo.toml` sets `harness = false` rather than
+// using the unstable built-in benchmark harness.
+
+mod fixtures;
+mod scenarios;
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+// `cargo bench -- NAME` runs only the scenarios whose names contain NAME.
+fn benchmarks(c: &mut Criterion) {
+    let fixtures = fixtures::Fixtures::new();
+    for scenario in scenarios::all() {
+        c.bench_function(scenario.name, |b| b.iter(|| black_box((scenario.run)(&fixtures))));
+    }
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
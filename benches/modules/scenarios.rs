@@ -0,0 +1,42 @@
+// What's measured, a child module of `./main.rs`. Each scenario reads its
+// input from `super::fixtures`.
+
+use modules_demo::modtree::{Format, ModuleTree};
+use modules_demo::registry;
+
+use super::fixtures::Fixtures;
+
+pub struct Scenario {
+    pub name: &'static str,
+    pub run: fn(&Fixtures) -> usize,
+}
+
+pub fn all() -> Vec<Scenario> {
+    vec![
+        Scenario { name: "load this crate", run: load_this_crate },
+        Scenario { name: "load generated crate", run: load_generated },
+        Scenario { name: "write tree as json", run: write_json },
+        Scenario { name: "find every example", run: find_examples },
+    ]
+}
+
+fn load_this_crate(fixtures: &Fixtures) -> usize {
+    ModuleTree::load(&fixtures.this_crate).unwrap().ids().count()
+}
+
+fn load_generated(fixtures: &Fixtures) -> usize {
+    let tree = ModuleTree::load(&fixtures.generated.root()).unwrap();
+    assert_eq!(tree.ids().count(), 1 + 8 + 8 * 8 + 8 * 8 * 8, "{:?}", tree.problems);
+    tree.ids().count()
+}
+
+fn write_json(fixtures: &Fixtures) -> usize {
+    let tree = ModuleTree::load(&fixtures.this_crate).unwrap();
+    let mut out = Vec::new();
+    Format::Json.write(&tree, &mut out).unwrap();
+    out.len()
+}
+
+fn find_examples(_: &Fixtures) -> usize {
+    registry::EXAMPLES.iter().filter(|e| registry::find(e.name()).is_some()).count()
+}
@@ -0,0 +1,83 @@
+// A build script: Cargo compiles and runs this before building the package,
+// and it can generate source code for the crate to include. See
+// `src/generated.rs` and `src/catalog.rs`, which include what this writes.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+// A build script is a crate root like any other, so it can have modules. This
+// one is shared with the library's tests, see the file.
+#[path = "build/spec.rs"]
+mod spec;
+
+// The platforms with a module in `src/`, see `mod platform` in `src/lib.rs`:
+// the `target_os` or `target_arch` value, the family, and the module's file.
+const PLATFORMS: &[(&str, &str, &str)] = &[
+    ("linux", "unix", "linux.rs"),
+    ("macos", "unix", "macos.rs"),
+    ("windows", "windows", "windows.rs"),
+    ("wasm32", "wasm", "wasm.rs"),
+];
+
+fn main() {
+    // Build scripts learn about the target from environment variables.
+    let target = env::var("TARGET").unwrap();
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+
+    let mut code = String::from("// Generated by `build.rs`. Don't edit: changes are overwritten on every build.\n\n");
+    writeln!(code, "pub const TARGET: &str = {target:?};").unwrap();
+    writeln!(code, "pub const TARGET_OS: &str = {target_os:?};").unwrap();
+    writeln!(code, "pub const TARGET_ARCH: &str = {target_arch:?};\n").unwrap();
+    writeln!(code, "pub const PLATFORMS: &[Platform] = &[").unwrap();
+    for (name, family, file) in PLATFORMS {
+        writeln!(code, "    Platform {{ name: {name:?}, family: {family:?}, file: {file:?} }},").unwrap();
+    }
+    writeln!(code, "];").unwrap();
+
+    // Generated files go in `OUT_DIR`, a directory Cargo gives each build
+    // script. Never write into `src/`.
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("generated.rs"), code).unwrap();
+
+    let spec = fs::read_to_string("spec/examples.toml").unwrap();
+    let entries = spec::parse(&spec).unwrap_or_else(|e| panic!("spec/examples.toml: {e}"));
+    fs::write(Path::new(&out_dir).join("catalog.rs"), catalog(&entries)).unwrap();
+
+    // Only rerun when these files change, rather than when anything in the
+    // package does, which is the default.
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=build/spec.rs");
+    println!("cargo:rerun-if-changed=spec/examples.toml");
+}
+
+// The source of the `catalog` module: every example's metadata from the spec
+// as constants, and the list of examples for the registry.
+fn catalog(entries: &[spec::Entry]) -> String {
+    let mut code = String::from("// Generated by `build.rs` from `spec/examples.toml`. Don't edit.\n\n");
+
+    writeln!(code, "pub const ENTRIES: &[Entry] = &[").unwrap();
+    for entry in entries {
+        let enabled = match &entry.feature {
+            Some(feature) => format!("cfg!(feature = {feature:?})"),
+            None => "true".to_string(),
+        };
+        writeln!(code, "    Entry {{ name: {:?}, topic: Topic::{}, feature: {:?}, enabled: {enabled} }},",
+                 entry.name, entry.topic, entry.feature).unwrap();
+    }
+    writeln!(code, "];\n").unwrap();
+
+    // The registration glue. Examples behind a feature get a `#[cfg]`, or
+    // their paths wouldn't resolve when it's off.
+    writeln!(code, "pub static EXAMPLES: &[&dyn Example] = &[").unwrap();
+    for entry in entries {
+        if let Some(feature) = &entry.feature {
+            writeln!(code, "    #[cfg(feature = {feature:?})]").unwrap();
+        }
+        writeln!(code, "    &{},", entry.item).unwrap();
+    }
+    writeln!(code, "];").unwrap();
+    code
+}
@@ -0,0 +1,77 @@
+// Parsing `spec/examples.toml`.
+
+// This file is a module of two crates: the build script declares it with
+// `#[path = "build/spec.rs"] mod spec;`, and the library's tests in
+// `src/catalog.rs` declare it again, to check the generated code against the
+// spec. Each crate compiles its own copy.
+
+// It reads only the subset of TOML the spec uses, rather than depending on
+// the `toml` crate: comments, blank lines, `[[example]]` headers, and
+// `key = "string"` lines.
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Entry {
+    pub name: String,
+    pub topic: String,
+    pub item: String,
+    pub feature: Option<String>,
+}
+
+pub fn parse(text: &str) -> Result<Vec<Entry>, String> {
+    let mut entries: Vec<Entry> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let error = |message: &str| format!("line {}: {message}", i + 1);
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line == "[[example]]" {
+            entries.push(Entry::default());
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(|| error("expected `key = \"value\"`"))?;
+        let value = value.trim()
+            .strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+            .ok_or_else(|| error("expected a string in double quotes"))?
+            .to_string();
+        let entry = entries.last_mut().ok_or_else(|| error("expected `[[example]]` first"))?;
+        match key.trim() {
+            "name" => entry.name = value,
+            "topic" => entry.topic = value,
+            "item" => entry.item = value,
+            "feature" => entry.feature = Some(value),
+            other => return Err(error(&format!("unknown key `{other}`"))),
+        }
+    }
+
+    for entry in &entries {
+        if entry.name.is_empty() || entry.topic.is_empty() || entry.item.is_empty() {
+            return Err(format!("example {entry:?} needs a `name`, `topic` and `item`"));
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_entries() {
+        let spec = "# Comment\n\n[[example]]\nname = \"a\"\ntopic = \"Basics\"\nitem = \"crate::a::Demo\"\n\
+                    [[example]]\nname = \"b\"\ntopic = \"Use\"\nitem = \"crate::b::Demo\"\nfeature = \"x\"\n";
+        let entries = parse(spec).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].item, "crate::a::Demo");
+        assert_eq!(entries[1].feature.as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("name = \"a\"").unwrap_err(), "line 1: expected `[[example]]` first");
+        assert!(parse("[[example]]\nname = a").unwrap_err().starts_with("line 2:"));
+        assert!(parse("[[example]]\ncolour = \"red\"").unwrap_err().contains("unknown key"));
+        assert!(parse("[[example]]\nname = \"a\"").unwrap_err().contains("needs"));
+    }
+}
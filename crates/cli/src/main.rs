@@ -0,0 +1,24 @@
+// The top of the workspace's chain: a binary using both library crates.
+// Run it from the repository root with `cargo run -p example-cli`.
+
+// A binary crate is a boundary as well, and a final one: nothing can depend
+// on it, so its `pub` items can't be used by any other crate, and `pub` here
+// only matters between its own modules.
+
+mod output {
+    pub fn print_all(lines: &[String]) {
+        for line in lines {
+            println!("{line}");
+        }
+    }
+}
+
+use example_core::Shape;
+
+fn main() {
+    let shapes = [Shape::Circle { radius: 1.0 }, Shape::Square { side: 2.0 }];
+    output::print_all(&examples_extra::describe_all(&shapes));
+
+    let settings = examples_extra::default_settings();
+    println!("Default precision from `examples-extra`: {}", examples_extra::precision(&settings));
+}
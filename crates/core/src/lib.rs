@@ -0,0 +1,78 @@
+// The bottom crate of the workspace's own chain: `example-cli` depends on
+// `examples-extra`, which depends on this.
+
+// Visibility is about modules, but a crate is a boundary too. `pub(crate)`
+// means "anywhere in this crate", so it's private as far as the other crates
+// in the workspace are concerned, even though they're built together and
+// live in the same repository. Being in one workspace shares a `Cargo.lock`
+// and a `target/` directory, not visibility.
+
+// Visible to every crate that depends on this one.
+pub fn describe(shape: &Shape) -> String {
+    format!("{} with area {:.2}", shape_name(shape), area(shape))
+}
+
+// Visible anywhere in this crate, and nowhere else. `examples-extra` calling
+// `example_core::shape_name` is error E0603, "function `shape_name` is private".
+pub(crate) fn shape_name(shape: &Shape) -> &'static str {
+    match shape {
+        Shape::Circle { .. } => "circle",
+        Shape::Square { .. } => "square",
+    }
+}
+
+// Private to the crate root module, which here is much the same as `pub(crate)`.
+fn area(shape: &Shape) -> f64 {
+    match *shape {
+        Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+        Shape::Square { side } => side * side,
+    }
+}
+
+// Some rules only apply across crates. `#[non_exhaustive]` promises nothing
+// to other crates about the full list of variants, so they must match with a
+// `_` arm, which keeps adding a variant here from breaking them. Inside this
+// crate it makes no difference, so `shape_name` above needs no `_`.
+#[non_exhaustive]
+pub enum Shape {
+    Circle { radius: f64 },
+    Square { side: f64 },
+}
+
+// Build a `Shape::Circle`. `$crate` makes the path work in other crates, and
+// `../edition-2015/` imports this the 2015 way, with `#[macro_use]`.
+#[macro_export]
+macro_rules! circle {
+    ($radius:expr) => {
+        $crate::Shape::Circle { radius: $radius }
+    };
+}
+
+// On a struct, `#[non_exhaustive]` stops other crates constructing it with a
+// struct expression, even though every field is `pub`: a new field would
+// break them. They must use a constructor, and match with `..`.
+#[non_exhaustive]
+pub struct Settings {
+    pub precision: usize,
+}
+
+impl Settings {
+    pub fn new(precision: usize) -> Settings {
+        Settings { precision }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Everything in the crate is reachable from its own tests, including
+    // `pub(crate)` items, and `#[non_exhaustive]` types can be built directly.
+    #[test]
+    fn inside_the_crate() {
+        let settings = Settings { precision: 1 };
+        assert_eq!(settings.precision, 1);
+        assert_eq!(shape_name(&Shape::Square { side: 2.0 }), "square");
+        assert_eq!(describe(&Shape::Square { side: 2.0 }), "square with area 4.00");
+    }
+}
@@ -0,0 +1,15 @@
+// Module path logic that doesn't need the standard library.
+
+// Without the `std` feature this crate is `no_std`: it links only `core`,
+// so it can be used on targets with no OS, such as microcontrollers.
+// `cfg_attr` applies `no_std` only when the feature is off.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// The module structure does most of the work of keeping the split clean:
+// everything in `path` uses only `core`, and is always compiled.
+pub mod path;
+
+// Everything that needs `std` goes in modules behind the feature, so there's
+// one `#[cfg]` per module rather than one per item.
+#[cfg(feature = "std")]
+pub mod owned;
@@ -0,0 +1,21 @@
+// Extras that allocate, so they need `std`. This whole module is left out of
+// `no_std` builds by the `#[cfg]` on its declaration in `lib.rs`.
+
+use crate::path::{self, PathError};
+
+// Split a path into owned segments, after checking it.
+pub fn segments(path: &str) -> Result<Vec<String>, PathError> {
+    path::validate(path)?;
+    Ok(path.split("::").map(str::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owned_segments() {
+        assert_eq!(segments("crate::a").unwrap(), vec!["crate", "a"]);
+        assert_eq!(segments("").unwrap_err().to_string(), "empty path");
+    }
+}
@@ -0,0 +1,68 @@
+// Checking and comparing module paths like `crate::a::b`, using only `core`.
+
+// `core` has most of what `std` has that doesn't need an allocator or an OS:
+// `fmt`, `str` methods, iterators, `Option` and `Result`.
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathError {
+    Empty,
+    EmptySegment,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PathError::Empty => "empty path",
+            PathError::EmptySegment => "empty segment in path",
+        })
+    }
+}
+
+// `std::error::Error` is only implemented when `std` is available. One of the
+// few places an individual item is gated rather than a whole module.
+#[cfg(feature = "std")]
+impl std::error::Error for PathError {}
+
+pub fn validate(path: &str) -> Result<(), PathError> {
+    if path.is_empty() {
+        return Err(PathError::Empty);
+    }
+    if path.split("::").any(str::is_empty) {
+        return Err(PathError::EmptySegment);
+    }
+    Ok(())
+}
+
+// The number of segments, e.g. 3 for `crate::a::b`.
+pub fn depth(path: &str) -> usize {
+    path.split("::").count()
+}
+
+// Whether `path` is `ancestor` or one of its descendants, which is when an
+// item that is `pub(in ancestor)` is visible from `path`.
+pub fn is_within(path: &str, ancestor: &str) -> bool {
+    let mut segments = path.split("::");
+    ancestor.split("::").all(|a| segments.next() == Some(a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation() {
+        assert_eq!(validate("crate::a"), Ok(()));
+        assert_eq!(validate(""), Err(PathError::Empty));
+        assert_eq!(validate("crate::::a"), Err(PathError::EmptySegment));
+    }
+
+    #[test]
+    fn within() {
+        assert_eq!(depth("crate::a::b"), 3);
+        assert!(is_within("crate::a::b", "crate::a"));
+        assert!(is_within("crate::a", "crate::a"));
+        assert!(!is_within("crate::ab", "crate::a"));
+        assert!(!is_within("crate", "crate::a"));
+    }
+}
@@ -0,0 +1,141 @@
+// Procedural macros for the `rust-module-example` crate.
+
+// The input is parsed with `syn` into a syntax tree, and the output is built
+// with `quote!`, which reads like the code it generates, with `#name` where a
+// value goes in. Errors are `syn::Error`s, which become `compile_error!`
+// calls pointing at the offending tokens.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, ItemFn, MetaNameValue, Token};
+
+// The `key = value, ...` arguments of `#[example(...)]`.
+type Args = Punctuated<MetaNameValue, Token![,]>;
+
+// `#[derive(Example)]` implements the crate's `Example` trait for a struct,
+// from an `#[example(...)]` attribute, e.g.:
+//
+//     #[derive(Example)]
+//     #[example(name = "derived", description = "...", tags = ["macros"],
+//               topic = Macros, source = "derived.rs")]
+//     pub struct Demo;
+//
+//     impl Demo {
+//         fn demo(&self) { ... }
+//     }
+//
+// `source` is a file to `include_str!`, relative to the file using the
+// derive. Use `section = "Heading"` instead for a section of `lib.rs`. The
+// generated `run` calls the struct's own `demo` method.
+//
+// `example` is declared as a helper attribute, so the compiler accepts it on
+// items that use the derive, and leaves it for the derive to read.
+#[proc_macro_derive(Example, attributes(example))]
+pub fn derive_example(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    if !matches!(input.data, Data::Struct(_)) {
+        return Err(syn::Error::new_spanned(name, "#[derive(Example)] only works on structs"));
+    }
+    let attr = input.attrs.iter().find(|attr| attr.path().is_ident("example")).ok_or_else(|| {
+        syn::Error::new_spanned(name, "#[derive(Example)] needs an #[example(key = value, ...)] attribute")
+    })?;
+    let args = attr.parse_args_with(Args::parse_terminated)?;
+    let Info { name: example, description, tags, topic, source } = Info::new(&args, attr)?;
+
+    // The generated code is expanded in the user's crate, so it names the
+    // trait by a path that works there. There's no `$crate` for proc macros:
+    // `crate::` is right here because only `rust-module-example` uses this.
+    Ok(quote! {
+        impl crate::example::Example for #name {
+            fn name(&self) -> &'static str { #example }
+            fn description(&self) -> &'static str { #description }
+            fn tags(&self) -> &'static [&'static str] { &#tags }
+            fn topic(&self) -> crate::example::Topic { crate::example::Topic::#topic }
+            fn source(&self) -> &'static str { #source }
+            fn run(&self) { #name::demo(self) }
+        }
+    })
+}
+
+// `#[example(...)]` on a function turns it into an example, with no struct
+// or trait impl to write, e.g.:
+//
+//     #[example(name = "attribute", description = "...", tags = ["macros"],
+//               topic = Macros, source = "attribute.rs")]
+//     pub fn attribute() { ... }
+//
+// The function is left as it is, and a `static` named after it in upper case,
+// here `ATTRIBUTE`, is added next to it: a `crate::example::FnExample` that
+// runs the function. The arguments are the same as for `derive(Example)`.
+// It also registers the `static` with `crate::registry::register!`.
+#[proc_macro_attribute]
+pub fn example(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args with Args::parse_terminated);
+    let item = match syn::parse::<ItemFn>(item) {
+        Ok(item) => item,
+        Err(e) => return syn::Error::new(e.span(), "#[example] only works on functions").into_compile_error().into(),
+    };
+    expand_fn(&args, &item).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand_fn(args: &Args, item: &ItemFn) -> syn::Result<TokenStream2> {
+    let function = &item.sig.ident;
+    let Info { name, description, tags, topic, source } = Info::new(args, function)?;
+    let example = format_ident!("{}", function.to_string().to_uppercase());
+    // The `static` has the same visibility as the function.
+    let vis = &item.vis;
+
+    Ok(quote! {
+        #item
+
+        #vis static #example: crate::example::FnExample = crate::example::FnExample {
+            name: #name,
+            description: #description,
+            tags: &#tags,
+            topic: crate::example::Topic::#topic,
+            source: || #source,
+            run: #function,
+        };
+        crate::registry::register!(#example);
+    })
+}
+
+// The parts of an example that come from `key = value` arguments.
+struct Info<'a> {
+    name: &'a Expr,
+    description: &'a Expr,
+    tags: TokenStream2,
+    topic: &'a Expr,
+    source: TokenStream2,
+}
+
+impl<'a> Info<'a> {
+    // Errors point at `at`: the attribute, or the function it's on.
+    fn new(args: &'a Args, at: impl ToTokens) -> syn::Result<Info<'a>> {
+        let arg = |key: &str| args.iter().find(|arg| arg.path.is_ident(key)).map(|arg| &arg.value);
+        let required = |key: &str| {
+            arg(key).ok_or_else(|| syn::Error::new_spanned(&at, format!("#[example(...)] is missing `{key}`")))
+        };
+
+        let source = match (arg("source"), arg("section")) {
+            (Some(file), None) => quote!(include_str!(#file)),
+            (None, Some(heading)) => quote!(crate::example::section(crate::example::LIB_RS, #heading)),
+            _ => return Err(syn::Error::new_spanned(&at, "#[example(...)] needs one of `source` or `section`")),
+        };
+
+        Ok(Info {
+            name: required("name")?,
+            description: required("description")?,
+            tags: arg("tags").map_or_else(|| quote!([]), ToTokens::to_token_stream),
+            topic: required("topic")?,
+            source,
+        })
+    }
+}
@@ -0,0 +1,70 @@
+// A tiny integer formatting library.
+
+// Modelled on `itoa`. It's the dependency that
+// `../../../src/reexport_dependency.rs` hides behind a facade module.
+
+// A reusable buffer to format integers into without allocating.
+pub struct Buffer {
+    bytes: [u8; 20],
+}
+
+impl Buffer {
+    pub fn new() -> Buffer {
+        Buffer { bytes: [0; 20] }
+    }
+
+    // Format `n` in decimal, returning a string borrowed from the buffer.
+    pub fn format(&mut self, mut n: u64) -> &str {
+        let mut start = self.bytes.len();
+        loop {
+            start -= 1;
+            self.bytes[start] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        std::str::from_utf8(&self.bytes[start..]).expect("digits are ASCII")
+    }
+}
+
+impl Default for Buffer {
+    fn default() -> Buffer {
+        Buffer::new()
+    }
+}
+
+// Format `n` with `separator` between each group of three digits.
+pub fn grouped(n: u64, separator: char) -> String {
+    let mut buffer = Buffer::new();
+    let digits = buffer.format(n);
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(separator);
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format() {
+        let mut buffer = Buffer::new();
+        assert_eq!(buffer.format(0), "0");
+        assert_eq!(buffer.format(42), "42");
+        assert_eq!(buffer.format(u64::MAX), "18446744073709551615");
+    }
+
+    #[test]
+    fn grouping() {
+        assert_eq!(grouped(7, ','), "7");
+        assert_eq!(grouped(1234, ','), "1,234");
+        assert_eq!(grouped(123456, '_'), "123_456");
+        assert_eq!(grouped(1234567, ','), "1,234,567");
+    }
+}
@@ -0,0 +1,5 @@
+// A private module, found by `mod helpers;` the same way in every edition.
+
+pub fn double(n: u64) -> u64 {
+    n * 2
+}
@@ -0,0 +1,73 @@
+// Modules and paths in the 2015 edition, which the 2018 edition changed.
+// The same code in 2018+ style is in `../../examples-extra/tests/editions.rs`,
+// which tests that both give the same results.
+
+// In 2015, a `use` path always starts from the crate root, and a dependency is
+// only an item at the root once it's declared with `extern crate`. So this
+// line is what lets `use digits::...` work anywhere in this crate.
+// In 2018+, dependencies are in scope everywhere without it.
+extern crate digits;
+
+// `#[macro_use]` on `extern crate` was the only way to import another crate's
+// `#[macro_export]` macros: all of them, into every module of this crate.
+// In 2018+, macros are imported with `use` like other items, e.g.
+// `use example_core::circle;`.
+#[macro_use]
+extern crate example_core;
+
+mod helpers;
+
+pub fn format_number(n: u64) -> String {
+    // `digits` is the item `extern crate` declared at the root.
+    let mut buffer = digits::Buffer::new();
+    buffer.format(n).to_string()
+}
+
+pub mod nested {
+    // These paths start from the crate root, even in a child module. 2018+
+    // would need `crate::helpers::double` for the second one.
+    use digits::grouped;
+    use helpers::double;
+
+    pub fn grouped_double(n: u64) -> String {
+        grouped(double(n), ',')
+    }
+
+    // In 2015 a leading `::` also means the crate root, so `::helpers` is the
+    // module above. In 2018+, `::helpers` means a crate called `helpers`.
+    pub fn leading_colons(n: u64) -> u64 {
+        ::helpers::double(n)
+    }
+
+    // `crate::` paths were added to 2015 too, in Rust 1.30, so code can be
+    // written to mean the same thing in every edition.
+    pub fn crate_prefix(n: u64) -> u64 {
+        crate::helpers::double(n)
+    }
+}
+
+pub fn unit_circle() -> String {
+    // `circle!` came in with `#[macro_use]` above, with no `use`.
+    example_core::describe(&circle!(1.0))
+}
+
+// `async` only became a keyword in 2018, so in 2015 it's an ordinary name.
+// A 2018+ crate can still call this, as `edition_2015::r#async`.
+pub fn async() -> &'static str {
+    "2015"
+}
+
+#[cfg(test)]
+mod tests {
+    // A 2015 child module sees the root's items through `use super::*` the
+    // same way as in 2018+.
+    use super::*;
+
+    #[test]
+    fn paths() {
+        assert_eq!(format_number(42), "42");
+        assert_eq!(nested::grouped_double(600_000), "1,200,000");
+        assert_eq!(nested::leading_colons(4), nested::crate_prefix(4));
+        assert_eq!(async(), "2015");
+    }
+}
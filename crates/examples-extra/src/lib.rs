@@ -0,0 +1,70 @@
+// A crate in the workspace that depends on another, `example-core`, to show
+// what does and doesn't cross the boundary between them.
+
+// The dependency's package name is `example-core`, but crate names can't
+// contain `-`, so in Rust it's `example_core`.
+use example_core::{Settings, Shape};
+
+// `pub` items cross the boundary: this calls `example_core::describe`.
+pub fn describe_all(shapes: &[Shape]) -> Vec<String> {
+    shapes.iter().map(example_core::describe).collect()
+}
+
+// `example_core::shape_name` is `pub(crate)`, so it's private here, and the
+// same name can't even be imported:
+//
+/// ```compile_fail,E0603
+/// example_core::shape_name(&example_core::Shape::Square { side: 1.0 });
+/// ```
+//
+// A `#[non_exhaustive]` enum from another crate needs a wildcard arm, or the
+// match is error E0004, "non-exhaustive patterns: `_` not covered":
+//
+/// ```compile_fail,E0004
+/// fn sides(shape: &example_core::Shape) -> u32 {
+///     match shape {
+///         example_core::Shape::Circle { .. } => 0,
+///         example_core::Shape::Square { .. } => 4,
+///     }
+/// }
+/// ```
+//
+// And a `#[non_exhaustive]` struct from another crate can't be built with a
+// struct expression, error E0639, even though its field is `pub`:
+//
+/// ```compile_fail,E0639
+/// let settings = example_core::Settings { precision: 2 };
+/// ```
+pub fn sides(shape: &Shape) -> Option<u32> {
+    match shape {
+        Shape::Circle { .. } => None,
+        Shape::Square { .. } => Some(4),
+        // Required here, though not in `example_core` itself.
+        _ => None,
+    }
+}
+
+// Reading a `pub` field of a `#[non_exhaustive]` struct is fine, and so is
+// destructuring it, as long as the pattern ends with `..`.
+pub fn precision(settings: &Settings) -> usize {
+    let Settings { precision, .. } = settings;
+    *precision
+}
+
+pub fn default_settings() -> Settings {
+    // The only way to make one from outside `example_core`.
+    Settings::new(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_core() {
+        let shapes = [Shape::Circle { radius: 1.0 }, Shape::Square { side: 2.0 }];
+        assert_eq!(describe_all(&shapes), vec!["circle with area 3.14", "square with area 4.00"]);
+        assert_eq!(sides(&shapes[1]), Some(4));
+        assert_eq!(precision(&default_settings()), 2);
+    }
+}
@@ -0,0 +1,32 @@
+// Integration tests are separate crates too, so they see `examples_extra` and
+// `example_core` exactly as any other dependent crate does: `pub` items only,
+// with the cross-crate rules for `#[non_exhaustive]`. The cases that don't
+// compile are `compile_fail` doctests in `../src/lib.rs`.
+
+use example_core::{Settings, Shape};
+
+#[test]
+fn pub_items_cross_crates() {
+    assert_eq!(example_core::describe(&Shape::Square { side: 3.0 }), "square with area 9.00");
+    assert_eq!(examples_extra::describe_all(&[Shape::Circle { radius: 2.0 }]),
+               vec!["circle with area 12.57"]);
+}
+
+#[test]
+fn non_exhaustive_enum_needs_wildcard() {
+    let shape = Shape::Circle { radius: 1.0 };
+    let name = match shape {
+        Shape::Circle { .. } => "circle",
+        Shape::Square { .. } => "square",
+        _ => "something newer",
+    };
+    assert_eq!(name, "circle");
+    assert_eq!(examples_extra::sides(&shape), None);
+}
+
+#[test]
+fn non_exhaustive_struct_needs_constructor() {
+    let settings = Settings::new(5);
+    let Settings { precision, .. } = settings;
+    assert_eq!(precision, 5);
+}
@@ -0,0 +1,47 @@
+// The 2018+ way of writing `../../edition-2015/src/lib.rs`, checked against
+// the 2015 original. This test crate is 2021 edition, like its package.
+
+// No `extern crate`: dependencies are in scope by name in every module.
+// Macros are imported by path like any other item.
+use example_core::circle;
+
+mod helpers {
+    pub fn double(n: u64) -> u64 {
+        n * 2
+    }
+}
+
+mod nested {
+    // `use` paths are relative to this module now, so the local module
+    // needs `crate::`. A dependency's name works with no prefix.
+    use crate::helpers::double;
+    use digits::grouped;
+
+    pub fn grouped_double(n: u64) -> String {
+        grouped(double(n), ',')
+    }
+}
+
+#[test]
+fn format_number() {
+    let mut buffer = digits::Buffer::new();
+    assert_eq!(buffer.format(42), edition_2015::format_number(42));
+}
+
+#[test]
+fn use_paths() {
+    assert_eq!(nested::grouped_double(600_000), edition_2015::nested::grouped_double(600_000));
+    assert_eq!(crate::helpers::double(4), edition_2015::nested::leading_colons(4));
+    assert_eq!(crate::helpers::double(4), edition_2015::nested::crate_prefix(4));
+}
+
+#[test]
+fn macro_import() {
+    assert_eq!(example_core::describe(&circle!(1.0)), edition_2015::unit_circle());
+}
+
+#[test]
+fn keyword_as_name() {
+    // `async` is a keyword here, so the 2015 function needs a raw identifier.
+    assert_eq!(edition_2015::r#async(), "2015");
+}
@@ -0,0 +1,23 @@
+// Uses test helpers from the root package, which it makes public with its
+// `test-util` feature. See `mock_platform` in `../../../src/lib.rs`.
+
+use modules_demo::mock_platform::{Unix, Windows};
+use modules_demo::modtree::test_dir::TestDir;
+use modules_demo::modtree::{find_crate_root, ModuleTree};
+use modules_demo::platform_api::library_file_name;
+
+#[test]
+fn mock_platforms() {
+    assert_eq!(library_file_name::<Unix>("core"), "libcore.so");
+    assert_eq!(library_file_name::<Windows>("core"), "core.dll");
+}
+
+#[test]
+fn test_dir_fixture() {
+    let dir = TestDir::new("examples_extra_test_util", &[
+        ("src/lib.rs", "pub mod shapes;"),
+        ("src/shapes.rs", "pub fn area() {}"),
+    ]);
+    let tree = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+    assert!(tree.find("crate::shapes").is_some());
+}
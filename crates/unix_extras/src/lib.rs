@@ -0,0 +1,35 @@
+// A tiny Unix-only library.
+
+// It's a target-specific dependency: the main crate lists it under
+// `[target.'cfg(unix)'.dependencies]`, so it's only built for Unix-like
+// targets, see `../../../src/target_deps.rs`. That's just as well, as it
+// uses `std::os::unix` and wouldn't compile anywhere else.
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+// The process ID of this process's parent.
+pub fn parent_pid() -> u32 {
+    std::os::unix::process::parent_id()
+}
+
+// The permission bits of a file, like `0o644`.
+pub fn mode(path: &Path) -> std::io::Result<u32> {
+    Ok(std::fs::metadata(path)?.permissions().mode() & 0o7777)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parent() {
+        assert_ne!(parent_pid(), std::process::id());
+    }
+
+    #[test]
+    fn manifest_mode() {
+        let mode = mode(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml").as_path()).unwrap();
+        assert_ne!(mode & 0o400, 0, "owner can read");
+    }
+}
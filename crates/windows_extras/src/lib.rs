@@ -0,0 +1,26 @@
+// A tiny Windows-only library.
+
+// The Windows half of `../../../src/target_deps.rs`: the main crate only
+// depends on it under `[target.'cfg(windows)'.dependencies]`. As a workspace
+// member it's still built on its own by `cargo build --workspace`, so the
+// `#![cfg(windows)]` below empties it on other targets, where
+// `std::os::windows` doesn't exist.
+#![cfg(windows)]
+
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
+
+// Encode a string as the null-terminated UTF-16 that Windows APIs expect.
+pub fn to_wide(s: &str) -> Vec<u16> {
+    OsStr::new(s).encode_wide().chain(Some(0)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide() {
+        assert_eq!(to_wide("ab"), vec![97, 98, 0]);
+    }
+}
@@ -0,0 +1,20 @@
+// # An example target
+
+// Cargo's "examples" are another kind of target, separate from the examples
+// this crate's runner lists: each file `examples/NAME.rs`, or directory
+// `examples/NAME/main.rs` like this one, is a small program of its own, run
+// with `cargo run --example mini_app`. `cargo test` builds them, so they
+// can't silently stop compiling.
+
+// Each one is an independent crate root with its own module tree, using the
+// library by name like the binaries do. This one has `./model.rs`, and
+// `./ui.rs` with a child of its own, `./ui/table.rs`.
+mod model;
+mod ui;
+
+use modules_demo::registry;
+
+fn main() {
+    let catalog = model::Catalog::from_registry(registry::EXAMPLES);
+    print!("{}", ui::render(&catalog));
+}
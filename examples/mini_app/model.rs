@@ -0,0 +1,23 @@
+// The app's data: how many of the library's examples there are per topic.
+
+use modules_demo::example::{Example, Topic};
+
+pub struct Catalog {
+    // In `Topic` order, one entry per topic with any examples.
+    pub topics: Vec<(Topic, usize)>,
+}
+
+impl Catalog {
+    pub fn from_registry(examples: &[&dyn Example]) -> Catalog {
+        let mut topics: Vec<(Topic, usize)> = Vec::new();
+        let mut sorted: Vec<Topic> = examples.iter().map(|e| e.topic()).collect();
+        sorted.sort();
+        for topic in sorted {
+            match topics.last_mut() {
+                Some((last, count)) if *last == topic => *count += 1,
+                _ => topics.push((topic, 1)),
+            }
+        }
+        Catalog { topics }
+    }
+}
@@ -0,0 +1,12 @@
+// Presentation. This module's own child is in `./ui/table.rs`, found the same
+// way as in any other crate.
+mod table;
+
+use crate::model::Catalog;
+
+pub fn render(catalog: &Catalog) -> String {
+    let rows: Vec<(String, String)> = catalog.topics.iter()
+        .map(|(topic, count)| (format!("{topic:?}"), count.to_string()))
+        .collect();
+    table::two_columns(("Topic", "Examples"), &rows)
+}
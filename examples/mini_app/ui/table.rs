@@ -0,0 +1,10 @@
+// Plain text tables, private to `ui`.
+
+pub fn two_columns(header: (&str, &str), rows: &[(String, String)]) -> String {
+    let width = rows.iter().map(|(left, _)| left.len()).chain([header.0.len()]).max().unwrap_or(0);
+    let mut out = format!("{:width$}  {}\n", header.0, header.1);
+    for (left, right) in rows {
+        out.push_str(&format!("{left:width$}  {right}\n"));
+    }
+    out
+}
@@ -0,0 +1,15 @@
+// expect: E0603
+
+// Everything in a module is private to it unless marked `pub`, so
+// `dinner` can't call `cook`. Let it, without moving `cook` out of
+// `kitchen`.
+
+mod kitchen {
+    fn cook() -> &'static str {
+        "soup"
+    }
+}
+
+pub fn dinner() -> &'static str {
+    kitchen::cook()
+}
@@ -0,0 +1,17 @@
+// expect: E0603
+
+// `bake` is `pub` now, but `dessert` still can't reach it: every module
+// on the path has to be visible too. Fix the path's way through
+// `kitchen`, without changing `dessert`.
+
+mod kitchen {
+    mod oven {
+        pub fn bake() -> &'static str {
+            "pie"
+        }
+    }
+}
+
+pub fn dessert() -> &'static str {
+    kitchen::oven::bake()
+}
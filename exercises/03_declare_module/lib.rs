@@ -0,0 +1,9 @@
+// expect: E0433
+
+// `./shapes.rs` is right next to this file, but Rust doesn't compile a
+// file just because it's there: a module only exists once its parent
+// declares it. Declare `shapes` so `total_area` can use it.
+
+pub fn total_area() -> f64 {
+    shapes::square(2.0) + shapes::square(3.0)
+}
@@ -0,0 +1,5 @@
+// The `shapes` module, once `lib.rs` declares it.
+
+pub fn square(side: f64) -> f64 {
+    side * side
+}
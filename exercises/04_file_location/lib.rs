@@ -0,0 +1,12 @@
+// expect: E0583
+
+// `shapes.rs` declares a child, `mod round;`, and the compiler looks for
+// its file in a directory named after the parent: `shapes/round.rs`, or
+// `shapes/round/mod.rs`. It's at `./round.rs` instead. Move the file to
+// where the compiler looks, without changing any code.
+
+pub mod shapes;
+
+pub fn unit_circle() -> f64 {
+    shapes::round::area(1.0)
+}
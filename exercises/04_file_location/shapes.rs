@@ -0,0 +1,3 @@
+// The `shapes` module, with a child `round` in a file of its own.
+
+pub mod round;
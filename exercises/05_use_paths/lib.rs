@@ -0,0 +1,18 @@
+// expect: E0432
+
+// A path in `use` starts from the current module, or a crate name, not
+// from the crate root. `format_price` is in the root, so inside
+// `checkout` it's not `format_price`. Fix the import's path, with
+// `crate::` or `super::`.
+
+pub fn format_price(cents: u32) -> String {
+    format!("${}.{:02}", cents / 100, cents % 100)
+}
+
+pub mod checkout {
+    use format_price;
+
+    pub fn total(items: &[u32]) -> String {
+        format_price(items.iter().sum())
+    }
+}
@@ -0,0 +1,18 @@
+// expect: E0603
+
+// `pub(super)` makes `audit` visible to its parent, `accounts`, but
+// `report` is in the crate root, one level further out. Widen `audit`'s
+// visibility just enough for `report` to call it, while keeping it out
+// of the crate's public API.
+
+mod accounts {
+    pub mod ledger {
+        pub(super) fn audit() -> u32 {
+            42
+        }
+    }
+}
+
+pub fn report() -> u32 {
+    accounts::ledger::audit()
+}
@@ -0,0 +1,14 @@
+// expect: E0364
+
+// `pub use` re-exports an item from somewhere else as if it were declared
+// here, so other crates can name it as `exercise::checksum`. But an item
+// can't be re-exported more widely than it's visible, and `checksum` is
+// only `pub(crate)`. Make the re-export work.
+
+mod internals {
+    pub(crate) fn checksum(data: &[u8]) -> u32 {
+        data.iter().map(|&b| u32::from(b)).sum()
+    }
+}
+
+pub use internals::checksum;
@@ -0,0 +1,20 @@
+// Feeds arbitrary strings to the parser for `use` paths, `Import::from_str`,
+// which reads paths typed or pasted on the command line. It must never
+// panic, and a path it accepts must print as text that parses back to the
+// same path.
+//
+// `../parse_path.dict` has fragments of paths for libFuzzer to splice in,
+// so its input looks like a path more often than random bytes would.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use modules_demo::modtree::Import;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else { return };
+    if let Ok(import) = s.parse::<Import>() {
+        let printed = import.to_string();
+        assert_eq!(printed.parse::<Import>(), Ok(import), "{s:?} printed as {printed:?}");
+    }
+});
@@ -0,0 +1,26 @@
+use crate::example::{self, Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "a" }
+
+    fn description(&self) -> &'static str {
+        "A module loaded from its own file, `./a.rs`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["basics", "files"] }
+
+    fn topic(&self) -> Topic { Topic::Basics }
+
+    fn source(&self) -> &'static str {
+        example::section(example::LIB_RS, "Rust modules example")
+    }
+
+    fn run(&self) {
+        println!("This code lives in `./a.rs`, declared in `lib.rs` with `mod a;`.");
+        println!("Its full path from the crate root is `{}`.", module_path!());
+    }
+}
@@ -0,0 +1,74 @@
+// # Versioned API modules
+
+// When an API has to change incompatibly but old callers must keep working,
+// one option is to keep each version in its own module. Callers pick a
+// version by path, `api::v1::greet` or `api::v2::greet`, and both can be
+// used side by side in the same program, even in the same function.
+
+// Each version lives in its own file under `./api/`.
+pub mod v1;
+pub mod v2;
+
+// `latest` is an alias for the newest version. Callers who want to track
+// changes use `api::latest::...`, and callers who want stability name
+// a version. Bumping this line is how a new version gets released.
+pub use v2 as latest;
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "api" }
+
+    fn description(&self) -> &'static str {
+        "Versioned `v1` and `v2` API modules with a `latest` alias"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["re-export", "multi-level", "files"] }
+
+    fn topic(&self) -> Topic { Topic::Use }
+
+    fn source(&self) -> &'static str {
+        concat!(include_str!("api.rs"), "\n", include_str!("api/v1.rs"), "\n", include_str!("api/v2.rs"))
+    }
+
+    fn run(&self) {
+        println!("v1::greet(\"Ferris\") = {:?}", v1::greet("Ferris"));
+        println!("v2::greet(\"Ferris\", Style::Formal) = {:?}", v2::greet("Ferris", v2::Style::Formal));
+        println!("latest::VERSION = {}", latest::VERSION);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{latest, v1, v2};
+
+    #[test]
+    fn v1_pinned() {
+        assert_eq!(v1::VERSION, 1);
+        assert_eq!(v1::greet("Ferris"), "Hello, Ferris!");
+    }
+
+    #[test]
+    fn v2_pinned() {
+        assert_eq!(v2::VERSION, 2);
+        assert_eq!(v2::greet("Ferris", v2::Style::Casual), "Hi, Ferris!");
+        assert_eq!(v2::greet("Ferris", v2::Style::Formal), "Good day, Ferris.");
+    }
+
+    #[test]
+    fn latest_is_v2() {
+        assert_eq!(latest::VERSION, v2::VERSION);
+        assert_eq!(latest::greet("Ferris", latest::Style::Casual), v2::greet("Ferris", v2::Style::Casual));
+    }
+
+    #[test]
+    fn shared_items() {
+        // `v2` re-exports `v1::Name`, so it's one type that works with both versions.
+        let name: v2::Name = v1::Name::new("Ferris");
+        assert_eq!(v1::greet(name.as_str()), "Hello, Ferris!");
+    }
+}
@@ -0,0 +1,21 @@
+// The first version of the API. Once released, nothing here changes
+// incompatibly; fixes that need a new signature go in a new version.
+
+pub const VERSION: u32 = 1;
+
+pub fn greet(name: &str) -> String {
+    format!("Hello, {name}!")
+}
+
+// A validated name, kept unchanged in later versions.
+pub struct Name(String);
+
+impl Name {
+    pub fn new(name: &str) -> Name {
+        Name(name.trim().to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
@@ -0,0 +1,20 @@
+// The second version of the API. `greet` gained a parameter, which would
+// have broken every `v1` caller if it had been changed in place.
+
+pub const VERSION: u32 = 2;
+
+pub enum Style {
+    Casual,
+    Formal,
+}
+
+pub fn greet(name: &str, style: Style) -> String {
+    match style {
+        Style::Casual => format!("Hi, {name}!"),
+        Style::Formal => format!("Good day, {name}."),
+    }
+}
+
+// Items that didn't change are re-exported from the previous version rather
+// than copied, so both versions share one type.
+pub use super::v1::Name;
@@ -0,0 +1,63 @@
+// # A quiz binary
+
+// A third binary crate in this package, run with `cargo run --bin quiz`. It
+// asks which example matches each description, using the library's registry,
+// plus a few questions about modules.
+
+// Like `./tree.rs`, it has its own module tree: `questions` in a file,
+// `./quiz/questions.rs`, loaded with `#[path]` for the reason given there,
+// and `score` inline below.
+#[path = "quiz/questions.rs"]
+mod questions;
+
+mod score {
+    // Private to `score`, so only `record` and `summary` can change it.
+    pub struct Score {
+        right: usize,
+        asked: usize,
+    }
+
+    impl Score {
+        pub fn new() -> Score {
+            Score { right: 0, asked: 0 }
+        }
+
+        pub fn record(&mut self, correct: bool) {
+            self.asked += 1;
+            if correct {
+                self.right += 1;
+            }
+        }
+
+        pub fn summary(&self) -> String {
+            format!("You got {} out of {}.", self.right, self.asked)
+        }
+    }
+}
+
+use std::io::{self, BufRead, Write};
+
+fn main() -> io::Result<()> {
+    let mut input = io::stdin().lock();
+    let mut out = io::stdout();
+    let mut score = score::Score::new();
+
+    writeln!(out, "Answer each question, or just press Enter to skip it.\n")?;
+    for question in questions::all() {
+        write!(out, "{}\n> ", question.prompt)?;
+        out.flush()?;
+
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            break;
+        }
+        let correct = question.check(&answer);
+        score.record(correct);
+        if correct {
+            writeln!(out, "Right!\n")?;
+        } else {
+            writeln!(out, "The answer is `{}`.\n", question.answer)?;
+        }
+    }
+    writeln!(out, "{}", score.summary())
+}
@@ -0,0 +1,59 @@
+// The quiz questions, a child module of `../quiz.rs`.
+
+use modules_demo::registry;
+
+pub struct Question {
+    pub prompt: String,
+    pub answer: String,
+}
+
+impl Question {
+    // Answers are compared ignoring surrounding whitespace and backticks.
+    pub fn check(&self, answer: &str) -> bool {
+        answer.trim().trim_matches('`') == self.answer
+    }
+}
+
+// Questions about modules, with fixed answers.
+const FIXED: &[(&str, &str)] = &[
+    ("Which file does `mod a;` in `src/lib.rs` load, apart from `src/a/mod.rs`?", "src/a.rs"),
+    ("Which keyword makes an item visible to the whole crate, but no other crate?", "pub(crate)"),
+    ("Which path prefix starts from the parent of the current module?", "super"),
+];
+
+pub fn all() -> Vec<Question> {
+    let fixed = FIXED.iter().map(|&(prompt, answer)| Question {
+        prompt: prompt.to_string(),
+        answer: answer.to_string(),
+    });
+
+    // One question for every few examples in the library's registry, so the
+    // quiz stays short.
+    let examples = registry::EXAMPLES.iter().step_by(8).map(|e| Question {
+        prompt: format!("Which example is this: \"{}\"?", e.description()),
+        answer: e.name().to_string(),
+    });
+
+    fixed.chain(examples).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_answers() {
+        let question = Question { prompt: String::new(), answer: "super".to_string() };
+        assert!(question.check(" `super`\n"));
+        assert!(!question.check("self"));
+    }
+
+    #[test]
+    fn example_questions_name_real_examples() {
+        let questions = all();
+        assert!(questions.len() > FIXED.len());
+        for question in &questions[FIXED.len()..] {
+            assert!(registry::find(&question.answer).is_some(), "{}", question.answer);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+// # Another binary
+
+// Besides `../main.rs`, Cargo builds each file in `src/bin/` as a binary
+// crate of its own, named after the file. Run this one with
+// `cargo run --bin tree -- [PATH] [--depth N]`.
+
+// Each binary is a separate crate root with its own module tree, and uses the
+// library the same way `main.rs` does, by the package name. Binaries can't
+// see each other: nothing here can use `main.rs`'s `cli` module, for example.
+use modules_demo::modtree::{ModuleId, ModuleTree};
+
+use std::path::Path;
+
+// A crate root declares children like a `mod.rs` file does, next to itself,
+// so a plain `mod args;` would load `./args.rs`. But Cargo would also build
+// that file as a binary called `args`, since it's in `src/bin/`. So this
+// names a file in a directory of this binary's own, `./tree/args.rs`, with
+// `#[path]`. The other way out is making this binary `./tree/main.rs`, with
+// its children next to it.
+#[path = "tree/args.rs"]
+mod args;
+
+fn main() {
+    let args = match args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Error: {e}\n\n{}", args::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let path = args.path.as_deref().unwrap_or(env!("CARGO_MANIFEST_DIR"));
+    let tree = modules_demo::modtree::find_crate_root(Path::new(path))
+        .and_then(|root| ModuleTree::load(&root));
+    match tree {
+        Ok(tree) => print(&tree, tree.root(), 0, args.depth),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+// Print module names indented by depth, down to `max_depth` if there is one.
+fn print(tree: &ModuleTree, id: ModuleId, depth: usize, max_depth: Option<usize>) {
+    let module = tree.module(id);
+    println!("{:1$}{2}", "", depth * 2, module.name);
+    if max_depth.is_some_and(|max| depth >= max) {
+        return;
+    }
+    for &child in &module.children {
+        print(tree, child, depth + 1, max_depth);
+    }
+}
@@ -0,0 +1,55 @@
+// Command line parsing for the `tree` binary, a child module of `../tree.rs`.
+
+pub const USAGE: &str = "\
+Usage: tree [--depth N] [PATH]
+
+Print the module names of the crate at PATH, which defaults to this crate,
+down to depth N if given.";
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub path: Option<String>,
+    pub depth: Option<usize>,
+}
+
+pub fn parse<I>(args: I) -> Result<Args, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut parsed = Args { path: None, depth: None };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--depth" => {
+                let value = args.next().ok_or("`--depth` requires a value")?;
+                parsed.depth = Some(value.parse().map_err(|_| format!("invalid depth '{value}'"))?);
+            }
+            _ if parsed.path.is_none() && !arg.starts_with('-') => parsed.path = Some(arg),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, String> {
+        parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(parse_strs(&[]), Ok(Args { path: None, depth: None }));
+    }
+
+    #[test]
+    fn path_and_depth() {
+        assert_eq!(parse_strs(&["--depth", "1", "crates/core"]),
+                   Ok(Args { path: Some("crates/core".to_string()), depth: Some(1) }));
+        assert!(parse_strs(&["--depth"]).is_err());
+        assert!(parse_strs(&["--depth", "deep"]).is_err());
+        assert!(parse_strs(&["a", "b"]).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+// Exporting the examples as a book, for `export book`: the same annotated
+// source the tour shows, as the source of an mdBook
+// (https://rust-lang.github.io/mdBook/), with a chapter for each topic.
+
+// The teaching is all in comments, so turning it into a book is a matter of
+// splitting each example's source into prose and code. A `//` comment at
+// the start of a line is prose, in Markdown already, and everything else is
+// code, including comments indented inside it, which explain that code
+// where it is. `// #` headings move down a level or two, to go under the
+// chapter's and the example's.
+//
+// Code blocks are `rust,ignore` because they're fragments of this crate
+// that don't compile on their own, which `mdbook test` would otherwise try.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::example::Topic;
+use crate::tour;
+
+// Each topic's chapter title and file name.
+fn chapter(topic: Topic) -> (&'static str, &'static str) {
+    match topic {
+        Topic::Basics => ("Basics", "basics.md"),
+        Topic::MultiLevel => ("Modules with children", "multi-level.md"),
+        Topic::Visibility => ("Visibility", "visibility.md"),
+        Topic::Use => ("Imports with `use`", "use.md"),
+        Topic::Cfg => ("Conditional compilation", "cfg.md"),
+        Topic::Macros => ("Macros", "macros.md"),
+        Topic::Generated => ("Generated code", "generated.md"),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Prose,
+    Code,
+}
+
+// Annotated source as Markdown.
+pub fn to_markdown(source: &str) -> String {
+    // Runs of prose or code lines. A blank line belongs to the run it's in.
+    let mut blocks: Vec<(Kind, Vec<String>)> = Vec::new();
+    for line in source.lines() {
+        let comment = line.strip_prefix("//").filter(|c| !c.starts_with('/') && !c.starts_with('!'));
+        let (kind, text) = match comment {
+            Some(text) => {
+                let text = text.strip_prefix(' ').unwrap_or(text);
+                let hashes = text.len() - text.trim_start_matches('#').len();
+                let text = match text[hashes..].strip_prefix(' ') {
+                    // `#` and `##` become `###`, `###` becomes `####`.
+                    Some(heading) if hashes > 0 => format!("{} {heading}", "#".repeat(hashes.max(2) + 1)),
+                    _ => text.to_string(),
+                };
+                (Kind::Prose, text)
+            }
+            None if line.trim().is_empty() => match blocks.last_mut() {
+                Some((_, lines)) => {
+                    lines.push(String::new());
+                    continue;
+                }
+                None => continue,
+            },
+            None => (Kind::Code, line.to_string()),
+        };
+        match blocks.last_mut() {
+            Some((last, lines)) if *last == kind => lines.push(text),
+            _ => blocks.push((kind, vec![text])),
+        }
+    }
+
+    let mut out = String::new();
+    for (kind, mut lines) in blocks {
+        while lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
+        }
+        if lines.is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        match kind {
+            Kind::Prose => out.push_str(&lines.join("\n")),
+            Kind::Code => out.push_str(&format!("```rust,ignore\n{}\n```", lines.join("\n"))),
+        }
+        out.push('\n');
+    }
+    out
+}
+
+const INTRODUCTION: &str = "\
+# Introduction
+
+This book is made from the annotated source of `rust-module-example`, a
+crate whose modules each show one way Rust's module system works, with
+comments explaining it.
+
+Each chapter is a topic, in the order the crate's guided tour teaches them,
+and each section an example from it. To see one run, clone the crate and
+use the command at the start of its section. Run `cargo run -- tour` to step
+through them all, and `cargo run -- quiz` to test yourself.
+";
+
+// The book's files, as paths relative to its directory and their text.
+pub fn book() -> Vec<(String, String)> {
+    let file = |path: &str, text: String| (path.to_string(), text);
+    let mut summary = "# Summary\n\n[Introduction](introduction.md)\n\n".to_string();
+    let mut chapters = Vec::new();
+
+    let steps = tour::steps();
+    for group in steps.chunk_by(|a, b| a.topic() == b.topic()) {
+        let (title, path) = chapter(group[0].topic());
+        summary.push_str(&format!("- [{title}]({path})\n"));
+        let mut text = format!("# {title}\n");
+        for (i, example) in group.iter().enumerate() {
+            text.push_str(&format!("\n## `{}`: {}\n\nRun it with `cargo run -- run {0}`.\n\n", example.name(),
+                                   example.description()));
+            // Some examples share their source, which only needs showing
+            // once.
+            match group[..i].iter().find(|e| e.source() == example.source()) {
+                Some(same) => text.push_str(&format!("Its source is the same as `{}`'s, above.\n", same.name())),
+                None => text.push_str(&to_markdown(example.source())),
+            }
+        }
+        chapters.push(file(&format!("src/{path}"), text));
+    }
+
+    let mut files = vec![
+        file("book.toml", "[book]\ntitle = \"Rust modules by example\"\nlanguage = \"en\"\nsrc = \"src\"\n".to_string()),
+        file("src/SUMMARY.md", summary),
+        file("src/introduction.md", INTRODUCTION.to_string()),
+    ];
+    files.extend(chapters);
+    files
+}
+
+// Writes the book into `dir`, replacing any files from an earlier export.
+pub fn write(dir: &Path, out: &mut dyn Write) -> io::Result<()> {
+    let files = book();
+    for (path, text) in &files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().expect("every file is in the book's directory"))?;
+        fs::write(path, text)?;
+    }
+    writeln!(out, "Wrote {} files to {}.", files.len(), dir.display())?;
+    writeln!(out, "Build the book with `mdbook build {0}`, or read it as you edit with `mdbook serve {0}`.",
+             dir.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+    use crate::registry;
+
+    #[test]
+    fn prose_and_code() {
+        let source = "\
+// # Title
+
+// Some prose,
+// over two lines.
+//
+//     indented = \"code\";
+mod a;
+
+/// A doc comment.
+fn f() {
+    // Inside the code.
+}
+// ### A sub-heading
+//! Inner doc.
+";
+        assert_eq!(to_markdown(source), "\
+### Title
+
+Some prose,
+over two lines.
+
+    indented = \"code\";
+
+```rust,ignore
+mod a;
+
+/// A doc comment.
+fn f() {
+    // Inside the code.
+}
+```
+
+#### A sub-heading
+
+```rust,ignore
+//! Inner doc.
+```
+");
+    }
+
+    #[test]
+    fn chapters() {
+        let files = book();
+        let summary = &files[1].1;
+        assert!(summary.starts_with("# Summary\n\n[Introduction](introduction.md)\n\n- [Basics](basics.md)\n"));
+        // Every chapter's in the summary, and every example in a chapter.
+        // They come after `book.toml`, the summary and the introduction.
+        let chapters = &files[3..];
+        for (path, _) in chapters {
+            assert!(summary.contains(&format!("]({})\n", &path["src/".len()..])), "{path} isn't in {summary}");
+        }
+        for example in registry::EXAMPLES {
+            let heading = format!("\n## `{}`: ", example.name());
+            assert_eq!(chapters.iter().filter(|(_, text)| text.contains(&heading)).count(), 1, "{}", example.name());
+        }
+        let multi_level = &chapters[1].1;
+        assert!(multi_level.contains("Run it with `cargo run -- run multi_level_style_2`.\n\n\
+                                      Its source is the same as `multi_level_style_1`'s, above.\n"), "{multi_level}");
+        let basics = &chapters[0].1;
+        assert!(basics.starts_with("# Basics\n\n## `a`: A module loaded from its own file, `./a.rs`\n\n\
+                                    Run it with `cargo run -- run a`.\n\n### Rust modules example\n\n"), "{basics}");
+    }
+
+    #[test]
+    fn write_files() {
+        let dir = TestDir::new("book_write_files", &[("book/src/basics.md", "old")]);
+        let mut out = Vec::new();
+        write(&dir.path().join("book"), &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().starts_with(&format!("Wrote {} files to ", book().len())));
+        assert!(fs::read_to_string(dir.path().join("book/src/basics.md")).unwrap().starts_with("# Basics\n"));
+        assert!(dir.path().join("book/book.toml").exists());
+    }
+}
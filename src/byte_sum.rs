@@ -0,0 +1,84 @@
+// # Per-architecture fast paths
+
+// Code using SIMD or other CPU-specific instructions is usually laid out as
+// one module per architecture, plus a portable fallback, all exposing the same
+// function. `#[cfg(target_arch = ...)]` picks the implementation at compile
+// time, and a `pub use` gives it a single path for callers.
+
+// Only compiled for x86_64, from `./byte_sum/x86_64.rs`.
+#[cfg(target_arch = "x86_64")]
+mod x86_64;
+
+// Always compiled, from `./byte_sum/portable.rs`: the fallback for other
+// architectures, a reference to test the fast paths against, and a way to
+// handle the leftover bytes that don't fill a whole SIMD register.
+mod portable;
+
+// Exactly one of these re-exports is compiled, so `byte_sum::sum` means the
+// best implementation for the target.
+#[cfg(target_arch = "x86_64")]
+pub use x86_64::sum;
+
+#[cfg(not(target_arch = "x86_64"))]
+pub use portable::sum;
+
+// Which implementation `sum` is.
+pub const IMPLEMENTATION: &str = if cfg!(target_arch = "x86_64") { "x86_64" } else { "portable" };
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "byte_sum" }
+
+    fn description(&self) -> &'static str {
+        "An x86_64 fast path with a portable fallback, chosen by `cfg(target_arch)`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["cfg", "multi-level", "files"] }
+
+    fn topic(&self) -> Topic { Topic::Cfg }
+
+    fn source(&self) -> &'static str {
+        concat!(include_str!("byte_sum.rs"), "\n",
+                include_str!("byte_sum/x86_64.rs"), "\n",
+                include_str!("byte_sum/portable.rs"))
+    }
+
+    fn run(&self) {
+        let text = "The quick brown fox jumps over the lazy dog";
+        println!("Using the {IMPLEMENTATION} implementation.");
+        println!("sum of the bytes of {text:?} = {}", sum(text.as_bytes()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every length from empty to a few SIMD registers, to cover the leftovers.
+    fn inputs() -> impl Iterator<Item = Vec<u8>> {
+        (0..100).map(|len| (0..len).map(|i| (i * 37 % 256) as u8).collect())
+    }
+
+    #[test]
+    fn matches_portable() {
+        for input in inputs() {
+            assert_eq!(sum(&input), portable::sum(&input), "length {}", input.len());
+        }
+    }
+
+    #[test]
+    fn large_values() {
+        assert_eq!(sum(&[255; 1000]), 255_000);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn x86_64_is_selected() {
+        assert_eq!(IMPLEMENTATION, "x86_64");
+    }
+}
@@ -0,0 +1,5 @@
+// The portable implementation, in plain Rust for any target.
+
+pub fn sum(bytes: &[u8]) -> u64 {
+    bytes.iter().map(|&b| u64::from(b)).sum()
+}
@@ -0,0 +1,30 @@
+// The x86_64 implementation, using SSE2 to add 16 bytes at a time.
+
+// SSE2 is part of the x86_64 baseline, so every x86_64 CPU has it and no
+// runtime feature detection is needed. Architecture-specific intrinsics live
+// in `std::arch::x86_64`, which only exists when building for x86_64: one
+// more reason this module needs its `cfg`.
+use std::arch::x86_64::{__m128i, _mm_add_epi64, _mm_loadu_si128, _mm_sad_epu8,
+                        _mm_setzero_si128, _mm_storeu_si128};
+
+pub fn sum(bytes: &[u8]) -> u64 {
+    let mut chunks = bytes.chunks_exact(16);
+    let mut lanes = [0u64; 2];
+
+    // SAFETY: the loads and stores are unaligned, and each one touches exactly
+    // 16 bytes: a whole chunk, or the two `u64` lanes.
+    unsafe {
+        let zero = _mm_setzero_si128();
+        let mut total = zero;
+        for chunk in &mut chunks {
+            let v = _mm_loadu_si128(chunk.as_ptr().cast::<__m128i>());
+            // The sum of absolute differences from zero adds each group of
+            // eight bytes into a 64 bit lane.
+            total = _mm_add_epi64(total, _mm_sad_epu8(v, zero));
+        }
+        _mm_storeu_si128(lanes.as_mut_ptr().cast::<__m128i>(), total);
+    }
+
+    // The fallback handles any bytes left over after the last whole chunk.
+    lanes[0] + lanes[1] + super::portable::sum(chunks.remainder())
+}
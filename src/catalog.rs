@@ -0,0 +1,56 @@
+// # The generated catalog
+
+// The list of examples isn't written in Rust: it's data in
+// `../spec/examples.toml`, which `../build.rs` turns into this module's
+// contents, `ENTRIES` and `EXAMPLES`. `registry::EXAMPLES` is `EXAMPLES`.
+// Adding an example means writing its module and adding an entry to the spec.
+include!(concat!(env!("OUT_DIR"), "/catalog.rs"));
+
+// The generated code names these without paths, so they must be in scope here.
+use crate::example::{Example, Topic};
+
+// One example's entry in the spec, whether or not it's compiled in.
+pub struct Entry {
+    pub name: &'static str,
+    pub topic: Topic,
+    // The Cargo feature it's behind, if any, and whether that's on.
+    pub feature: Option<&'static str>,
+    pub enabled: bool,
+}
+
+#[cfg(test)]
+// The build script's parser, reused to read the spec at test time.
+#[path = "../build/spec.rs"]
+mod spec;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> Vec<spec::Entry> {
+        spec::parse(include_str!("../spec/examples.toml")).unwrap()
+    }
+
+    #[test]
+    fn entries_match_spec() {
+        let spec = spec();
+        assert_eq!(ENTRIES.len(), spec.len());
+        for (entry, spec) in ENTRIES.iter().zip(&spec) {
+            assert_eq!(entry.name, spec.name);
+            assert_eq!(format!("{:?}", entry.topic), spec.topic);
+            assert_eq!(entry.feature, spec.feature.as_deref());
+        }
+    }
+
+    // The glue names the right items: each compiled-in example reports the
+    // name and topic its spec entry gives, in the same order.
+    #[test]
+    fn examples_match_spec() {
+        let enabled: Vec<&Entry> = ENTRIES.iter().filter(|e| e.enabled).collect();
+        assert_eq!(EXAMPLES.len(), enabled.len());
+        for (example, entry) in EXAMPLES.iter().zip(enabled) {
+            assert_eq!(example.name(), entry.name);
+            assert_eq!(example.topic(), entry.topic);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// # Conditional attributes with `cfg_attr`
+
+// `#[cfg(...)]` keeps or removes a whole item. `#[cfg_attr(predicate, attr)]`
+// keeps the item either way, and only applies `attr` when `predicate` holds.
+
+// The best known use is optional `serde` support in libraries:
+//
+//     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+//     pub struct Settings { ... }
+//
+// so users who don't enable the `serde` feature don't compile `serde` at all.
+// This crate has no `serde` dependency, so the example below gates derives
+// from `std` on the `advanced` feature instead, which works the same way.
+
+// Doctests compile without `cfg(test)`, so the doctest below checks that the
+// `Debug` derive really is only there in tests: formatting with `{:?}` outside
+// of them is error E0277, "`Settings` doesn't implement `Debug`".
+
+/// ```compile_fail,E0277
+/// #[cfg_attr(test, derive(Debug))]
+/// struct Settings;
+///
+/// fn main() {
+///     println!("{:?}", Settings);
+/// }
+/// ```
+pub mod config {
+    // Tests want `assert_eq!`, which needs `Debug` and `PartialEq`, but
+    // normal builds don't have to pay for either.
+    #[cfg_attr(test, derive(Debug, PartialEq))]
+    // With the `advanced` feature, `Settings` can also be cloned and defaulted.
+    #[cfg_attr(feature = "advanced", derive(Clone, Default))]
+    pub struct Settings {
+        pub verbose: bool,
+        pub level: u8,
+    }
+
+    // One `cfg_attr` can apply several attributes, separated by commas:
+    #[cfg_attr(test, derive(Debug), allow(dead_code))]
+    pub struct Unused;
+
+    pub fn quiet() -> Settings {
+        Settings { verbose: false, level: 1 }
+    }
+}
+
+// `cfg_attr` works on `mod` declarations too, most usefully with `path`:
+//
+//     #[cfg_attr(unix, path = "sys/unix.rs")]
+//     #[cfg_attr(windows, path = "sys/windows.rs")]
+//     mod sys;
+//
+// which is a shorter way to write the per-platform `mod platform`
+// declarations in `lib.rs`.
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "cfg_attrs" }
+
+    fn description(&self) -> &'static str {
+        "Applying attributes conditionally with `#[cfg_attr]`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["cfg", "attributes"] }
+
+    fn topic(&self) -> Topic { Topic::Cfg }
+
+    fn source(&self) -> &'static str {
+        include_str!("cfg_attrs.rs")
+    }
+
+    fn run(&self) {
+        let settings = config::quiet();
+        println!("config::quiet() has verbose = {}, level = {}.", settings.verbose, settings.level);
+        println!("`Settings` derives `Debug` only under `cfg(test)`, so it can't be printed with {{:?}} here.");
+        if cfg!(feature = "advanced") {
+            println!("The `advanced` feature is enabled, so `Settings` also derives `Clone` and `Default`.");
+        } else {
+            println!("The `advanced` feature is disabled, so `Settings` doesn't derive `Clone` or `Default`.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::config::{self, Settings};
+
+    // Only compiles because `cfg_attr(test, ...)` derived `Debug` and `PartialEq`.
+    #[test]
+    fn test_only_derives() {
+        assert_eq!(config::quiet(), Settings { verbose: false, level: 1 });
+        assert_eq!(format!("{:?}", config::Unused), "Unused");
+    }
+
+    #[cfg(feature = "advanced")]
+    #[test]
+    fn feature_derives() {
+        let settings = Settings::default();
+        assert_eq!(settings.clone(), Settings { verbose: false, level: 0 });
+    }
+}
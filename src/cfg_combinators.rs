@@ -0,0 +1,111 @@
+// # Combining `cfg` predicates
+
+// `cfg` predicates combine with `all(...)`, `any(...)` and `not(...)`, which
+// nest as deeply as needed. Here each child module is declared several
+// times, and the predicates are written so exactly one declaration of each
+// is compiled on any target.
+
+// Which OS branch is compiled. Together the four predicates cover every
+// target, and no two can be true at once.
+
+// Unix-like, but not macOS: Linux, the BSDs, Android and so on.
+#[cfg(all(unix, not(target_os = "macos")))]
+mod os {
+    pub const BRANCH: &str = "all(unix, not(target_os = \"macos\"))";
+}
+
+// macOS is also `unix`, which is why the first predicate needs `not`.
+#[cfg(target_os = "macos")]
+mod os {
+    pub const BRANCH: &str = "target_os = \"macos\"";
+}
+
+// Windows, or WebAssembly without an OS. Emscripten is both `unix` and
+// `wasm`, so `not(unix)` leaves it to the first branch.
+#[cfg(all(not(unix), any(windows, target_family = "wasm")))]
+mod os {
+    pub const BRANCH: &str = "all(not(unix), any(windows, target_family = \"wasm\"))";
+}
+
+// Everything else: the negation of the union of all the branches above.
+#[cfg(not(any(unix, windows, target_family = "wasm")))]
+mod os {
+    pub const BRANCH: &str = "not(any(unix, windows, target_family = \"wasm\"))";
+}
+
+// A second, independent choice, made with a pair of predicates that are
+// each other's negation, so exactly one holds.
+
+#[cfg(any(target_pointer_width = "16", target_pointer_width = "32"))]
+mod pointer {
+    pub const BRANCH: &str = "any(target_pointer_width = \"16\", target_pointer_width = \"32\")";
+}
+
+#[cfg(not(any(target_pointer_width = "16", target_pointer_width = "32")))]
+mod pointer {
+    pub const BRANCH: &str = "not(any(target_pointer_width = \"16\", target_pointer_width = \"32\"))";
+}
+
+// Report the predicate of each module that was compiled into this build.
+pub fn compiled_branches() -> [&'static str; 2] {
+    [os::BRANCH, pointer::BRANCH]
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "cfg_combinators" }
+
+    fn description(&self) -> &'static str {
+        "Selecting modules with `cfg(all(...))`, `cfg(any(...))` and `cfg(not(...))`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["cfg"] }
+
+    fn topic(&self) -> Topic { Topic::Cfg }
+
+    fn source(&self) -> &'static str {
+        include_str!("cfg_combinators.rs")
+    }
+
+    fn run(&self) {
+        let [os, pointer] = compiled_branches();
+        println!("`mod os` compiled from #[cfg({os})]");
+        println!("`mod pointer` compiled from #[cfg({pointer})]");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compiled_branches;
+
+    // `cfg!` evaluates a predicate to a `bool`, so it can check which
+    // `#[cfg]` declaration was chosen.
+    #[test]
+    fn compiled_branch_matches_cfg() {
+        let [os, pointer] = compiled_branches();
+
+        let expected_os = if cfg!(all(unix, not(target_os = "macos"))) {
+            "all(unix, not(target_os = \"macos\"))"
+        } else if cfg!(target_os = "macos") {
+            "target_os = \"macos\""
+        } else if cfg!(any(windows, target_family = "wasm")) {
+            "all(not(unix), any(windows, target_family = \"wasm\"))"
+        } else {
+            "not(any(unix, windows, target_family = \"wasm\"))"
+        };
+        assert_eq!(os, expected_os);
+
+        assert_eq!(pointer.starts_with("not("), std::mem::size_of::<usize>() >= 8);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux() {
+        assert_eq!(compiled_branches()[0], "all(unix, not(target_os = \"macos\"))");
+    }
+}
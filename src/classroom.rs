@@ -0,0 +1,462 @@
+// The quiz for a whole class, for `serve --classroom`: a small web server
+// on the local network that students join with a name, each answering the
+// same questions in their browser, while the instructor watches the
+// results come in.
+
+// Every page is HTML made here, with plain forms, so it works in any
+// browser without JavaScript. The results page reloads itself every few
+// seconds to stay live.
+//
+// It's meant for a workshop on a network everyone trusts: a student is
+// told apart by a token in their page's address rather than logging in,
+// and the results page's address has a key in it that's only printed on
+// the instructor's terminal. Nothing's saved, so stopping the server ends
+// the session.
+//
+// The key and tokens are secrets, so they can't come from the quiz's
+// shuffling `Rng`: each of its numbers is its whole state, from which the
+// numbers before and after can be worked out, so one student's token would
+// lead to the key and to everyone else's. Each is 128 bits read from the
+// system's randomness instead, with nothing linking one to another.
+//
+// `Classroom::handle` turns a request into a response without any I/O, so
+// the tests below can take a quiz without a network. `serve` does the
+// rest, answering connections on a few threads.
+
+use std::collections::hash_map::RandomState;
+use std::fs::File;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{BufReader, Read};
+use std::net::{IpAddr, Ipv4Addr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+// Reading requests and writing responses, in `./classroom/http.rs`.
+pub mod http;
+
+use http::{Request, Response};
+use crate::quiz::export::{escape_html, html};
+use crate::quiz::report::percent;
+use crate::quiz::{Question, TOPICS};
+
+const MAX_NAME: usize = 40;
+// More than a workshop has, so a script joining over and over can't grow
+// the roster without end.
+const MAX_STUDENTS: usize = 500;
+// The threads answering connections, and how many more connections can
+// wait for one before new ones wait to be accepted.
+const WORKERS: usize = 16;
+const QUEUE: usize = 64;
+// How often the results page reloads, in seconds.
+const REFRESH: u32 = 3;
+
+struct Student {
+    name: String,
+    token: String,
+    // Each answer so far, in order, `None` for a question skipped.
+    answers: Vec<Option<bool>>,
+}
+
+pub struct Classroom {
+    questions: Vec<Question>,
+    // In the address of the results page, so only the instructor finds it.
+    key: String,
+    students: Vec<Student>,
+}
+
+// A new secret, as 32 hex digits.
+fn secret() -> String {
+    let mut bytes = [0; 16];
+    if File::open("/dev/urandom").and_then(|mut random| random.read_exact(&mut bytes)).is_err() {
+        // Where there's no `/dev/urandom`, as on Windows, hash with
+        // `RandomState`, which `std` keys from the system's randomness.
+        // The time, process and count only make each hash different.
+        static COUNT: AtomicU64 = AtomicU64::new(0);
+        for half in bytes.chunks_mut(8) {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u128(SystemTime::UNIX_EPOCH.elapsed().map_or(0, |d| d.as_nanos()));
+            hasher.write_u32(std::process::id());
+            hasher.write_u64(COUNT.fetch_add(1, Ordering::Relaxed));
+            half.copy_from_slice(&hasher.finish().to_le_bytes());
+        }
+    }
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+impl Classroom {
+    // A class taking `questions`.
+    pub fn new(questions: Vec<Question>) -> Classroom {
+        Classroom { questions, key: secret(), students: Vec::new() }
+    }
+
+    // The path of the instructor's results page.
+    pub fn results_path(&self) -> String {
+        format!("/results/{}", self.key)
+    }
+
+    pub fn handle(&mut self, request: &Request) -> Response {
+        let segments: Vec<_> = request.path.split('/').filter(|s| !s.is_empty()).collect();
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", []) => Response::page(200, self.join_page("")),
+            ("POST", ["join"]) => self.join(request.field("name").unwrap_or("")),
+            ("GET", ["student", token]) => match self.student(token) {
+                Some(i) => Response::page(200, self.question_page(i)),
+                None => not_found(),
+            },
+            ("POST", ["student", token, "answer"]) => match self.student(token) {
+                Some(i) => self.answer(i, request),
+                None => not_found(),
+            },
+            ("GET", ["results", key]) if *key == self.key => Response::page(200, self.results_page()),
+            ("GET" | "POST", _) => not_found(),
+            _ => Response::page(405, page("Not allowed", "<p>Only GET and POST are allowed.</p>")),
+        }
+    }
+
+    fn student(&self, token: &str) -> Option<usize> {
+        self.students.iter().position(|s| s.token == token)
+    }
+
+    fn join_page(&self, error: &str) -> String {
+        let error = if error.is_empty() { String::new() } else { format!("<p class=\"error\">{}</p>", html(error)) };
+        page("Join the quiz", &format!("\
+<h1>Join the quiz</h1>
+<p>{} questions on which modules can use what.</p>
+{error}<form method=\"post\" action=\"/join\">
+<label>Your name <input name=\"name\" maxlength=\"{MAX_NAME}\" autofocus></label>
+<button>Join</button>
+</form>", self.questions.len()))
+    }
+
+    fn join(&mut self, name: &str) -> Response {
+        let name = name.trim();
+        let error = if name.is_empty() {
+            "Please enter a name."
+        } else if name.chars().count() > MAX_NAME {
+            "That name's too long."
+        } else if self.students.iter().any(|s| s.name == name) {
+            "Someone's already using that name, please pick another."
+        } else if self.students.len() == MAX_STUDENTS {
+            "Sorry, the class is full."
+        } else {
+            ""
+        };
+        if !error.is_empty() {
+            return Response::page(400, self.join_page(error));
+        }
+        let token = secret();
+        self.students.push(Student { name: name.to_string(), token: token.clone(), answers: Vec::new() });
+        Response::see_other(format!("/student/{token}"))
+    }
+
+    // The next question for student `i`, or their score when they're done.
+    fn question_page(&self, i: usize) -> String {
+        let student = &self.students[i];
+        let n = student.answers.len();
+        let Some(question) = self.questions.get(n) else {
+            return page("Done", &format!("<h1>Done, {}</h1>\n<p>You got {} out of {}.</p>",
+                                        escape_html(&student.name), self.right(student), self.questions.len()));
+        };
+        page(&format!("Question {}", n + 1), &format!("\
+<h1>Question {} of {}</h1>
+<pre>{}</pre>
+<p>{}</p>
+<form method=\"post\" action=\"/student/{}/answer\">
+<input type=\"hidden\" name=\"question\" value=\"{n}\">
+<button name=\"answer\" value=\"yes\">Yes</button>
+<button name=\"answer\" value=\"no\">No</button>
+<button name=\"answer\" value=\"skip\">Skip</button>
+</form>", n + 1, self.questions.len(), escape_html(question.snippet.trim_end()), html(&question.prompt()),
+                                                     student.token))
+    }
+
+    fn answer(&mut self, i: usize, request: &Request) -> Response {
+        let answer = match request.field("answer") {
+            Some("yes") => Some(true),
+            Some("no") => Some(false),
+            Some("skip") => None,
+            _ => return Response::page(400, page("Bad answer", "<p>The answer should be yes, no or skip.</p>")),
+        };
+        let student = &mut self.students[i];
+        let n = student.answers.len();
+        // Posting the same form twice, or an old one, doesn't answer the
+        // next question too.
+        if request.field("question") != Some(&n.to_string()) || n >= self.questions.len() {
+            return Response::see_other(format!("/student/{}", student.token));
+        }
+        student.answers.push(answer);
+
+        let question = &self.questions[n];
+        let yes_no = if question.answer { "yes" } else { "no" };
+        let verdict = match answer {
+            Some(answer) if answer == question.answer => format!("Right, {yes_no}."),
+            Some(_) => format!("Not quite, the answer is {yes_no}."),
+            None => format!("The answer is {yes_no}."),
+        };
+        let next = if n + 1 < self.questions.len() { "Next question" } else { "See your score" };
+        Response::page(200, page(&format!("Question {}", n + 1), &format!("\
+<h1>{verdict}</h1>
+<pre>{}</pre>
+<p>{}</p>
+<p>{}</p>
+<p><a href=\"/student/{}\">{next}</a></p>", escape_html(question.snippet.trim_end()), html(&question.prompt()),
+                                              html(&question.explanation), student.token)))
+    }
+
+    fn right(&self, student: &Student) -> usize {
+        student.answers.iter().zip(&self.questions).filter(|&(answer, q)| *answer == Some(q.answer)).count()
+    }
+
+    // Each student's score, then the class's by topic and by question.
+    fn results_page(&self) -> String {
+        let row = |cells: &[String]| {
+            format!("<tr>{}</tr>\n", cells.iter().map(|c| format!("<td>{c}</td>")).collect::<String>())
+        };
+        let n = self.questions.len();
+
+        let mut students = String::new();
+        for student in &self.students {
+            let right = self.right(student);
+            students += &row(&[escape_html(&student.name), format!("{} of {n}", student.answers.len()),
+                               right.to_string(), percent(right, student.answers.len())]);
+        }
+
+        // How many times question `i` was answered, and answered right.
+        let score = |i: usize| {
+            let answers = self.students.iter().filter_map(|s| s.answers.get(i));
+            answers.fold((0, 0), |(asked, right), &answer| {
+                (asked + 1, right + usize::from(answer == Some(self.questions[i].answer)))
+            })
+        };
+        let mut topics = String::new();
+        for topic in TOPICS {
+            let (asked, right) = (0..n).filter(|&i| self.questions[i].topic == *topic).map(score)
+                .fold((0, 0), |(a, r), (asked, right)| (a + asked, r + right));
+            topics += &row(&[topic.to_string(), asked.to_string(), right.to_string(), percent(right, asked)]);
+        }
+        let mut questions = String::new();
+        for (i, question) in self.questions.iter().enumerate() {
+            let (asked, right) = score(i);
+            questions += &row(&[(i + 1).to_string(), question.topic.to_string(), html(&question.prompt()),
+                                asked.to_string(), right.to_string(), percent(right, asked)]);
+        }
+
+        let joined = match self.students.len() {
+            1 => "1 student has".to_string(),
+            count => format!("{count} students have"),
+        };
+        page("Results", &format!("\
+<meta http-equiv=\"refresh\" content=\"{REFRESH}\">
+<h1>Results</h1>
+<p>{joined} joined. This page updates every {REFRESH} seconds.</p>
+<h2>Students</h2>
+<table>
+<tr><th>Name</th><th>Answered</th><th>Right</th><th>Score</th></tr>
+{students}</table>
+<h2>Topics</h2>
+<table>
+<tr><th>Topic</th><th>Answered</th><th>Right</th><th>Score</th></tr>
+{topics}</table>
+<h2>Questions</h2>
+<table>
+<tr><th>#</th><th>Topic</th><th>Question</th><th>Answered</th><th>Right</th><th>Score</th></tr>
+{questions}</table>"))
+    }
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!("\
+<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<title>{} - Rust modules quiz</title>
+<style>
+body {{ font-family: sans-serif; max-width: 50em; margin: 1em auto; padding: 0 1em; }}
+pre {{ background: #f4f4f4; padding: 1em; }}
+button {{ font-size: 1.2em; margin-right: 0.5em; }}
+td, th {{ padding: 0.2em 0.8em; text-align: left; }}
+.error {{ color: #b00; }}
+</style>
+</head>
+<body>
+{body}
+</body>
+</html>
+", escape_html(title))
+}
+
+fn not_found() -> Response {
+    Response::page(404, page("Not found", "<p>There's nothing here. <a href=\"/\">Join the quiz</a>?</p>"))
+}
+
+// This computer's address on the local network, for students to type in:
+// the one it would send from to reach the internet. Connecting a UDP
+// socket only picks the route, it sends nothing.
+pub fn lan_address() -> IpAddr {
+    let local = || {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.connect("192.0.2.1:9")?;
+        socket.local_addr()
+    };
+    local().map_or(IpAddr::V4(Ipv4Addr::LOCALHOST), |address| address.ip())
+}
+
+// Answers requests on `listener` until the process is stopped.
+pub fn serve(listener: TcpListener, classroom: Classroom) {
+    // A fixed set of workers take connections from a queue in turn, so
+    // however many arrive, only `WORKERS` are answered at once. When the
+    // queue's full, accepting waits for it. The workers take turns with the
+    // classroom, and only borrow it, as `thread::scope` waits for them all
+    // before it's dropped.
+    let classroom = Mutex::new(classroom);
+    let (queue, connections) = mpsc::sync_channel::<TcpStream>(QUEUE);
+    let connections = Mutex::new(connections);
+    thread::scope(|scope| {
+        for _ in 0..WORKERS {
+            scope.spawn(|| loop {
+                let next = connections.lock().unwrap_or_else(PoisonError::into_inner).recv();
+                let Ok(stream) = next else { break };
+                // A connection that fails only affects the one browser.
+                let _ = respond(stream, &classroom);
+            });
+        }
+        for stream in listener.incoming().flatten() {
+            if queue.send(stream).is_err() {
+                break;
+            }
+        }
+        drop(queue);
+    });
+}
+
+fn respond(stream: TcpStream, classroom: &Mutex<Classroom>) -> std::io::Result<()> {
+    // Browsers open connections they may never use, which shouldn't keep a
+    // thread waiting forever.
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let response = match http::read_request(&mut BufReader::new(&stream)) {
+        Ok(request) => classroom.lock().unwrap_or_else(PoisonError::into_inner).handle(&request),
+        Err(e) => Response::page(e.status, page("Bad request", &format!("<p>{}</p>", escape_html(&e.message)))),
+    };
+    response.write_to(&mut &stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::{hand_written, Rng};
+
+    fn get(classroom: &mut Classroom, path: &str) -> Response {
+        classroom.handle(&Request { method: "GET".to_string(), path: path.to_string(), form: vec![] })
+    }
+
+    fn post(classroom: &mut Classroom, path: &str, form: &str) -> Response {
+        classroom.handle(&Request { method: "POST".to_string(), path: path.to_string(), form: http::parse_form(form) })
+    }
+
+    #[test]
+    fn take_the_quiz() {
+        // The answers are no then yes.
+        let mut classroom = Classroom::new(hand_written()[..2].to_vec());
+        assert!(get(&mut classroom, "/").body.contains("<p>2 questions on which modules can use what.</p>"));
+
+        let joined = post(&mut classroom, "/join", "name=+Ada+%3Cb%3E");
+        assert_eq!(joined.status, 303);
+        let student = joined.location.unwrap();
+        let page = get(&mut classroom, &student).body;
+        assert!(page.contains("<h1>Question 1 of 2</h1>\n<pre>mod kitchen {\n    fn cook() {}\n}\nmod dining {}</pre>\n\
+                               <p>Can code in <code>crate::dining</code> call <code>crate::kitchen::cook()</code>?</p>"),
+                "{page}");
+
+        let answer = format!("{student}/answer");
+        let page = post(&mut classroom, &answer, "question=0&answer=yes").body;
+        assert!(page.contains("<h1>Not quite, the answer is no.</h1>"), "{page}");
+        assert!(page.contains(&format!("<a href=\"{student}\">Next question</a>")), "{page}");
+        // Posting the first question again is ignored.
+        assert_eq!(post(&mut classroom, &answer, "question=0&answer=no").location.as_ref(), Some(&student));
+        let page = post(&mut classroom, &answer, "question=1&answer=yes").body;
+        assert!(page.contains("<h1>Right, yes.</h1>"), "{page}");
+        assert!(get(&mut classroom, &student).body.contains("<h1>Done, Ada &lt;b&gt;</h1>\n<p>You got 1 out of 2.</p>"));
+
+        let results = classroom.results_path();
+        let results = get(&mut classroom, &results).body;
+        assert!(results.contains("<p>1 student has joined."), "{results}");
+        assert!(results.contains("<tr><td>Ada &lt;b&gt;</td><td>2 of 2</td><td>1</td><td>50%</td></tr>"), "{results}");
+        assert!(results.contains("<tr><td>visibility</td><td>2</td><td>1</td><td>50%</td></tr>\n\
+                                  <tr><td>paths</td><td>0</td><td>0</td><td>-</td></tr>"), "{results}");
+        assert!(results.contains("<tr><td>1</td><td>visibility</td><td>Can code in <code>crate::dining</code> call \
+                                  <code>crate::kitchen::cook()</code>?</td><td>1</td><td>0</td><td>0%</td></tr>"),
+                "{results}");
+    }
+
+    #[test]
+    fn mistakes() {
+        let mut classroom = Classroom::new(hand_written());
+        assert_eq!(post(&mut classroom, "/join", "name=++").status, 400);
+        assert_eq!(post(&mut classroom, "/join", &format!("name={}", "x".repeat(41))).status, 400);
+        assert_eq!(post(&mut classroom, "/join", "name=Ada").status, 303);
+        let page = post(&mut classroom, "/join", "name=Ada");
+        assert_eq!(page.status, 400);
+        assert!(page.body.contains("Someone's already using that name"), "{}", page.body);
+
+        classroom.students.extend((1..MAX_STUDENTS).map(|i| Student {
+            name: format!("student {i}"),
+            token: secret(),
+            answers: Vec::new(),
+        }));
+        let page = post(&mut classroom, "/join", "name=Latecomer");
+        assert_eq!(page.status, 400);
+        assert!(page.body.contains("Sorry, the class is full."), "{}", page.body);
+
+        assert_eq!(get(&mut classroom, "/student/0123").status, 404);
+        assert_eq!(get(&mut classroom, "/results/0123").status, 404);
+        assert_eq!(get(&mut classroom, "/results").status, 404);
+        let student = classroom.students[0].token.clone();
+        assert_eq!(post(&mut classroom, &format!("/student/{student}/answer"), "question=0&answer=maybe").status, 400);
+        let delete = Request { method: "DELETE".to_string(), path: "/".to_string(), form: vec![] };
+        assert_eq!(classroom.handle(&delete).status, 405);
+    }
+
+    // Undoes one step of `quiz::Rng`, as someone guessing a token from
+    // another might.
+    fn step_back(mut x: u64) -> u64 {
+        // Each `x ^= x << n` is undone by repeating it until the shifted
+        // bits run out, and the same for `>>`.
+        let undo = |x: &mut u64, shift: &dyn Fn(u64) -> u64, n: u32| {
+            let y = *x;
+            for _ in 0..64 / n {
+                *x = y ^ shift(*x);
+            }
+        };
+        undo(&mut x, &|x| x << 17, 17);
+        undo(&mut x, &|x| x >> 7, 7);
+        undo(&mut x, &|x| x << 13, 13);
+        x
+    }
+
+    #[test]
+    fn tokens_are_secret() {
+        let mut classroom = Classroom::new(hand_written());
+        post(&mut classroom, "/join", "name=a");
+        post(&mut classroom, "/join", "name=b");
+        let (a, b) = (&classroom.students[0].token, &classroom.students[1].token);
+        assert_ne!(a, b);
+        assert_eq!(a.len(), 32);
+        assert_ne!(classroom.results_path(), Classroom::new(hand_written()).results_path());
+
+        // Stepping a generator on from any part of a token, or back, leads
+        // to no other secret.
+        let others = [&classroom.key, b];
+        for start in [&a[..16], &a[16..]] {
+            let start = u64::from_str_radix(start, 16).unwrap();
+            assert_eq!(step_back(Rng(start).next()), start);
+            let near = [Rng(start).next(), step_back(start)].map(|x| format!("{x:016x}"));
+            for secret in others {
+                assert!(!near.iter().any(|x| secret.contains(x.as_str())), "{a} leads to {secret}");
+            }
+        }
+    }
+}
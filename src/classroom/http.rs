@@ -0,0 +1,219 @@
+// Just enough HTTP/1.1 for the classroom server, with only `std`: reading a
+// request with a form in its body, and writing a response. A child module
+// of `../classroom.rs`.
+
+// Every response closes the connection, so there's no keep-alive or
+// chunked encoding to handle, and browsers send forms as
+// `application/x-www-form-urlencoded`, so that's the only body read.
+
+use std::io::{self, BufRead, Read, Write};
+
+// Bodies are a name or an answer, so anything bigger is a mistake.
+const MAX_BODY: usize = 16 * 1024;
+const MAX_HEADERS: usize = 100;
+// The longest request line or header. Lines are read only this far, so a
+// line that never ends can't fill the memory.
+const MAX_LINE: u64 = 8 * 1024;
+
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    // Without any query string.
+    pub path: String,
+    pub form: Vec<(String, String)>,
+}
+
+impl Request {
+    // The value of `name` in the request's form.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.form.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+}
+
+// Why a request couldn't be read, with the status to answer it with.
+#[derive(Debug, PartialEq)]
+pub struct BadRequest {
+    pub status: u16,
+    pub message: String,
+}
+
+impl From<io::Error> for BadRequest {
+    fn from(e: io::Error) -> BadRequest {
+        invalid(&e.to_string())
+    }
+}
+
+fn invalid(message: &str) -> BadRequest {
+    BadRequest { status: 400, message: message.to_string() }
+}
+
+// Reads a line into `line`, as `read_line` does, unless it's longer than
+// `MAX_LINE`. `Ok(false)` if the input had ended.
+fn read_line(input: &mut dyn BufRead, line: &mut String, too_long: BadRequest) -> Result<bool, BadRequest> {
+    line.clear();
+    let read = input.take(MAX_LINE).read_line(line)?;
+    if read as u64 == MAX_LINE && !line.ends_with('\n') {
+        return Err(too_long);
+    }
+    Ok(read > 0)
+}
+
+pub fn read_request(input: &mut dyn BufRead) -> Result<Request, BadRequest> {
+    let mut line = String::new();
+    read_line(input, &mut line, invalid("the request line is too long"))?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target), Some(_version)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid("expected a request line like `GET / HTTP/1.1`"));
+    };
+    let path = target.split('?').next().unwrap_or(target).to_string();
+    let method = method.to_string();
+
+    let mut length = 0;
+    let mut form_body = false;
+    let too_large = |message: &str| BadRequest { status: 431, message: message.to_string() };
+    // The headers end at an empty line.
+    for headers in 0.. {
+        if !read_line(input, &mut line, too_large("a header is too long"))? {
+            return Err(invalid("the headers didn't end"));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if headers == MAX_HEADERS {
+            return Err(too_large("too many headers"));
+        }
+        let (name, value) = header.split_once(':').ok_or_else(|| invalid("expected a header like `Name: value`"))?;
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            length = value.parse().map_err(|_| invalid("bad Content-Length"))?;
+        } else if name.eq_ignore_ascii_case("content-type") {
+            form_body = value.starts_with("application/x-www-form-urlencoded");
+        }
+    }
+    if length > MAX_BODY {
+        return Err(invalid("the body is too big"));
+    }
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    let form = if form_body { parse_form(&String::from_utf8_lossy(&body)) } else { Vec::new() };
+    Ok(Request { method, path, form })
+}
+
+// `a=1&b=two+words` as pairs, decoding `%XX` escapes.
+pub fn parse_form(body: &str) -> Vec<(String, String)> {
+    body.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(name), decode(value))
+        })
+        .collect()
+}
+
+fn decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = || std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok().and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex()) {
+            (b'+', _) => out.push(b' '),
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 2;
+            }
+            (byte, _) => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    // Where a redirect goes.
+    pub location: Option<String>,
+    pub body: String,
+}
+
+impl Response {
+    pub fn page(status: u16, body: String) -> Response {
+        Response { status, location: None, body }
+    }
+
+    // After a form is posted, a "303 See Other" sends the browser to get
+    // `location`, so reloading the page doesn't post the form again.
+    pub fn see_other(location: String) -> Response {
+        Response { status: 303, location: Some(location), body: String::new() }
+    }
+
+    pub fn write_to(&self, out: &mut dyn Write) -> io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            303 => "See Other",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            431 => "Request Header Fields Too Large",
+            _ => "",
+        };
+        write!(out, "HTTP/1.1 {} {reason}\r\nContent-Type: text/html; charset=utf-8\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n", self.status, self.body.len())?;
+        if let Some(location) = &self.location {
+            write!(out, "Location: {location}\r\n")?;
+        }
+        write!(out, "\r\n{}", self.body)?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests() {
+        let request = read_request(&mut "GET /results/abc?x=1 HTTP/1.1\r\nHost: here\r\n\r\n".as_bytes()).unwrap();
+        assert_eq!(request, Request { method: "GET".to_string(), path: "/results/abc".to_string(), form: vec![] });
+
+        let text = "POST /join HTTP/1.1\r\nContent-Type: application/x-www-form-urlencoded\r\n\
+                    content-length: 26\r\n\r\nname=Ada+L%C3%B6we&x=%2&y=";
+        let request = read_request(&mut text.as_bytes()).unwrap();
+        assert_eq!(request.field("name"), Some("Ada Löwe"));
+        assert_eq!(request.field("x"), Some("%2"));
+        assert_eq!(request.field("y"), Some(""));
+        assert_eq!(request.field("z"), None);
+    }
+
+    #[test]
+    fn bad_requests() {
+        let error = |text: &str| read_request(&mut text.as_bytes()).unwrap_err().message;
+        assert_eq!(error("\r\n"), "expected a request line like `GET / HTTP/1.1`");
+        assert_eq!(error("GET / HTTP/1.1\r\nHost: here\r\n"), "the headers didn't end");
+        assert_eq!(error("GET / HTTP/1.1\r\nHost\r\n\r\n"), "expected a header like `Name: value`");
+        assert_eq!(error("POST / HTTP/1.1\r\nContent-Length: 99999999\r\n\r\n"), "the body is too big");
+
+        // Lines are only read so far, however long they go on.
+        let status = |text: &str| read_request(&mut text.as_bytes()).unwrap_err().status;
+        let long = "x".repeat(MAX_LINE as usize);
+        assert_eq!(error(&format!("GET /{long}")), "the request line is too long");
+        assert_eq!(status(&format!("GET /{long}")), 400);
+        assert_eq!(error(&format!("GET / HTTP/1.1\r\nA: {long}")), "a header is too long");
+        assert_eq!(status(&format!("GET / HTTP/1.1\r\nA: {long}")), 431);
+        assert_eq!(status(&format!("GET / HTTP/1.1\r\n{}\r\n", "A: b\r\n".repeat(101))), 431);
+        assert!(read_request(&mut format!("GET / HTTP/1.1\r\nA: {}\r\n\r\n", &long[10..]).as_bytes()).is_ok());
+        assert!(read_request(&mut "POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\nab".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn responses() {
+        let mut out = Vec::new();
+        Response::see_other("/student/1".to_string()).write_to(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "HTTP/1.1 303 See Other\r\n\
+                                                      Content-Type: text/html; charset=utf-8\r\n\
+                                                      Content-Length: 0\r\nConnection: close\r\n\
+                                                      Location: /student/1\r\n\r\n");
+    }
+}
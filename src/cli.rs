@@ -0,0 +1,767 @@
+// Command line parsing for the example runner.
+
+// This is hand-rolled rather than using a crate like `clap`, so the
+// example has no dependencies and stays easy to read.
+
+use modules_demo::modtree::project::Layout;
+use modules_demo::modtree::scaffold::Style;
+use modules_demo::modtree::{Format, Import};
+use modules_demo::quiz::export::DeckFormat;
+
+pub const USAGE: &str = "\
+Usage: rust-module-example [COMMAND]
+
+Commands:
+  list           List every example with a short description
+  run <EXAMPLE>  Run a single example and print its output
+  tour [--record FILE | --replay FILE]
+                 Step through every example in teaching order, running
+                 others or answering quiz questions on the way. With
+                 `--record`, save the session as JSON in FILE, and with
+                 `--replay`, play a saved one back
+  quiz [--count N] [--seed SEED] [--bank FILE]... [--results FILE]
+                 Answer N (10 by default) questions on whether a module
+                 can call a function, each explained. SEED picks the
+                 questions, so the same one repeats a quiz. Questions
+                 come from the built-in ones, `questions/*.toml`, and
+                 each bank FILE. The score for each topic is saved as
+                 JSON in the results FILE, beside the progress file by
+                 default
+  quiz export [--format FORMAT] [--bank FILE]...
+                 Print every question as a flashcard, for spaced
+                 repetition. FORMAT is anki (the default), tab-separated
+                 text for Anki's File > Import, or csv
+  report [FILE]  Show the scores saved by the last quiz, or in the
+                 results FILE, as a table by topic
+  export book [DIR]
+                 Write the examples' annotated source as an mdBook in
+                 DIR, `book/` by default, a chapter for each topic
+  serve --classroom [--port PORT] [--count N] [--seed SEED] [--bank FILE]...
+                 Host the quiz for a class on the local network, on
+                 PORT (8080 by default). Students join in a browser with
+                 their name, and a page only the instructor is shown
+                 has everyone's results as they come in
+  verify [--watch] [DIR]
+                 Compile the exercises in DIR, `exercises/` by default,
+                 in order, stopping at the first that needs fixing. With
+                 `--watch`, check it again whenever a file is saved
+  progress       Sum up the examples run, exercises fixed and quiz
+                 questions answered right so far
+  hint <EXERCISE> [--level N] [DIR]
+                 Show the first N (1 by default) of an exercise's hints:
+                 the concept, where to look, then the exact fix
+  tree [--format FORMAT] [PATH]
+                 Print the module tree of the crate at PATH, which
+                 defaults to this crate. FORMAT is text (the default),
+                 dot for Graphviz, json, or mermaid for Markdown
+  tree diff <OLD> <NEW>
+                 Compare the module trees of two crates, usually one
+                 crate before and after a change: modules added,
+                 removed or moved, and visibility changes
+  analyze <ANALYSIS> [PATH]
+                 Analyze the crate at PATH, which defaults to this
+                 crate. ANALYSIS is one of:
+                   deps        which modules `use` which, and any cycles
+                   unused-pub  visible items nothing in the crate uses
+                   orphans     .rs files no `mod` declaration loads
+                   visible-from <ITEM>
+                               the modules that can name ITEM, e.g.
+                               `crate::a::f`
+                   api         every path another crate can name
+                   effective-vis <ITEM>
+                               how far ITEM can really be seen, given
+                               its modules' visibility and re-exports
+  which-file <MODULE> [PATH]
+                 Print the files a module such as `crate::a::b` can be
+                 loaded from, in the crate at PATH, and which exists
+  resolve [--from MODULE] [--] USE_PATH [PATH]
+                 Follow a path like `super::f` the way the compiler
+                 would, as written in MODULE (`crate` by default), and
+                 say what it names and whether it's visible there
+  scaffold new-module <NAME> [--style STYLE] [--parent MODULE] [--tests] [PATH]
+                 Create module NAME in MODULE (`crate` by default) of the
+                 crate at PATH: its file, in STYLE 2018 (the default) or
+                 mod-rs, and its `mod` declaration. With `--tests`, the
+                 file starts with a `tests` module
+  scaffold prelude [--count N] [--dry-run] [PATH]
+                 Add a `prelude` module re-exporting the N (10 by default)
+                 public items the crate's code names most. With
+                 `--dry-run`, only print the `pub use`s it would have
+  scaffold project <NAME> [--layout LAYOUT] [DIR]
+                 Create a starter crate NAME in DIR (the current one by
+                 default), its modules in LAYOUT: flat (the default),
+                 layered or feature-sliced
+  refactor <REFACTOR> [PATH]
+                 Change the modules of the crate at PATH, which defaults
+                 to this crate. REFACTOR is one of:
+                   convert-style <MODULE> [--to STYLE]
+                               move MODULE and its descendants' files
+                               to STYLE, 2018 or mod-rs, by default the
+                               one MODULE doesn't use
+                   extract <MODULE> [--style STYLE]
+                               move inline module MODULE to a file of
+                               its own, in STYLE, 2018 by default
+                   inline <MODULE>
+                               move MODULE's file into its parent, as
+                               an inline module
+                   rename <MODULE> <NAME>
+                               rename MODULE to NAME: its files, its
+                               `mod` declaration and paths to it
+                   move-item <ITEM> --to <MODULE>
+                               move ITEM, e.g. `crate::a::f`, to
+                               MODULE, leaving a `use` of it behind
+                   flatten-reexports [--facade NAME]
+                               add module NAME, `facade` by default, re-
+                               exporting each item the crate re-exports
+                               from where it's defined, and list the
+                               re-exports that makes redundant
+                   split <MODULE> [--by kind]
+                               move MODULE's types, functions and
+                               traits to new children `types`, `fns`
+                               and `traits`, re-exported from MODULE
+  browse         Browse the module tree and source in the terminal,
+                 when built with `--features tui`
+  help           Print this message
+
+With no command, prints a greeting and runs a few examples.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    // No arguments, run the library's short `greet` demo.
+    Default,
+    List,
+    Run { name: String },
+    // At most one of `record` and `replay` is set.
+    Tour { record: Option<String>, replay: Option<String> },
+    // `seed` is `None` for a different quiz each time.
+    // `results` is `None` to save them beside the progress file.
+    Quiz { count: usize, seed: Option<u64>, banks: Vec<String>, results: Option<String> },
+    QuizExport { format: DeckFormat, banks: Vec<String> },
+    Report { file: Option<String> },
+    ExportBook { dir: Option<String> },
+    // `serve --classroom`, the only mode of `serve` so far.
+    Serve { port: u16, count: usize, seed: Option<u64>, banks: Vec<String> },
+    Verify { watch: bool, dir: Option<String> },
+    Progress,
+    Hint { exercise: String, level: usize, dir: Option<String> },
+    // `path` may be a package directory or a crate root file.
+    Tree { path: Option<String>, format: Format },
+    TreeDiff { old: String, new: String },
+    Analyze { analysis: Analysis, path: Option<String> },
+    WhichFile { module: String, path: Option<String> },
+    Resolve { from: String, use_path: Import, path: Option<String> },
+    Refactor { refactor: Refactor, path: Option<String> },
+    NewModule { name: String, style: Style, parent: String, tests: bool, path: Option<String> },
+    Prelude { count: usize, dry_run: bool, path: Option<String> },
+    // `dir` is where the new crate's directory goes.
+    Project { name: String, layout: Layout, dir: Option<String> },
+    #[cfg(feature = "tui")]
+    Browse,
+    Help,
+}
+
+// What `analyze` reports on.
+#[derive(Debug, PartialEq)]
+pub enum Analysis {
+    Deps,
+    UnusedPub,
+    Orphans,
+    VisibleFrom { item: String },
+    EffectiveVis { item: String },
+    Api,
+}
+
+// What `refactor` changes.
+#[derive(Debug, PartialEq)]
+pub enum Refactor {
+    ConvertStyle { module: String, to: Option<Style> },
+    Extract { module: String, style: Style },
+    Inline { module: String },
+    Rename { module: String, new_name: String },
+    MoveItem { item: String, to: String },
+    FlattenReexports { facade: String },
+    Split { module: String },
+}
+
+// `args` should not include the program name, i.e. pass `std::env::args().skip(1)`.
+pub fn parse<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+
+    let command = match args.next().as_deref() {
+        None => Command::Default,
+        Some("help" | "-h" | "--help") => Command::Help,
+        Some("list") => Command::List,
+        Some("run") => match args.next() {
+            Some(name) => Command::Run { name },
+            None => return Err("`run` requires an example name".to_string()),
+        },
+        Some("tour") => {
+            let mut record = None;
+            let mut replay = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--record" => record = Some(args.next().ok_or("`--record` requires a file")?),
+                    "--replay" => replay = Some(args.next().ok_or("`--replay` requires a file")?),
+                    _ => return Err(format!("unexpected argument '{arg}'")),
+                }
+            }
+            if record.is_some() && replay.is_some() {
+                return Err("`tour` can't `--record` and `--replay` at once".to_string());
+            }
+            Command::Tour { record, replay }
+        }
+        Some("quiz") if args.peek().map(String::as_str) == Some("export") => {
+            args.next();
+            let mut format = DeckFormat::Anki;
+            let mut banks = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--format" => {
+                        let value = args.next().ok_or("`--format` requires a value")?;
+                        format = value.parse()?;
+                    }
+                    "--bank" => banks.push(args.next().ok_or("`--bank` requires a file")?),
+                    _ => return Err(format!("unexpected argument '{arg}'")),
+                }
+            }
+            Command::QuizExport { format, banks }
+        }
+        Some("quiz") => {
+            let mut count = 10;
+            let mut seed = None;
+            let mut banks = Vec::new();
+            let mut results = None;
+            while let Some(arg) = args.next() {
+                let mut number = |flag: &str| {
+                    let value = args.next().ok_or(format!("`{flag}` requires a number"))?;
+                    value.parse::<u64>().map_err(|_| format!("'{value}' isn't a number"))
+                };
+                match arg.as_str() {
+                    "--count" => count = number("--count")? as usize,
+                    "--seed" => seed = Some(number("--seed")?),
+                    "--bank" => banks.push(args.next().ok_or("`--bank` requires a file")?),
+                    "--results" => results = Some(args.next().ok_or("`--results` requires a file")?),
+                    _ => return Err(format!("unexpected argument '{arg}'")),
+                }
+            }
+            Command::Quiz { count, seed, banks, results }
+        }
+        Some("report") => Command::Report { file: args.next() },
+        Some("verify") => {
+            let mut watch = false;
+            let mut dir = None;
+            for arg in args.by_ref() {
+                match arg.as_str() {
+                    "--watch" => watch = true,
+                    _ if dir.is_none() && !arg.starts_with('-') => dir = Some(arg),
+                    _ => return Err(format!("unexpected argument '{arg}'")),
+                }
+            }
+            Command::Verify { watch, dir }
+        }
+        Some("export") => match args.next().as_deref() {
+            Some("book") => {
+                let dir = args.next();
+                if let Some(extra) = args.next() {
+                    return Err(format!("unexpected argument '{extra}'"));
+                }
+                Command::ExportBook { dir }
+            }
+            _ => return Err("`export` requires what to export: `book`".to_string()),
+        },
+        Some("serve") => {
+            let mut classroom = false;
+            let mut port = 8080;
+            let mut count = 10;
+            let mut seed = None;
+            let mut banks = Vec::new();
+            while let Some(arg) = args.next() {
+                let mut number = |flag: &str| {
+                    let value = args.next().ok_or(format!("`{flag}` requires a number"))?;
+                    value.parse::<u64>().map_err(|_| format!("'{value}' isn't a number"))
+                };
+                match arg.as_str() {
+                    "--classroom" => classroom = true,
+                    "--port" => {
+                        let value = number("--port")?;
+                        port = u16::try_from(value).map_err(|_| format!("'{value}' isn't a port number"))?;
+                    }
+                    "--count" => count = number("--count")? as usize,
+                    "--seed" => seed = Some(number("--seed")?),
+                    "--bank" => banks.push(args.next().ok_or("`--bank` requires a file")?),
+                    _ => return Err(format!("unexpected argument '{arg}'")),
+                }
+            }
+            if !classroom {
+                return Err("`serve` requires `--classroom`, the only mode there is so far".to_string());
+            }
+            Command::Serve { port, count, seed, banks }
+        }
+        Some("progress") => Command::Progress,
+        Some("hint") => {
+            let exercise = args.next().ok_or("`hint` requires an exercise, e.g. `01_private_function`")?;
+            let mut level = 1;
+            let mut dir = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--level" => {
+                        let value = args.next().ok_or("`--level` requires a number")?;
+                        level = value.parse().map_err(|_| format!("'{value}' isn't a number"))?;
+                    }
+                    _ if dir.is_none() && !arg.starts_with('-') => dir = Some(arg),
+                    _ => return Err(format!("unexpected argument '{arg}'")),
+                }
+            }
+            Command::Hint { exercise, level, dir }
+        }
+        #[cfg(feature = "tui")]
+        Some("browse") => Command::Browse,
+        #[cfg(not(feature = "tui"))]
+        Some("browse") => return Err("`browse` requires building with `--features tui`".to_string()),
+        Some("tree") if args.peek().map(String::as_str) == Some("diff") => {
+            args.next();
+            match (args.next(), args.next()) {
+                (Some(old), Some(new)) => Command::TreeDiff { old, new },
+                _ => return Err("`tree diff` requires two crate paths".to_string()),
+            }
+        }
+        Some("tree") => {
+            let mut path = None;
+            let mut format = Format::Text;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--format" => {
+                        let value = args.next().ok_or("`--format` requires a value")?;
+                        format = value.parse()?;
+                    }
+                    _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+                    _ => return Err(format!("unexpected argument '{arg}'")),
+                }
+            }
+            Command::Tree { path, format }
+        }
+        Some("analyze") => {
+            let analysis = match args.next().as_deref() {
+                Some("deps") => Analysis::Deps,
+                Some("unused-pub") => Analysis::UnusedPub,
+                Some("orphans") => Analysis::Orphans,
+                Some("visible-from") => match args.next() {
+                    Some(item) => Analysis::VisibleFrom { item },
+                    None => return Err("`visible-from` requires an item path".to_string()),
+                },
+                Some("api") => Analysis::Api,
+                Some("effective-vis") => match args.next() {
+                    Some(item) => Analysis::EffectiveVis { item },
+                    None => return Err("`effective-vis` requires an item path".to_string()),
+                },
+                Some(other) => return Err(format!("unknown analysis '{other}'")),
+                None => return Err("`analyze` requires an analysis, e.g. `deps`".to_string()),
+            };
+            Command::Analyze { analysis, path: args.next() }
+        }
+        Some("which-file") => match args.next() {
+            Some(module) => Command::WhichFile { module, path: args.next() },
+            None => return Err("`which-file` requires a module path, e.g. `crate::a`".to_string()),
+        },
+        Some("resolve") => {
+            let mut from = "crate".to_string();
+            let mut positional = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--from" if positional.is_empty() => {
+                        from = args.next().ok_or("`--from` requires a module path")?;
+                    }
+                    "--" if positional.is_empty() => positional.extend(args.by_ref()),
+                    _ => positional.push(arg),
+                }
+            }
+            let mut positional = positional.into_iter();
+            let use_path = positional.next()
+                .ok_or("`resolve` requires a path to resolve, e.g. `super::f`")?
+                .parse()?;
+            let path = positional.next();
+            if let Some(extra) = positional.next() {
+                return Err(format!("unexpected argument '{extra}'"));
+            }
+            Command::Resolve { from, use_path, path }
+        }
+        Some("refactor") => {
+            let refactor = match args.next().as_deref() {
+                Some("convert-style") => {
+                    let module = args.next().ok_or("`convert-style` requires a module path")?;
+                    let mut to = None;
+                    if args.peek().map(String::as_str) == Some("--to") {
+                        args.next();
+                        to = Some(args.next().ok_or("`--to` requires a style")?.parse()?);
+                    }
+                    Refactor::ConvertStyle { module, to }
+                }
+                Some("extract") => {
+                    let module = args.next().ok_or("`extract` requires a module path")?;
+                    let mut style = Style::Edition2018;
+                    if args.peek().map(String::as_str) == Some("--style") {
+                        args.next();
+                        style = args.next().ok_or("`--style` requires a value")?.parse()?;
+                    }
+                    Refactor::Extract { module, style }
+                }
+                Some("inline") => match args.next() {
+                    Some(module) => Refactor::Inline { module },
+                    None => return Err("`inline` requires a module path".to_string()),
+                },
+                Some("rename") => match (args.next(), args.next()) {
+                    (Some(module), Some(new_name)) => Refactor::Rename { module, new_name },
+                    _ => return Err("`rename` requires a module path and a new name".to_string()),
+                },
+                Some("move-item") => {
+                    let item = args.next().ok_or("`move-item` requires an item path")?;
+                    if args.next().as_deref() != Some("--to") {
+                        return Err("`move-item` requires `--to MODULE`".to_string());
+                    }
+                    let to = args.next().ok_or("`--to` requires a module path")?;
+                    Refactor::MoveItem { item, to }
+                }
+                Some("flatten-reexports") => {
+                    let mut facade = "facade".to_string();
+                    if args.peek().map(String::as_str) == Some("--facade") {
+                        args.next();
+                        facade = args.next().ok_or("`--facade` requires a module name")?;
+                    }
+                    Refactor::FlattenReexports { facade }
+                }
+                Some("split") => {
+                    let module = args.next().ok_or("`split` requires a module path")?;
+                    if args.peek().map(String::as_str) == Some("--by") {
+                        args.next();
+                        match args.next().as_deref() {
+                            Some("kind") => {}
+                            Some(other) => return Err(format!("unknown split '{other}', expected kind")),
+                            None => return Err("`--by` requires a value".to_string()),
+                        }
+                    }
+                    Refactor::Split { module }
+                }
+                Some(other) => return Err(format!("unknown refactor '{other}'")),
+                None => return Err("`refactor` requires a refactor, e.g. `convert-style`".to_string()),
+            };
+            Command::Refactor { refactor, path: args.next() }
+        }
+        Some("scaffold") => match args.next().as_deref() {
+            Some("new-module") => {
+                let name = args.next().ok_or("`new-module` requires a module name")?;
+                let mut style = Style::Edition2018;
+                let mut parent = "crate".to_string();
+                let mut tests = false;
+                let mut path = None;
+                while let Some(arg) = args.next() {
+                    match arg.as_str() {
+                        "--style" => style = args.next().ok_or("`--style` requires a value")?.parse()?,
+                        "--parent" => parent = args.next().ok_or("`--parent` requires a module path")?,
+                        "--tests" => tests = true,
+                        _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+                        _ => return Err(format!("unexpected argument '{arg}'")),
+                    }
+                }
+                Command::NewModule { name, style, parent, tests, path }
+            }
+            Some("prelude") => {
+                let mut count = 10;
+                let mut dry_run = false;
+                let mut path = None;
+                while let Some(arg) = args.next() {
+                    match arg.as_str() {
+                        "--count" => {
+                            let value = args.next().ok_or("`--count` requires a number")?;
+                            count = value.parse().map_err(|_| format!("'{value}' isn't a number"))?;
+                        }
+                        "--dry-run" => dry_run = true,
+                        _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+                        _ => return Err(format!("unexpected argument '{arg}'")),
+                    }
+                }
+                Command::Prelude { count, dry_run, path }
+            }
+            Some("project") => {
+                let name = args.next().ok_or("`project` requires a crate name")?;
+                let mut layout = Layout::Flat;
+                let mut dir = None;
+                while let Some(arg) = args.next() {
+                    match arg.as_str() {
+                        "--layout" => layout = args.next().ok_or("`--layout` requires a value")?.parse()?,
+                        _ if dir.is_none() && !arg.starts_with('-') => dir = Some(arg),
+                        _ => return Err(format!("unexpected argument '{arg}'")),
+                    }
+                }
+                Command::Project { name, layout, dir }
+            }
+            Some(other) => return Err(format!("unknown scaffold '{other}'")),
+            None => return Err("`scaffold` requires what to create, e.g. `new-module`".to_string()),
+        },
+        Some(other) => return Err(format!("unknown command '{other}'")),
+    };
+
+    if let Some(extra) = args.next() {
+        return Err(format!("unexpected argument '{extra}'"));
+    }
+
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Command, String> {
+        parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn no_args() {
+        assert_eq!(parse_strs(&[]), Ok(Command::Default));
+    }
+
+    #[test]
+    fn list() {
+        assert_eq!(parse_strs(&["list"]), Ok(Command::List));
+    }
+
+    #[test]
+    fn run() {
+        assert_eq!(parse_strs(&["run", "use_examples"]),
+                   Ok(Command::Run { name: "use_examples".to_string() }));
+        assert!(parse_strs(&["run"]).is_err());
+        assert!(parse_strs(&["run", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn tour() {
+        assert_eq!(parse_strs(&["tour"]), Ok(Command::Tour { record: None, replay: None }));
+        assert_eq!(parse_strs(&["tour", "--record", "s.json"]),
+                   Ok(Command::Tour { record: Some("s.json".to_string()), replay: None }));
+        assert_eq!(parse_strs(&["tour", "--replay", "s.json"]),
+                   Ok(Command::Tour { record: None, replay: Some("s.json".to_string()) }));
+        assert!(parse_strs(&["tour", "--record"]).is_err());
+        assert!(parse_strs(&["tour", "--record", "a.json", "--replay", "b.json"]).is_err());
+        assert!(parse_strs(&["tour", "extra"]).is_err());
+    }
+
+    #[test]
+    fn quiz() {
+        assert_eq!(parse_strs(&["quiz"]), Ok(Command::Quiz { count: 10, seed: None, banks: vec![], results: None }));
+        assert_eq!(parse_strs(&["quiz", "--seed", "7", "--count", "3"]),
+                   Ok(Command::Quiz { count: 3, seed: Some(7), banks: vec![], results: None }));
+        assert_eq!(parse_strs(&["quiz", "--bank", "a.toml", "--bank", "b.toml", "--results", "r.json"]),
+                   Ok(Command::Quiz { count: 10, seed: None, banks: vec!["a.toml".to_string(), "b.toml".to_string()],
+                                      results: Some("r.json".to_string()) }));
+        assert!(parse_strs(&["quiz", "--bank"]).is_err());
+        assert!(parse_strs(&["quiz", "--results"]).is_err());
+        assert!(parse_strs(&["quiz", "--count"]).is_err());
+        assert!(parse_strs(&["quiz", "--seed", "-1"]).is_err());
+        assert!(parse_strs(&["quiz", "extra"]).is_err());
+        assert_eq!(parse_strs(&["quiz", "export"]), Ok(Command::QuizExport { format: DeckFormat::Anki, banks: vec![] }));
+        assert_eq!(parse_strs(&["quiz", "export", "--bank", "a.toml", "--format", "csv"]),
+                   Ok(Command::QuizExport { format: DeckFormat::Csv, banks: vec!["a.toml".to_string()] }));
+        assert!(parse_strs(&["quiz", "export", "--format", "apkg"]).is_err());
+        assert!(parse_strs(&["quiz", "export", "--count", "3"]).is_err());
+        assert_eq!(parse_strs(&["report"]), Ok(Command::Report { file: None }));
+        assert_eq!(parse_strs(&["report", "r.json"]), Ok(Command::Report { file: Some("r.json".to_string()) }));
+        assert!(parse_strs(&["report", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn export_book() {
+        assert_eq!(parse_strs(&["export", "book"]), Ok(Command::ExportBook { dir: None }));
+        assert_eq!(parse_strs(&["export", "book", "out"]), Ok(Command::ExportBook { dir: Some("out".to_string()) }));
+        assert!(parse_strs(&["export"]).is_err());
+        assert!(parse_strs(&["export", "pdf"]).is_err());
+        assert!(parse_strs(&["export", "book", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn serve() {
+        assert_eq!(parse_strs(&["serve", "--classroom"]),
+                   Ok(Command::Serve { port: 8080, count: 10, seed: None, banks: vec![] }));
+        assert_eq!(parse_strs(&["serve", "--port", "0", "--classroom", "--count", "5", "--seed", "2", "--bank", "a.toml"]),
+                   Ok(Command::Serve { port: 0, count: 5, seed: Some(2), banks: vec!["a.toml".to_string()] }));
+        assert!(parse_strs(&["serve"]).is_err());
+        assert!(parse_strs(&["serve", "--classroom", "--port", "65536"]).is_err());
+        assert!(parse_strs(&["serve", "--classroom", "--port"]).is_err());
+    }
+
+    #[test]
+    fn verify() {
+        assert_eq!(parse_strs(&["verify"]), Ok(Command::Verify { watch: false, dir: None }));
+        assert_eq!(parse_strs(&["verify", "mine"]),
+                   Ok(Command::Verify { watch: false, dir: Some("mine".to_string()) }));
+        assert_eq!(parse_strs(&["verify", "--watch"]), Ok(Command::Verify { watch: true, dir: None }));
+        assert!(parse_strs(&["verify", "--wait"]).is_err());
+        assert!(parse_strs(&["verify", "a", "b"]).is_err());
+        assert_eq!(parse_strs(&["progress"]), Ok(Command::Progress));
+        assert_eq!(parse_strs(&["hint", "3", "--level", "2"]),
+                   Ok(Command::Hint { exercise: "3".to_string(), level: 2, dir: None }));
+        assert!(parse_strs(&["hint"]).is_err());
+        assert!(parse_strs(&["hint", "3", "--level", "all"]).is_err());
+    }
+
+    #[test]
+    fn tree() {
+        assert_eq!(parse_strs(&["tree"]), Ok(Command::Tree { path: None, format: Format::Text }));
+        assert_eq!(parse_strs(&["tree", "src/main.rs", "--format", "dot"]),
+                   Ok(Command::Tree { path: Some("src/main.rs".to_string()),
+                                      format: Format::Dot }));
+        assert!(parse_strs(&["tree", "--format"]).is_err());
+        assert!(parse_strs(&["tree", "--format", "png"]).is_err());
+        assert!(parse_strs(&["tree", "a", "b"]).is_err());
+        assert_eq!(parse_strs(&["tree", "diff", "old", "new"]),
+                   Ok(Command::TreeDiff { old: "old".to_string(), new: "new".to_string() }));
+        assert!(parse_strs(&["tree", "diff", "old"]).is_err());
+        assert!(parse_strs(&["tree", "diff", "a", "b", "c"]).is_err());
+    }
+
+    #[test]
+    fn analyze() {
+        assert_eq!(parse_strs(&["analyze", "deps"]),
+                   Ok(Command::Analyze { analysis: Analysis::Deps, path: None }));
+        assert_eq!(parse_strs(&["analyze", "deps", "crates/core"]),
+                   Ok(Command::Analyze { analysis: Analysis::Deps,
+                                         path: Some("crates/core".to_string()) }));
+        assert_eq!(parse_strs(&["analyze", "unused-pub"]),
+                   Ok(Command::Analyze { analysis: Analysis::UnusedPub, path: None }));
+        assert_eq!(parse_strs(&["analyze", "orphans", "."]),
+                   Ok(Command::Analyze { analysis: Analysis::Orphans, path: Some(".".to_string()) }));
+        assert_eq!(parse_strs(&["analyze", "visible-from", "crate::a::f"]),
+                   Ok(Command::Analyze { analysis: Analysis::VisibleFrom { item: "crate::a::f".to_string() },
+                                         path: None }));
+        assert!(parse_strs(&["analyze", "visible-from"]).is_err());
+        assert_eq!(parse_strs(&["analyze", "effective-vis", "a::f", "."]),
+                   Ok(Command::Analyze { analysis: Analysis::EffectiveVis { item: "a::f".to_string() },
+                                         path: Some(".".to_string()) }));
+        assert!(parse_strs(&["analyze", "effective-vis"]).is_err());
+        assert_eq!(parse_strs(&["analyze", "api"]),
+                   Ok(Command::Analyze { analysis: Analysis::Api, path: None }));
+        assert!(parse_strs(&["analyze"]).is_err());
+        assert!(parse_strs(&["analyze", "everything"]).is_err());
+        assert!(parse_strs(&["analyze", "deps", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn which_file() {
+        assert_eq!(parse_strs(&["which-file", "crate::a"]),
+                   Ok(Command::WhichFile { module: "crate::a".to_string(), path: None }));
+        assert_eq!(parse_strs(&["which-file", "crate::a", "crates/core"]),
+                   Ok(Command::WhichFile { module: "crate::a".to_string(),
+                                           path: Some("crates/core".to_string()) }));
+        assert!(parse_strs(&["which-file"]).is_err());
+    }
+
+    #[test]
+    fn resolve() {
+        let resolve = |from: &str, use_path: &str, path: Option<&str>| Ok(Command::Resolve {
+            from: from.to_string(),
+            use_path: use_path.parse().unwrap(),
+            path: path.map(String::from),
+        });
+        assert_eq!(parse_strs(&["resolve", "--from", "crate::use_examples", "--", "super::f"]),
+                   resolve("crate::use_examples", "super::f", None));
+        assert_eq!(parse_strs(&["resolve", "a::b", "crates/core"]),
+                   resolve("crate", "a::b", Some("crates/core")));
+        assert!(parse_strs(&["resolve"]).is_err());
+        assert!(parse_strs(&["resolve", "--from"]).is_err());
+        assert!(parse_strs(&["resolve", "a::{b, c}"]).is_err());
+        assert!(parse_strs(&["resolve", "a", "b", "c"]).is_err());
+    }
+
+    #[test]
+    fn refactor() {
+        assert_eq!(parse_strs(&["refactor", "convert-style", "crate::a"]),
+                   Ok(Command::Refactor { refactor: Refactor::ConvertStyle { module: "crate::a".to_string(),
+                                                                             to: None },
+                                          path: None }));
+        assert_eq!(parse_strs(&["refactor", "convert-style", "crate", "--to", "mod-rs", "my_crate"]),
+                   Ok(Command::Refactor { refactor: Refactor::ConvertStyle { module: "crate".to_string(),
+                                                                             to: Some(Style::ModRs) },
+                                          path: Some("my_crate".to_string()) }));
+        assert!(parse_strs(&["refactor"]).is_err());
+        assert!(parse_strs(&["refactor", "tidy"]).is_err());
+        assert!(parse_strs(&["refactor", "convert-style"]).is_err());
+        assert!(parse_strs(&["refactor", "convert-style", "crate", "--to"]).is_err());
+        assert!(parse_strs(&["refactor", "convert-style", "crate", "--to", "2015"]).is_err());
+        assert_eq!(parse_strs(&["refactor", "extract", "crate::tests"]),
+                   Ok(Command::Refactor { refactor: Refactor::Extract { module: "crate::tests".to_string(),
+                                                                        style: Style::Edition2018 },
+                                          path: None }));
+        assert_eq!(parse_strs(&["refactor", "extract", "crate::tests", "--style", "mod-rs", "."]),
+                   Ok(Command::Refactor { refactor: Refactor::Extract { module: "crate::tests".to_string(),
+                                                                        style: Style::ModRs },
+                                          path: Some(".".to_string()) }));
+        assert!(parse_strs(&["refactor", "extract"]).is_err());
+        assert_eq!(parse_strs(&["refactor", "inline", "crate::a"]),
+                   Ok(Command::Refactor { refactor: Refactor::Inline { module: "crate::a".to_string() },
+                                          path: None }));
+        assert!(parse_strs(&["refactor", "inline"]).is_err());
+        assert_eq!(parse_strs(&["refactor", "rename", "crate::a", "b"]),
+                   Ok(Command::Refactor { refactor: Refactor::Rename { module: "crate::a".to_string(),
+                                                                       new_name: "b".to_string() },
+                                          path: None }));
+        assert!(parse_strs(&["refactor", "rename", "crate::a"]).is_err());
+        assert_eq!(parse_strs(&["refactor", "move-item", "crate::a::f", "--to", "crate::b"]),
+                   Ok(Command::Refactor { refactor: Refactor::MoveItem { item: "crate::a::f".to_string(),
+                                                                         to: "crate::b".to_string() },
+                                          path: None }));
+        assert!(parse_strs(&["refactor", "move-item", "crate::a::f", "crate::b"]).is_err());
+        assert!(parse_strs(&["refactor", "move-item", "crate::a::f", "--to"]).is_err());
+        assert_eq!(parse_strs(&["refactor", "flatten-reexports"]),
+                   Ok(Command::Refactor { refactor: Refactor::FlattenReexports { facade: "facade".to_string() },
+                                          path: None }));
+        assert_eq!(parse_strs(&["refactor", "flatten-reexports", "--facade", "api", "."]),
+                   Ok(Command::Refactor { refactor: Refactor::FlattenReexports { facade: "api".to_string() },
+                                          path: Some(".".to_string()) }));
+        assert!(parse_strs(&["refactor", "flatten-reexports", "--facade"]).is_err());
+        assert_eq!(parse_strs(&["refactor", "split", "crate::a", "--by", "kind"]),
+                   Ok(Command::Refactor { refactor: Refactor::Split { module: "crate::a".to_string() },
+                                          path: None }));
+        assert!(parse_strs(&["refactor", "split", "crate::a", "--by", "size"]).is_err());
+    }
+
+    #[test]
+    fn scaffold() {
+        assert_eq!(parse_strs(&["scaffold", "new-module", "shapes"]),
+                   Ok(Command::NewModule { name: "shapes".to_string(), style: Style::Edition2018,
+                                           parent: "crate".to_string(), tests: false, path: None }));
+        assert_eq!(parse_strs(&["scaffold", "new-module", "round", "--style", "mod-rs",
+                                "--parent", "crate::shapes", "--tests", "my_crate"]),
+                   Ok(Command::NewModule { name: "round".to_string(), style: Style::ModRs,
+                                           parent: "crate::shapes".to_string(), tests: true,
+                                           path: Some("my_crate".to_string()) }));
+        assert!(parse_strs(&["scaffold"]).is_err());
+        assert!(parse_strs(&["scaffold", "new-crate"]).is_err());
+        assert!(parse_strs(&["scaffold", "new-module"]).is_err());
+        assert!(parse_strs(&["scaffold", "new-module", "a", "--style", "2015"]).is_err());
+        assert!(parse_strs(&["scaffold", "new-module", "a", "b", "c"]).is_err());
+        assert_eq!(parse_strs(&["scaffold", "prelude"]),
+                   Ok(Command::Prelude { count: 10, dry_run: false, path: None }));
+        assert_eq!(parse_strs(&["scaffold", "prelude", "--dry-run", "my_crate", "--count", "3"]),
+                   Ok(Command::Prelude { count: 3, dry_run: true, path: Some("my_crate".to_string()) }));
+        assert!(parse_strs(&["scaffold", "prelude", "--count", "many"]).is_err());
+        assert_eq!(parse_strs(&["scaffold", "project", "tasks"]),
+                   Ok(Command::Project { name: "tasks".to_string(), layout: Layout::Flat, dir: None }));
+        assert_eq!(parse_strs(&["scaffold", "project", "tasks", "/tmp", "--layout", "feature-sliced"]),
+                   Ok(Command::Project { name: "tasks".to_string(), layout: Layout::FeatureSliced,
+                                         dir: Some("/tmp".to_string()) }));
+        assert!(parse_strs(&["scaffold", "project"]).is_err());
+        assert!(parse_strs(&["scaffold", "project", "tasks", "--layout", "hexagonal"]).is_err());
+    }
+
+    #[test]
+    fn help() {
+        assert_eq!(parse_strs(&["--help"]), Ok(Command::Help));
+    }
+
+    #[test]
+    fn unknown() {
+        assert!(parse_strs(&["frobnicate"]).is_err());
+    }
+}
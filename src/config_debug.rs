@@ -0,0 +1,45 @@
+// Settings for debug builds, e.g. `cargo build` or `cargo run`.
+// `lib.rs` loads this file as `crate::config` when `debug_assertions` is on.
+
+pub const PROFILE: &str = "debug";
+
+// How much to log: 0 is errors only, 3 is everything.
+pub const LOG_LEVEL: u8 = 3;
+
+// Expensive consistency checks are worth it while developing.
+pub const CHECK_INVARIANTS: bool = true;
+
+use crate::example::{self, Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "config" }
+
+    fn description(&self) -> &'static str {
+        "Selecting a module per build profile with `cfg(debug_assertions)`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["cfg", "files"] }
+
+    fn topic(&self) -> Topic { Topic::Cfg }
+
+    fn source(&self) -> &'static str {
+        example::section(example::LIB_RS, "Advanced usage")
+    }
+
+    fn run(&self) {
+        println!("This code lives in `./config_debug.rs`, loaded as `crate::config`.");
+        println!("PROFILE = {PROFILE:?}, LOG_LEVEL = {LOG_LEVEL}, CHECK_INVARIANTS = {CHECK_INVARIANTS}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn selected_by_profile() {
+        assert_eq!(super::PROFILE == "debug", cfg!(debug_assertions));
+    }
+}
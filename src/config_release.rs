@@ -0,0 +1,45 @@
+// Settings for release builds, e.g. `cargo build --release`.
+// `lib.rs` loads this file as `crate::config` when `debug_assertions` is off.
+
+pub const PROFILE: &str = "release";
+
+// How much to log: 0 is errors only, 3 is everything.
+pub const LOG_LEVEL: u8 = 1;
+
+// Skip expensive consistency checks in production.
+pub const CHECK_INVARIANTS: bool = false;
+
+use crate::example::{self, Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "config" }
+
+    fn description(&self) -> &'static str {
+        "Selecting a module per build profile with `cfg(debug_assertions)`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["cfg", "files"] }
+
+    fn topic(&self) -> Topic { Topic::Cfg }
+
+    fn source(&self) -> &'static str {
+        example::section(example::LIB_RS, "Advanced usage")
+    }
+
+    fn run(&self) {
+        println!("This code lives in `./config_release.rs`, loaded as `crate::config`.");
+        println!("PROFILE = {PROFILE:?}, LOG_LEVEL = {LOG_LEVEL}, CHECK_INVARIANTS = {CHECK_INVARIANTS}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn selected_by_profile() {
+        assert_eq!(super::PROFILE == "debug", cfg!(debug_assertions));
+    }
+}
@@ -0,0 +1,122 @@
+// # Moving an item without breaking users
+
+// Sooner or later an item lives in the wrong module. Moving it changes its
+// path, and every user's `use` of the old path stops compiling. The usual
+// fix is to move the item, and leave a re-export or forwarding item at the
+// old path marked `#[deprecated]`, so old code still builds but can get a
+// warning pointing at the new path. A later major version can then delete it.
+
+// The item's new home.
+pub mod new_location {
+    pub struct Level(pub u8);
+
+    pub fn parse_level(s: &str) -> Option<Level> {
+        s.parse().ok().filter(|&n| n <= 5).map(Level)
+    }
+}
+
+// The old path, kept working for now.
+pub mod old_location {
+    // One catch: rustc accepts `#[deprecated]` on a `pub use`, but currently
+    // ignores it, so using `old_location::Level` gives no warning at all.
+    // The first doctest below checks that.
+    //
+    // A type alias or a forwarding function is a separate item, so
+    // `#[deprecated]` on one of those does warn, as the second doctest checks.
+
+    /// ```
+    /// #![deny(deprecated)]
+    /// mod new_location {
+    ///     pub struct Level(pub u8);
+    /// }
+    ///
+    /// mod old_location {
+    ///     #[deprecated(note = "moved to `new_location::Level`")]
+    ///     pub use super::new_location::Level;
+    /// }
+    ///
+    /// fn main() {
+    ///     let _ = old_location::Level(1);
+    /// }
+    /// ```
+    #[deprecated(since = "0.2.0", note = "moved to `new_location::Level`")]
+    pub use super::new_location::Level;
+
+    /// ```compile_fail
+    /// #![deny(deprecated)]
+    /// mod new_location {
+    ///     pub fn parse_level(s: &str) -> Option<u8> {
+    ///         s.parse().ok()
+    ///     }
+    /// }
+    ///
+    /// mod old_location {
+    ///     #[deprecated(note = "moved to `new_location::parse_level`")]
+    ///     pub fn parse_level(s: &str) -> Option<u8> {
+    ///         super::new_location::parse_level(s)
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     old_location::parse_level("1");
+    /// }
+    /// ```
+    #[deprecated(since = "0.2.0", note = "moved to `new_location::parse_level`")]
+    pub fn parse_level(s: &str) -> Option<super::new_location::Level> {
+        super::new_location::parse_level(s)
+    }
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "deprecated_reexport" }
+
+    fn description(&self) -> &'static str {
+        "Moving an item and keeping a `#[deprecated]` path at the old location"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["use", "re-export", "deprecated"] }
+
+    fn topic(&self) -> Topic { Topic::Use }
+
+    fn source(&self) -> &'static str {
+        include_str!("deprecated_reexport.rs")
+    }
+
+    fn run(&self) {
+        let level = new_location::parse_level("3").map(|l| l.0);
+        println!("new_location::parse_level(\"3\") = {level:?}");
+
+        // Code that hasn't migrated yet still compiles, with a warning
+        // unless it opts out like this.
+        #[allow(deprecated)]
+        let level = old_location::parse_level("4").map(|l| l.0);
+        println!("old_location::parse_level(\"4\") = {level:?}, with a deprecation warning");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{new_location, old_location};
+
+    #[test]
+    fn new_path() {
+        assert_eq!(new_location::parse_level("5").map(|l| l.0), Some(5));
+        assert!(new_location::parse_level("6").is_none());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn old_paths_still_work() {
+        assert_eq!(old_location::parse_level("2").map(|l| l.0), Some(2));
+
+        // The re-export names the same type, so values pass between old and new code.
+        let level: new_location::Level = old_location::Level(1);
+        assert_eq!(level.0, 1);
+    }
+}
@@ -0,0 +1,123 @@
+// The interface shared by every demonstration module, so the runner can
+// list and run them without knowing anything else about them.
+
+// `Sync` is required so examples can be stored in the `static` registry.
+pub trait Example: Sync {
+    // Unique name used on the command line, e.g. `run name_resolution`.
+    fn name(&self) -> &'static str;
+
+    // A one-line summary of what the example demonstrates.
+    fn description(&self) -> &'static str;
+
+    // Short topic labels, e.g. "visibility", shown by the `list` command.
+    fn tags(&self) -> &'static [&'static str];
+
+    // Where the example fits in the guided tour.
+    fn topic(&self) -> Topic;
+
+    // The annotated source explaining the example, shown by the tour.
+    fn source(&self) -> &'static str;
+
+    // Print a walkthrough of the example to stdout.
+    fn run(&self);
+}
+
+// Topics in the order they are taught, each building on the ones before.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Topic {
+    Basics,
+    MultiLevel,
+    Visibility,
+    Use,
+    Cfg,
+    Macros,
+    Generated,
+}
+
+// An example made from a plain function, usually by the `#[example]`
+// attribute macro from the `derive-example` crate, so it needs no struct or
+// `impl Example` of its own.
+pub struct FnExample {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub tags: &'static [&'static str],
+    pub topic: Topic,
+    // A function rather than a `&str`, so it can call `section`, which isn't `const`.
+    pub source: fn() -> &'static str,
+    pub run: fn(),
+}
+
+impl Example for FnExample {
+    fn name(&self) -> &'static str { self.name }
+
+    fn description(&self) -> &'static str { self.description }
+
+    fn tags(&self) -> &'static [&'static str] { self.tags }
+
+    fn topic(&self) -> Topic { self.topic }
+
+    fn source(&self) -> &'static str { (self.source)() }
+
+    fn run(&self) { (self.run)() }
+}
+
+// The annotated walkthrough in `lib.rs`, which most examples take their
+// `source()` from.
+pub const LIB_RS: &str = include_str!("lib.rs");
+
+// Pick out the part of `text` under the heading comment `// # heading` or
+// `// ## heading`, up to the next heading at one of those levels.
+// `### ` sub-headings are included in the section.
+//
+// Panics if the heading isn't found, which a test in each example catches.
+pub fn section(text: &'static str, heading: &str) -> &'static str {
+    let is_heading = |line: &str| line.starts_with("// # ") || line.starts_with("// ## ");
+
+    let mut start = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if let Some(start) = start {
+            if is_heading(trimmed) {
+                return &text[start..offset];
+            }
+        } else if is_heading(trimmed) && trimmed.trim_start_matches(['/', '#', ' ']) == heading {
+            start = Some(offset);
+        }
+        offset += line.len();
+    }
+
+    match start {
+        Some(start) => &text[start..],
+        None => panic!("heading '{heading}' not found"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "\
+// # Top
+intro
+// ## First
+one
+// ### Sub
+sub
+// ## Second
+two
+";
+
+    #[test]
+    fn section_runs_to_next_heading() {
+        assert_eq!(section(TEXT, "Top"), "// # Top\nintro\n");
+        assert_eq!(section(TEXT, "First"), "// ## First\none\n// ### Sub\nsub\n");
+        assert_eq!(section(TEXT, "Second"), "// ## Second\ntwo\n");
+    }
+
+    #[test]
+    #[should_panic]
+    fn section_missing() {
+        section(TEXT, "Sub");
+    }
+}
@@ -0,0 +1,383 @@
+// The hands-on exercises in `../exercises/`, for the `verify` command, in
+// the style of rustlings: each is a small crate that doesn't compile yet,
+// and the learner fixes them one by one.
+
+// Each exercise is a directory, run in the order of their names, with its
+// crate root in `lib.rs` and any other modules' files next to it. The root
+// starts with the error it fails with, as `// expect: E0603`, then a
+// comment saying what to fix. `verify` compiles them in order with
+// `rustc`, as `../tests/compile_fail.rs` does, and stops at the first
+// that fails, showing the compiler's error and that comment.
+//
+// Next to `lib.rs`, `hints.md` has a hint for each of `HINT_LEVELS`, under
+// `## Concept` and so on, each giving more away than the last, so `hint`
+// can reveal as little as gets the learner unstuck.
+//
+// `verify --watch` keeps going after a failure, and checks again whenever a
+// file in the exercises changes, from the exercise that failed. The `notify`
+// crate says when one does, using each OS's own file events.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+// The headings in `hints.md`, in the order they're revealed: the idea the
+// exercise is about, where the problem is, and exactly how to fix it.
+pub const HINT_LEVELS: [&str; 3] = ["Concept", "Location", "Fix"];
+
+#[derive(Debug, PartialEq)]
+pub struct Exercise {
+    pub name: String,
+    // The exercise's `lib.rs`.
+    pub root: PathBuf,
+}
+
+impl Exercise {
+    // The error code the exercise fails with until it's fixed.
+    pub fn expected_error(&self) -> io::Result<Option<String>> {
+        let text = fs::read_to_string(&self.root)?;
+        Ok(text.lines().next().and_then(|l| l.strip_prefix("// expect: ")).map(str::to_string))
+    }
+
+    // What to fix: the comment at the top of `lib.rs`, after the expected
+    // error, as one line per paragraph.
+    pub fn task(&self) -> io::Result<String> {
+        let text = fs::read_to_string(&self.root)?;
+        let comment = text.lines()
+            .skip_while(|l| l.starts_with("// expect: ") || l.trim().is_empty())
+            .map_while(|l| l.strip_prefix("//"))
+            .map(str::trim)
+            .collect::<Vec<_>>();
+        Ok(comment.split(|l| l.is_empty())
+            .map(|paragraph| paragraph.join(" "))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    // The text under each of `HINT_LEVELS` in `hints.md`.
+    pub fn hints(&self) -> io::Result<Vec<String>> {
+        let path = self.root.with_file_name("hints.md");
+        let text = fs::read_to_string(&path)?;
+        let invalid = |message: String| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {message}", path.display()))
+        };
+        let mut hints: Vec<(&str, Vec<&str>)> = Vec::new();
+        for line in text.lines() {
+            match (line.strip_prefix("## "), hints.last_mut()) {
+                (Some(heading), _) => hints.push((heading.trim(), Vec::new())),
+                (None, Some((_, lines))) => lines.push(line),
+                (None, None) if line.trim().is_empty() => {}
+                (None, None) => return Err(invalid("expected a `## Concept` heading first".to_string())),
+            }
+        }
+        let headings: Vec<_> = hints.iter().map(|(heading, _)| *heading).collect();
+        if headings != HINT_LEVELS {
+            return Err(invalid(format!("expected the headings {HINT_LEVELS:?}, found {headings:?}")));
+        }
+        Ok(hints.into_iter().map(|(_, lines)| lines.join("\n").trim().to_string()).collect())
+    }
+
+    // Compiles the exercise as a library, with paths in errors relative to
+    // `base`. `Err` has the compiler's errors if it fails.
+    pub fn compile(&self, base: &Path, out_dir: &Path) -> io::Result<Result<(), String>> {
+        // `RUSTC` is set when run by Cargo; otherwise use the `rustc` on the
+        // `PATH`.
+        let output = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()))
+            .args(["--edition", "2021", "--crate-type", "lib", "--crate-name", "exercise"])
+            .args(["--emit", "metadata", "-A", "warnings", "--out-dir"])
+            .arg(out_dir)
+            .arg(self.root.strip_prefix(base).unwrap_or(&self.root))
+            .current_dir(base)
+            .output()?;
+        Ok(match output.status.success() {
+            true => Ok(()),
+            false => Err(String::from_utf8_lossy(&output.stderr).into_owned()),
+        })
+    }
+}
+
+// The exercises in `dir`, in order.
+pub fn list(dir: &Path) -> io::Result<Vec<Exercise>> {
+    let mut exercises = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let root = path.join("lib.rs");
+        if root.is_file() {
+            let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+            exercises.push(Exercise { name, root });
+        }
+    }
+    exercises.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(exercises)
+}
+
+// The exercise in `dir` called `name`, which may leave out its number, as in
+// `private_function`, or be only the number, as in `1`.
+pub fn find(dir: &Path, name: &str) -> io::Result<Exercise> {
+    let exercises = list(dir)?;
+    let number = name.parse::<u32>().ok();
+    let position = exercises.iter().position(|e| {
+        let (n, rest) = e.name.split_once('_').unwrap_or(("", &e.name));
+        e.name == name || rest == name || number.is_some() && n.parse().ok() == number
+    });
+    match position {
+        Some(i) => Ok(exercises.into_iter().nth(i).expect("found")),
+        None => {
+            let names: Vec<_> = exercises.iter().map(|e| e.name.as_str()).collect();
+            Err(io::Error::new(io::ErrorKind::NotFound,
+                               format!("no exercise '{name}', expected one of {}", names.join(", "))))
+        }
+    }
+}
+
+// Prints the first `level` hints for exercise `name` in `dir`.
+pub fn hint(dir: &Path, name: &str, level: usize, out: &mut dyn Write) -> io::Result<()> {
+    let exercise = find(dir, name)?;
+    let hints = exercise.hints()?;
+    let level = level.clamp(1, hints.len());
+    for (i, hint) in hints[..level].iter().enumerate() {
+        writeln!(out, "Hint {} of {}, {}:", i + 1, hints.len(), HINT_LEVELS[i].to_lowercase())?;
+        for line in hint.lines() {
+            writeln!(out, "  {line}")?;
+        }
+        writeln!(out)?;
+    }
+    match level < hints.len() {
+        true => writeln!(out, "Still stuck? `hint {} --level {}` tells you more.", exercise.name, level + 1),
+        false => writeln!(out, "That's every hint. Run `verify` again once it's fixed."),
+    }
+}
+
+// What `verify` found: the exercises that compiled, in order, and the
+// first that didn't, if any.
+#[derive(Debug, PartialEq)]
+pub struct Verified {
+    pub compiled: Vec<String>,
+    pub failed: Option<String>,
+}
+
+// Where the first error in rustc's output `stderr` is, as `path:line:column`.
+fn location(stderr: &str) -> Option<&str> {
+    stderr.lines().find_map(|l| l.trim_start().strip_prefix("--> "))
+}
+
+// Compiles the exercises in `dir` in order, stopping at the first that
+// fails.
+pub fn verify(dir: &Path, out: &mut dyn Write) -> io::Result<Verified> {
+    verify_from(dir, None, out)
+}
+
+// Like `verify`, but skipping the exercises before `first`.
+fn verify_from(dir: &Path, first: Option<&str>, out: &mut dyn Write) -> io::Result<Verified> {
+    let exercises = list(dir)?;
+    if exercises.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("no exercises in '{}'", dir.display())));
+    }
+    let base = dir.parent().unwrap_or(dir);
+    let out_dir = std::env::temp_dir().join(format!("rust-module-example-verify-{}", std::process::id()));
+
+    let mut verified = Verified { compiled: Vec::new(), failed: None };
+    let mut result = Ok(());
+    let start = first.and_then(|name| exercises.iter().position(|e| e.name == name)).unwrap_or(0);
+    for (done, exercise) in exercises.iter().enumerate().skip(start) {
+        match exercise.compile(base, &out_dir) {
+            Ok(Ok(())) => {
+                writeln!(out, "Compiled {}", exercise.name)?;
+                verified.compiled.push(exercise.name.clone());
+            }
+            Ok(Err(stderr)) => {
+                writeln!(out, "{} doesn't compile yet:\n\n{}", exercise.name, stderr.trim_end())?;
+                let root = exercise.root.strip_prefix(base).unwrap_or(&exercise.root).display().to_string();
+                writeln!(out, "\nFix it, starting at {}:", location(&stderr).unwrap_or(&root))?;
+                for paragraph in exercise.task()?.lines() {
+                    writeln!(out, "  {paragraph}")?;
+                }
+                writeln!(out, "\n{done} of {} exercises done. Stuck? Try `hint {}`.", exercises.len(),
+                         exercise.name)?;
+                verified.failed = Some(exercise.name.clone());
+                break;
+            }
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+    let _ = fs::remove_dir_all(&out_dir);
+    result?;
+    if verified.failed.is_none() {
+        writeln!(out, "All {} exercises compile. Well done!", exercises.len())?;
+    }
+    Ok(verified)
+}
+
+// Editors often save a file in several steps, each an event of its own, so
+// the events that come this soon after a change are part of it.
+const SETTLE: Duration = Duration::from_millis(100);
+
+// The path of the next file created, changed or removed, or `None` if
+// there's no change within `timeout`.
+fn next_change(events: &Receiver<notify::Result<Event>>, timeout: Option<Duration>) -> io::Result<Option<PathBuf>> {
+    loop {
+        let event = match timeout {
+            None => events.recv().map_err(|_| io::Error::other("stopped watching for changes"))?,
+            Some(timeout) => match events.recv_timeout(timeout) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => return Ok(None),
+                Err(RecvTimeoutError::Disconnected) => return Err(io::Error::other("stopped watching for changes")),
+            },
+        };
+        let event = event.map_err(io::Error::other)?;
+        // Reading a file, or only its permissions changing, isn't a change
+        // to check.
+        match event.kind {
+            EventKind::Modify(ModifyKind::Metadata(_)) => {}
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
+                return Ok(event.paths.into_iter().next());
+            }
+            _ => {}
+        }
+    }
+}
+
+// Verifies the exercises in `dir`, then again from the one that failed each
+// time a file changes, until they all compile. `on_verified` is called with
+// each result.
+pub fn watch(dir: &Path, out: &mut dyn Write, on_verified: &mut dyn FnMut(&Verified)) -> io::Result<()> {
+    // Watching starts before the first check, so a change made while it
+    // runs isn't missed.
+    let (sender, events) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(io::Error::other)?;
+    watcher.watch(dir, RecursiveMode::Recursive).map_err(io::Error::other)?;
+
+    let mut first = None;
+    loop {
+        let verified = verify_from(dir, first.as_deref(), out)?;
+        on_verified(&verified);
+        first = match verified.failed {
+            Some(failed) => Some(failed),
+            None => return Ok(()),
+        };
+        writeln!(out, "\nWatching {} for changes. Press Ctrl-C to stop.", dir.display())?;
+        out.flush()?;
+        let changed = next_change(&events, None)?;
+        while next_change(&events, Some(SETTLE))?.is_some() {}
+        let base = dir.parent().unwrap_or(dir);
+        let changed = changed.as_deref().map(|path| path.strip_prefix(base).unwrap_or(path));
+        writeln!(out, "\n{} changed, verifying again.\n", changed.map_or(dir.display(), |p| p.display()))?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    #[test]
+    fn task_and_error() {
+        let dir = TestDir::new("exercises_task", &[
+            ("exercises/01_a/lib.rs", "// expect: E0603\n\n// Make it\n// work.\n//\n// Then stop.\n\nfn f() {}\n// Not this.\n"),
+        ]);
+        let exercises = list(&dir.path().join("exercises")).unwrap();
+        assert_eq!(exercises[0].name, "01_a");
+        assert_eq!(exercises[0].expected_error().unwrap(), Some("E0603".to_string()));
+        assert_eq!(exercises[0].task().unwrap(), "Make it work.\nThen stop.");
+    }
+
+    #[test]
+    fn watch_until_fixed() {
+        let dir = TestDir::new("exercises_watch", &[
+            ("exercises/01_fixed/lib.rs", "pub fn f() {}\n"),
+            ("exercises/02_broken/lib.rs", "mod a { fn f() {} }\npub fn g() { a::f() }\n"),
+        ]);
+        let exercises = dir.path().join("exercises");
+        let broken = exercises.join("02_broken/lib.rs");
+        let mut out = Vec::new();
+        let mut results = Vec::new();
+        // The fix is made once the first check's done, as a learner would,
+        // for watching to find.
+        watch(&exercises, &mut out, &mut |v| {
+            if results.is_empty() {
+                fs::write(&broken, "mod a { pub fn f() {} }\npub fn g() { a::f() }\n").unwrap();
+            }
+            results.push(v.compiled.clone());
+        }).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Watching "), "{out}");
+        assert!(out.ends_with("\n\nexercises/02_broken/lib.rs changed, verifying again.\n\n\
+                               Compiled 02_broken\nAll 2 exercises compile. Well done!\n"), "{out}");
+        assert_eq!(results, [vec!["01_fixed".to_string()], vec!["02_broken".to_string()]]);
+    }
+
+    #[test]
+    fn hints() {
+        let hints = "## Concept\n\nIdea,\nover two lines.\n\n## Location\n\nHere.\n\n## Fix\n\nThis.\n";
+        let dir = TestDir::new("exercises_hints", &[
+            ("exercises/01_private_function/lib.rs", ""),
+            ("exercises/01_private_function/hints.md", hints),
+            ("exercises/02_bad/lib.rs", ""),
+            ("exercises/02_bad/hints.md", "## Concept\n\nIdea.\n\n## Fix\n\nThis.\n"),
+        ]);
+        let dir = &dir.path().join("exercises");
+        for name in ["01_private_function", "private_function", "1", "01"] {
+            assert_eq!(find(dir, name).unwrap().name, "01_private_function");
+        }
+        assert_eq!(find(dir, "3").unwrap_err().to_string(),
+                   "no exercise '3', expected one of 01_private_function, 02_bad");
+        assert_eq!(find(dir, "1").unwrap().hints().unwrap(), ["Idea,\nover two lines.", "Here.", "This."]);
+        assert!(find(dir, "bad").unwrap().hints().unwrap_err().to_string()
+            .ends_with("expected the headings [\"Concept\", \"Location\", \"Fix\"], found [\"Concept\", \"Fix\"]"));
+
+        let mut out = Vec::new();
+        hint(dir, "1", 2, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+Hint 1 of 3, concept:
+  Idea,
+  over two lines.
+
+Hint 2 of 3, location:
+  Here.
+
+Still stuck? `hint 01_private_function --level 3` tells you more.
+");
+        let mut out = Vec::new();
+        hint(dir, "1", 9, &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().ends_with("  This.\n\nThat's every hint. Run `verify` again once \
+                                                            it's fixed.\n"));
+    }
+
+    #[test]
+    fn stops_at_first_failure() {
+        let dir = TestDir::new("exercises_verify", &[
+            ("exercises/01_fixed/lib.rs", "// expect: E0603\n\n// Done.\n\nmod a { pub fn f() {} }\npub fn g() { a::f() }\n"),
+            ("exercises/02_broken/lib.rs", "// expect: E0603\n\n// Make `f` visible.\n\n\
+                                            mod a { fn f() {} }\npub fn g() { a::f() }\n"),
+            ("exercises/03_later/lib.rs", "fn main() {\n"),
+            ("exercises/notes.txt", ""),
+        ]);
+        let mut out = Vec::new();
+        assert_eq!(verify(&dir.path().join("exercises"), &mut out).unwrap(),
+                   Verified { compiled: vec!["01_fixed".to_string()], failed: Some("02_broken".to_string()) });
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("Compiled 01_fixed\n02_broken doesn't compile yet:\n\nerror[E0603]"), "{out}");
+        assert!(out.ends_with("\
+Fix it, starting at exercises/02_broken/lib.rs:6:17:
+  Make `f` visible.
+
+1 of 3 exercises done. Stuck? Try `hint 02_broken`.
+"), "{out}");
+
+        let dir = TestDir::new("exercises_verify_done", &[("exercises/01_fixed/lib.rs", "pub fn f() {}\n")]);
+        let mut out = Vec::new();
+        assert_eq!(verify(&dir.path().join("exercises"), &mut out).unwrap().failed, None);
+        assert_eq!(String::from_utf8(out).unwrap(), "Compiled 01_fixed\nAll 1 exercises compile. Well done!\n");
+        assert!(verify(&dir.path().join("nowhere"), &mut Vec::new()).is_err());
+    }
+}
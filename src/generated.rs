@@ -0,0 +1,80 @@
+// # Generated code
+
+// Some modules are partly written by a program. `../build.rs` runs before
+// the crate is compiled, and writes Rust source to `generated.rs` in the
+// directory Cargo names in the `OUT_DIR` environment variable. This module
+// pastes that file in here with `include!`:
+include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+
+// `include!` works like copying the file's text into this spot, so the
+// generated items become items of this module, with paths like
+// `crate::generated::PLATFORMS`, and the generated code can use this
+// module's items in turn: it builds `Platform`s, defined just below, without
+// a `use`. Unlike `mod`, `include!` doesn't make a new module.
+
+// `env!` reads `OUT_DIR` at compile time, and `concat!` joins it to the file
+// name, since `include!` needs a single string literal.
+
+// To see the generated file, look under `target/debug/build/*/out/`.
+
+pub struct Platform {
+    pub name: &'static str,
+    pub family: &'static str,
+    pub file: &'static str,
+}
+
+// The platform this was built for, if it has a module of its own.
+pub fn current() -> Option<&'static Platform> {
+    PLATFORMS.iter().find(|p| p.name == TARGET_OS || p.name == TARGET_ARCH)
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "generated" }
+
+    fn description(&self) -> &'static str {
+        "Including code written by a build script with `include!`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["build script", "include"] }
+
+    fn topic(&self) -> Topic { Topic::Generated }
+
+    fn source(&self) -> &'static str {
+        include_str!("generated.rs")
+    }
+
+    fn run(&self) {
+        println!("build.rs generated this table of platforms with their own modules:");
+        for platform in PLATFORMS {
+            println!("  {:8} {:8} src/{}", platform.name, platform.family, platform.file);
+        }
+        match current() {
+            Some(platform) => println!("This build, for {TARGET}, uses src/{}.", platform.file),
+            None => println!("This build, for {TARGET}, has no platform module."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_table() {
+        assert_eq!(PLATFORMS.len(), 4);
+        assert!(PLATFORMS.iter().any(|p| p.name == "linux" && p.file == "linux.rs"));
+    }
+
+    #[test]
+    fn generated_target_matches_cfg() {
+        assert_eq!(TARGET_OS, std::env::consts::OS);
+        #[cfg(target_os = "linux")]
+        assert_eq!(current().map(|p| p.file), Some("linux.rs"));
+    }
+}
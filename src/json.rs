@@ -0,0 +1,357 @@
+// A minimal JSON value, pretty printer and parser, enough for this crate's
+// exports and reading back its own files without depending on `serde`.
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    // Keys keep their insertion order, so output is stable.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    // Build an object from `(key, value)` pairs.
+    pub fn object<K: Into<String>>(fields: impl IntoIterator<Item = (K, Value)>) -> Value {
+        Value::Object(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    // The value of `key`, if this is an object with it.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    // A whole number that fits in a `usize`.
+    pub fn as_usize(&self) -> Option<usize> {
+        match *self {
+            Value::Number(n) if n >= 0.0 && n.fract() == 0.0 && n <= usize::MAX as f64 => Some(n as usize),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Value {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Value {
+        Value::String(s)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Value {
+        Value::Bool(b)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(n: usize) -> Value {
+        Value::Number(n as f64)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(o: Option<T>) -> Value {
+        o.map_or(Value::Null, Into::into)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(v: Vec<T>) -> Value {
+        Value::Array(v.into_iter().map(Into::into).collect())
+    }
+}
+
+// `{}` prints the value pretty-printed with two space indents.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_value(self, 0, f)
+    }
+}
+
+fn write_value(value: &Value, indent: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let pad = |f: &mut fmt::Formatter<'_>, n: usize| write!(f, "{:1$}", "", n * 2);
+
+    match value {
+        Value::Null => f.write_str("null"),
+        Value::Bool(b) => write!(f, "{b}"),
+        Value::Number(n) if n.is_finite() => write!(f, "{n}"),
+        Value::Number(_) => f.write_str("null"),
+        Value::String(s) => write_string(s, f),
+        Value::Array(items) if items.is_empty() => f.write_str("[]"),
+        Value::Array(items) => {
+            f.write_str("[\n")?;
+            for (i, item) in items.iter().enumerate() {
+                pad(f, indent + 1)?;
+                write_value(item, indent + 1, f)?;
+                f.write_str(if i + 1 < items.len() { ",\n" } else { "\n" })?;
+            }
+            pad(f, indent)?;
+            f.write_str("]")
+        }
+        Value::Object(fields) if fields.is_empty() => f.write_str("{}"),
+        Value::Object(fields) => {
+            f.write_str("{\n")?;
+            for (i, (key, item)) in fields.iter().enumerate() {
+                pad(f, indent + 1)?;
+                write_string(key, f)?;
+                f.write_str(": ")?;
+                write_value(item, indent + 1, f)?;
+                f.write_str(if i + 1 < fields.len() { ",\n" } else { "\n" })?;
+            }
+            pad(f, indent)?;
+            f.write_str("}")
+        }
+    }
+}
+
+fn write_string(s: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+// Parsing reads the whole of RFC 8259 JSON, with errors saying which line
+// they're on.
+impl FromStr for Value {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Value, String> {
+        let mut parser = Parser { text: s, pos: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos < s.len() {
+            return Err(parser.error("unexpected text after the value"));
+        }
+        Ok(value)
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    // A byte offset into `text`.
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> String {
+        format!("line {}: {message}", self.text[..self.pos].matches('\n').count() + 1)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    // Skips `token` if it's next, after any whitespace.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let found = self.text[self.pos..].starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.eat(token) { Ok(()) } else { Err(self.error(&format!("expected `{token}`"))) }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => {
+                self.pos += 1;
+                let mut fields = Vec::new();
+                if !self.eat("}") {
+                    loop {
+                        self.skip_whitespace();
+                        if self.peek() != Some('"') {
+                            return Err(self.error("expected a string key"));
+                        }
+                        let key = self.string()?;
+                        self.expect(":")?;
+                        fields.push((key, self.value()?));
+                        if !self.eat(",") {
+                            self.expect("}")?;
+                            break;
+                        }
+                    }
+                }
+                Ok(Value::Object(fields))
+            }
+            Some('[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if !self.eat("]") {
+                    loop {
+                        items.push(self.value()?);
+                        if !self.eat(",") {
+                            self.expect("]")?;
+                            break;
+                        }
+                    }
+                }
+                Ok(Value::Array(items))
+            }
+            Some('"') => self.string().map(Value::String),
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let rest = &self.text[self.pos..];
+                let len = rest.find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c))).unwrap_or(rest.len());
+                let number = rest[..len].parse().map_err(|_| self.error(&format!("'{}' isn't a number", &rest[..len])))?;
+                self.pos += len;
+                Ok(Value::Number(number))
+            }
+            _ if self.eat("null") => Ok(Value::Null),
+            _ if self.eat("true") => Ok(Value::Bool(true)),
+            _ if self.eat("false") => Ok(Value::Bool(false)),
+            None => Err(self.error("expected a value, found the end")),
+            Some(_) => Err(self.error("expected a value")),
+        }
+    }
+
+    // A string, starting at its opening quote.
+    fn string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("this string's closing `\"` is missing"))?;
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let escape = self.peek().ok_or_else(|| self.error("a string can't end with `\\`"))?;
+                    self.pos += escape.len_utf8();
+                    out.push(match escape {
+                        '"' => '"',
+                        '\\' => '\\',
+                        '/' => '/',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => {
+                            let mut code = self.hex()?;
+                            // Characters outside the Basic Multilingual Plane
+                            // are written as a pair of surrogates.
+                            if (0xd800..0xdc00).contains(&code) && self.text[self.pos..].starts_with("\\u") {
+                                self.pos += 2;
+                                let low = self.hex()?;
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff);
+                            }
+                            char::from_u32(code).ok_or_else(|| self.error("invalid `\\u` escape"))?
+                        }
+                        other => return Err(self.error(&format!("unknown escape `\\{other}`"))),
+                    });
+                }
+                c if (c as u32) < 0x20 => return Err(self.error("control characters in strings must be escaped")),
+                c => out.push(c),
+            }
+        }
+    }
+
+    // The four hex digits of a `\u` escape.
+    fn hex(&mut self) -> Result<u32, String> {
+        let digits = self.text.get(self.pos..self.pos + 4).ok_or_else(|| self.error("invalid `\\u` escape"))?;
+        let code = u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid `\\u` escape"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pretty_print() {
+        let value = Value::object([
+            ("name", Value::from("a \"quoted\"\nline")),
+            ("count", Value::from(3usize)),
+            ("missing", Value::from(None::<&str>)),
+            ("empty", Value::Array(vec![])),
+            ("list", Value::from(vec![true, false])),
+        ]);
+        assert_eq!(value.to_string(), r#"{
+  "name": "a \"quoted\"\nline",
+  "count": 3,
+  "missing": null,
+  "empty": [],
+  "list": [
+    true,
+    false
+  ]
+}"#);
+    }
+
+    #[test]
+    fn control_characters() {
+        assert_eq!(Value::from("\u{1}").to_string(), r#""\u0001""#);
+    }
+
+    #[test]
+    fn parse() {
+        let value = Value::object([
+            ("name", Value::from("a \"quoted\"\nline \u{1} é")),
+            ("count", Value::from(3usize)),
+            ("missing", Value::Null),
+            ("empty", Value::Array(vec![])),
+            ("nested", Value::object([("list", Value::from(vec![true, false]))])),
+        ]);
+        assert_eq!(value.to_string().parse(), Ok(value.clone()));
+        assert_eq!(" {\"a\":[1, -2.5e1, {}],\"b\" : \"\\u00e9\\ud83d\\ude00\\/\"} ".parse(), Ok(Value::object([
+            ("a", Value::Array(vec![Value::Number(1.0), Value::Number(-25.0), Value::Object(vec![])])),
+            ("b", Value::from("é😀/")),
+        ])));
+        assert_eq!(value.get("count").and_then(Value::as_usize), Some(3));
+        assert_eq!(value.get("name").and_then(Value::as_str), Some("a \"quoted\"\nline \u{1} é"));
+        assert_eq!(value.get("nope"), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |text: &str| text.parse::<Value>().unwrap_err();
+        assert_eq!(error(""), "line 1: expected a value, found the end");
+        assert_eq!(error("{\n  \"a\": 1,\n}"), "line 3: expected a string key");
+        assert_eq!(error("[1 2]"), "line 1: expected `]`");
+        assert_eq!(error("\"open"), "line 1: this string's closing `\"` is missing");
+        assert_eq!(error("\"\\x\""), "line 1: unknown escape `\\x`");
+        assert_eq!(error("1 2"), "line 1: unexpected text after the value");
+        assert_eq!(error("nul"), "line 1: expected a value");
+        assert_eq!(error("1.2.3"), "line 1: '1.2.3' isn't a number");
+    }
+}
@@ -0,0 +1,850 @@
+// Mute some warnings by unused code in the examples.
+#![allow(dead_code, unused_imports)]
+
+// This is the root of the library crate. The binary crate rooted at
+// `./main.rs` is a thin command line wrapper around it.
+
+// # Rust modules example
+
+// Most of the time a module means a file, but there are other
+// options, see below.
+
+// The modules form a tree from the root of the crate (usually
+// `src/main.rs` for a bin, or `src/lib.rs` for a library) down to each module.
+
+// Every child module must be explicitly declared with the `mod` keyword.
+// Just having the file present in the tree will not do anything. To find
+// files like that, run `cargo run -- analyze orphans`.
+
+// This introduces the module `a` into the current module's child modules,
+// using the definition in `./a.rs`.
+mod a;
+
+// ## Multi-level modules
+
+// When a module will have its own child modules, there are 2 possible styles
+
+// Style 1 has been in Rust since the beginning, and I actually prefer it as it keeps
+// the child module contents in the same sub-directory:
+
+mod multi_level_style_1; // references `multi_level_style_1/mod.rs`
+
+// In directory `./multi_level_style_1/` there is `mod.rs` and
+// `child.rs`, with `mod.rs` declaring `child.rs`.
+
+// Style 2 was added in the 2018 edition of Rust and is now officially recommended.
+// It was designed to avoid having lots of files named `mod.rs` in the
+// project, as well as increase consistency with modules that do not
+// have children:
+
+mod multi_level_style_2; // references `multi_level_style_2.rs`, which declares a child in
+                         // `multi_level_style_2/child.rs`
+
+// To see where the compiler looks for a module's file in either style, run
+// e.g. `cargo run -- which-file crate::multi_level_style_2::child`.
+
+// It causes a compilation error when there are 2 files available for
+// the same module name in the different styles,
+// e.g. if there is `./foo.rs` and `./foo/mod.rs` and a module is declared with `mod foo`.
+// That's error E0761, "file for module `foo` found at both ...". The crate in
+// `../tests/fixtures/module_conflict/` has exactly that, and a test checks it.
+
+// ## Inline modules
+
+// A module may also be declared in the parent file:
+mod inline {
+    // `inline` has its own scope, in particular to refer to other items
+    // in lib.rs you must refer to them from the crate root like `crate::item`,
+    // relative to the current module like `super::item`.
+
+    // Even though we're in `lib.rs` this has to be declared `pub`
+    // for other items in the crate root module to see it.
+    pub fn inline_fn() {
+        super::f();
+        crate::f();
+
+        inline_private();
+    }
+
+    // Only `inline` and its child modules can see `inline_private`.
+    fn inline_private() {}
+
+    // Inline modules need their own `use` statements too, just like file modules.
+    use crate::example::{self, Example, Topic};
+
+    pub struct Demo;
+
+    crate::registry::register!(Demo);
+
+    impl Example for Demo {
+        fn name(&self) -> &'static str { "inline" }
+
+        fn description(&self) -> &'static str {
+            "A module declared inline with `mod inline { ... }`"
+        }
+
+        fn tags(&self) -> &'static [&'static str] { &["basics", "inline"] }
+
+        fn topic(&self) -> Topic { Topic::Basics }
+
+        fn source(&self) -> &'static str {
+            example::section(example::LIB_RS, "Inline modules")
+        }
+
+        fn run(&self) {
+            println!("Calling inline::inline_fn(), which calls back up to `crate::f`.");
+            inline_fn();
+        }
+    }
+}
+
+fn f() {}
+
+// Inline modules are often used to contain unit tests:
+
+#[cfg(test)] // Only processed when building tests, like with `cargo test` or `cargo build --tests`.
+mod tests {
+    #[test]
+    fn ok() {
+        assert_eq!(1 + 1, 2);
+    }
+}
+
+// ## Advanced usage
+
+// A module declaration can override the file path that it loads:
+#[path = "path_override_foo.rs"]
+mod path_override;
+
+// The main reason I've seen this is with per-platform conditional compilation of modules:
+
+#[cfg(target_os = "linux")] // Only processed when the target OS is Linux.
+#[path = "linux.rs"]
+mod platform;
+
+#[cfg(target_os = "macos")] // Only processed when the target OS is MacOS.
+#[path = "macos.rs"]
+mod platform;
+
+#[cfg(windows)] // Only processed when the target OS is Windows.
+#[path = "windows.rs"]
+mod platform;
+
+// Only processed when building WebAssembly, e.g. for a browser with
+// `cargo build --target wasm32-unknown-unknown`. That target has no OS, so
+// it's neither `unix` nor `windows`; the CPU architecture picks it out instead.
+#[cfg(target_arch = "wasm32")]
+#[path = "wasm.rs"]
+mod platform;
+
+// Anything else gets a stub whose only content is a `compile_error!`, so an
+// unsupported target fails straight away with a clear message. The `cfg` is
+// the negation of all the ones above, and must be kept in sync with them.
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows, target_arch = "wasm32")))]
+#[path = "unsupported.rs"]
+mod platform;
+
+// The `cfg`s must not overlap: two `mod platform` declarations in one build
+// are error E0428, "the name `platform` is defined multiple times".
+
+// Registered here rather than in each platform file, because `platform_check`
+// compiles those files a second time for its tests.
+crate::registry::register!(platform::Demo);
+
+// The same trick works with any `cfg`, not just the target. The
+// `debug_assertions` option is on in the default `dev` profile and off with
+// `--release`, so this picks settings per build profile. It's `pub` so that
+// `main.rs` can report which one is active.
+#[cfg(debug_assertions)]
+#[path = "config_debug.rs"]
+pub mod config;
+
+#[cfg(not(debug_assertions))]
+#[path = "config_release.rs"]
+pub mod config;
+
+// The target's CPU architecture can select modules too, see `./byte_sum.rs`
+// for a fast path on x86_64 with a portable fallback.
+mod byte_sum;
+
+// In tests, the platform files that can build anywhere are all compiled
+// together, to check they implement the same `platform_api::PlatformApi`.
+#[cfg(test)]
+mod platform_check;
+
+// Stand-in platforms for tests of code that's generic over the platform, see
+// `platform_api` below.
+//
+// Test helpers like this are usually `#[cfg(test)]`, but then other crates
+// can't use them: a crate's `cfg(test)` is only set when building its own
+// tests. So this is also compiled, and `pub`, when the `test-util` feature
+// is on. A dependent crate enables it for its tests only:
+//
+//     [dev-dependencies]
+//     rust-module-example = { path = "../..", features = ["test-util"] }
+//
+// `../crates/examples-extra/tests/test_util.rs` does just that.
+// `modtree::test_dir` is shared the same way.
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock_platform;
+
+// This allows code in this module to use items in `platform::*` without caring
+// what implementation is going to be included:
+fn use_platform() -> &'static str {
+    platform::api::FAMILY
+}
+
+// `platform` is private though. To give users of a crate the same uniform
+// API, a public module can re-export the selected implementation's items:
+pub mod platform_api {
+    // Exactly one `platform` module exists in any build, so this always
+    // re-exports one consistent set of items.
+    pub use crate::platform::api::*;
+
+    // Nothing so far makes the platform files agree with each other, though.
+    // If `windows.rs` forgot `DLL_SUFFIX`, a Linux build would never notice.
+    // So each platform file also implements this trait for a `Platform`
+    // type, and a missing or mistyped item is then a compile error in that
+    // file. `./platform_check.rs` compiles them all on one host, in tests.
+    pub trait PlatformApi {
+        const FAMILY: &'static str;
+        const EXE_SUFFIX: &'static str;
+        const DLL_SUFFIX: &'static str;
+        fn path_separator() -> char;
+    }
+
+    // The implementation for the current target.
+    pub use crate::platform::Platform as Current;
+
+    // Code that takes the platform as a type parameter, like the functions
+    // below, has a seam: the rest of the crate passes `Current`, and tests
+    // can pass a stand-in from `../mock_platform.rs` instead. That way a
+    // Linux host still tests the Windows behaviour, and the other way round.
+
+    // The file name of an executable, e.g. `tree` or `tree.exe`.
+    pub fn executable_file_name<P: PlatformApi>(name: &str) -> String {
+        format!("{name}{}", P::EXE_SUFFIX)
+    }
+
+    // The file name of a dynamic library. Only Unix adds a `lib` prefix, so
+    // it's `libfoo.so` but `foo.dll`.
+    pub fn library_file_name<P: PlatformApi>(name: &str) -> String {
+        let prefix = if P::FAMILY == "unix" { "lib" } else { "" };
+        format!("{prefix}{name}{}", P::DLL_SUFFIX)
+    }
+
+    // `parts` joined with the platform's path separator.
+    pub fn join_path<P: PlatformApi>(parts: &[&str]) -> String {
+        parts.join(P::path_separator().encode_utf8(&mut [0; 4]))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        #[test]
+        fn same_items_as_platform() {
+            assert_eq!(super::FAMILY, crate::platform::api::FAMILY);
+            assert_eq!(super::path_separator(), std::path::MAIN_SEPARATOR);
+            assert_eq!(super::EXE_SUFFIX, std::env::consts::EXE_SUFFIX);
+            assert_eq!(super::DLL_SUFFIX, std::env::consts::DLL_SUFFIX);
+        }
+
+        #[test]
+        fn trait_matches_items() {
+            use super::{Current, PlatformApi};
+
+            assert_eq!(Current::FAMILY, super::FAMILY);
+            assert_eq!(Current::path_separator(), super::path_separator());
+        }
+
+        #[test]
+        fn current_file_names() {
+            use super::{executable_file_name, library_file_name, Current};
+            use std::env::consts::{DLL_PREFIX, DLL_SUFFIX, EXE_SUFFIX};
+
+            assert_eq!(executable_file_name::<Current>("tree"), format!("tree{EXE_SUFFIX}"));
+            assert_eq!(library_file_name::<Current>("foo"), format!("{DLL_PREFIX}foo{DLL_SUFFIX}"));
+        }
+
+        // Both families' behaviour, whichever this host is.
+        #[test]
+        fn mock_platforms() {
+            use super::{executable_file_name, join_path, library_file_name};
+            use crate::mock_platform::{Unix, Windows};
+
+            assert_eq!(executable_file_name::<Unix>("tree"), "tree");
+            assert_eq!(executable_file_name::<Windows>("tree"), "tree.exe");
+            assert_eq!(library_file_name::<Unix>("foo"), "libfoo.so");
+            assert_eq!(library_file_name::<Windows>("foo"), "foo.dll");
+            assert_eq!(join_path::<Unix>(&["src", "lib.rs"]), "src/lib.rs");
+            assert_eq!(join_path::<Windows>(&["src", "lib.rs"]), "src\\lib.rs");
+        }
+    }
+}
+
+// Dependency crates can also be compiled conditionally based on the
+// build target, enabled feature flags, and other factors. See
+// `./target_deps.rs` for a dependency only used on some targets.
+mod target_deps;
+
+// Features can decide whether a crate needs `std` at all. `./no_std_core.rs`
+// uses a dependency whose `std`-only modules are behind a `std` feature.
+mod no_std_core;
+
+// Predicates can be combined with `all`, `any` and `not`, as in
+// `./cfg_combinators.rs`.
+mod cfg_combinators;
+
+// Attributes can be conditional too, with `#[cfg_attr]`, see `./cfg_attrs.rs`.
+mod cfg_attrs;
+
+// ## Name resolution
+mod name_resolution {
+    // Everything is private by default in Rust, including modules.
+    // `private_inner` is visible in `name_resolution` and its child
+    // modules, but not by `name_resolution`'s parents.
+    mod private_inner {
+
+        // `a` is visible to `private_inner` and its child modules, but nowhere else.
+        fn a() {}
+
+        // `pub` keyword makes an item exported by its parent module,
+        // so `b` is visible to any module that can see
+        // `private_inner`, but the crate root cannot see `private_inner`, so it still
+        // cannot see `b`.
+        pub fn b() {}
+    }
+
+    // Calling `private_inner::a()` here will not compile, because `a` is not
+    // exported with `pub`. It's error E0603, "function `a` is private", which
+    // `cargo test` checks with this `compile_fail` doctest:
+
+    /// ```compile_fail,E0603
+    /// mod private_inner {
+    ///     fn a() {}
+    /// }
+    ///
+    /// fn main() {
+    ///     private_inner::a();
+    /// }
+    /// ```
+    fn test_private_inner() {
+        // Access a child module's exported item with the syntax `${child_module}::{item}`:
+        private_inner::b();
+    }
+
+    // The crate root sees this module and everything exported by it.
+    pub mod public_inner {
+        pub fn a() {}
+    }
+
+    use crate::example::{self, Example, Topic};
+
+    pub struct Demo;
+
+    crate::registry::register!(Demo);
+
+    impl Example for Demo {
+        fn name(&self) -> &'static str { "name_resolution" }
+
+        fn description(&self) -> &'static str {
+            "Private by default, and exporting items with `pub`"
+        }
+
+        fn tags(&self) -> &'static [&'static str] { &["visibility"] }
+
+        fn topic(&self) -> Topic { Topic::Visibility }
+
+        fn source(&self) -> &'static str {
+            example::section(example::LIB_RS, "Name resolution")
+        }
+
+        fn run(&self) {
+            println!("Calling private_inner::b(), which is `pub` inside a private module.");
+            test_private_inner();
+            println!("Calling public_inner::a(), which the crate root can also see.");
+            public_inner::a();
+        }
+    }
+}
+
+// ## Visibility modifiers
+
+// Restricted visibilities like `pub(crate)` are demonstrated in
+// `./visibility.rs` and its children in `./visibility/`.
+mod visibility;
+
+// `pub(crate)` items can be used anywhere in the crate, including here at
+// the root, a couple of modules away from where they are defined.
+fn use_pub_crate_from_root() -> String {
+    format!("counter::next(41) = {}, from the crate root.",
+            visibility::pub_crate::counter::next(41))
+}
+
+// ## Imports with `use`
+
+// Items visible in a scope can be imported into that scope with the `use` keyword:
+mod use_examples {
+
+    mod use_inner {
+        pub fn a() {}
+        pub fn b() {}
+    }
+
+    // `use_inner::a` is now in scope as `a` in `use_examples`.
+    use use_inner::a;
+
+    fn test_use() {
+        // `use_inner::a` can be called explicitly.
+        use_inner::a();
+
+        // But because we imported it with `use` we can also call it simply as `a`.
+        a();
+
+        // Function definitions also have a scope, so can contain `use` statements,
+        // which work as you'd expect.
+        use use_inner::b;
+        b();
+    }
+
+    mod use_wildcard {
+        pub fn not() {}
+        pub fn my() {}
+        pub fn favourite() {}
+    }
+
+    // `use` also supports wildcards.
+    // I personally don't like this because with multiple wildcards it's more difficult
+    // to know where a particular item comes from.
+    use use_wildcard::*;
+
+    fn test_use_wildcard() {
+        not();
+        my();
+        favourite();
+    }
+
+    // `use` supports renaming, useful to avoid name clashes:
+
+    mod use_rename {
+        pub fn a() {}
+    }
+
+    // This would be a compile error, because we already imported `use_inner::a` above.
+    // use use_rename::a;
+
+    // This imports `use_rename::a` as `a_renamed`.
+    use use_rename::a as a_renamed;
+
+    // Renaming to `_` imports an item without giving it a name at all. That's only
+    // useful for traits: a trait's methods can only be called with method syntax
+    // when the trait is in scope, but it doesn't need to be in scope by name.
+
+    mod use_trait_ext {
+        // An "extension trait", adding a method to `str`.
+        pub trait Shout {
+            fn shout(&self) -> String;
+        }
+
+        impl Shout for str {
+            fn shout(&self) -> String {
+                format!("{}!", self.to_uppercase())
+            }
+        }
+    }
+
+    mod use_trait_clash {
+        // A different item that happens to have the same name.
+        pub struct Shout;
+    }
+
+    // `use_trait_clash::Shout` takes the name `Shout` in this module...
+    use use_trait_clash::Shout;
+
+    // ...so `use use_trait_ext::Shout;` would be a compile error, but this works,
+    // bringing the trait's methods into scope without its name.
+    use use_trait_ext::Shout as _;
+
+    fn test_use_trait_as_underscore() -> String {
+        // `Shout` here is the struct.
+        let _clash = Shout;
+
+        // And `.shout()` is the trait method.
+        "modules".shout()
+    }
+
+    // `use` supports a nested syntax which avoids repetition in imports:
+
+    mod use_nested_1 {
+        pub mod use_nested_2 {
+            pub fn g() {}
+            pub fn h() {}
+        }
+
+        pub mod use_nested_3 {
+            pub fn g() {}
+            pub fn i() {}
+        }
+
+        pub fn j() {}
+    }
+
+    use use_nested_1::{
+        use_nested_2::{g, h},
+        use_nested_3::{g as use_nested_3_g, i},
+        j
+    };
+
+    fn test_use_nested() {
+        g();
+        use_nested_3_g();
+        h();
+        i();
+        j();
+    }
+
+    // Inside braces, `self` refers to the module being imported from, so a
+    // single `use` can bring in both a module and some of its items:
+
+    mod use_self_1 {
+        pub mod use_self_2 {
+            pub fn k() {}
+            pub fn l() {}
+        }
+    }
+
+    // This is the same as `use use_self_1::use_self_2; use use_self_1::use_self_2::k;`.
+    use use_self_1::use_self_2::{self, k};
+
+    fn test_use_self() {
+        // `k` is imported by name...
+        k();
+
+        // ...and other items are still reachable through the imported module name.
+        use_self_2::l();
+    }
+
+    // `use use_self_1::{self}` would be a compile error here though, because
+    // `use_self_1` is already declared in this module, so it can't be imported again.
+
+    // ### Advanced: glob imports and shadowing.
+
+    // Two glob imports may both bring in the same name, and an explicit import
+    // or local definition of that name takes precedence over both of them:
+    mod use_globs {
+        mod english {
+            pub fn hello() -> &'static str { "hello" }
+            pub fn goodbye() -> &'static str { "goodbye" }
+        }
+
+        mod french {
+            pub fn hello() -> &'static str { "bonjour" }
+            pub fn goodbye() -> &'static str { "au revoir" }
+            pub fn please() -> &'static str { "s'il vous plaît" }
+        }
+
+        // Both globs import `hello` and `goodbye`. That on its own is not an error.
+        use english::*;
+        use french::*;
+
+        // An explicit `use` shadows anything imported by a glob, so `hello`
+        // unambiguously means `french::hello` in this module.
+        use french::hello;
+
+        pub fn greeting() -> &'static str {
+            hello()
+        }
+
+        // Names only imported by one glob, like `please`, work as usual.
+        pub fn request() -> &'static str {
+            please()
+        }
+
+        // `goodbye` is ambiguous though: both globs import it and nothing shadows
+        // it. Using it is an error, E0659 "`goodbye` is ambiguous":
+        // pub fn farewell() -> &'static str { goodbye() }
+
+        // A module's own items also take precedence over glob imports.
+        pub mod local {
+            use super::english::*;
+
+            pub fn goodbye() -> &'static str { "bye" }
+
+            pub fn farewell() -> &'static str {
+                goodbye()
+            }
+        }
+    }
+
+    // ### Advanced: items and imports with the same name.
+
+    // Unlike a glob, an explicit `use` doesn't lose quietly to a local item with the
+    // same name. Defining `fn a()` and also importing `use other::a;` in one module
+    // is error E0255: "the name `a` is defined multiple times".
+    //
+    // The doc comment below holds that case as a `compile_fail` doctest, so
+    // `cargo test` checks that it really fails with E0255.
+
+    /// ```compile_fail,E0255
+    /// mod other {
+    ///     pub fn a() {}
+    /// }
+    ///
+    /// use other::a;
+    ///
+    /// fn a() {}
+    /// # fn main() {}
+    /// ```
+    mod use_shadow {
+        mod other {
+            pub fn a() -> &'static str { "other::a" }
+        }
+
+        pub fn a() -> &'static str { "use_shadow::a" }
+
+        // The fix is the same as for two clashing imports: rename one with `as`.
+        use other::a as other_a;
+
+        pub fn both() -> (&'static str, &'static str) {
+            (a(), other_a())
+        }
+    }
+
+    // ### Advanced: re-exporting.
+
+    // Items visible in a scope can be exported by that scope with the `pub use` keywords:
+    mod inner_1 {
+        mod inner_2 {
+            pub fn x() {}
+        }
+
+        // `inner_2::x` is now imported into `inner_1`'s scope as `x`, but it has also been
+        // exported because of the `pub`.
+        pub use inner_2::x;
+    }
+
+    // `inner_1::inner_2::x()` doesn't compile, because `inner_2` is not
+    // exported by `inner_1`. It's error E0603, "module `inner_2` is private":
+
+    /// ```compile_fail,E0603
+    /// mod inner_1 {
+    ///     mod inner_2 {
+    ///         pub fn x() {}
+    ///     }
+    ///
+    ///     pub use inner_2::x;
+    /// }
+    ///
+    /// fn main() {
+    ///     inner_1::inner_2::x();
+    /// }
+    /// ```
+    fn test_pub_use() {
+        // This works fine and refers to the same function, because
+        // `inner_1` exported `inner_2::x` with `pub use`.
+        inner_1::x();
+    }
+
+    // ----
+
+    // `use` and `pub use` can be applied to almost any item in a module, including:
+    //
+    // * `const`
+    // * `enum`
+    // * `fn`
+    // * `mod`
+    // * `static`
+    // * `struct`
+    // * `trait`
+    // * `type`
+
+    // Macros have some different rules, explained in `./macros.rs`.
+
+    use crate::example::{self, Example, Topic};
+
+    pub struct Demo;
+
+    crate::registry::register!(Demo);
+
+    impl Example for Demo {
+        fn name(&self) -> &'static str { "use_examples" }
+
+        fn description(&self) -> &'static str {
+            "Importing items with `use`, wildcards, renames and re-exports"
+        }
+
+        fn tags(&self) -> &'static [&'static str] { &["use", "re-export"] }
+
+        fn topic(&self) -> Topic { Topic::Use }
+
+        fn source(&self) -> &'static str {
+            example::section(example::LIB_RS, "Imports with `use`")
+        }
+
+        fn run(&self) {
+            println!("Calling `a` imported with `use use_inner::a`.");
+            test_use();
+            println!("Calling `not`, `my` and `favourite` imported with a wildcard.");
+            test_use_wildcard();
+            println!("Calling `use_rename::a` imported as `a_renamed`.");
+            a_renamed();
+            println!("Calling `.shout()` from a trait imported with `as _`: {}",
+                     test_use_trait_as_underscore());
+            println!("Calling items imported with nested `use` syntax.");
+            test_use_nested();
+            println!("Calling `k` and `use_self_2::l`, imported with `use use_self_1::use_self_2::{{self, k}}`.");
+            test_use_self();
+            println!("Calling `inner_1::x`, re-exported with `pub use`.");
+            test_pub_use();
+            println!("An explicit `use` shadows two globs: `hello()` returns {:?}.",
+                     use_globs::greeting());
+            println!("A local definition shadows a glob: `goodbye()` returns {:?}.",
+                     use_globs::local::farewell());
+            println!("A local `a` and `other::a` imported as `other_a`: {:?}.",
+                     use_shadow::both());
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        // Tests need the trait in scope too, and it can be anonymous here as well.
+        use super::use_trait_ext::Shout as _;
+
+        #[test]
+        fn trait_imported_as_underscore() {
+            assert_eq!(super::test_use_trait_as_underscore(), "MODULES!");
+            assert_eq!("tests".shout(), "TESTS!");
+        }
+
+        #[test]
+        fn explicit_import_shadows_globs() {
+            assert_eq!(super::use_globs::greeting(), "bonjour");
+            assert_eq!(super::use_globs::request(), "s'il vous plaît");
+        }
+
+        #[test]
+        fn local_item_shadows_glob() {
+            assert_eq!(super::use_globs::local::farewell(), "bye");
+        }
+
+        #[test]
+        fn renamed_import_beside_local_item() {
+            assert_eq!(super::use_shadow::both(), ("use_shadow::a", "other::a"));
+        }
+    }
+}
+
+// Re-exports are also how crates hide their dependencies behind an API of
+// their own, see `./reexport_dependency.rs`.
+mod reexport_dependency;
+
+// A `prelude` module gathers the most commonly used items for a single glob
+// import. This crate's is `./prelude.rs`, and `./prelude_consumer.rs` uses it.
+mod prelude_consumer;
+
+// The next few examples are more involved, so they're behind the `advanced`
+// Cargo feature, declared in `Cargo.toml`. It's on by default, and
+// `cargo run --no-default-features -- list` builds without them.
+
+// A `#[cfg]` on a `mod` declaration removes the whole module, and its file
+// isn't even read. Anything naming the module, like the registry and the
+// `pub use` below, needs the same `#[cfg]`.
+
+// Re-exports also let an item move to a new module while its old path keeps
+// working, see `./deprecated_reexport.rs`.
+#[cfg(feature = "advanced")]
+mod deprecated_reexport;
+
+// Re-exports can alias a whole module too: `./api.rs` keeps versions of an
+// API in `v1` and `v2` modules, with `latest` re-exporting the newest one.
+#[cfg(feature = "advanced")]
+mod api;
+
+// A re-exported item can be re-exported again. `./reexport_chain.rs` surfaces
+// a function defined three modules deep one level at a time, and this last
+// link makes it available at the crate root as `crate::deep_fn`.
+#[cfg(feature = "advanced")]
+mod reexport_chain;
+#[cfg(feature = "advanced")]
+pub use reexport_chain::deep_fn;
+
+// ## Macros
+
+// `macro_rules!` macros are scoped differently from other items, see
+// `./macros.rs` and its children in `./macros/`. It's `pub` so that other
+// crates can reach the macros it re-exports by path.
+pub mod macros;
+
+// `square!` is defined deep inside `macros::exported`, but `#[macro_export]`
+// puts it in the crate root, so it's usable here without a `use`. Other crates
+// can use it too, as `modules_demo::square!`.
+fn square_at_root(n: i32) -> i32 {
+    square!(n)
+}
+
+// Macros can also be re-exported with `use` like other items. This makes
+// `repeat!` from `./macros/reexport.rs` usable as `crate::repeat!`.
+pub(crate) use macros::reexport::repeat;
+
+// ## Generated code
+
+// A build script can write source code for a module to include, see
+// `./generated.rs` and `../build.rs`.
+mod generated;
+
+// ## The library's public API
+
+// Apart from a few items marked `pub` like `platform_api`, everything above
+// is private to this crate. These modules are `pub`, so they are exported for
+// the binary in `./main.rs`, or any other crate, to use as
+// `modules_demo::registry` and so on.
+
+// Every example module implements the `Example` trait from `./example.rs`,
+// and is listed in `../spec/examples.toml`, from which `./registry.rs` gets
+// its list so the runner can find it by name.
+pub mod example;
+pub mod registry;
+
+// The list of examples in `registry`, generated from a spec by `../build.rs`.
+pub mod catalog;
+
+// The guided tour, `./tour.rs`, walks through the registry in teaching order.
+pub mod tour;
+
+// The same annotated source as an mdBook, by `./book.rs`.
+pub mod book;
+
+// The visibility quiz, `./quiz.rs`, asks which modules can call what.
+pub mod quiz;
+
+// Runs the broken crates in `../exercises/` for the `verify` command.
+pub mod exercises;
+
+// What the learner has done so far, saved between runs by `./progress.rs`.
+pub mod progress;
+
+// The quiz for a whole class over HTTP, `./classroom.rs`, for workshops.
+pub mod classroom;
+
+// Parses a crate's source to show its module tree. `./modtree.rs` has its
+// own children in `./modtree/`, in the style of `multi_level_style_2`.
+pub mod modtree;
+
+// A small JSON printer and parser used for machine-readable output.
+pub mod json;
+
+// Re-exports of the items above that most users need, for
+// `use modules_demo::prelude::*;`.
+pub mod prelude;
+
+// Whole modules can be left out of a build. This one is only compiled when
+// the `tui` feature is enabled, e.g. with `cargo run --features tui -- browse`.
+#[cfg(feature = "tui")]
+pub mod tui;
+
+// A short demo used when the binary is run without a command.
+pub fn greet() {
+    println!("Hello, world! Running on platform family '{}'", use_platform());
+    inline::inline_fn();
+
+    name_resolution::public_inner::a();
+}
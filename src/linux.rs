@@ -0,0 +1,58 @@
+// The items every platform file must provide, with the same names and types.
+// `platform_api` in `lib.rs` re-exports whichever set was compiled.
+pub mod api {
+    pub const FAMILY: &str = "unix";
+
+    // Appended to executable names, e.g. `cargo` or `cargo.exe`.
+    pub const EXE_SUFFIX: &str = "";
+
+    // Appended to dynamic library names, e.g. `libfoo.so` or `foo.dll`.
+    pub const DLL_SUFFIX: &str = ".so";
+
+    pub fn path_separator() -> char {
+        '/'
+    }
+}
+
+// Implementing `PlatformApi` makes the compiler check that this file has the
+// same items, with the same types, as the other platform files.
+pub struct Platform;
+
+impl crate::platform_api::PlatformApi for Platform {
+    const FAMILY: &'static str = api::FAMILY;
+    const EXE_SUFFIX: &'static str = api::EXE_SUFFIX;
+    const DLL_SUFFIX: &'static str = api::DLL_SUFFIX;
+
+    fn path_separator() -> char {
+        api::path_separator()
+    }
+}
+
+use crate::example::{self, Example, Topic};
+
+pub struct Demo;
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "platform" }
+
+    fn description(&self) -> &'static str {
+        "Selecting a module per target OS with `#[cfg]` and `#[path]`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["cfg", "files"] }
+
+    fn topic(&self) -> Topic { Topic::Cfg }
+
+    fn source(&self) -> &'static str {
+        example::section(example::LIB_RS, "Advanced usage")
+    }
+
+    fn run(&self) {
+        println!("This code lives in `./linux.rs`, loaded as `crate::platform`.");
+        println!("Running on platform family '{}'.", api::FAMILY);
+        println!("Through the public `crate::platform_api`, the path separator is {:?}.",
+                 crate::platform_api::path_separator());
+        println!("A dynamic library called `foo` is in the file {:?}.",
+                 crate::platform_api::library_file_name::<Platform>("foo"));
+    }
+}
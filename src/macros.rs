@@ -0,0 +1,28 @@
+// # Macros and modules
+
+// Everything else in a module tree is found by path, in any order: a function
+// can be called above its definition, and from any module that can see it.
+// `macro_rules!` macros are different. By default they have textual scope:
+// a macro can only be used after its definition in the source, reading the
+// crate top to bottom with each `mod` declaration's file pasted in where it's
+// declared. It stays in scope until the end of the module that defined it,
+// including inside any child modules declared after it.
+
+// Each child module here shows one part of how macros and modules interact.
+
+// Defined before the `mod` declarations below, so every child module can use
+// `double!` without importing it, even though they're in other files.
+macro_rules! double {
+    ($e:expr) => {
+        $e * 2
+    };
+}
+
+pub mod ordering;
+pub mod exported;
+pub mod legacy;
+pub mod reexport;
+pub mod hygiene;
+pub mod generated;
+pub mod derived;
+pub mod attribute;
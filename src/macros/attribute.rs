@@ -0,0 +1,47 @@
+// # Attribute macros
+
+// An attribute macro is a procedural macro used as an attribute, like
+// `#[example(...)]` below. It's given the item it's on, and replaces it with
+// whatever it returns. Like derives, attribute macros must be defined in a
+// proc-macro crate, here `../../crates/derive-example/`, and are imported by
+// path from it.
+use derive_example::example;
+
+// A derive can only add code next to a struct, enum or union. An attribute
+// can go on any item, including functions, and can change the item itself.
+
+// `#[example]` keeps this function, and adds a `static ATTRIBUTE` next to it:
+// an `Example` with these details, which runs the function. The spec of
+// examples, `../../spec/examples.toml`, lists that `static`, so this example
+// needs no struct or trait impl.
+#[example(
+    name = "attribute",
+    description = "An attribute macro turning a plain function into an example",
+    tags = ["macros", "dependencies"],
+    topic = Macros,
+    source = "attribute.rs",
+)]
+pub fn attribute() {
+    println!("This is a plain function, made into an example by #[example(...)].");
+    println!("The macro added `static ATTRIBUTE` next to it, in this module.");
+}
+
+// The generated `static` is an ordinary item of this module, with a path like
+// any other: `crate::macros::attribute::ATTRIBUTE`. The proc macro can't see
+// the module tree it's expanded in, so it can't add the example to the
+// central list: macros only ever see the tokens they're given, and can't
+// edit other files. It can register it by emitting `crate::registry::register!`, though,
+// since that works from any module.
+
+#[cfg(test)]
+mod tests {
+    use crate::example::{Example, Topic};
+
+    #[test]
+    fn generated_static() {
+        let example: &dyn Example = &super::ATTRIBUTE;
+        assert_eq!(example.name(), "attribute");
+        assert_eq!(example.topic(), Topic::Macros);
+        assert!(example.source().starts_with("// # Attribute macros"));
+    }
+}
@@ -0,0 +1,54 @@
+// # Procedural macros from a companion crate
+
+// `macro_rules!` macros can be defined anywhere, but procedural macros, like
+// custom derives, must be defined in a crate of their own with
+// `proc-macro = true` in its `Cargo.toml`. The compiler builds that crate
+// first, for the machine doing the compiling, and runs it while compiling
+// this one. So a crate can't use its own proc macros: they'd have to run
+// before the crate they're in was compiled.
+
+// This crate's companion is `../../crates/derive-example/`, a dependency
+// in `Cargo.toml`. Its `derive(Example)` is imported by path like any item:
+use derive_example::Example;
+
+// That import didn't clash with the `Example` trait, although they have the
+// same name: derive macros live in the macro namespace, and traits in the type
+// namespace. `serde::Serialize` is the same: one `use`, a trait and a derive.
+use crate::example::Example;
+
+// The derive implements the `Example` trait from the `#[example(...)]`
+// attribute, instead of the five one-line methods written out by hand in
+// every other example.
+#[derive(Example)]
+#[example(
+    name = "derived",
+    description = "Implementing `Example` with a derive macro from a proc-macro crate",
+    tags = ["macros", "dependencies"],
+    topic = Macros,
+    source = "derived.rs",
+)]
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Demo {
+    // The generated `run` calls this.
+    fn demo(&self) {
+        println!("`{}` implements `Example` with #[derive(Example)].", self.name());
+        println!("Its tags are {:?}, and its topic is {:?}.", self.tags(), self.topic());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::{Example, Topic};
+
+    #[test]
+    fn derived_impl() {
+        let demo: &dyn Example = &super::Demo;
+        assert_eq!(demo.name(), "derived");
+        assert_eq!(demo.tags(), ["macros", "dependencies"]);
+        assert_eq!(demo.topic(), Topic::Macros);
+        assert!(demo.source().starts_with("// # Procedural macros"));
+    }
+}
@@ -0,0 +1,95 @@
+// # Exporting macros with `#[macro_export]`
+
+// Textual scope ends with the module that defines a macro, so on its own a
+// macro can never leave its module, let alone its crate. `#[macro_export]`
+// changes that: however deep the definition is, the macro becomes an item of
+// the crate root, usable by path from anywhere in the crate as `crate::square!`,
+// and from other crates as `modules_demo::square!`.
+
+pub mod deep {
+    pub mod deeper {
+        // Exported, so it lives at `crate::square`, not `crate::macros::...`.
+        #[macro_export]
+        macro_rules! square {
+            ($e:expr) => {
+                $e * $e
+            };
+        }
+
+        // Not exported, so only usable by textual scope, below here in this module.
+        macro_rules! cube {
+            ($e:expr) => {
+                $e * $e * $e
+            };
+        }
+
+        pub fn both(n: i32) -> (i32, i32) {
+            (square!(n), cube!(n))
+        }
+    }
+
+    // Textual scope ended with `deeper`, so neither macro can be used here
+    // by name alone. `square!` can still be used by its path, but `cube!`
+    // can't be reached at all: `crate::cube!` is error E0433 "could not find
+    // `cube` in the crate root". The doctest below checks that.
+
+    /// ```compile_fail,E0433
+    /// mod deeper {
+    ///     macro_rules! cube {
+    ///         ($e:expr) => { $e * $e * $e };
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     crate::cube!(2);
+    /// }
+    /// ```
+    pub fn square_only(n: i32) -> i32 {
+        crate::square!(n)
+    }
+}
+
+// Anywhere else in the crate, an exported macro is used by its path from the
+// crate root, in any order, just like a function. `lib.rs` has an example.
+pub fn by_path(n: i32) -> i32 {
+    crate::square!(n)
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "macro_export" }
+
+    fn description(&self) -> &'static str {
+        "`#[macro_export]` makes a macro from a deep module usable at the crate root"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["macros", "visibility"] }
+
+    fn topic(&self) -> Topic { Topic::Macros }
+
+    fn source(&self) -> &'static str {
+        include_str!("exported.rs")
+    }
+
+    fn run(&self) {
+        println!("deep::deeper::both(3) uses `square!` and `cube!` = {:?}", deep::deeper::both(3));
+        println!("crate::square!(5) = {}", by_path(5));
+        println!("From the crate root, `square!(6)` = {}", crate::square_at_root(6));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn exported_macro_paths() {
+        assert_eq!(super::deep::deeper::both(2), (4, 8));
+        assert_eq!(super::deep::square_only(3), 9);
+        assert_eq!(super::by_path(4), 16);
+        assert_eq!(crate::square!(5), 25);
+    }
+}
@@ -0,0 +1,114 @@
+// # Modules generated by a macro
+
+// A macro can expand to any items, including `mod` blocks, so repetitive
+// module trees can be written once as a macro. Each call below makes one
+// module per unit, each with the same items inside.
+
+macro_rules! unit_modules {
+    ($($name:ident: $symbol:literal = $metres:literal;)*) => {
+        $(
+            pub mod $name {
+                pub const SYMBOL: &str = $symbol;
+
+                pub fn to_metres(value: f64) -> f64 {
+                    value * $metres
+                }
+
+                pub fn from_metres(metres: f64) -> f64 {
+                    metres / $metres
+                }
+            }
+        )*
+
+        // The macro can also generate code that refers to the modules it made.
+        pub const SYMBOLS: &[&str] = &[$($name::SYMBOL),*];
+    };
+}
+
+// Generated modules are ordinary modules, so each is visible as a child of
+// the module the macro was called in: `length::metric::metres`, and so on.
+pub mod length {
+    pub mod metric {
+        unit_modules! {
+            millimetres: "mm" = 0.001;
+            metres: "m" = 1.0;
+            kilometres: "km" = 1000.0;
+        }
+    }
+
+    pub mod imperial {
+        unit_modules! {
+            inches: "in" = 0.0254;
+            feet: "ft" = 0.3048;
+            miles: "mi" = 1609.344;
+        }
+    }
+}
+
+// Paths to the generated items resolve like any others, from anywhere that
+// can see them.
+pub fn miles_to_kilometres(miles: f64) -> f64 {
+    length::metric::kilometres::from_metres(length::imperial::miles::to_metres(miles))
+}
+
+// One thing can't see them though: tools that read the source without
+// expanding macros. `cargo run -- tree` parses the source much like that, so it
+// shows `length::metric` but none of the unit modules inside it.
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "generated_modules" }
+
+    fn description(&self) -> &'static str {
+        "A `macro_rules!` macro that expands to `mod` blocks"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["macros", "inline"] }
+
+    fn topic(&self) -> Topic { Topic::Macros }
+
+    fn source(&self) -> &'static str {
+        include_str!("generated.rs")
+    }
+
+    fn run(&self) {
+        println!("Generated metric units: {:?}", length::metric::SYMBOLS);
+        println!("Generated imperial units: {:?}", length::imperial::SYMBOLS);
+        println!("26.2 mi = {:.1} km", miles_to_kilometres(26.2));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::length::{imperial, metric};
+
+    #[test]
+    fn generated_paths() {
+        assert_eq!(metric::metres::SYMBOL, "m");
+        assert_eq!(imperial::feet::SYMBOL, "ft");
+        assert_eq!(metric::SYMBOLS, ["mm", "m", "km"]);
+        assert_eq!(metric::kilometres::to_metres(2.0), 2000.0);
+        assert_eq!(imperial::inches::from_metres(0.254), 10.0);
+    }
+
+    #[test]
+    fn conversions_across_modules() {
+        assert!((super::miles_to_kilometres(1.0) - 1.609344).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tree_does_not_expand_macros() {
+        use crate::modtree::ModuleTree;
+        use std::path::Path;
+
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs");
+        let tree = ModuleTree::load(&root).unwrap();
+        assert!(tree.find("crate::macros::generated::length::metric").is_some());
+        assert!(tree.find("crate::macros::generated::length::metric::metres").is_none());
+    }
+}
@@ -0,0 +1,95 @@
+// # `$crate` in exported macros
+
+// A macro expands where it's invoked, so paths inside it are resolved there,
+// not where it was defined. Helper functions called from an exported macro
+// need a path that works from any module of any crate.
+
+// Inside a macro, `$crate` is that path: it always means the crate that
+// defined the macro, wherever the expansion ends up.
+
+pub mod helpers {
+    pub fn label(name: &str, value: i32) -> String {
+        format!("{name} = {value}")
+    }
+}
+
+// Works everywhere: in this crate `$crate` means `crate`, and in other crates
+// it means `::modules_demo`.
+#[macro_export]
+macro_rules! labelled {
+    ($e:expr) => {
+        $crate::macros::hygiene::helpers::label(stringify!($e), $e)
+    };
+}
+
+// A relative path like `helpers::label` would break straight away, in any
+// module without its own `helpers`. A path from `crate::` is subtler: it works
+// from every module in this crate, so this crate's own tests pass, but in
+// another crate `crate` means *that* crate, which has no `macros` module.
+//
+// This one is broken on purpose, and hidden from the docs. The doctest below
+// is compiled as a separate crate, like any user of this library, and checks
+// that using it fails with E0433, "could not find `macros` in the crate root".
+// Clippy spots this mistake too, so its lint has to be allowed here.
+
+/// ```compile_fail,E0433
+/// let s = modules_demo::labelled_from_crate!(1 + 1);
+/// ```
+#[doc(hidden)]
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! labelled_from_crate {
+    ($e:expr) => {
+        crate::macros::hygiene::helpers::label(stringify!($e), $e)
+    };
+}
+
+pub mod elsewhere {
+    // A module with nothing imported, where `helpers` means nothing.
+    pub fn use_both() -> (String, String) {
+        (crate::labelled!(2 * 3), crate::labelled_from_crate!(2 * 4))
+    }
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "macro_hygiene" }
+
+    fn description(&self) -> &'static str {
+        "Exported macros call helpers through `$crate`, not `crate`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["macros", "paths"] }
+
+    fn topic(&self) -> Topic { Topic::Macros }
+
+    fn source(&self) -> &'static str {
+        include_str!("hygiene.rs")
+    }
+
+    fn run(&self) {
+        let (dollar_crate, plain_crate) = elsewhere::use_both();
+        println!("With `$crate`: {dollar_crate}");
+        println!("With `crate`, which only works inside this crate: {plain_crate}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn from_another_module() {
+        let (dollar_crate, plain_crate) = super::elsewhere::use_both();
+        assert_eq!(dollar_crate, "2 * 3 = 6");
+        assert_eq!(plain_crate, "2 * 4 = 8");
+    }
+
+    #[test]
+    fn from_tests_module() {
+        assert_eq!(crate::labelled!(1 + 2), "1 + 2 = 3");
+    }
+}
@@ -0,0 +1,95 @@
+// # `#[macro_use]`, the pre-2018 way
+
+// Before the 2018 edition, macros couldn't be imported with `use`. The
+// only way to share them was to stretch their textual scope with
+// `#[macro_use]`, so older crates often start `lib.rs` with:
+//
+//     #[macro_use]
+//     mod macros;
+//
+// declared first, so the macros are in scope in every module after it.
+// Macros from other crates were brought in the same way, with
+// `#[macro_use] extern crate some_crate;`. Both still work, and you'll meet
+// them in older codebases.
+
+// `#[macro_use]` on a module keeps its macros in scope after the module ends,
+// for the rest of the parent module.
+#[macro_use]
+mod old_style {
+    macro_rules! halve {
+        ($e:expr) => {
+            $e / 2
+        };
+    }
+}
+
+// Without `#[macro_use]` above, `halve!` would be out of scope here.
+pub fn old_way(n: i32) -> i32 {
+    halve!(n)
+}
+
+// The modern way treats macros more like other items: `#[macro_export]`
+// puts the macro at the crate root, and `use` imports it by path, in any
+// module and in any order.
+mod new_style {
+    #[macro_export]
+    macro_rules! quarter {
+        ($e:expr) => {
+            $e / 4
+        };
+    }
+}
+
+pub mod user {
+    // Imported like a function would be. No attribute on any `mod` needed.
+    use crate::quarter;
+
+    pub fn new_way(n: i32) -> i32 {
+        quarter!(n)
+    }
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "macro_use" }
+
+    fn description(&self) -> &'static str {
+        "The legacy `#[macro_use] mod` pattern next to importing macros with `use`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["macros", "use"] }
+
+    fn topic(&self) -> Topic { Topic::Macros }
+
+    fn source(&self) -> &'static str {
+        include_str!("legacy.rs")
+    }
+
+    fn run(&self) {
+        println!("halve!(10), in scope through `#[macro_use] mod old_style` = {}", old_way(10));
+        println!("quarter!(10), imported with `use crate::quarter` = {}", user::new_way(10));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn old_way() {
+        assert_eq!(super::old_way(10), 5);
+        // Still in textual scope here, as this module comes after `old_style`.
+        assert_eq!(halve!(8), 4);
+    }
+
+    #[test]
+    fn new_way() {
+        use crate::quarter;
+
+        assert_eq!(super::user::new_way(12), 3);
+        assert_eq!(quarter!(8), 2);
+    }
+}
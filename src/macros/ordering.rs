@@ -0,0 +1,87 @@
+// # Textual order of macros
+
+// `double!` was defined in `../macros.rs` before `mod ordering;`, so it's
+// in scope here without any `use`.
+pub fn from_parent() -> i32 {
+    double!(21)
+}
+
+// A macro used before its definition is an error, "cannot find macro
+// `triple` in this scope", even in the same file. The doctest below checks
+// that it really fails. A function here would be fine in either order.
+
+/// ```compile_fail
+/// fn nine() -> i32 {
+///     triple!(3)
+/// }
+///
+/// macro_rules! triple {
+///     ($e:expr) => { $e * 3 };
+/// }
+/// # fn main() {}
+/// ```
+macro_rules! triple {
+    ($e:expr) => {
+        $e * 3
+    };
+}
+
+// From here on `triple!` works, in this module and in child modules
+// declared below, inline or in files.
+pub fn after_definition() -> i32 {
+    triple!(3)
+}
+
+pub mod nested {
+    pub fn both() -> i32 {
+        triple!(double!(1))
+    }
+}
+
+// `triple!`'s scope ends with this module, so `../macros.rs` and its other
+// children can't use it, even after `mod ordering;`. Sharing a macro beyond
+// that takes `#[macro_use]`, `#[macro_export]` or a `pub use`.
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "macro_ordering" }
+
+    fn description(&self) -> &'static str {
+        "`macro_rules!` macros are in scope from their definition down, in textual order"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["macros"] }
+
+    fn topic(&self) -> Topic { Topic::Macros }
+
+    fn source(&self) -> &'static str {
+        concat!(include_str!("../macros.rs"), "\n", include_str!("ordering.rs"))
+    }
+
+    fn run(&self) {
+        println!("double!(21) from the parent module = {}", from_parent());
+        println!("triple!(3) after its definition = {}", after_definition());
+        println!("triple!(double!(1)) in a child module = {}", nested::both());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn macros_in_scope() {
+        assert_eq!(super::from_parent(), 42);
+        assert_eq!(super::after_definition(), 9);
+        assert_eq!(super::nested::both(), 6);
+    }
+
+    // The tests module is declared after both macros, so it can use them too.
+    #[test]
+    fn tests_module_sees_macros() {
+        assert_eq!(triple!(double!(2)), 12);
+    }
+}
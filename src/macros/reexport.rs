@@ -0,0 +1,99 @@
+// # Re-exporting macros with `use`
+
+// Since the 2018 edition a `macro_rules!` macro can be imported and
+// re-exported with `use`, like any other item. Once a `use` has given it a
+// path, it can be invoked by that path, from anywhere that can see it, and
+// in any order: no textual scope rules.
+
+pub mod inner {
+    macro_rules! repeat {
+        ($s:expr, $n:expr) => {
+            $s.repeat($n)
+        };
+    }
+
+    // This gives the macro the path `crate::macros::reexport::inner::repeat`.
+    // A macro without `#[macro_export]` can't leave its crate, so `pub use`
+    // here would be error E0364, "`repeat` is only public within the crate,
+    // and cannot be re-exported outside". `pub(crate)` is as far as it goes.
+    // The doctest below checks that.
+
+    /// ```compile_fail,E0364
+    /// mod inner {
+    ///     macro_rules! repeat {
+    ///         ($s:expr, $n:expr) => { $s.repeat($n) };
+    ///     }
+    ///
+    ///     pub use repeat;
+    /// }
+    /// # fn main() {}
+    /// ```
+    pub(crate) use repeat;
+}
+
+// Re-exports chain as usual. This one makes `crate::macros::reexport::repeat`,
+// and `lib.rs` re-exports it again as `crate::repeat`.
+pub(crate) use inner::repeat;
+
+pub fn by_module_path() -> String {
+    inner::repeat!("ab", 2)
+}
+
+// A macro used by other crates needs `#[macro_export]`, which always puts it at
+// the crate root. To give it a path in a module instead, a crate can export
+// it under a hidden name, and `pub use` it where it belongs. Other crates
+// then write `modules_demo::macros::reexport::shout!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __shout {
+    ($s:expr) => {
+        format!("{}!", $s.to_uppercase())
+    };
+}
+
+pub use __shout as shout;
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "macro_reexport" }
+
+    fn description(&self) -> &'static str {
+        "Giving `macro_rules!` macros paths with `use` and `pub use`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["macros", "use", "re-export"] }
+
+    fn topic(&self) -> Topic { Topic::Macros }
+
+    fn source(&self) -> &'static str {
+        include_str!("reexport.rs")
+    }
+
+    fn run(&self) {
+        println!("inner::repeat!(\"ab\", 2) = {:?}", by_module_path());
+        println!("crate::repeat!(\"ha\", 3) = {:?}", crate::repeat!("ha", 3));
+        println!("shout!(\"hey\") = {:?}", shout!("hey"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn every_path_is_the_same_macro() {
+        assert_eq!(super::by_module_path(), "abab");
+        assert_eq!(super::repeat!("ab", 2), "abab");
+        assert_eq!(crate::macros::reexport::repeat!("ab", 2), "abab");
+        assert_eq!(crate::repeat!("ab", 2), "abab");
+    }
+
+    #[test]
+    fn hidden_export_with_module_path() {
+        assert_eq!(super::shout!("hi"), "HI!");
+        assert_eq!(crate::__shout!("hi"), "HI!");
+    }
+}
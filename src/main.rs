@@ -1,260 +1,404 @@
-// Mute some warnings by unused code in the examples.
-#![allow(dead_code, unused_imports)]
+// # The binary crate
+
+// A package can contain one library crate, rooted at `./lib.rs`, and any
+// number of binary crates. This binary is rooted here at `./main.rs`, and
+// `./bin/` has two more.
+
+// The library is a separate crate, so the binary can only see what it exports
+// with `pub`, through a path starting with the library's crate name. That's
+// normally the package name with `-` replaced by `_`, here
+// `rust_module_example`, but `Cargo.toml` renames the library:
+//
+//     [lib]
+//     name = "modules_demo"
+//
+// So the path starts `modules_demo::`, and `rust_module_example::` would be
+// error E0433, "failed to resolve: use of unresolved module". The package
+// name, and the name of this binary, are still `rust-module-example`.
+use modules_demo::{book, classroom, exercises, modtree, progress, quiz, registry, tour};
+
+use std::io;
+use std::path::Path;
+
+// The binary has its own module tree. This declares `./cli.rs` as a child
+// of the binary's root module; the library doesn't know about it.
+mod cli;
 
-// # Rust modules example
-
-// Most of the time a module means a file, but there are other
-// options, see below.
-
-// The modules form a tree from the root of the crate (usually
-// `src/main.rs` for a bin, or `src/lib.rs` for a library) down to each module.
-
-// Every child module must be explicitly declared with the `mod` keyword.
-// Just having the file present in the tree will not do anything.
-
-// This introduces the module `a` into the current module's child modules,
-// using the definition in `./a.rs`.
-mod a;
-
-// ## Multi-level modules
-
-// When a module will have its own child modules, there are 2 possible styles
-
-// Style 1 has been in Rust since the beginning, and I actually prefer it as it keeps
-// the child module contents in the same sub-directory:
-
-mod multi_level_style_1; // references `multi_level_style_1/mod.rs`
-
-// In directory `./multi_level_style_1/` there is `mod.rs` and
-// `child.rs`, with `mod.rs` declaring `child.rs`.
-
-// Style 2 was added in the 2018 edition of Rust and is now officially recommended.
-// It was designed to avoid having lots of files named `mod.rs` in the
-// project, as well as increase consistency with modules that do not
-// have children:
-
-mod multi_level_style_2; // references `multi_level_style_2.rs`, which declares a child in
-                         // `multi_level_style_2/child.rs`
-
-// It causes a compilation error when there are 2 files available for
-// the same module name in the different styles,
-// e.g. if there is `./foo.rs` and `./foo/mod.rs` and a module is declared with `mod foo`.
-
-// ## Inline modules
-
-// A module may also be declared in the parent file:
-mod inline {
-    // `inline` has its own scope, in particular to refer to other items
-    // in main.rs you must refer to them from the crate root like `crate::item`,
-    // relative to the current module like `super::item`.
-
-    // Even though we're in `main.rs` this has to be declared `pub`
-    // for other items in the top-level `main` module to see it.
-    pub fn inline_fn() {
-        super::f();
-        crate::f();
+fn main() {
+    let command = match cli::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("Error: {e}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
 
-        inline_private();
+    match command {
+        cli::Command::Default => {
+            modules_demo::greet();
+            // `config` is a different file in debug and release builds.
+            println!("Built with the {} config.", modules_demo::config::PROFILE);
+        }
+        cli::Command::List => {
+            or_exit(registry::write_list(&mut io::stdout()));
+        }
+        cli::Command::Run { name } => match registry::find(&name) {
+            Some(example) => {
+                example.run();
+                progress::record(|p| {
+                    p.examples.insert(name);
+                });
+            }
+            None => {
+                eprintln!("Error: unknown example '{name}'");
+                std::process::exit(1);
+            }
+        },
+        cli::Command::Tour { record: _, replay: Some(file) } => {
+            let result = tour::session::load(Path::new(&file))
+                .and_then(|events| tour::session::replay(&events, &mut io::stdin().lock(), &mut io::stdout()));
+            if let Err(e) = result {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Tour { record, replay: None } => {
+            // `quiz` at the tour's prompt asks these in turn.
+            let questions = question_bank(&[]).unwrap_or_else(|e| {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            });
+            let questions = quiz::pick(questions, usize::MAX, time_seed());
+            let events = or_exit(tour::run(&questions, &mut io::stdin().lock(), &mut io::stdout()));
+            progress::record(|p| {
+                for event in &events {
+                    match event {
+                        tour::Event::Shown(name) | tour::Event::Ran(name) => {
+                            p.examples.insert(name.to_string());
+                        }
+                        tour::Event::Answered { question, answer } if *answer == Some(question.answer) => {
+                            p.questions.insert(question.id());
+                        }
+                        tour::Event::Answered { .. } => {}
+                    }
+                }
+            });
+            if let Some(file) = record {
+                match tour::session::save(Path::new(&file), &events) {
+                    Ok(()) => println!("Saved the session in {file}, play it back with `tour --replay {file}`."),
+                    Err(e) => {
+                        eprintln!("Error: couldn't save the session to {file}: {e}");
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
+        cli::Command::Quiz { count, seed, banks, results } => {
+            let questions = question_bank(&banks).unwrap_or_else(|e| {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            });
+            // Without a seed, the time picks the questions.
+            let seed = seed.unwrap_or_else(time_seed);
+            println!("Answer y or n, or just press Enter to skip a question.\n");
+            let questions = quiz::pick(questions, count, seed);
+            let answers = or_exit(quiz::run(&questions, &mut io::stdin().lock(), &mut io::stdout()));
+            progress::record(|p| {
+                let right = questions.iter().zip(&answers).filter(|&(_, &right)| right);
+                p.questions.extend(right.map(|(q, _)| q.id()));
+            });
+            if !answers.is_empty() {
+                // Saved as absolute paths, so the retake hint in `report`
+                // works from any directory.
+                let banks: Vec<_> = banks.iter()
+                    .map(|bank| std::path::absolute(bank).map_or_else(|_| bank.clone(), |p| p.display().to_string()))
+                    .collect();
+                save_results(results, &quiz::report::results(&questions, &answers, seed, &banks));
+            }
+        }
+        cli::Command::QuizExport { format, banks } => {
+            let result = question_bank(&banks)
+                .and_then(|questions| {
+                    quiz::export::write(&questions, format, &mut io::stdout()).map_err(|e| e.to_string())
+                });
+            if let Err(e) = result {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::ExportBook { dir } => {
+            let dir = dir.unwrap_or_else(|| "book".to_string());
+            if let Err(e) = book::write(Path::new(&dir), &mut io::stdout()) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Serve { port, count, seed, banks } => {
+            if let Err(e) = serve(port, count, seed, &banks) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Report { file } => {
+            if let Err(e) = report(file) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Progress => {
+            if let Err(e) = show_progress() {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Hint { exercise, level, dir } => {
+            let default = Path::new(env!("CARGO_MANIFEST_DIR")).join("exercises");
+            let dir = dir.map_or(default, Into::into);
+            if let Err(e) = exercises::hint(&dir, &exercise, level, &mut io::stdout()) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Verify { watch: true, dir } => {
+            let default = Path::new(env!("CARGO_MANIFEST_DIR")).join("exercises");
+            let dir = dir.map_or(default, Into::into);
+            let mut record = |verified: &exercises::Verified| {
+                progress::record(|p| p.exercises.extend(verified.compiled.iter().cloned()));
+            };
+            if let Err(e) = exercises::watch(&dir, &mut io::stdout(), &mut record) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Verify { watch: false, dir } => {
+            let default = Path::new(env!("CARGO_MANIFEST_DIR")).join("exercises");
+            let dir = dir.map_or(default, Into::into);
+            match exercises::verify(&dir, &mut io::stdout()) {
+                Ok(verified) => {
+                    progress::record(|p| p.exercises.extend(verified.compiled));
+                    if verified.failed.is_some() {
+                        std::process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        cli::Command::Tree { path, format } => {
+            let path = path.as_deref().unwrap_or(env!("CARGO_MANIFEST_DIR"));
+            if let Err(e) = tree(Path::new(path), format) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::TreeDiff { old, new } => {
+            let result = load(Path::new(&old)).and_then(|old| {
+                let new = load(Path::new(&new))?;
+                modtree::diff::write(&old, &new, &mut io::stdout())
+            });
+            if let Err(e) = result {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Analyze { analysis, path } => {
+            let path = path.as_deref().unwrap_or(env!("CARGO_MANIFEST_DIR"));
+            if let Err(e) = analyze(Path::new(path), analysis) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::WhichFile { module, path } => {
+            let path = path.as_deref().unwrap_or(env!("CARGO_MANIFEST_DIR"));
+            let result = load(Path::new(path)).and_then(|tree| {
+                modtree::which_file::write(&tree, &module, &mut io::stdout())
+            });
+            if let Err(e) = result {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Resolve { from, use_path, path } => {
+            let path = path.as_deref().unwrap_or(env!("CARGO_MANIFEST_DIR"));
+            let result = load(Path::new(path)).and_then(|tree| {
+                let from = tree.find(&from).ok_or_else(|| io::Error::new(
+                    io::ErrorKind::NotFound, format!("no module `{from}` in this crate")))?;
+                modtree::resolve::write(&tree, from, &use_path, &mut io::stdout())
+            });
+            if let Err(e) = result {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Refactor { refactor, path } => {
+            let path = path.as_deref().unwrap_or(env!("CARGO_MANIFEST_DIR"));
+            if let Err(e) = apply_refactor(Path::new(path), refactor) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::NewModule { name, style, parent, tests, path } => {
+            let path = path.as_deref().unwrap_or(env!("CARGO_MANIFEST_DIR"));
+            let result = load(Path::new(path)).and_then(|tree| {
+                modtree::scaffold::write(&tree, &parent, &name, style, tests, &mut io::stdout())
+            });
+            if let Err(e) = result {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Prelude { count, dry_run, path } => {
+            let path = path.as_deref().unwrap_or(env!("CARGO_MANIFEST_DIR"));
+            let result = load(Path::new(path)).and_then(|tree| {
+                modtree::prelude::write(&tree, count, dry_run, &mut io::stdout())
+            });
+            if let Err(e) = result {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Project { name, layout, dir } => {
+            let dir = dir.as_deref().unwrap_or(".");
+            if let Err(e) = modtree::project::write(Path::new(dir), &name, layout, &mut io::stdout()) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        #[cfg(feature = "tui")]
+        cli::Command::Browse => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs");
+            let result = modtree::ModuleTree::load(&root).and_then(|tree| modules_demo::tui::run(&tree));
+            if let Err(e) = result {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Help => println!("{}", cli::USAGE),
     }
-
-    // Only `inline` and its child modules can see `inline_private`.
-    fn inline_private() {}
 }
 
-fn f() {}
-
-// Inline modules are often used to contain unit tests:
-
-#[cfg(test)] // Only processed when building tests, like with `cargo test` or `cargo build --tests`.
-mod tests {
-    #[test]
-    fn ok() {
-        assert_eq!(1 + 1, 2);
-    }
+fn show_progress() -> io::Result<()> {
+    let path = progress::path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory to keep progress in"))?;
+    let saved = progress::Progress::load(&path)?;
+    let exercises = exercises::list(&Path::new(env!("CARGO_MANIFEST_DIR")).join("exercises"))?;
+    let questions = question_bank(&[]).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        .iter()
+        .map(|q| q.id())
+        .collect();
+    let next = tour::steps().into_iter().map(|e| e.name()).find(|name| !saved.examples.contains(*name));
+    progress::write(&saved, (registry::EXAMPLES.len(), exercises.len()), &questions, next, &mut io::stdout())?;
+    println!("\nSaved in {}.", path.display());
+    Ok(())
 }
 
-// ## Advanced usage
-
-// A module declaration can override the file path that it loads:
-#[path = "path_override_foo.rs"]
-mod path_override;
-
-// The main reason I've seen this is with per-platform conditional compilation of modules:
-
-#[cfg(unix)] // Only processed when the target OS is Unix-like, e.g. MacOS or Linux.
-#[path = "unix.rs"]
-mod platform;
-
-#[cfg(windows)] // Only processed when the target OS is Windows.
-#[path = "windows.rs"]
-mod platform;
-
-// This allows code in this module to use items in `platform::*` without caring
-// what implementation is going to be included:
-fn use_platform() -> &'static str {
-    platform::FAMILY
+// The result of reading input or writing output, or else the end of the
+// program. A broken pipe, as in `list | head -1`, means nothing's reading
+// the output any more, so there's nobody to tell.
+fn or_exit<T>(result: io::Result<T>) -> T {
+    result.unwrap_or_else(|e| {
+        if e.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("Error: {e}");
+        }
+        std::process::exit(1);
+    })
 }
 
-// Dependency crates can also be compiled conditionally based on the
-// build target, enabled feature flags, and other factors.
-
-// ## Name resolution
-mod name_resolution {
-    // Everything is private by default in Rust, including modules.
-    // `private_inner` is visible in `name_resolution` and its child
-    // modules, but not by `name_resolution`'s parents.
-    mod private_inner {
-
-        // `a` is visible to `private_inner` and its child modules, but nowhere else.
-        fn a() {}
-
-        // `pub` keyword makes an item exported by its parent module,
-        // so `b` is visible to any module that can see
-        // `private_inner`, but `main` cannot see `private_inner`, so it still
-        // cannot see `b`.
-        pub fn b() {}
-    }
-
-    fn test_private_inner() {
-        // Access a child module's exported item with the syntax `${child_module}::{item}`:
-        private_inner::b();
-
-        // This will not compile, because `a` is not exported with `pub`:
-        // private_inner::a();
-    }
-
-    // `main` sees this module and everything exported by it.
-    pub mod public_inner {
-        pub fn a() {}
-    }
+// A seed that's different each time.
+fn time_seed() -> u64 {
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+    now.map_or(1, |d| d.as_nanos() as u64)
 }
 
-// ## Imports with `use`
-
-// Items visible in a scope can be imported into that scope with the `use` keyword:
-mod use_examples {
-
-    mod use_inner {
-        pub fn a() {}
-        pub fn b() {}
-    }
-
-    // `use_inner::a` is now in scope as `a` in `use_examples`.
-    use use_inner::a;
-
-    fn test_use() {
-        // `use_inner::a` can be called explicitly.
-        use_inner::a();
-
-        // But because we imported it with `use` we can also call it simply as `a`.
-        a();
-
-        // Function definitions also have a scope, so can contain `use` statements,
-        // which work as you'd expect.
-        use use_inner::b;
-        b();
-    }
-
-    mod use_wildcard {
-        pub fn not() {}
-        pub fn my() {}
-        pub fn favourite() {}
-    }
-
-    // `use` also supports wildcards.
-    // I personally don't like this because with multiple wildcards it's more difficult
-    // to know where a particular item comes from.
-    use use_wildcard::*;
+// Hosts a quiz of `count` questions for a class. It only returns if
+// something's wrong.
+fn serve(port: u16, count: usize, seed: Option<u64>, banks: &[String]) -> Result<(), String> {
+    let questions = quiz::pick(question_bank(banks)?, count, seed.unwrap_or_else(time_seed));
+    let listener = std::net::TcpListener::bind(("0.0.0.0", port))
+        .map_err(|e| format!("can't listen on port {port}: {e}"))?;
+    // With port 0 the system picks one.
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    println!("Hosting a quiz of {} questions. Press Ctrl-C to stop.\n", questions.len());
+    let classroom = classroom::Classroom::new(questions);
+    let host = format!("http://{}:{port}", classroom::lan_address());
+    println!("Students join at {host}/");
+    println!("Results, for you only, are at {host}{}", classroom.results_path());
+    classroom::serve(listener, classroom);
+    Ok(())
+}
 
-    fn test_use_wildcard() {
-        not();
-        my();
-        favourite();
+// The quiz's built-in questions, those in `questions/`, and those in `banks`.
+fn question_bank(banks: &[String]) -> Result<Vec<quiz::Question>, String> {
+    let mut questions = quiz::built_in();
+    questions.extend(quiz::bank::load_dir(&Path::new(env!("CARGO_MANIFEST_DIR")).join("questions"))?);
+    for bank in banks {
+        questions.extend(quiz::bank::load(Path::new(bank))?);
     }
+    Ok(questions)
+}
 
-    // `use` supports renaming, useful to avoid name clashes:
-
-    mod use_rename {
-        pub fn a() {}
+// Saves a quiz's results to `file`, or beside the progress file. Like
+// progress, failing to is only a warning.
+fn save_results(file: Option<String>, results: &modules_demo::json::Value) {
+    let Some(path) = file.map(Into::into).or_else(quiz::report::path) else { return };
+    match quiz::report::save(&path, results) {
+        Ok(()) => println!("Scores by topic saved in {}, see `report`.", path.display()),
+        Err(e) => eprintln!("Warning: couldn't save the results to {}: {e}", path.display()),
     }
+}
 
-    // This would be a compile error, because we already imported `use_inner::a` above.
-    // use use_rename::a;
-
-    // This imports `use_rename::a` as `a_renamed`.
-    use use_rename::a as a_renamed;
-
-    // `use` supports a nested syntax which avoids repetition in imports:
-
-    mod use_nested_1 {
-        pub mod use_nested_2 {
-            pub fn g() {}
-            pub fn h() {}
-        }
-
-        pub mod use_nested_3 {
-            pub fn g() {}
-            pub fn i() {}
+fn report(file: Option<String>) -> io::Result<()> {
+    let path = file.map(Into::into).or_else(quiz::report::path)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory to find quiz results in"))?;
+    let results = match quiz::report::load(&path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let message = format!("no quiz results in {} yet, take the `quiz` first", path.display());
+            return Err(io::Error::new(io::ErrorKind::NotFound, message));
         }
-
-        pub fn j() {}
-    }
-
-    use use_nested_1::{
-        use_nested_2::{g, h},
-        use_nested_3::{g as use_nested_3_g, i},
-        j
+        results => results?,
     };
+    quiz::report::write(&results, &mut io::stdout())
+}
 
-    fn test_use_nested() {
-        g();
-        use_nested_3_g();
-        h();
-        i();
-        j();
-    }
-
-    // ### Advanced: re-exporting.
-
-    // Items visible in a scope can be exported by that scope with the `pub use` keywords:
-    mod inner_1 {
-        mod inner_2 {
-            pub fn x() {}
-        }
+fn tree(path: &Path, format: modtree::Format) -> io::Result<()> {
+    let tree = load(path)?;
+    format.write(&tree, &mut io::stdout())
+}
 
-        // `inner_2::x` is now imported into `inner_1`'s scope as `x`, but it has also been
-        // exported because of the `pub`.
-        pub use inner_2::x;
+fn analyze(path: &Path, analysis: cli::Analysis) -> io::Result<()> {
+    let tree = load(path)?;
+    let out = &mut io::stdout();
+    match analysis {
+        cli::Analysis::Deps => modtree::deps::write(&tree, out),
+        cli::Analysis::UnusedPub => modtree::unused_pub::write(&tree, out),
+        cli::Analysis::Orphans => modtree::orphans::write(&tree, out),
+        cli::Analysis::VisibleFrom { item } => modtree::visible_from::write(&tree, &item, out),
+        cli::Analysis::EffectiveVis { item } => modtree::effective::write(&tree, &item, out),
+        cli::Analysis::Api => modtree::api::write(&tree, out),
     }
+}
 
-    fn test_pub_use() {
-        // This doesn't compile, because `inner_2` is not exported by `inner_1`.
-        // inner_1::inner_2::x();
-
-        // This works fine and refers to the same function, because
-        // `inner_1` exported `inner_2::x` with `pub use`.
-        inner_1::x();
+fn apply_refactor(path: &Path, refactor: cli::Refactor) -> io::Result<()> {
+    let tree = load(path)?;
+    let out = &mut io::stdout();
+    match refactor {
+        cli::Refactor::ConvertStyle { module, to } => modtree::convert_style::write(&tree, &module, to, out),
+        cli::Refactor::Extract { module, style } => modtree::extract::write(&tree, &module, style, out),
+        cli::Refactor::Inline { module } => modtree::inline::write(&tree, &module, out),
+        cli::Refactor::Rename { module, new_name } => modtree::rename::write(&tree, &module, &new_name, out),
+        cli::Refactor::MoveItem { item, to } => modtree::move_item::write(&tree, &item, &to, out),
+        cli::Refactor::FlattenReexports { facade } => modtree::flatten::write(&tree, &facade, out),
+        cli::Refactor::Split { module } => modtree::split::write(&tree, &module, out),
     }
-
-    // ----
-
-    // `use` and `pub use` can be applied to almost any item in a module, including:
-    //
-    // * `const`
-    // * `enum`
-    // * `fn`
-    // * `mod`
-    // * `static`
-    // * `struct`
-    // * `trait`
-    // * `type`
-
-    // Macros have some different rules, I may come back to explain those another time.
 }
 
-fn main() {
-    println!("Hello, world! Running on platform family '{}'", use_platform());
-    inline::inline_fn();
-
-    name_resolution::public_inner::a();
+// Load the tree of the crate at `path`, reporting any problems with it.
+fn load(path: &Path) -> io::Result<modtree::ModuleTree> {
+    let tree = modtree::ModuleTree::load(&modtree::find_crate_root(path)?)?;
+    for problem in &tree.problems {
+        eprintln!("{problem}");
+    }
+    Ok(tree)
 }
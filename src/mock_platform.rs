@@ -0,0 +1,33 @@
+// Stand-in platforms, so tests on any host can exercise both the Unix and
+// the Windows behaviour of code generic over `PlatformApi`.
+
+// `./platform_check.rs` compiles the real platform files, to check they
+// agree with each other. These are independent of them: each is only the
+// values a test needs, so a test's expectations don't change when a
+// platform file does.
+
+use crate::platform_api::PlatformApi;
+
+pub struct Unix;
+
+impl PlatformApi for Unix {
+    const FAMILY: &'static str = "unix";
+    const EXE_SUFFIX: &'static str = "";
+    const DLL_SUFFIX: &'static str = ".so";
+
+    fn path_separator() -> char {
+        '/'
+    }
+}
+
+pub struct Windows;
+
+impl PlatformApi for Windows {
+    const FAMILY: &'static str = "windows";
+    const EXE_SUFFIX: &'static str = ".exe";
+    const DLL_SUFFIX: &'static str = ".dll";
+
+    fn path_separator() -> char {
+        '\\'
+    }
+}
@@ -0,0 +1,809 @@
+// A model of a crate's module tree, built by parsing its source files.
+
+// This uses a small hand-rolled parser rather than a full one like `syn`: it
+// only needs to find `mod`, `use` and item declarations, and skips over
+// everything else.
+
+// Conditional compilation isn't evaluated, so every `#[cfg(..)] mod platform;`
+// declaration in `lib.rs` appears in the tree, each with its attributes.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+mod lexer;
+mod parser;
+
+// Output formats, one module each.
+pub mod dot;
+pub mod json;
+pub mod mermaid;
+pub mod text;
+
+// Analyses of a tree, for the `analyze`, `which-file` and `resolve`
+// commands, one module each.
+pub mod deps;
+pub mod orphans;
+pub mod unused_pub;
+pub mod which_file;
+pub mod resolve;
+pub mod visible_from;
+pub mod effective;
+pub mod api;
+
+// Comparing two trees, for `tree diff`.
+pub mod diff;
+
+// Changing a crate's files, for the `scaffold` and `refactor` commands.
+// `edit` applies the changes the others plan.
+pub mod edit;
+pub mod scaffold;
+pub mod prelude;
+pub mod project;
+pub mod convert_style;
+pub mod extract;
+pub mod inline;
+pub mod rename;
+pub mod move_item;
+pub mod flatten;
+pub mod split;
+
+use parser::{Entry, EntryKind};
+
+#[derive(Debug)]
+pub struct ModuleTree {
+    // The directory that file paths are shown relative to, usually the
+    // directory containing `Cargo.toml`.
+    pub base: PathBuf,
+    pub files: Vec<SourceFile>,
+    // `modules[0]` is the crate root, and parents always come before their children.
+    pub modules: Vec<Module>,
+    // Errors rustc would report about the module structure, such as missing files.
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub text: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ModuleId(pub usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FileId(pub usize);
+
+// A byte range in one of the tree's source files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub file: FileId,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug)]
+pub struct Module {
+    // "crate" for the root module.
+    pub name: String,
+    pub parent: Option<ModuleId>,
+    pub children: Vec<ModuleId>,
+    pub vis: Visibility,
+    // Outer attributes on the `mod` declaration, e.g. `cfg(test)`.
+    pub attrs: Vec<String>,
+    // The file containing the module's items, or `None` if it couldn't be read.
+    pub file: Option<FileId>,
+    // Declared with `mod name { ... }` rather than loaded from its own file.
+    pub inline: bool,
+    // The `mod` declaration in the parent, `None` for the crate root.
+    pub decl: Option<Span>,
+    // Where a `mod name;` declaration in this module looks for `name.rs` and
+    // `name/mod.rs`.
+    pub child_dir: PathBuf,
+    pub items: Vec<Item>,
+    pub uses: Vec<Use>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    Private,
+    Public,
+    Crate,
+    Super,
+    SelfOnly,
+    // `pub(in path)`, holding the path's segments.
+    In(Vec<String>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+    Fn,
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    Type,
+    Const,
+    Static,
+    Macro,
+    ExternCrate,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Item {
+    pub name: String,
+    pub kind: ItemKind,
+    pub vis: Visibility,
+    pub attrs: Vec<String>,
+    pub span: Span,
+}
+
+// A single `use` declaration, which may import several paths.
+#[derive(Debug)]
+pub struct Use {
+    pub vis: Visibility,
+    pub attrs: Vec<String>,
+    pub imports: Vec<Import>,
+    pub span: Span,
+}
+
+// One path imported by a `use` declaration, with nested groups expanded:
+// `use a::{b, c as d};` has imports `a::b` and `a::c as d`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Import {
+    pub path: Vec<String>,
+    // `Some("_")` for `use Trait as _;`.
+    pub alias: Option<String>,
+    // `use a::*;` has path `a` and `glob: true`.
+    pub glob: bool,
+}
+
+impl Import {
+    // The name the import introduces into scope, or `None` for globs and `as _`.
+    pub fn name(&self) -> Option<&str> {
+        match &self.alias {
+            _ if self.glob => None,
+            Some(alias) if alias == "_" => None,
+            Some(alias) => Some(alias),
+            None => self.path.last().map(String::as_str),
+        }
+    }
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Visibility::Private => Ok(()),
+            Visibility::Public => write!(f, "pub"),
+            Visibility::Crate => write!(f, "pub(crate)"),
+            Visibility::Super => write!(f, "pub(super)"),
+            Visibility::SelfOnly => write!(f, "pub(self)"),
+            Visibility::In(path) => write!(f, "pub(in {})", path.join("::")),
+        }
+    }
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ItemKind::Fn => "fn",
+            ItemKind::Struct => "struct",
+            ItemKind::Enum => "enum",
+            ItemKind::Union => "union",
+            ItemKind::Trait => "trait",
+            ItemKind::Type => "type",
+            ItemKind::Const => "const",
+            ItemKind::Static => "static",
+            ItemKind::Macro => "macro_rules!",
+            ItemKind::ExternCrate => "extern crate",
+        })
+    }
+}
+
+impl fmt::Display for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // A leading `::` segment is already a separator.
+        let path = self.path.join("::").replacen("::::", "::", 1);
+        write!(f, "{path}")?;
+        if self.glob {
+            write!(f, "::*")?;
+        }
+        if let Some(alias) = &self.alias {
+            write!(f, " as {alias}")?;
+        }
+        Ok(())
+    }
+}
+
+// Parse one path as it would be written in a `use` declaration, e.g.
+// `super::f`, `crate::a::*` or `a::b as c`, for paths typed on the command
+// line. Groups like `a::{b, c}` are rejected, as they're several paths.
+//
+// The text must be in the form `Display` writes, give or take whitespace,
+// so parsing and printing round trip.
+impl std::str::FromStr for Import {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Import, String> {
+        let tokens = lexer::tokenize(s);
+        let imports = parser::use_tree(s, &tokens);
+        let error = || format!("expected a path like `crate::a::b`, found '{}'", s.trim());
+        let import = match imports.as_slice() {
+            [import] if !import.path.is_empty() => import.clone(),
+            _ => return Err(error()),
+        };
+
+        // Anything the parser ignored or dropped shows up as a difference here.
+        let texts = |s: &str| -> Vec<String> {
+            lexer::tokenize(s).iter().map(|t| t.text(s).to_string()).collect()
+        };
+        if texts(&import.to_string()) != texts(s) {
+            return Err(error());
+        }
+        Ok(import)
+    }
+}
+
+// How to print a tree, chosen with `tree --format`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Dot,
+    Json,
+    Mermaid,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "dot" => Ok(Format::Dot),
+            "json" => Ok(Format::Json),
+            "mermaid" => Ok(Format::Mermaid),
+            _ => Err(format!("unknown format '{s}', expected one of: text, dot, json, mermaid")),
+        }
+    }
+}
+
+impl Format {
+    pub fn write(self, tree: &ModuleTree, out: &mut dyn io::Write) -> io::Result<()> {
+        match self {
+            Format::Text => text::write(tree, out),
+            Format::Dot => dot::write(tree, out),
+            Format::Json => json::write(tree, out),
+            Format::Mermaid => mermaid::write(tree, out),
+        }
+    }
+}
+
+// Find the crate root file for `path`, which may be the root file itself, or
+// a package directory containing `src/lib.rs` or `src/main.rs`.
+pub fn find_crate_root(path: &Path) -> io::Result<PathBuf> {
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+    ["src/lib.rs", "src/main.rs"].iter()
+        .map(|candidate| path.join(candidate))
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no src/lib.rs or src/main.rs in '{}'", path.display())))
+}
+
+impl ModuleTree {
+    // Load the module tree of the crate rooted at `root_file`, e.g. `src/lib.rs`.
+    //
+    // Only a missing root file is an error; problems further down the tree
+    // are recorded in `problems`.
+    pub fn load(root_file: &Path) -> io::Result<ModuleTree> {
+        let text = fs::read_to_string(root_file)?;
+        Ok(ModuleTree::from_source(root_file, text))
+    }
+
+    // The module tree of `text`, as if it were read from `root_file`. Any
+    // `mod name;` declarations in it are still loaded from disk, relative
+    // to `root_file`, so a snippet of only inline modules needs no files.
+    pub fn from_source(root_file: &Path, text: String) -> ModuleTree {
+        let dir = root_file.parent().unwrap_or(Path::new("")).to_path_buf();
+        let base = match dir.file_name() {
+            Some(name) if name == "src" => dir.parent().unwrap_or(&dir).to_path_buf(),
+            _ => dir.clone(),
+        };
+
+        let mut tree = ModuleTree {
+            base,
+            files: Vec::new(),
+            modules: Vec::new(),
+            problems: Vec::new(),
+        };
+        tree.modules.push(Module {
+            name: "crate".to_string(),
+            parent: None,
+            children: Vec::new(),
+            vis: Visibility::Public,
+            attrs: Vec::new(),
+            file: None,
+            inline: false,
+            decl: None,
+            child_dir: dir.clone(),
+            items: Vec::new(),
+            uses: Vec::new(),
+        });
+        tree.add_file(ModuleId(0), root_file.to_path_buf(), text, &dir);
+        tree
+    }
+
+    pub fn root(&self) -> ModuleId {
+        ModuleId(0)
+    }
+
+    pub fn module(&self, id: ModuleId) -> &Module {
+        &self.modules[id.0]
+    }
+
+    pub fn file(&self, id: FileId) -> &SourceFile {
+        &self.files[id.0]
+    }
+
+    // All module ids, parents before children.
+    pub fn ids(&self) -> impl Iterator<Item = ModuleId> {
+        (0..self.modules.len()).map(ModuleId)
+    }
+
+    // The names from the crate root down to `id`, e.g. `["crate", "a", "b"]`.
+    pub fn path(&self, id: ModuleId) -> Vec<&str> {
+        let mut path = Vec::new();
+        let mut next = Some(id);
+        while let Some(id) = next {
+            path.push(self.module(id).name.as_str());
+            next = self.module(id).parent;
+        }
+        path.reverse();
+        path
+    }
+
+    // `id`'s path joined with `::`, e.g. `crate::a::b`.
+    pub fn path_string(&self, id: ModuleId) -> String {
+        self.path(id).join("::")
+    }
+
+    // Find a module by its full path, e.g. `crate::a::b`. When several
+    // modules share a path because of `#[cfg]`, the first one is returned.
+    pub fn find(&self, path: &str) -> Option<ModuleId> {
+        let mut segments = path.split("::");
+        if segments.next() != Some("crate") {
+            return None;
+        }
+        segments.try_fold(self.root(), |id, name| self.child(id, name))
+    }
+
+    // The child of `id` called `name`. As with `find`, the first match wins.
+    pub fn child(&self, id: ModuleId, name: &str) -> Option<ModuleId> {
+        self.module(id).children.iter().copied().find(|&c| self.module(c).name == name)
+    }
+
+    // Whether `id` is `ancestor`, or inside it.
+    pub fn is_within(&self, id: ModuleId, ancestor: ModuleId) -> bool {
+        std::iter::successors(Some(id), |&m| self.module(m).parent).any(|m| m == ancestor)
+    }
+
+    // The module that an item declared in `owner` with visibility `vis` is
+    // visible inside: it can be named there and in all its descendants. For
+    // `pub` and `pub(crate)` that's the whole crate, though `pub` items can
+    // be seen from other crates too.
+    //
+    // `None` for a `pub(in path)` whose path isn't an ancestor of `owner`,
+    // which is a compile error.
+    pub fn visible_within(&self, vis: &Visibility, owner: ModuleId) -> Option<ModuleId> {
+        match vis {
+            Visibility::Private | Visibility::SelfOnly => Some(owner),
+            Visibility::Super => Some(self.module(owner).parent.unwrap_or(owner)),
+            Visibility::Crate | Visibility::Public => Some(self.root()),
+            Visibility::In(path) => {
+                let mut segments = path.iter().map(String::as_str);
+                let mut current = match segments.next()? {
+                    "crate" => self.root(),
+                    "self" => owner,
+                    "super" => self.module(owner).parent?,
+                    _ => return None,
+                };
+                for segment in segments {
+                    current = match segment {
+                        "super" => self.module(current).parent?,
+                        name => self.child(current, name)?,
+                    };
+                }
+                self.is_within(owner, current).then_some(current)
+            }
+        }
+    }
+
+    // The module an import in module `from` reaches into: the deepest module
+    // named along its path. `use crate::a::b::f;` gives `crate::a::b`, and
+    // `use super::*;` gives `from`'s parent.
+    //
+    // `None` if the path doesn't start at a module in this crate, e.g. `std::io`.
+    // Re-exports aren't followed.
+    pub fn use_target(&self, from: ModuleId, import: &Import) -> Option<ModuleId> {
+        let mut segments = import.path.iter().map(String::as_str);
+        let mut current = match segments.next()? {
+            "crate" => self.root(),
+            "self" => from,
+            "super" => self.module(from).parent?,
+            name => self.child(from, name)?,
+        };
+        for segment in segments {
+            current = match segment {
+                "super" => match self.module(current).parent {
+                    Some(parent) => parent,
+                    None => break,
+                },
+                name => match self.child(current, name) {
+                    Some(child) => child,
+                    None => break,
+                },
+            };
+        }
+        Some(current)
+    }
+
+    // Each pair of modules where the first imports from the second with `use`,
+    // as found by `use_target`. There are no duplicates, and modules importing
+    // from themselves are left out.
+    pub fn use_edges(&self) -> Vec<(ModuleId, ModuleId)> {
+        let mut edges = Vec::new();
+        for from in self.ids() {
+            for import in self.module(from).uses.iter().flat_map(|u| &u.imports) {
+                if let Some(to) = self.use_target(from, import) {
+                    if to != from && !edges.contains(&(from, to)) {
+                        edges.push((from, to));
+                    }
+                }
+            }
+        }
+        edges
+    }
+
+    // A file path relative to `base`, with `/` separators on every platform.
+    pub fn display_path(&self, path: &Path) -> String {
+        let relative = path.strip_prefix(&self.base).unwrap_or(path);
+        relative.components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    // The source text covered by `span`.
+    pub fn span_text(&self, span: Span) -> &str {
+        &self.file(span.file).text[span.start..span.end]
+    }
+
+    // The line `span` starts on, counting from 1.
+    pub fn line(&self, span: Span) -> usize {
+        self.file(span.file).text[..span.start].matches('\n').count() + 1
+    }
+
+    fn add_file(&mut self, module: ModuleId, path: PathBuf, text: String, child_dir: &Path) {
+        let tokens = lexer::tokenize(&text);
+        let entries = parser::parse(&text, &tokens);
+
+        let file = FileId(self.files.len());
+        let file_dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        self.files.push(SourceFile { path, text });
+        self.modules[module.0].file = Some(file);
+        self.modules[module.0].child_dir = child_dir.to_path_buf();
+
+        self.add_entries(module, file, entries, child_dir, &file_dir);
+    }
+
+    // `child_dir` is where `mod name;` looks for `name.rs` and `name/mod.rs`.
+    // `path_attr_dir` is what `#[path = "..."]` attributes are relative to.
+    fn add_entries(&mut self, module: ModuleId, file: FileId, entries: Vec<Entry>,
+                   child_dir: &Path, path_attr_dir: &Path) {
+        for Entry { attrs, vis, kind, start, end } in entries {
+            let span = Span { file, start, end };
+            match kind {
+                EntryKind::Mod { name, body } => {
+                    let child = ModuleId(self.modules.len());
+                    self.modules.push(Module {
+                        name: name.clone(),
+                        parent: Some(module),
+                        children: Vec::new(),
+                        vis,
+                        attrs,
+                        file: None,
+                        inline: body.is_some(),
+                        decl: Some(span),
+                        // Until its file is found, at least: see `add_file`.
+                        child_dir: child_dir.join(&name),
+                        items: Vec::new(),
+                        uses: Vec::new(),
+                    });
+                    self.modules[module.0].children.push(child);
+
+                    match body {
+                        Some(body) => {
+                            self.modules[child.0].file = Some(file);
+                            let dir = child_dir.join(&name);
+                            self.add_entries(child, file, body, &dir, &dir);
+                        }
+                        None => self.add_file_module(child, child_dir, path_attr_dir),
+                    }
+                }
+                EntryKind::Use(imports) => {
+                    self.modules[module.0].uses.push(Use { vis, attrs, imports, span });
+                }
+                EntryKind::Item { name, kind } => {
+                    self.modules[module.0].items.push(Item { name, kind, vis, attrs, span });
+                }
+                EntryKind::Other => {}
+            }
+        }
+    }
+
+    // Find and load the file for a `mod name;` declaration.
+    fn add_file_module(&mut self, id: ModuleId, child_dir: &Path, path_attr_dir: &Path) {
+        let module = self.module(id);
+        let name = module.name.clone();
+        let path_attr = module.attrs.iter().find_map(|a| path_attribute(a));
+
+        let (path, dir) = if let Some(path_attr) = path_attr {
+            // Files loaded with `#[path]` look for their children next to themselves,
+            // like `mod.rs` files.
+            let path = path_attr_dir.join(path_attr);
+            let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+            (path, dir)
+        } else {
+            let dir = child_dir.join(&name);
+            let file_rs = child_dir.join(format!("{name}.rs"));
+            let mod_rs = dir.join("mod.rs");
+            match (file_rs.is_file(), mod_rs.is_file()) {
+                (true, true) => {
+                    self.problems.push(format!(
+                        "error[E0761]: file for module `{}` found at both \"{}\" and \"{}\"",
+                        name, self.display_path(&file_rs), self.display_path(&mod_rs)));
+                    return;
+                }
+                (false, true) => (mod_rs, dir),
+                _ => (file_rs, dir),
+            }
+        };
+
+        if self.ancestor_files(id).any(|f| self.file(f).path == path) {
+            self.problems.push(format!(
+                "module `{}` includes its own file \"{}\"",
+                self.path_string(id), self.display_path(&path)));
+            return;
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(text) => self.add_file(id, path, text, &dir),
+            Err(e) => self.problems.push(format!(
+                "error[E0583]: file not found for module `{}` at \"{}\": {}",
+                self.path_string(id), self.display_path(&path), e)),
+        }
+    }
+
+    fn ancestor_files(&self, id: ModuleId) -> impl Iterator<Item = FileId> + '_ {
+        std::iter::successors(self.module(id).parent, |&p| self.module(p).parent)
+            .filter_map(|p| self.module(p).file)
+    }
+}
+
+// The path in a `path = "..."` attribute.
+fn path_attribute(attr: &str) -> Option<&str> {
+    let value = attr.strip_prefix("path")?.trim_start().strip_prefix('=')?.trim();
+    value.strip_prefix('"')?.strip_suffix('"')
+}
+
+// Only compiled for this crate's own tests, unless the `test-util` feature
+// is on: then it's public, for other crates' tests to use too.
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_dir {
+    // Creates a throwaway directory of source files for a test, removed on drop.
+
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    pub struct TestDir(PathBuf);
+
+    impl TestDir {
+        // `name` must be unique among tests, as tests run concurrently.
+        pub fn new(name: &str, files: &[(&str, &str)]) -> TestDir {
+            let dir = std::env::temp_dir()
+                .join(format!("rust-module-example-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&dir);
+            for (path, contents) in files {
+                let path = dir.join(path);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, contents).unwrap();
+            }
+            TestDir(dir)
+        }
+
+        pub fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_dir::TestDir;
+    use super::*;
+
+    fn load(dir: &TestDir) -> ModuleTree {
+        ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap()
+    }
+
+    fn files(tree: &ModuleTree) -> Vec<(String, String)> {
+        tree.ids().skip(1).map(|id| {
+            let module = tree.module(id);
+            let file = module.file.map_or("-".to_string(), |f| tree.display_path(&tree.file(f).path));
+            (tree.path_string(id), file)
+        }).collect()
+    }
+
+    #[test]
+    fn file_layouts() {
+        let dir = TestDir::new("file_layouts", &[
+            ("src/lib.rs", "mod a; mod b; #[path = \"other.rs\"] mod c; mod inline { mod d; }"),
+            ("src/a.rs", "mod child;"),
+            ("src/a/child.rs", ""),
+            ("src/b/mod.rs", "mod child;"),
+            ("src/b/child.rs", ""),
+            ("src/other.rs", "mod child;"),
+            ("src/child.rs", ""),
+            ("src/inline/d.rs", "mod e;"),
+            ("src/inline/d/e.rs", ""),
+        ]);
+        let tree = load(&dir);
+        assert_eq!(tree.problems, Vec::<String>::new());
+        assert_eq!(files(&tree), vec![
+            ("crate::a".to_string(), "src/a.rs".to_string()),
+            ("crate::a::child".to_string(), "src/a/child.rs".to_string()),
+            ("crate::b".to_string(), "src/b/mod.rs".to_string()),
+            ("crate::b::child".to_string(), "src/b/child.rs".to_string()),
+            ("crate::c".to_string(), "src/other.rs".to_string()),
+            ("crate::c::child".to_string(), "src/child.rs".to_string()),
+            ("crate::inline".to_string(), "src/lib.rs".to_string()),
+            ("crate::inline::d".to_string(), "src/inline/d.rs".to_string()),
+            ("crate::inline::d::e".to_string(), "src/inline/d/e.rs".to_string()),
+        ]);
+        assert!(tree.module(tree.find("crate::inline").unwrap()).inline);
+
+        let child_dirs: Vec<_> = ["crate", "crate::a", "crate::b", "crate::c", "crate::inline::d"].iter()
+            .map(|path| tree.display_path(&tree.module(tree.find(path).unwrap()).child_dir))
+            .collect();
+        assert_eq!(child_dirs, vec!["src", "src/a", "src/b", "src", "src/inline/d"]);
+    }
+
+    #[test]
+    fn problems() {
+        let dir = TestDir::new("problems", &[
+            ("src/main.rs", "mod both; mod missing; #[path = \"main.rs\"] mod again;"),
+            ("src/both.rs", ""),
+            ("src/both/mod.rs", ""),
+        ]);
+        let tree = load(&dir);
+        assert_eq!(tree.problems.len(), 3, "{:?}", tree.problems);
+        assert!(tree.problems[0].contains("E0761"));
+        assert!(tree.problems[1].contains("E0583"));
+        assert!(tree.problems[2].contains("includes its own file"));
+    }
+
+    #[test]
+    fn items_and_uses() {
+        let dir = TestDir::new("items_and_uses", &[
+            ("src/lib.rs", "pub use a::f; mod a { pub(crate) fn f() {} pub struct S; }"),
+        ]);
+        let tree = load(&dir);
+        let root = tree.module(tree.root());
+        assert_eq!(root.uses[0].imports[0].to_string(), "a::f");
+        assert_eq!(tree.span_text(root.uses[0].span), "pub use a::f;");
+
+        let a = tree.module(tree.find("crate::a").unwrap());
+        let items: Vec<_> = a.items.iter()
+            .map(|i| format!("{} {} {}", i.vis, i.kind, i.name))
+            .collect();
+        assert_eq!(items, vec!["pub(crate) fn f", "pub struct S"]);
+    }
+
+    #[test]
+    fn parse_import() {
+        let parse = |s: &str| s.parse::<Import>().map(|i| i.to_string());
+        for path in ["super::f", "crate::a::b", "a::*", "a::b as c", "Trait as _", "::std::io"] {
+            assert_eq!(parse(path).as_deref(), Ok(path));
+        }
+        assert_eq!(parse(" crate :: a ").as_deref(), Ok("crate::a"));
+
+        for bad in ["", "a::", "a::{b, c}", "a b", "a as", "use a;", "1", "*", "a::*::b"] {
+            assert!(parse(bad).is_err(), "{bad:?} parsed");
+        }
+    }
+
+    #[test]
+    fn visibility_scopes() {
+        let dir = TestDir::new("visibility_scopes", &[
+            ("src/lib.rs", "mod a { pub mod b { mod c {} } }"),
+        ]);
+        let tree = load(&dir);
+        let c = tree.find("crate::a::b::c").unwrap();
+        let scope = |vis: Visibility| tree.visible_within(&vis, c).map(|m| tree.path_string(m));
+        let path = |p: &str| Visibility::In(p.split("::").map(String::from).collect());
+
+        assert_eq!(scope(Visibility::Private).as_deref(), Some("crate::a::b::c"));
+        assert_eq!(scope(Visibility::Super).as_deref(), Some("crate::a::b"));
+        assert_eq!(scope(Visibility::Public).as_deref(), Some("crate"));
+        assert_eq!(scope(path("crate::a")).as_deref(), Some("crate::a"));
+        assert_eq!(scope(path("super::super")).as_deref(), Some("crate::a"));
+        assert_eq!(scope(path("self")).as_deref(), Some("crate::a::b::c"));
+        // Not an ancestor of `c`.
+        assert_eq!(scope(path("crate::x")), None);
+
+        assert!(tree.is_within(c, tree.root()));
+        assert!(!tree.is_within(tree.root(), c));
+    }
+
+    #[test]
+    fn use_targets() {
+        let dir = TestDir::new("use_targets", &[
+            ("src/lib.rs", "
+                mod a { pub mod b { pub fn f() {} } }
+                mod c {
+                    use crate::a::b::f;
+                    use super::a::b;
+                    use self::d::*;
+                    use std::io;
+                    use d::g;
+                    use crate::a::b::super::b;
+                    mod d { pub fn g() {} }
+                }
+            "),
+        ]);
+        let tree = load(&dir);
+        let c = tree.find("crate::c").unwrap();
+        let targets: Vec<_> = tree.module(c).uses.iter()
+            .map(|u| tree.use_target(c, &u.imports[0]).map(|t| tree.path_string(t)))
+            .collect();
+        assert_eq!(targets, vec![
+            Some("crate::a::b".to_string()),
+            Some("crate::a::b".to_string()),
+            Some("crate::c::d".to_string()),
+            None,
+            Some("crate::c::d".to_string()),
+            Some("crate::a::b".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn this_crate() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs");
+        let tree = ModuleTree::load(&root).unwrap();
+        assert_eq!(tree.problems, Vec::<String>::new());
+
+        for path in ["crate::a", "crate::multi_level_style_1::child",
+                     "crate::multi_level_style_2::child", "crate::inline",
+                     "crate::name_resolution::private_inner", "crate::modtree::lexer"] {
+            assert!(tree.find(path).is_some(), "{path} not found");
+        }
+
+        // Every platform module is found, as `cfg` isn't evaluated.
+        let platforms: Vec<_> = tree.module(tree.root()).children.iter()
+            .map(|&id| tree.module(id))
+            .filter(|m| m.name == "platform")
+            .map(|m| tree.display_path(&tree.file(m.file.unwrap()).path))
+            .collect();
+        assert_eq!(platforms, vec!["src/linux.rs", "src/macos.rs", "src/windows.rs",
+                                   "src/wasm.rs", "src/unsupported.rs"]);
+    }
+}
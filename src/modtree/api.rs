@@ -0,0 +1,158 @@
+// The crate's public API, for the `analyze api` command: every path another
+// crate could write to name something in this one.
+
+// Only `pub` counts, at every step: a `pub fn` in a `pub(crate)` module
+// isn't reachable from outside. `pub use` adds paths, whatever the
+// visibility of the module the item was declared in, which is how a crate
+// can keep its files private and export a flat API. This is a small
+// version of what the `cargo-public-api` tool lists, without the details
+// of each item's signature.
+
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+
+use super::resolve::{self, Target};
+use super::{ModuleId, ModuleTree, Visibility};
+
+// One line of the API, e.g. `pub fn crate::a::f`, sorted by path.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Entry {
+    pub path: String,
+    pub kind: String,
+    // The declared path, when this is a re-export.
+    pub reexport_of: Option<String>,
+}
+
+pub fn api(tree: &ModuleTree) -> Vec<Entry> {
+    let mut entries = BTreeSet::new();
+    let mut visited = BTreeSet::new();
+    walk(tree, tree.root(), "crate", &mut visited, &mut entries);
+    entries.into_iter().collect()
+}
+
+// Adds everything public in `module`, reached as `path`. A module can be
+// reached by more than one path; its contents are listed under the first.
+fn walk(tree: &ModuleTree, module: ModuleId, path: &str, visited: &mut BTreeSet<usize>,
+        entries: &mut BTreeSet<Entry>) {
+    if !visited.insert(module.0) {
+        return;
+    }
+    let declared = |id: ModuleId, name: &str| format!("{}::{name}", tree.path_string(id));
+    let add = |name: &str, kind: String, original: String, entries: &mut BTreeSet<Entry>| {
+        let path = format!("{path}::{name}");
+        let reexport_of = (path != original).then_some(original);
+        entries.insert(Entry { path, kind, reexport_of });
+    };
+
+    let m = tree.module(module);
+    for &child in &m.children {
+        let child_module = tree.module(child);
+        if child_module.vis == Visibility::Public {
+            add(&child_module.name, "mod".to_string(), tree.path_string(child), entries);
+            walk(tree, child, &format!("{path}::{}", child_module.name), visited, entries);
+        }
+    }
+    for item in m.items.iter().filter(|item| item.vis == Visibility::Public) {
+        add(&item.name, item.kind.to_string(), declared(module, &item.name), entries);
+    }
+
+    for declaration in m.uses.iter().filter(|u| u.vis == Visibility::Public) {
+        for import in &declaration.imports {
+            let resolution = resolve::resolve(tree, module, import);
+            if !resolution.accessible() {
+                continue;
+            }
+            match (resolution.target(), import.name()) {
+                // Everything public in the module, as if re-exported by name.
+                (Some(Target::Module(target)), None) if import.glob => {
+                    let target_module = tree.module(target);
+                    for &child in &target_module.children {
+                        let child_module = tree.module(child);
+                        if child_module.vis == Visibility::Public {
+                            add(&child_module.name, "mod".to_string(), tree.path_string(child), entries);
+                            walk(tree, child, &format!("{path}::{}", child_module.name), visited, entries);
+                        }
+                    }
+                    for item in target_module.items.iter().filter(|i| i.vis == Visibility::Public) {
+                        add(&item.name, item.kind.to_string(), declared(target, &item.name), entries);
+                    }
+                }
+                // Re-exporting a private module is error E0365.
+                (Some(Target::Module(target)), Some(name))
+                    if tree.module(target).vis == Visibility::Public || target == tree.root() => {
+                    add(name, "mod".to_string(), tree.path_string(target), entries);
+                    walk(tree, target, &format!("{path}::{name}"), visited, entries);
+                }
+                // Re-exporting a less than `pub` item is error E0364.
+                (Some(Target::Item(found_in, item)), Some(name)) if item.vis == Visibility::Public => {
+                    add(name, item.kind.to_string(), declared(found_in, &item.name), entries);
+                }
+                (Some(Target::External), Some(name)) => {
+                    add(name, "use".to_string(), import.path.join("::").replacen("::::", "::", 1), entries);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+pub fn write(tree: &ModuleTree, out: &mut dyn Write) -> io::Result<()> {
+    let entries = api(tree);
+    for entry in &entries {
+        write!(out, "pub {} {}", entry.kind, entry.path)?;
+        match &entry.reexport_of {
+            Some(original) => writeln!(out, " (re-export of {original})")?,
+            None => writeln!(out)?,
+        }
+    }
+    let reexports = entries.iter().filter(|e| e.reexport_of.is_some()).count();
+    writeln!(out, "\n{} public paths, {reexports} of them re-exports.", entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    #[test]
+    fn public_paths() {
+        let dir = TestDir::new("api_public_paths", &[("src/lib.rs", "
+            pub fn top() {}
+            fn private() {}
+            pub mod shapes {
+                pub struct Circle;
+                pub(crate) struct Internal;
+                pub mod round { pub fn area() {} }
+                mod hidden { pub fn lost() {} }
+            }
+            mod detail {
+                pub fn flat() {}
+                pub(crate) fn not_exportable() {}
+                pub mod more { pub fn g() {} }
+                pub use super::shapes as shapes_again;
+            }
+            pub use detail::flat;
+            pub use detail::flat as also_flat;
+            pub use detail::more::*;
+            pub use detail::not_exportable;
+            pub use std::collections::HashMap;
+            use detail::more;
+        ")]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+        let mut out = Vec::new();
+        write(&tree, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+pub use crate::HashMap (re-export of std::collections::HashMap)
+pub fn crate::also_flat (re-export of crate::detail::flat)
+pub fn crate::flat (re-export of crate::detail::flat)
+pub fn crate::g (re-export of crate::detail::more::g)
+pub mod crate::shapes
+pub struct crate::shapes::Circle
+pub mod crate::shapes::round
+pub fn crate::shapes::round::area
+pub fn crate::top
+
+9 public paths, 4 of them re-exports.
+");
+    }
+}
@@ -0,0 +1,152 @@
+// Converting modules between the two layout styles, for the
+// `refactor convert-style` command: `name/mod.rs` to `name.rs`, or back.
+
+// As `../lib.rs` explains, both styles keep a module's children in
+// `name/`, so converting a module only moves its own file, and nothing
+// else changes: not its children's files, and not a single line of code.
+// The one exception is `#[path]`. On a `mod` declaration in `name.rs` or
+// `name/mod.rs`, it's relative to the directory containing the file, which
+// is different in the two styles, so those files aren't moved.
+
+use std::io::{self, Write};
+
+use super::edit::{self, Change};
+use super::scaffold::Style;
+use super::{path_attribute, Module, ModuleId, ModuleTree};
+
+// The style of `id`'s file, or `None` if it doesn't have one of its own:
+// the crate root, inline modules, and those loaded with `#[path]`.
+pub fn style(tree: &ModuleTree, id: ModuleId) -> Option<Style> {
+    let module = tree.module(id);
+    if module.parent.is_none() || module.inline || has_path_attribute(module) {
+        return None;
+    }
+    let path = &tree.file(module.file?).path;
+    Some(if path.ends_with("mod.rs") { Style::ModRs } else { Style::Edition2018 })
+}
+
+pub(super) fn has_path_attribute(module: &Module) -> bool {
+    module.attrs.iter().any(|a| path_attribute(a).is_some())
+}
+
+// The files to move to put `module` and its descendants in style `to`.
+pub fn convert_style(tree: &ModuleTree, module: ModuleId, to: Style) -> Result<Vec<Change>, String> {
+    let mut changes = Vec::new();
+    let mut stack = vec![module];
+    while let Some(id) = stack.pop() {
+        let m = tree.module(id);
+        stack.extend(m.children.iter().rev());
+        if style(tree, id).is_none_or(|style| style == to) {
+            continue;
+        }
+
+        let from = &tree.file(m.file.expect("`style` checks there's a file")).path;
+        if let Some(child) = m.children.iter().map(|&c| tree.module(c))
+            .find(|c| !c.inline && has_path_attribute(c)) {
+            return Err(format!("{} has `#[path]` on `mod {}`, which would load a different file \
+                                after the move", tree.display_path(from), child.name));
+        }
+        let dir = &tree.module(m.parent.expect("`style` checks it isn't the root")).child_dir;
+        let to_path = match to {
+            Style::ModRs => dir.join(&m.name).join("mod.rs"),
+            Style::Edition2018 => dir.join(format!("{}.rs", m.name)),
+        };
+        if to_path.exists() {
+            return Err(format!("{} already exists", tree.display_path(&to_path)));
+        }
+        changes.push(Change::Move { from: from.clone(), to: to_path });
+    }
+    Ok(changes)
+}
+
+// Converts `module_path` and its descendants to `to`, or if that's `None`,
+// to the style `module_path` doesn't use.
+pub fn write(tree: &ModuleTree, module_path: &str, to: Option<Style>, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let module = tree.find(module_path)
+        .ok_or_else(|| invalid(format!("no module `{module_path}` in this crate")))?;
+    let to = match (to, style(tree, module)) {
+        (Some(to), _) => to,
+        (None, Some(Style::ModRs)) => Style::Edition2018,
+        (None, Some(Style::Edition2018)) => Style::ModRs,
+        (None, None) => return Err(invalid(format!(
+            "`{module_path}` has no file in either style, so give a style with `--to`"))),
+    };
+
+    let changes = convert_style(tree, module, to).map_err(invalid)?;
+    if changes.is_empty() {
+        return writeln!(out, "`{module_path}` and its descendants are already in the {to} style.");
+    }
+    edit::apply(tree, &changes, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::find_crate_root;
+    use crate::modtree::test_dir::TestDir;
+
+    fn files() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("src/lib.rs", "mod a; mod b { mod c; } #[path = \"elsewhere.rs\"] mod d;"),
+            ("src/a/mod.rs", "mod x; mod y;"),
+            ("src/a/x.rs", ""),
+            ("src/a/y/mod.rs", "mod z;"),
+            ("src/a/y/z.rs", ""),
+            ("src/b/c.rs", ""),
+            ("src/elsewhere.rs", ""),
+        ]
+    }
+
+    // Converts `module` in a crate made of `files`, and returns the paths of
+    // the files in the new tree, and the output.
+    fn convert(test: &str, files: &[(&str, &str)], module: &str, to: Option<Style>) -> (Vec<String>, String) {
+        let dir = TestDir::new(test, files);
+        let tree = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        let mut out = Vec::new();
+        write(&tree, module, to, &mut out).unwrap();
+
+        let tree = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        assert_eq!(tree.problems, Vec::<String>::new());
+        let paths = tree.files.iter().map(|f| tree.display_path(&f.path)).collect();
+        (paths, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn to_2018_and_back() {
+        let (paths, out) = convert("convert_style_to_2018", &files(), "crate", Some(Style::Edition2018));
+        assert_eq!(paths, ["src/lib.rs", "src/a.rs", "src/a/x.rs", "src/a/y.rs", "src/a/y/z.rs", "src/b/c.rs",
+                           "src/elsewhere.rs"]);
+        assert_eq!(out, "Moved src/a/mod.rs to src/a.rs\nMoved src/a/y/mod.rs to src/a/y.rs\n");
+
+        let (paths, _) = convert("convert_style_to_mod_rs", &files(), "crate", Some(Style::ModRs));
+        assert_eq!(paths, ["src/lib.rs", "src/a/mod.rs", "src/a/x/mod.rs", "src/a/y/mod.rs", "src/a/y/z/mod.rs",
+                           "src/b/c/mod.rs", "src/elsewhere.rs"]);
+    }
+
+    #[test]
+    fn default_style() {
+        // The opposite of the module's own, for the whole subtree.
+        let (paths, _) = convert("convert_style_default", &files(), "crate::a::y", None);
+        assert_eq!(paths, ["src/lib.rs", "src/a/mod.rs", "src/a/x.rs", "src/a/y.rs", "src/a/y/z.rs", "src/b/c.rs",
+                           "src/elsewhere.rs"]);
+        let (_, out) = convert("convert_style_nothing", &files(), "crate::a::x", Some(Style::Edition2018));
+        assert_eq!(out, "`crate::a::x` and its descendants are already in the 2018 style.\n");
+    }
+
+    #[test]
+    fn errors() {
+        let dir = TestDir::new("convert_style_errors", &[
+            ("src/lib.rs", "mod a;"),
+            ("src/a.rs", "#[path = \"other.rs\"] mod other;"),
+            ("src/other.rs", ""),
+        ]);
+        let tree = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        let convert = |module| convert_style(&tree, tree.find(module).unwrap(), Style::ModRs);
+        assert_eq!(convert("crate::a"),
+                   Err("src/a.rs has `#[path]` on `mod other`, which would load a different file after the move"
+                       .to_string()));
+        assert!(write(&tree, "crate", None, &mut Vec::new()).is_err());
+        assert!(write(&tree, "crate::nope", None, &mut Vec::new()).is_err());
+    }
+}
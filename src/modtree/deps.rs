@@ -0,0 +1,238 @@
+// Which modules depend on which through `use` declarations, for the
+// `analyze deps` command, and the cycles among those dependencies.
+
+// A cycle means none of its modules can be understood, or moved to another
+// crate, without the others. Some are harmless, like a `tests` module using
+// `super::*` while its parent never mentions it, but a long cycle through
+// distant modules is often a sign that they should be one module, or that
+// something they share belongs in a module of its own.
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+use super::{ModuleId, ModuleTree};
+
+pub struct Deps {
+    // `(from, to)` when `from` imports from `to`, as `ModuleTree::use_edges`.
+    pub edges: Vec<(ModuleId, ModuleId)>,
+    // One cycle through each group of modules that depend on each other,
+    // starting and ending with the same module.
+    pub cycles: Vec<Vec<ModuleId>>,
+}
+
+pub fn analyze(tree: &ModuleTree) -> Deps {
+    let edges = tree.use_edges();
+    let cycles = components(tree, &edges).into_iter()
+        .filter(|component| component.len() > 1)
+        .map(|component| shortest_cycle(&edges, &component))
+        .collect();
+    Deps { edges, cycles }
+}
+
+pub fn write(tree: &ModuleTree, out: &mut dyn Write) -> io::Result<()> {
+    let deps = analyze(tree);
+    // A module with `#[cfg]` alternatives is several modules at one path,
+    // which would print the same edges and cycles once for each.
+    let mut edges: Vec<String> = Vec::new();
+    for &(from, to) in &deps.edges {
+        let edge = format!("{} -> {}", tree.path_string(from), tree.path_string(to));
+        if !edges.contains(&edge) {
+            edges.push(edge);
+        }
+    }
+    let mut cycles: Vec<String> = Vec::new();
+    for cycle in &deps.cycles {
+        let path: Vec<_> = cycle.iter().map(|&id| tree.path_string(id)).collect();
+        let cycle = path.join(" -> ");
+        if !cycles.contains(&cycle) {
+            cycles.push(cycle);
+        }
+    }
+
+    for edge in &edges {
+        writeln!(out, "{edge}")?;
+    }
+    writeln!(out)?;
+    match cycles.len() {
+        0 => writeln!(out, "No cycles.")?,
+        1 => writeln!(out, "1 cycle:")?,
+        n => writeln!(out, "{n} cycles:")?,
+    }
+    for cycle in &cycles {
+        writeln!(out, "  {cycle}")?;
+    }
+    Ok(())
+}
+
+// The strongly connected components of the graph: groups in which every
+// module can reach every other by following edges. This is Tarjan's
+// algorithm. Components come out in an order depending on the edges, so
+// each one is sorted, and then the list of them.
+fn components(tree: &ModuleTree, edges: &[(ModuleId, ModuleId)]) -> Vec<Vec<ModuleId>> {
+    struct State<'a> {
+        edges: &'a [(ModuleId, ModuleId)],
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        stack: Vec<ModuleId>,
+        on_stack: Vec<bool>,
+        next_index: usize,
+        components: Vec<Vec<ModuleId>>,
+    }
+
+    fn visit(state: &mut State, id: ModuleId) {
+        state.index[id.0] = Some(state.next_index);
+        state.low[id.0] = state.next_index;
+        state.next_index += 1;
+        state.stack.push(id);
+        state.on_stack[id.0] = true;
+
+        let edges = state.edges;
+        for &(_, to) in edges.iter().filter(|(from, _)| *from == id) {
+            match state.index[to.0] {
+                None => {
+                    visit(state, to);
+                    state.low[id.0] = state.low[id.0].min(state.low[to.0]);
+                }
+                Some(index) if state.on_stack[to.0] => {
+                    state.low[id.0] = state.low[id.0].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(state.low[id.0]) == state.index[id.0] {
+            let mut component = Vec::new();
+            while let Some(member) = state.stack.pop() {
+                state.on_stack[member.0] = false;
+                component.push(member);
+                if member == id {
+                    break;
+                }
+            }
+            component.sort();
+            state.components.push(component);
+        }
+    }
+
+    let count = tree.modules.len();
+    let mut state = State {
+        edges,
+        index: vec![None; count],
+        low: vec![0; count],
+        stack: Vec::new(),
+        on_stack: vec![false; count],
+        next_index: 0,
+        components: Vec::new(),
+    };
+    for id in tree.ids() {
+        if state.index[id.0].is_none() {
+            visit(&mut state, id);
+        }
+    }
+    state.components.sort();
+    state.components
+}
+
+// The shortest cycle through the first module of `component`, found by a
+// breadth-first search back to it.
+fn shortest_cycle(edges: &[(ModuleId, ModuleId)], component: &[ModuleId]) -> Vec<ModuleId> {
+    let start = component[0];
+    let mut came_from: Vec<(ModuleId, ModuleId)> = Vec::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(id) = queue.pop_front() {
+        for &(_, to) in edges.iter().filter(|(from, _)| *from == id) {
+            if to == start {
+                let mut cycle = vec![start, id];
+                let mut current = id;
+                while let Some(&(_, previous)) = came_from.iter().find(|(c, _)| *c == current) {
+                    cycle.push(previous);
+                    current = previous;
+                }
+                cycle.reverse();
+                return cycle;
+            }
+            if component.contains(&to) && !came_from.iter().any(|(c, _)| *c == to) {
+                came_from.push((to, id));
+                queue.push_back(to);
+            }
+        }
+    }
+    unreachable!("every module in a component is on a cycle through its first")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    fn output(name: &str, lib_rs: &str) -> String {
+        let dir = TestDir::new(name, &[("src/lib.rs", lib_rs)]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+        let mut out = Vec::new();
+        write(&tree, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn no_cycles() {
+        assert_eq!(output("deps_no_cycles", "
+            mod a { use crate::b::f; use std::io; }
+            mod b { pub fn f() {} }
+        "), "crate::a -> crate::b\n\nNo cycles.\n");
+    }
+
+    #[test]
+    fn cycles() {
+        assert_eq!(output("deps_cycles", "
+            mod a { use crate::b::f; }
+            mod b { use crate::c::g; pub fn f() {} }
+            mod c { use crate::a; use crate::b; pub fn g() {} }
+            mod d { use super::e::*; }
+            mod e { use super::d::*; }
+            mod f { use crate::a; }
+        "), "\
+crate::a -> crate::b
+crate::b -> crate::c
+crate::c -> crate::a
+crate::c -> crate::b
+crate::d -> crate::e
+crate::e -> crate::d
+crate::f -> crate::a
+
+2 cycles:
+  crate::a -> crate::b -> crate::c -> crate::a
+  crate::d -> crate::e -> crate::d
+");
+    }
+
+    #[test]
+    fn cfg_alternatives() {
+        // Each `platform` is a module of its own, at the same path.
+        assert_eq!(output("deps_cfg_alternatives", "
+            pub fn f() {}
+            #[cfg(unix)]
+            mod platform { use crate::f; use crate::shared::g; }
+            #[cfg(windows)]
+            mod platform { use crate::f; use crate::shared::g; }
+            mod shared { pub fn g() {} use crate::platform; }
+        "), "\
+crate::platform -> crate
+crate::platform -> crate::shared
+crate::shared -> crate::platform
+
+1 cycle:
+  crate::platform -> crate::shared -> crate::platform
+");
+    }
+
+    #[test]
+    fn parent_and_child() {
+        // The child's `super` and the parent's `child` each name the other.
+        assert_eq!(output("deps_parent_and_child", "
+            use child::f;
+            pub fn g() {}
+            mod child { use super::g; pub fn f() {} }
+        "), "crate -> crate::child\ncrate::child -> crate\n\n1 cycle:\n  crate -> crate::child -> crate\n");
+    }
+}
@@ -0,0 +1,171 @@
+// The differences between two module trees, for the `tree diff` command:
+// usually the same crate before and after a change, to check a refactoring
+// did what was intended.
+
+// Modules are matched by path. One that disappears from one path and
+// appears at another, with the same name and the same item names, is
+// reported as moved rather than as one removed and one added; that's what
+// moving a file, or a `mod` declaration, to a new parent looks like.
+// Visibility changes are reported for modules and items that are in both
+// trees, including moved ones. Item bodies aren't compared.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+
+use super::{Module, ModuleTree, Visibility};
+
+#[derive(Debug, PartialEq)]
+pub enum Change {
+    Added { path: String },
+    Removed { path: String },
+    Moved { from: String, to: String },
+    // A module's or item's visibility, at its path in the new tree.
+    Visibility { path: String, old: Visibility, new: Visibility },
+}
+
+pub fn diff(old: &ModuleTree, new: &ModuleTree) -> Vec<Change> {
+    let old_modules = by_path(old);
+    let new_modules = by_path(new);
+
+    let mut removed: Vec<&String> = old_modules.keys().filter(|p| !new_modules.contains_key(*p)).collect();
+    let mut added: Vec<&String> = new_modules.keys().filter(|p| !old_modules.contains_key(*p)).collect();
+
+    let mut pairs: Vec<(&String, &String)> = old_modules.keys()
+        .filter(|p| new_modules.contains_key(*p))
+        .map(|p| (p, p))
+        .collect();
+    let mut changes = Vec::new();
+    removed.retain(|&from| {
+        let module = old_modules[from];
+        let moved_to = added.iter().position(|to| {
+            let candidate = new_modules[*to];
+            candidate.name == module.name && item_names(candidate) == item_names(module)
+        });
+        match moved_to {
+            Some(index) => {
+                let to = added.remove(index);
+                changes.push(Change::Moved { from: from.clone(), to: to.clone() });
+                pairs.push((from, to));
+                false
+            }
+            None => true,
+        }
+    });
+    changes.extend(removed.into_iter().map(|path| Change::Removed { path: path.clone() }));
+    changes.extend(added.into_iter().map(|path| Change::Added { path: path.clone() }));
+
+    for (old_path, new_path) in pairs {
+        let (old_module, new_module) = (old_modules[old_path], new_modules[new_path]);
+        // The crate root has no visibility of its own.
+        if new_module.parent.is_some() && old_module.vis != new_module.vis {
+            changes.push(Change::Visibility { path: new_path.clone(),
+                                              old: old_module.vis.clone(),
+                                              new: new_module.vis.clone() });
+        }
+        for item in &new_module.items {
+            let Some(old_item) = old_module.items.iter().find(|i| i.name == item.name && i.kind == item.kind)
+            else { continue };
+            if old_item.vis != item.vis {
+                changes.push(Change::Visibility { path: format!("{new_path}::{}", item.name),
+                                                  old: old_item.vis.clone(),
+                                                  new: item.vis.clone() });
+            }
+        }
+    }
+    changes
+}
+
+// Modules by path. Of several with the same path, chosen between by
+// `#[cfg]`, only the first is compared.
+fn by_path(tree: &ModuleTree) -> BTreeMap<String, &Module> {
+    let mut modules = BTreeMap::new();
+    for id in tree.ids() {
+        modules.entry(tree.path_string(id)).or_insert(tree.module(id));
+    }
+    modules
+}
+
+fn item_names(module: &Module) -> BTreeSet<&str> {
+    module.items.iter().map(|item| item.name.as_str()).collect()
+}
+
+pub fn write(old: &ModuleTree, new: &ModuleTree, out: &mut dyn Write) -> io::Result<()> {
+    let changes = diff(old, new);
+    if changes.is_empty() {
+        return writeln!(out, "No differences.");
+    }
+    let vis = |vis: &Visibility| match vis {
+        Visibility::Private => "private".to_string(),
+        vis => vis.to_string(),
+    };
+    let (mut added, mut removed, mut moved, mut visibility) = (0, 0, 0, 0);
+    for change in &changes {
+        match change {
+            Change::Added { path } => {
+                added += 1;
+                writeln!(out, "+ mod {path}")?;
+            }
+            Change::Removed { path } => {
+                removed += 1;
+                writeln!(out, "- mod {path}")?;
+            }
+            Change::Moved { from, to } => {
+                moved += 1;
+                writeln!(out, "> mod {from} moved to {to}")?;
+            }
+            Change::Visibility { path, old, new } => {
+                visibility += 1;
+                writeln!(out, "~ {path}: {} -> {}", vis(old), vis(new))?;
+            }
+        }
+    }
+    writeln!(out, "\n{added} added, {removed} removed, {moved} moved, {visibility} visibility changes.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    fn load(dir: &TestDir) -> ModuleTree {
+        ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap()
+    }
+
+    #[test]
+    fn changes() {
+        let old = TestDir::new("diff_changes_old", &[
+            ("src/lib.rs", "mod a; mod gone {} pub mod b { fn f() {} pub struct S; }"),
+            ("src/a.rs", "pub mod inner { pub fn helper() {} }"),
+        ]);
+        let new = TestDir::new("diff_changes_new", &[
+            ("src/lib.rs", "pub mod a; mod inner { pub fn helper() {} } pub mod b { pub(crate) fn f() {} pub struct S; } mod fresh {}"),
+            ("src/a.rs", ""),
+        ]);
+        let output = |old: &ModuleTree, new: &ModuleTree| {
+            let mut out = Vec::new();
+            write(old, new, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let (old, new) = (load(&old), load(&new));
+
+        assert_eq!(diff(&old, &new), vec![
+            Change::Moved { from: "crate::a::inner".to_string(), to: "crate::inner".to_string() },
+            Change::Removed { path: "crate::gone".to_string() },
+            Change::Added { path: "crate::fresh".to_string() },
+            Change::Visibility { path: "crate::a".to_string(), old: Visibility::Private, new: Visibility::Public },
+            Change::Visibility { path: "crate::b::f".to_string(), old: Visibility::Private, new: Visibility::Crate },
+            Change::Visibility { path: "crate::inner".to_string(), old: Visibility::Public, new: Visibility::Private },
+        ]);
+        assert_eq!(output(&old, &new), "\
+> mod crate::a::inner moved to crate::inner
+- mod crate::gone
++ mod crate::fresh
+~ crate::a: private -> pub
+~ crate::b::f: private -> pub(crate)
+~ crate::inner: pub -> private
+
+1 added, 1 removed, 1 moved, 3 visibility changes.
+");
+        assert_eq!(output(&old, &old), "No differences.\n");
+    }
+}
@@ -0,0 +1,92 @@
+// Prints a `ModuleTree` as a Graphviz graph, e.g. for slides:
+//
+//     cargo run -- tree --format dot | dot -Tsvg > modules.svg
+//
+// Solid edges go from parent to child module. Dashed edges go from a module
+// to each module in this crate that it imports from with `use`.
+
+use std::io::{self, Write};
+
+use super::{ModuleId, ModuleTree, Visibility};
+
+pub fn write(tree: &ModuleTree, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "digraph modules {{")?;
+    writeln!(out, "    node [shape=box, fontname=monospace];")?;
+
+    for id in tree.ids() {
+        let module = tree.module(id);
+        let mut label = match &module.vis {
+            Visibility::Private => format!("mod {}", module.name),
+            vis => format!("{vis} mod {}", module.name),
+        };
+        if id == tree.root() {
+            label = "crate".to_string();
+        }
+        for attr in module.attrs.iter().filter(|a| a.starts_with("cfg")) {
+            label = format!("#[{attr}]\n{label}");
+        }
+        match module.file {
+            Some(_) if module.inline => {}
+            Some(file) => label.push_str(&format!("\n{}", tree.display_path(&tree.file(file).path))),
+            None => label.push_str("\n(file not found)"),
+        }
+        let style = if module.inline { ", style=rounded" } else { "" };
+        writeln!(out, "    {} [label={}{style}];", node(id), quote(&label))?;
+    }
+
+    for id in tree.ids() {
+        for &child in &tree.module(id).children {
+            writeln!(out, "    {} -> {};", node(id), node(child))?;
+        }
+    }
+
+    for (from, to) in tree.use_edges() {
+        writeln!(out, "    {} -> {} [style=dashed, color=blue, constraint=false];",
+                 node(from), node(to))?;
+    }
+
+    writeln!(out, "}}")
+}
+
+// Module names aren't unique, so nodes are named by module id.
+fn node(id: ModuleId) -> String {
+    format!("m{}", id.0)
+}
+
+fn quote(s: &str) -> String {
+    let escaped = s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    #[test]
+    fn graph() {
+        let dir = TestDir::new("dot_graph", &[
+            ("src/lib.rs", "pub mod a; #[cfg(test)] mod tests { use super::a::f; }"),
+            ("src/a.rs", "pub fn f() {} use crate::a::f as g;"),
+        ]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+
+        let mut out = Vec::new();
+        write(&tree, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), r##"digraph modules {
+    node [shape=box, fontname=monospace];
+    m0 [label="crate\nsrc/lib.rs"];
+    m1 [label="pub mod a\nsrc/a.rs"];
+    m2 [label="#[cfg(test)]\nmod tests", style=rounded];
+    m0 -> m1;
+    m0 -> m2;
+    m2 -> m1 [style=dashed, color=blue, constraint=false];
+}
+"##);
+    }
+
+    #[test]
+    fn quoting() {
+        assert_eq!(quote("path = \"a\\b.rs\"\nx"), r#""path = \"a\\b.rs\"\nx""#);
+    }
+}
@@ -0,0 +1,196 @@
+// Changes to a crate's files, planned by the `scaffold` and `refactor`
+// commands and then applied here, so each command only has to work out
+// what to change and can be tested without touching the disk.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use super::lexer::{self, TokenKind};
+use super::ModuleTree;
+
+#[derive(Debug, PartialEq)]
+pub enum Change {
+    // A new file, which mustn't already exist.
+    Create { path: PathBuf, text: String },
+    // New contents for an existing file.
+    Modify { path: PathBuf, text: String },
+    Move { from: PathBuf, to: PathBuf },
+    Delete { path: PathBuf },
+}
+
+// Apply `changes` in order, printing each. Directories are created as
+// needed, and removed when a change leaves them empty.
+pub fn apply(tree: &ModuleTree, changes: &[Change], out: &mut dyn Write) -> io::Result<()> {
+    for change in changes {
+        match change {
+            Change::Create { path, text } => {
+                if path.exists() {
+                    return Err(io::Error::new(io::ErrorKind::AlreadyExists,
+                                              format!("{} already exists", tree.display_path(path))));
+                }
+                create_parent(path)?;
+                fs::write(path, text)?;
+                writeln!(out, "Created {}", tree.display_path(path))?;
+            }
+            Change::Modify { path, text } => {
+                fs::write(path, text)?;
+                writeln!(out, "Updated {}", tree.display_path(path))?;
+            }
+            Change::Move { from, to } => {
+                create_parent(to)?;
+                fs::rename(from, to)?;
+                remove_empty_parent(from);
+                writeln!(out, "Moved {} to {}", tree.display_path(from), tree.display_path(to))?;
+            }
+            Change::Delete { path } => {
+                fs::remove_file(path)?;
+                remove_empty_parent(path);
+                writeln!(out, "Deleted {}", tree.display_path(path))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn create_parent(path: &std::path::Path) -> io::Result<()> {
+    match path.parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+// `remove_dir` only removes empty directories, so other errors are fine.
+fn remove_empty_parent(path: &std::path::Path) {
+    if let Some(dir) = path.parent() {
+        let _ = fs::remove_dir(dir);
+    }
+}
+
+// The byte offset of the start of the line containing `pos`.
+pub fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |i| i + 1)
+}
+
+// The whitespace at the start of the line containing `pos`.
+pub fn indent(text: &str, pos: usize) -> &str {
+    let line = &text[line_start(text, pos)..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+// The lines of `text`, each with whether it starts inside a string literal,
+// where its indentation is part of the string and mustn't change.
+fn lines(text: &str) -> Vec<(&str, bool)> {
+    let literals: Vec<_> = lexer::tokenize(text).into_iter()
+        .filter(|t| t.kind == TokenKind::Literal)
+        .collect();
+    let mut start = 0;
+    text.split_inclusive('\n')
+        .map(|line| {
+            let in_literal = literals.iter().any(|t| t.start < start && start < t.end);
+            start += line.len();
+            (line, in_literal)
+        })
+        .collect()
+}
+
+// The indentation that every non-blank line of `text` has, in columns.
+pub fn common_indent(text: &str) -> usize {
+    lines(text).into_iter()
+        .filter(|(line, in_literal)| !in_literal && !line.trim().is_empty())
+        .map(|(line, _)| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0)
+}
+
+// `text` with `remove` columns of indentation taken off each line and `add`
+// put on. Blank lines are left empty, and lines continuing a string literal
+// are left alone.
+pub fn reindent(text: &str, remove: usize, add: &str) -> String {
+    lines(text).into_iter()
+        .map(|(line, in_literal)| {
+            if in_literal {
+                line.to_string()
+            } else if line.trim().is_empty() {
+                line.trim_start_matches([' ', '\t']).to_string()
+            } else {
+                let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+                format!("{add}{}", &line[indent.min(remove)..])
+            }
+        })
+        .collect()
+}
+
+// Whether `name` can be a module name: an identifier, and not a keyword.
+// That includes the keywords reserved for later, and those of later
+// editions, like `gen`, so the name works in any crate.
+pub fn is_identifier(name: &str) -> bool {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+        "type", "unsafe", "use", "where", "while",
+        // Reserved.
+        "abstract", "become", "box", "do", "final", "gen", "macro", "override", "priv", "try", "typeof",
+        "unsized", "virtual", "yield",
+    ];
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+        && !KEYWORDS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    #[test]
+    fn text_helpers() {
+        let text = "mod a;\n    mod b {}\n";
+        assert_eq!(line_start(text, 13), 7);
+        assert_eq!(indent(text, 13), "    ");
+        assert_eq!(indent(text, 2), "");
+        assert!(is_identifier("new_module"));
+        assert!(is_identifier("_private2"));
+        assert!(!is_identifier("2d"));
+        assert!(!is_identifier("a-b"));
+        assert!(!is_identifier("mod"));
+        assert!(!is_identifier("try"));
+        assert!(!is_identifier("gen"));
+        assert!(!is_identifier(""));
+    }
+
+    #[test]
+    fn indentation() {
+        let text = "    fn f() {\n\n        \"a\n  b\"\n    }\n";
+        assert_eq!(common_indent(text), 4);
+        assert_eq!(reindent(text, 4, ""), "fn f() {\n\n    \"a\n  b\"\n}\n");
+        assert_eq!(reindent(text, 0, "  "), "      fn f() {\n\n          \"a\n  b\"\n      }\n");
+        assert_eq!(common_indent(""), 0);
+    }
+
+    #[test]
+    fn apply_changes() {
+        let dir = TestDir::new("edit_apply_changes", &[("src/lib.rs", "mod a;"), ("src/a/mod.rs", "")]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+        let src = dir.path().join("src");
+        let mut out = Vec::new();
+        apply(&tree, &[
+            Change::Move { from: src.join("a/mod.rs"), to: src.join("a.rs") },
+            Change::Create { path: src.join("b/c.rs"), text: "fn f() {}\n".to_string() },
+            Change::Modify { path: src.join("lib.rs"), text: "mod a;\nmod b;\n".to_string() },
+        ], &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+Moved src/a/mod.rs to src/a.rs
+Created src/b/c.rs
+Updated src/lib.rs
+");
+        assert!(!src.join("a").exists());
+        assert_eq!(fs::read_to_string(src.join("b/c.rs")).unwrap(), "fn f() {}\n");
+
+        let create = Change::Create { path: src.join("a.rs"), text: String::new() };
+        assert!(apply(&tree, &[create], &mut Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+// An item's effective visibility, for the `analyze effective-vis` command:
+// how far it can really be seen, rather than what its own `pub` says.
+
+// To name an item you must name every module on its path, so the item is
+// only as visible as the least visible of them. A `pub fn` in a private
+// module is effectively `pub(crate)`, or narrower, and isn't part of the
+// crate's API at all. The compiler computes this too, as "effective
+// visibilities", to decide what is exported and which lints apply.
+//
+// Like the compiler's, this also counts re-exports: `pub use a::f;` at the
+// crate root makes `f` public whatever `a` is, as long as the `use` can
+// see `f`. A re-export can't make an item more visible than its own
+// modifier, which is error E0364, so that caps it. Glob re-exports aren't
+// followed.
+
+use std::io::{self, Write};
+
+use super::resolve::{self, Target};
+use super::{Item, ModuleId, ModuleTree, Visibility};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Effective {
+    // Visible to other crates.
+    Public,
+    // Visible only inside this module and its descendants.
+    Within(ModuleId),
+}
+
+impl Effective {
+    // The effective visibility as it would be written on an item, e.g.
+    // `pub(crate)`, or `pub(in crate::a)`.
+    pub fn to_string(self, tree: &ModuleTree) -> String {
+        match self {
+            Effective::Public => "pub".to_string(),
+            Effective::Within(id) if id == tree.root() => "pub(crate)".to_string(),
+            Effective::Within(id) => format!("pub(in {})", tree.path_string(id)),
+        }
+    }
+
+    // The wider of two visibilities. Two unrelated modules give the
+    // smallest module containing both.
+    fn max(self, other: Effective, tree: &ModuleTree) -> Effective {
+        match (self, other) {
+            (Effective::Public, _) | (_, Effective::Public) => Effective::Public,
+            (Effective::Within(a), Effective::Within(b)) => {
+                let common = std::iter::successors(Some(a), |&m| tree.module(m).parent)
+                    .find(|&m| tree.is_within(b, m))
+                    .expect("the crate root contains every module");
+                Effective::Within(common)
+            }
+        }
+    }
+}
+
+// The effective visibility of something declared in `module` with
+// visibility `vis`, through its declared path only. `None` if `vis` is a
+// `pub(in path)` that doesn't name an ancestor.
+pub fn declared(tree: &ModuleTree, module: ModuleId, vis: &Visibility) -> Option<Effective> {
+    let mut public = *vis == Visibility::Public;
+    let mut narrowest = tree.visible_within(vis, module)?;
+
+    // The modules on the path are all ancestors of `module`, so each scope
+    // is an ancestor too, and the narrowest is the deepest.
+    let mut current = module;
+    while let Some(parent) = tree.module(current).parent {
+        let module_vis = &tree.module(current).vis;
+        public &= *module_vis == Visibility::Public;
+        let scope = tree.visible_within(module_vis, parent)?;
+        if tree.is_within(scope, narrowest) {
+            narrowest = scope;
+        }
+        current = parent;
+    }
+
+    Some(if public { Effective::Public } else { Effective::Within(narrowest) })
+}
+
+// A re-export that widens an item's visibility, as found by `effective`.
+pub struct Reexport {
+    pub module: ModuleId,
+    pub import: String,
+    pub effective: Effective,
+}
+
+// The effective visibility of `item`, declared in `module`, counting
+// re-exports, and the re-exports that made it wider than `declared`.
+pub fn effective(tree: &ModuleTree, module: ModuleId, item: &Item)
+                 -> Option<(Effective, Vec<Reexport>)> {
+    let declared = declared(tree, module, &item.vis)?;
+    let own = tree.visible_within(&item.vis, module)?;
+    let mut result = declared;
+    let mut reexports = Vec::new();
+
+    for id in tree.ids() {
+        for declaration in &tree.module(id).uses {
+            if declaration.vis == Visibility::Private {
+                continue;
+            }
+            for import in declaration.imports.iter().filter(|i| !i.glob && i.name().is_some()) {
+                let resolution = resolve::resolve(tree, id, import);
+                let Some(Target::Item(found_in, found)) = resolution.target() else { continue };
+                if found_in != module || !std::ptr::eq(found, item) || !resolution.accessible() {
+                    continue;
+                }
+                let Some(mut through) = self::declared(tree, id, &declaration.vis) else { continue };
+                // `id` can see the item, so `own` and any scope around `id`
+                // are nested, and the narrower one is the deeper.
+                through = match through {
+                    _ if item.vis == Visibility::Public => through,
+                    Effective::Within(scope) if tree.is_within(scope, own) => through,
+                    _ => Effective::Within(own),
+                };
+                let widened = result.max(through, tree);
+                if widened != result {
+                    result = widened;
+                    reexports.push(Reexport { module: id, import: import.to_string(), effective: through });
+                }
+            }
+        }
+    }
+    Some((result, reexports))
+}
+
+pub fn write(tree: &ModuleTree, item_path: &str, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let path: super::Import = item_path.parse().map_err(invalid)?;
+    let resolution = resolve::resolve(tree, tree.root(), &path);
+    if let Some(error) = &resolution.error {
+        return Err(invalid(format!("`{path}` doesn't resolve: {error}")));
+    }
+    let Some(Target::Item(module, item)) = resolution.target() else {
+        return Err(invalid(format!("`{path}` isn't an item in this crate")));
+    };
+    let item_path = format!("{}::{}", tree.path_string(module), item.name);
+
+    let private_or = |vis: &Visibility| match vis {
+        Visibility::Private => "private".to_string(),
+        vis => format!("`{vis}`"),
+    };
+    writeln!(out, "{} `{item_path}` is {} in `{}`.", item.kind, private_or(&item.vis),
+             tree.path_string(module))?;
+    let mut current = module;
+    while let Some(parent) = tree.module(current).parent {
+        writeln!(out, "module `{}` is {} in `{}`.", tree.path_string(current),
+                 private_or(&tree.module(current).vis), tree.path_string(parent))?;
+        current = parent;
+    }
+
+    let Some(declared) = declared(tree, module, &item.vis) else {
+        return writeln!(out, "\nIts `pub(in ...)` path isn't an ancestor module, error E0742.");
+    };
+    writeln!(out, "\nThrough that path it's effectively `{}`.", declared.to_string(tree))?;
+
+    let (effective, reexports) = effective(tree, module, item).expect("`declared` is `Some`");
+    for reexport in &reexports {
+        writeln!(out, "`use {};` in `{}` re-exports it as `{}`.", reexport.import,
+                 tree.path_string(reexport.module), reexport.effective.to_string(tree))?;
+    }
+    if !reexports.is_empty() {
+        writeln!(out, "So it's effectively `{}`.", effective.to_string(tree))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    // The effective visibility of every item in the crate, as `path: vis`.
+    fn effective_visibilities(name: &str, lib_rs: &str) -> Vec<String> {
+        let dir = TestDir::new(name, &[("src/lib.rs", lib_rs)]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+        tree.ids().flat_map(|id| tree.module(id).items.iter().map(move |item| (id, item)))
+            .map(|(id, item)| {
+                let effective = effective(&tree, id, item)
+                    .map_or("invalid".to_string(), |(e, _)| e.to_string(&tree));
+                format!("{}::{}: {effective}", tree.path_string(id), item.name)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nesting() {
+        assert_eq!(effective_visibilities("effective_nesting", "
+            pub fn exported() {}
+            fn private() {}
+            mod private_mod {
+                pub fn public_in_private() {}
+                pub(crate) fn crate_in_private() {}
+                pub mod inner { pub fn deep() {} pub(super) fn up_one() {} }
+            }
+            pub mod public_mod {
+                pub fn exported() {}
+                pub(crate) fn crate_only() {}
+                pub(self) fn self_only() {}
+                pub(crate) mod crate_mod { pub fn capped() {} }
+                pub mod inner {
+                    pub(in crate::public_mod) fn in_path() {}
+                    pub(super) fn up_one() {}
+                    pub(in crate::private_mod) fn bad_path() {}
+                    mod hidden { pub fn deep() {} }
+                }
+            }
+        "), vec![
+            "crate::exported: pub",
+            "crate::private: pub(crate)",
+            "crate::private_mod::public_in_private: pub(crate)",
+            "crate::private_mod::crate_in_private: pub(crate)",
+            "crate::private_mod::inner::deep: pub(crate)",
+            "crate::private_mod::inner::up_one: pub(in crate::private_mod)",
+            "crate::public_mod::exported: pub",
+            "crate::public_mod::crate_only: pub(crate)",
+            "crate::public_mod::self_only: pub(in crate::public_mod)",
+            "crate::public_mod::crate_mod::capped: pub(crate)",
+            "crate::public_mod::inner::in_path: pub(in crate::public_mod)",
+            "crate::public_mod::inner::up_one: pub(in crate::public_mod)",
+            "crate::public_mod::inner::bad_path: invalid",
+            "crate::public_mod::inner::hidden::deep: pub(in crate::public_mod::inner)",
+        ]);
+    }
+
+    #[test]
+    fn reexports() {
+        assert_eq!(effective_visibilities("effective_reexports", "
+            mod a {
+                pub fn widened() {}
+                pub fn crate_wide() {}
+                pub(super) fn not_reexportable() {}
+                mod b { pub fn sideways() {} }
+                pub(crate) use self::b::sideways;
+            }
+            mod c { pub(crate) use crate::a::crate_wide; }
+            pub use a::widened;
+            pub use a::not_reexportable;
+        "), vec![
+            "crate::a::widened: pub",
+            "crate::a::crate_wide: pub(crate)",
+            // The `pub use` at the root can see it, but can't make it more
+            // than `pub(super)`, which the compiler reports as error E0364.
+            "crate::a::not_reexportable: pub(crate)",
+            "crate::a::b::sideways: pub(crate)",
+        ]);
+    }
+}
@@ -0,0 +1,197 @@
+// Moving an inline module into a file of its own, for the
+// `refactor extract` command: `mod name { ... }` becomes `mod name;`, and
+// the body goes in `name.rs`, or `name/mod.rs`.
+
+// Nothing else needs to change, because an inline module and a file module
+// are the same thing to the compiler, as `../lib.rs` shows. Its attributes,
+// like `#[cfg(test)]`, stay on the declaration, and its path stays the same,
+// so every `use` still works. A `mod child;` inside it already looks for
+// its file in `name/`, in either style.
+//
+// The one difference is `#[path]` on a `mod` declared in the body. In an
+// inline module that's relative to `name/`, as it is in `name/mod.rs`, but
+// in `name.rs` it's relative to the directory `name.rs` is in.
+
+use std::io::{self, Write};
+
+use super::convert_style::has_path_attribute;
+use super::edit::{self, Change};
+use super::lexer::{self, TokenKind};
+use super::scaffold::Style;
+use super::{ModuleId, ModuleTree};
+
+pub fn extract(tree: &ModuleTree, module: ModuleId, style: Style) -> Result<Vec<Change>, String> {
+    let m = tree.module(module);
+    let path = tree.path_string(module);
+    let decl = match m.decl {
+        Some(decl) if m.inline => decl,
+        _ => return Err(format!("`{path}` isn't an inline module")),
+    };
+    if has_path_attribute(m) {
+        return Err(format!("`{path}` has a `#[path]` attribute, which would load that file instead"));
+    }
+    let path_child = m.children.iter().map(|&c| tree.module(c)).find(|c| !c.inline && has_path_attribute(c));
+    if let (Some(child), Style::Edition2018) = (path_child, style) {
+        return Err(format!("`mod {}` in `{path}` has a `#[path]` attribute, which would load a \
+                            different file from `{}.rs`, so use the mod-rs style", child.name, m.name));
+    }
+
+    let file = tree.file(decl.file);
+    let text = &file.text;
+    let open = body_start(&text[decl.start..decl.end]).ok_or(format!("can't find the body of `{path}`"))?;
+    let (header, body) = (&text[decl.start..decl.start + open], &text[decl.start + open + 1..decl.end - 1]);
+
+    let body = edit::reindent(body, edit::common_indent(body), "");
+    let body = body.trim_start_matches('\n').trim_end();
+    let new_text = if body.is_empty() { String::new() } else { format!("{body}\n") };
+
+    let dir = &tree.module(m.parent.expect("an inline module has a parent")).child_dir;
+    let new_path = match style {
+        Style::ModRs => dir.join(&m.name).join("mod.rs"),
+        Style::Edition2018 => dir.join(format!("{}.rs", m.name)),
+    };
+    // A file in the other style would be found too, which is error E0761.
+    for existing in [dir.join(format!("{}.rs", m.name)), dir.join(&m.name).join("mod.rs")] {
+        if existing.exists() {
+            return Err(format!("{} already exists", tree.display_path(&existing)));
+        }
+    }
+
+    let parent_text = format!("{}{};{}", &text[..decl.start], header.trim_end(), &text[decl.end..]);
+    Ok(vec![
+        Change::Create { path: new_path, text: new_text },
+        Change::Modify { path: file.path.clone(), text: parent_text },
+    ])
+}
+
+// The offset in `item`, an inline `mod` declaration, of the `{` opening its
+// body: the first after the `mod` keyword, skipping any attributes.
+pub(super) fn body_start(item: &str) -> Option<usize> {
+    let tokens = lexer::tokenize(item);
+    let mut depth = 0;
+    let mod_keyword = tokens.iter().position(|t| {
+        match t.kind {
+            TokenKind::Punct('[') => depth += 1,
+            TokenKind::Punct(']') => depth -= 1,
+            _ => {}
+        }
+        depth == 0 && t.kind == TokenKind::Ident && t.text(item) == "mod"
+    })?;
+    tokens[mod_keyword..].iter().find(|t| t.is_punct('{')).map(|t| t.start)
+}
+
+pub fn write(tree: &ModuleTree, module_path: &str, style: Style, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let module = tree.find(module_path)
+        .ok_or_else(|| invalid(format!("no module `{module_path}` in this crate")))?;
+    let changes = extract(tree, module, style).map_err(invalid)?;
+    edit::apply(tree, &changes, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::diff::diff;
+    use crate::modtree::find_crate_root;
+    use crate::modtree::test_dir::TestDir;
+    use std::fs;
+
+    const LIB_RS: &str = "\
+pub fn f() {}
+
+mod outer {
+    #![allow(dead_code)]
+
+    /// Kept with the declaration.
+    #[cfg(test)]
+    pub(crate) mod tests {
+        use super::*;
+
+        const TEXT: &str = \"a
+  b\";
+
+        mod deeper {
+            mod leaf;
+        }
+    }
+}
+";
+
+    // Extracts `module` from `LIB_RS`, and returns the crate's files.
+    fn extracted(test: &str, module: &str, style: Style) -> Vec<(String, String)> {
+        let dir = TestDir::new(test, &[("src/lib.rs", LIB_RS), ("src/outer/tests/deeper/leaf.rs", "")]);
+        let before = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        write(&before, module, style, &mut Vec::new()).unwrap();
+
+        // Round trip: it's the same tree, from different files.
+        let after = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        assert_eq!(after.problems, Vec::<String>::new());
+        assert_eq!(diff(&before, &after), vec![]);
+        assert_eq!(after.modules.len(), before.modules.len());
+        after.files.iter()
+            .map(|f| (after.display_path(&f.path), fs::read_to_string(&f.path).unwrap()))
+            .collect()
+    }
+
+    fn file(path: &str, text: &str) -> (String, String) {
+        (path.to_string(), text.to_string())
+    }
+
+    #[test]
+    fn nested() {
+        let files = extracted("extract_nested", "crate::outer::tests", Style::Edition2018);
+        assert_eq!(files[0], file("src/lib.rs", "\
+pub fn f() {}
+
+mod outer {
+    #![allow(dead_code)]
+
+    /// Kept with the declaration.
+    #[cfg(test)]
+    pub(crate) mod tests;
+}
+"));
+        assert_eq!(files[1], file("src/outer/tests.rs", "\
+use super::*;
+
+const TEXT: &str = \"a
+  b\";
+
+mod deeper {
+    mod leaf;
+}
+"));
+        assert_eq!(files[2].0, "src/outer/tests/deeper/leaf.rs");
+    }
+
+    #[test]
+    fn top_level() {
+        let files = extracted("extract_top_level", "crate::outer", Style::ModRs);
+        assert_eq!(files[0], file("src/lib.rs", "pub fn f() {}\n\nmod outer;\n"));
+        assert_eq!(files[1].0, "src/outer/mod.rs");
+        assert!(files[1].1.starts_with("#![allow(dead_code)]\n\n/// Kept with the declaration.\n#[cfg(test)]\n"));
+    }
+
+    #[test]
+    fn errors() {
+        let dir = TestDir::new("extract_errors", &[
+            ("src/lib.rs", "mod file; mod empty {} mod other {} mod paths { #[path = \"x.rs\"] mod x; }"),
+            ("src/file.rs", ""),
+            ("src/paths/x.rs", ""),
+            ("src/empty.rs", ""),
+            ("src/other/mod.rs", ""),
+        ]);
+        let tree = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        let extract = |module, style| extract(&tree, tree.find(module).unwrap(), style);
+        assert_eq!(extract("crate::file", Style::ModRs), Err("`crate::file` isn't an inline module".to_string()));
+        assert_eq!(extract("crate::empty", Style::Edition2018), Err("src/empty.rs already exists".to_string()));
+        assert!(extract("crate::paths", Style::Edition2018).is_err());
+        assert_eq!(extract("crate::paths", Style::ModRs).unwrap()[0],
+                   Change::Create { path: dir.path().join("src/paths/mod.rs"),
+                                   text: "#[path = \"x.rs\"] mod x;\n".to_string() });
+        // A file in either style would be a second file for the module.
+        assert_eq!(extract("crate::empty", Style::ModRs), Err("src/empty.rs already exists".to_string()));
+        assert_eq!(extract("crate::other", Style::Edition2018), Err("src/other/mod.rs already exists".to_string()));
+        assert_eq!(extract("crate::other", Style::ModRs), Err("src/other/mod.rs already exists".to_string()));
+    }
+}
@@ -0,0 +1,276 @@
+// Gathering a crate's re-exports into one facade module, for the
+// `refactor flatten-reexports` command.
+
+// A crate often re-exports an item several times over on the way up to its
+// root: `c` defines `Item`, `b` has `pub use c::Item;`, `a` has
+// `pub use b::Item;`, and the root has `pub use a::Item;`. Each `pub use`
+// only passes the item along, and finding where it's really defined means
+// following the chain by hand. A facade does it once: a module at the root,
+// here `crate::facade`, with `pub use crate::a::b::c::Item;` for each item,
+// naming the module that defines it.
+//
+// Once the facade has them, the re-exports in the middle of each chain are
+// redundant, and this reports them. It doesn't remove them, as code may use
+// those paths too, and nor does it touch the re-exports chains start from,
+// which are the paths the crate's users know. Where the defining module
+// isn't visible from the root, the facade re-exports the item from the
+// deepest module in the chain that is, and the re-exports below that are
+// still needed.
+//
+// Glob re-exports like `pub use a::*;` aren't followed, and only `pub`
+// re-exports of items count, not of modules.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+
+use super::edit::{self, Change};
+use super::resolve::{self, Target};
+use super::{scaffold, Import, ItemKind, ModuleId, ModuleTree, Span, Visibility};
+
+// A `pub use` of one item: the module it's in, the import, and the whole
+// declaration, which may import other things too.
+#[derive(Clone, Copy, Debug)]
+struct Reexport<'a> {
+    module: ModuleId,
+    import: &'a Import,
+    span: Span,
+}
+
+impl Reexport<'_> {
+    fn key(&self) -> (usize, String) {
+        (self.module.0, self.import.to_string())
+    }
+}
+
+// The re-exports of an item, from the one a path starts at, through those
+// it passes through in order, to the module defining it.
+struct Chain<'a> {
+    reexports: Vec<Reexport<'a>>,
+    defined_in: ModuleId,
+    name: &'a str,
+}
+
+pub struct Facade {
+    pub changes: Vec<Change>,
+    // Each line of the facade, e.g. `pub use crate::a::b::Item;`.
+    pub lines: Vec<String>,
+    // The re-exports it makes redundant, as `(module, declaration, span)`.
+    pub redundant: Vec<(ModuleId, String, Span)>,
+}
+
+pub fn flatten(tree: &ModuleTree, name: &str) -> Result<Facade, String> {
+    let root = tree.root();
+    if !edit::is_identifier(name) {
+        return Err(format!("'{name}' isn't a valid module name"));
+    }
+    if tree.child(root, name).is_some() {
+        return Err(format!("the crate already has a module `{name}`"));
+    }
+    let path = tree.module(root).child_dir.join(format!("{name}.rs"));
+    if path.exists() {
+        return Err(format!("{} already exists", tree.display_path(&path)));
+    }
+
+    let chains = chains(tree);
+    let passed_through: BTreeSet<_> = chains.iter().flat_map(|c| c.reexports[1..].iter().map(|r| r.key())).collect();
+
+    // The facade exports each item under the name it's re-exported as
+    // closest to the root, by a re-export that others don't pass through.
+    let mut items: BTreeMap<(usize, &str), &Chain> = BTreeMap::new();
+    for chain in chains.iter().filter(|c| !passed_through.contains(&c.reexports[0].key())) {
+        let key = (chain.defined_in.0, chain.name);
+        let depth = |c: &Chain| tree.path(c.reexports[0].module).len();
+        if items.get(&key).is_none_or(|&existing| depth(chain) < depth(existing)) {
+            items.insert(key, chain);
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut needed = BTreeSet::new();
+    let mut exported: BTreeMap<&str, String> = BTreeMap::new();
+    for chain in items.values() {
+        // The deepest module along the chain whose name for the item can be
+        // used from the root, starting with the one defining it.
+        let mut sources = vec![(chain.defined_in, chain.name)];
+        sources.extend(chain.reexports.iter().rev()
+            .map(|r| (r.module, r.import.name().expect("only named imports are followed"))));
+        let Some(position) = sources.iter().position(|&(module, item)| {
+            format!("{}::{item}", tree.path_string(module)).parse::<Import>()
+                .is_ok_and(|path| resolve::resolve(tree, root, &path).accessible())
+        }) else {
+            continue;
+        };
+        // Re-exports at or below the one the facade uses are still needed.
+        let len = chain.reexports.len();
+        needed.extend(chain.reexports[len - position..].iter().map(|r| r.key()));
+
+        let (module, item) = sources[position];
+        let export_name = chain.reexports[0].import.name().expect("only named imports are followed");
+        let path = format!("{}::{item}", tree.path_string(module));
+        if let Some(other) = exported.insert(export_name, path.clone()) {
+            return Err(format!("`{other}` and `{path}` are both re-exported as `{export_name}`, \
+                                so they can't both be in one facade"));
+        }
+        lines.push(match export_name == item {
+            true => format!("pub use {path};"),
+            false => format!("pub use {path} as {export_name};"),
+        });
+    }
+    if lines.is_empty() {
+        return Err("the crate doesn't re-export any items with `pub use`".to_string());
+    }
+    lines.sort();
+
+    let mut redundant: Vec<_> = chains.iter()
+        .flat_map(|c| &c.reexports[1..])
+        .filter(|r| !needed.contains(&r.key()))
+        .map(|r| (r.module, format!("pub use {};", r.import), r.span))
+        .collect();
+    redundant.sort_by_key(|(module, declaration, span)| (span.file.0, span.start, *module, declaration.clone()));
+    redundant.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+
+    let text = format!("// A facade for this crate: each item it re-exports, from the module that\n\
+                        // defines it.\n\n{}\n", lines.join("\n"));
+    let root_file = tree.file(tree.module(root).file.ok_or("the crate root's file wasn't found")?);
+    let changes = vec![
+        Change::Create { path, text },
+        Change::Modify { path: root_file.path.clone(),
+                         text: scaffold::declare(tree, root, &root_file.text, &format!("pub mod {name};")) },
+    ];
+    Ok(Facade { changes, lines, redundant })
+}
+
+// Every chain of `pub use` re-exports of an item in the crate.
+fn chains(tree: &ModuleTree) -> Vec<Chain<'_>> {
+    let mut chains = Vec::new();
+    for module in tree.ids() {
+        let reexports = tree.module(module).uses.iter()
+            .filter(|u| u.vis == Visibility::Public)
+            .flat_map(|u| u.imports.iter().map(move |import| Reexport { module, import, span: u.span }))
+            .filter(|r| r.import.name().is_some());
+        for first in reexports {
+            let mut reexports = vec![first];
+            let mut current = first;
+            loop {
+                let resolution = resolve::resolve(tree, current.module, current.import);
+                let Some(Target::Item(defined_in, item)) = resolution.target() else { break };
+                if matches!(item.kind, ItemKind::Macro | ItemKind::ExternCrate) {
+                    break;
+                }
+                let last = resolution.steps.last().expect("a target has a step");
+                // The name was imported where it was found, so follow that import.
+                let next = last.via.as_ref().and_then(|_| {
+                    let u = tree.module(last.found_in).uses.iter()
+                        .find(|u| u.imports.iter().any(|i| i.name() == Some(&last.segment)))?;
+                    let import = u.imports.iter().find(|i| i.name() == Some(&last.segment))?;
+                    Some(Reexport { module: last.found_in, import, span: u.span })
+                });
+                match next {
+                    Some(next) if reexports.len() < 16 && next.key() != current.key() => {
+                        reexports.push(next);
+                        current = next;
+                    }
+                    _ => {
+                        chains.push(Chain { reexports, defined_in, name: &item.name });
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    chains
+}
+
+pub fn write(tree: &ModuleTree, name: &str, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let facade = flatten(tree, name).map_err(invalid)?;
+    edit::apply(tree, &facade.changes, out)?;
+
+    let count = facade.lines.len();
+    writeln!(out, "`crate::{name}` re-exports {count} item{} from where {} defined:",
+             if count == 1 { "" } else { "s" }, if count == 1 { "it's" } else { "they're" })?;
+    for line in &facade.lines {
+        writeln!(out, "  {line}")?;
+    }
+    if facade.redundant.is_empty() {
+        return writeln!(out, "No other re-exports become redundant.");
+    }
+    writeln!(out, "These re-exports only pass an item along to another, so they're now redundant:")?;
+    for (module, declaration, span) in &facade.redundant {
+        writeln!(out, "  {}:{}: `{declaration}` in `{}`", tree.display_path(&tree.file(span.file).path),
+                 tree.line(*span), tree.path_string(*module))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::find_crate_root;
+    use crate::modtree::test_dir::TestDir;
+    use std::fs;
+
+    fn load(dir: &TestDir) -> ModuleTree {
+        ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn chains() {
+        let dir = TestDir::new("flatten_chains", &[
+            ("src/lib.rs", "pub mod a;\nmod private;\n\npub use a::{Item, Thing as Renamed};\npub use private::f;\n"),
+            ("src/a.rs", "pub mod b;\n\npub use b::Item;\npub use b::c::Thing;\n"),
+            ("src/a/b.rs", "pub mod c;\n\npub use c::Item;\n"),
+            ("src/a/b/c.rs", "pub struct Item;\npub struct Thing;\n"),
+            ("src/private.rs", "mod inner;\n\npub use inner::f;\n"),
+            ("src/private/inner.rs", "pub fn f() {}\n"),
+        ]);
+        let mut out = Vec::new();
+        write(&load(&dir), "facade", &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+Created src/facade.rs
+Updated src/lib.rs
+`crate::facade` re-exports 3 items from where they're defined:
+  pub use crate::a::b::c::Item;
+  pub use crate::a::b::c::Thing as Renamed;
+  pub use crate::private::f;
+These re-exports only pass an item along to another, so they're now redundant:
+  src/a.rs:3: `pub use b::Item;` in `crate::a`
+  src/a.rs:4: `pub use b::c::Thing;` in `crate::a`
+  src/a/b.rs:3: `pub use c::Item;` in `crate::a::b`
+");
+        assert_eq!(fs::read_to_string(dir.path().join("src/facade.rs")).unwrap(), "\
+// A facade for this crate: each item it re-exports, from the module that
+// defines it.
+
+pub use crate::a::b::c::Item;
+pub use crate::a::b::c::Thing as Renamed;
+pub use crate::private::f;
+");
+        assert!(fs::read_to_string(dir.path().join("src/lib.rs")).unwrap()
+            .starts_with("pub mod a;\nmod private;\npub mod facade;\n"));
+        assert_eq!(load(&dir).problems, Vec::<String>::new());
+    }
+
+    #[test]
+    fn errors() {
+        let dir = TestDir::new("flatten_errors", &[
+            ("src/lib.rs", "mod a { pub fn f() {} } mod b { pub fn f() {} } mod c { pub use crate::a::f; }\n\
+                            pub use a::f; pub use b::f as g; mod taken {}"),
+        ]);
+        let tree = load(&dir);
+        assert!(flatten(&tree, "facade").is_ok());
+        assert_eq!(flatten(&tree, "taken").err(), Some("the crate already has a module `taken`".to_string()));
+        assert!(flatten(&tree, "not-a-name").is_err());
+
+        let dir = TestDir::new("flatten_errors_clash", &[
+            ("src/lib.rs", "pub mod a { pub fn f() {} } pub mod b { pub fn f() {} } pub use a::f;\n\
+                            pub mod c { pub use crate::b::f; }"),
+        ]);
+        assert_eq!(flatten(&load(&dir), "facade").err(),
+                   Some("`crate::a::f` and `crate::b::f` are both re-exported as `f`, so they can't both be in \
+                         one facade".to_string()));
+
+        let dir = TestDir::new("flatten_errors_none", &[("src/lib.rs", "pub mod a { pub fn f() {} }")]);
+        assert!(flatten(&load(&dir), "facade").is_err());
+    }
+}
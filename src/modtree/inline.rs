@@ -0,0 +1,160 @@
+// Moving a file module's contents into its parent, for the
+// `refactor inline` command: `mod name;` becomes `mod name { ... }`, and
+// `name.rs`, or `name/mod.rs`, is deleted.
+
+// This is `extract` in reverse, in `./extract.rs`, and the same reasoning
+// applies: to the compiler the two forms are the same module, so nothing
+// else changes. Children declared with `mod child;` are still found in
+// `name/`, and the file's inner attributes, like `#![allow(...)]`, are just
+// as valid at the start of the block.
+
+use std::io::{self, Write};
+
+use super::convert_style::{self, has_path_attribute};
+use super::edit::{self, Change};
+use super::scaffold::Style;
+use super::{ModuleId, ModuleTree};
+
+pub fn inline(tree: &ModuleTree, module: ModuleId) -> Result<Vec<Change>, String> {
+    let m = tree.module(module);
+    let path = tree.path_string(module);
+    let (Some(decl), Some(file), false) = (m.decl, m.file, m.inline) else {
+        return Err(format!("`{path}` isn't a module with a file of its own"));
+    };
+    if has_path_attribute(m) {
+        return Err(format!("`{path}` is loaded with `#[path]`, so its children may be found \
+                            somewhere else once it's inline"));
+    }
+    // As in `extract`, a `#[path]` in `name.rs` is relative to a different
+    // directory from one in an inline module.
+    let path_child = m.children.iter().map(|&c| tree.module(c)).find(|c| !c.inline && has_path_attribute(c));
+    if let (Some(child), Some(Style::Edition2018)) = (path_child, convert_style::style(tree, module)) {
+        return Err(format!("`mod {}` in `{path}` has a `#[path]` attribute, which would load a \
+                            different file once it's inline", child.name));
+    }
+
+    let file = tree.file(file);
+    let parent_file = tree.file(decl.file);
+    let text = &parent_file.text;
+    let header = text[decl.start..decl.end].trim_end_matches(';').trim_end();
+    let indent = edit::indent(text, decl.start);
+
+    let body = file.text.trim_start_matches('\n').trim_end();
+    let block = if body.is_empty() {
+        format!("{header} {{}}")
+    } else {
+        let body = edit::reindent(body, 0, &format!("{indent}    "));
+        format!("{header} {{\n{body}\n{indent}}}")
+    };
+    let parent_text = format!("{}{block}{}", &text[..decl.start], &text[decl.end..]);
+    Ok(vec![
+        Change::Modify { path: parent_file.path.clone(), text: parent_text },
+        Change::Delete { path: file.path.clone() },
+    ])
+}
+
+pub fn write(tree: &ModuleTree, module_path: &str, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let module = tree.find(module_path)
+        .ok_or_else(|| invalid(format!("no module `{module_path}` in this crate")))?;
+    let changes = inline(tree, module).map_err(invalid)?;
+    edit::apply(tree, &changes, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::diff::diff;
+    use crate::modtree::extract;
+    use crate::modtree::find_crate_root;
+    use crate::modtree::test_dir::TestDir;
+    use std::fs;
+
+    fn load(dir: &TestDir) -> ModuleTree {
+        ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn into_parent() {
+        let dir = TestDir::new("inline_into_parent", &[
+            ("src/lib.rs", "mod outer {\n    #[cfg(test)]\n    pub mod a;\n    mod empty;\n}\n"),
+            ("src/outer/a/mod.rs", "#![allow(dead_code)]\n\nconst S: &str = \"x\n y\";\n\nmod b;\n"),
+            ("src/outer/a/b.rs", "fn f() {}\n"),
+            ("src/outer/empty.rs", ""),
+        ]);
+        let before = load(&dir);
+        write(&before, "crate::outer::a", &mut Vec::new()).unwrap();
+        write(&load(&dir), "crate::outer::empty", &mut Vec::new()).unwrap();
+
+        let after = load(&dir);
+        assert_eq!(after.problems, Vec::<String>::new());
+        assert_eq!(diff(&before, &after), vec![]);
+        assert_eq!(fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(), "\
+mod outer {
+    #[cfg(test)]
+    pub mod a {
+        #![allow(dead_code)]
+
+        const S: &str = \"x
+ y\";
+
+        mod b;
+    }
+    mod empty {}
+}
+");
+        assert!(!dir.path().join("src/outer/a/mod.rs").exists());
+        assert!(dir.path().join("src/outer/a/b.rs").exists());
+        assert!(!dir.path().join("src/outer/empty.rs").exists());
+    }
+
+    // Inlining and extracting again gives back the files it started with.
+    #[test]
+    fn round_trip() {
+        let lib_rs = "pub mod a;\n\n#[cfg(test)]\nmod tests;\n";
+        let a_rs = "// A comment.\npub fn f() {\n    println!(\"f\");\n}\n";
+        let tests_rs = "use super::*;\n";
+        let dir = TestDir::new("inline_round_trip", &[("src/lib.rs", lib_rs), ("src/a.rs", a_rs),
+                                                     ("src/tests.rs", tests_rs)]);
+        for module in ["crate::a", "crate::tests"] {
+            write(&load(&dir), module, &mut Vec::new()).unwrap();
+        }
+        assert_eq!(fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(), "\
+pub mod a {
+    // A comment.
+    pub fn f() {
+        println!(\"f\");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+}
+");
+        for module in ["crate::a", "crate::tests"] {
+            extract::write(&load(&dir), module, Style::Edition2018, &mut Vec::new()).unwrap();
+        }
+        let src = dir.path().join("src");
+        assert_eq!(fs::read_to_string(src.join("lib.rs")).unwrap(), lib_rs);
+        assert_eq!(fs::read_to_string(src.join("a.rs")).unwrap(), a_rs);
+        assert_eq!(fs::read_to_string(src.join("tests.rs")).unwrap(), tests_rs);
+    }
+
+    #[test]
+    fn errors() {
+        let dir = TestDir::new("inline_errors", &[
+            ("src/lib.rs", "mod inline {} mod missing; #[path = \"other.rs\"] mod moved; mod paths;"),
+            ("src/other.rs", ""),
+            ("src/paths.rs", "#[path = \"x.rs\"] mod x;"),
+            ("src/x.rs", ""),
+        ]);
+        let tree = load(&dir);
+        let inline = |module| inline(&tree, tree.find(module).unwrap());
+        assert_eq!(inline("crate"), Err("`crate` isn't a module with a file of its own".to_string()));
+        assert!(inline("crate::inline").is_err());
+        assert!(inline("crate::missing").is_err());
+        assert!(inline("crate::moved").is_err());
+        assert!(inline("crate::paths").is_err());
+    }
+}
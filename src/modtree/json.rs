@@ -0,0 +1,118 @@
+// Prints a `ModuleTree` as JSON for other tools to consume.
+//
+// The layout is versioned with a top-level "version" field. Fields are
+// always present and always in the same order, and modules, items and
+// imports appear in source order.
+
+use std::io::{self, Write};
+
+use super::{ModuleId, ModuleTree, Visibility};
+use crate::json::Value;
+
+// Bump this when changing the layout in a way that could break consumers.
+pub const VERSION: usize = 1;
+
+pub fn write(tree: &ModuleTree, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "{}", to_json(tree))
+}
+
+pub fn to_json(tree: &ModuleTree) -> Value {
+    Value::object([
+        ("version", Value::from(VERSION)),
+        ("root", module(tree, tree.root())),
+        ("problems", Value::from(tree.problems.clone())),
+    ])
+}
+
+fn module(tree: &ModuleTree, id: ModuleId) -> Value {
+    let m = tree.module(id);
+
+    let items = m.items.iter().map(|item| Value::object([
+        ("name", Value::from(item.name.as_str())),
+        ("kind", Value::from(item.kind.to_string())),
+        ("visibility", visibility(&item.vis)),
+        ("attributes", Value::from(item.attrs.clone())),
+    ])).collect();
+
+    let uses = m.uses.iter().flat_map(|u| u.imports.iter().map(|import| Value::object([
+        ("path", Value::from(import.path.join("::"))),
+        ("alias", Value::from(import.alias.as_deref())),
+        ("glob", Value::from(import.glob)),
+        ("visibility", visibility(&u.vis)),
+    ]))).collect();
+
+    Value::object([
+        ("name", Value::from(m.name.as_str())),
+        ("path", Value::from(tree.path_string(id))),
+        ("file", Value::from(m.file.map(|f| tree.display_path(&tree.file(f).path)))),
+        ("inline", Value::from(m.inline)),
+        ("visibility", visibility(&m.vis)),
+        ("attributes", Value::from(m.attrs.clone())),
+        ("items", Value::Array(items)),
+        ("uses", Value::Array(uses)),
+        ("children", Value::Array(m.children.iter().map(|&c| module(tree, c)).collect())),
+    ])
+}
+
+fn visibility(vis: &Visibility) -> Value {
+    match vis {
+        Visibility::Private => Value::from("private"),
+        vis => Value::from(vis.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    #[test]
+    fn layout() {
+        let dir = TestDir::new("json_layout", &[
+            ("src/lib.rs", "pub use a::f as g; mod a { pub(crate) fn f() {} }"),
+        ]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+
+        assert_eq!(to_json(&tree).to_string(), r#"{
+  "version": 1,
+  "root": {
+    "name": "crate",
+    "path": "crate",
+    "file": "src/lib.rs",
+    "inline": false,
+    "visibility": "pub",
+    "attributes": [],
+    "items": [],
+    "uses": [
+      {
+        "path": "a::f",
+        "alias": "g",
+        "glob": false,
+        "visibility": "pub"
+      }
+    ],
+    "children": [
+      {
+        "name": "a",
+        "path": "crate::a",
+        "file": "src/lib.rs",
+        "inline": true,
+        "visibility": "private",
+        "attributes": [],
+        "items": [
+          {
+            "name": "f",
+            "kind": "fn",
+            "visibility": "pub(crate)",
+            "attributes": []
+          }
+        ],
+        "uses": [],
+        "children": []
+      }
+    ]
+  },
+  "problems": []
+}"#);
+    }
+}
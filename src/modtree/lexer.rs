@@ -0,0 +1,260 @@
+// A small tokenizer for Rust source, just detailed enough to find module
+// structure: it understands comments, string and character literals and
+// lifetimes so braces inside them don't confuse the parser, and otherwise
+// hands out identifiers and single punctuation characters.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenKind {
+    Ident,
+    Lifetime,
+    // A string, byte string, raw string, character or number literal.
+    Literal,
+    Punct(char),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    // Byte offsets into the source text.
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Token {
+    pub fn text<'a>(&self, src: &'a str) -> &'a str {
+        &src[self.start..self.end]
+    }
+
+    pub fn is_punct(&self, c: char) -> bool {
+        self.kind == TokenKind::Punct(c)
+    }
+}
+
+// Unterminated comments and literals run to the end of the input rather than
+// failing, so half-written files still give a useful tree.
+pub fn tokenize(src: &str) -> Vec<Token> {
+    let bytes = src.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+
+        let kind = match c {
+            _ if c.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = skip_block_comment(bytes, i);
+                continue;
+            }
+            b'"' => {
+                i = skip_quoted(bytes, i + 1, b'"');
+                TokenKind::Literal
+            }
+            b'\'' => {
+                // A character literal, or a lifetime like `'a` with no closing quote.
+                if let Some(end) = char_literal_end(bytes, i) {
+                    i = end;
+                    TokenKind::Literal
+                } else {
+                    i += 1;
+                    while i < bytes.len() && is_ident_continue(bytes[i]) {
+                        i += 1;
+                    }
+                    if i == start + 1 { TokenKind::Punct('\'') } else { TokenKind::Lifetime }
+                }
+            }
+            b'0'..=b'9' => {
+                while i < bytes.len() && (is_ident_continue(bytes[i]) || bytes[i] == b'.'
+                                          && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)) {
+                    i += 1;
+                }
+                TokenKind::Literal
+            }
+            _ if is_ident_start(c) => {
+                if let Some(end) = prefixed_literal_end(bytes, i) {
+                    i = end;
+                    TokenKind::Literal
+                } else {
+                    // Raw identifiers like `r#type` are a single token.
+                    if c == b'r' && bytes.get(i + 1) == Some(&b'#')
+                        && bytes.get(i + 2).is_some_and(|&b| is_ident_start(b)) {
+                        i += 2;
+                    }
+                    while i < bytes.len() && is_ident_continue(bytes[i]) {
+                        i += 1;
+                    }
+                    TokenKind::Ident
+                }
+            }
+            _ => {
+                // Step over a whole UTF-8 character.
+                let ch = src[i..].chars().next().expect("i is on a char boundary");
+                i += ch.len_utf8();
+                TokenKind::Punct(ch)
+            }
+        };
+
+        tokens.push(Token { kind, start, end: i });
+    }
+
+    tokens
+}
+
+fn is_ident_start(c: u8) -> bool {
+    c == b'_' || c.is_ascii_alphabetic() || c >= 0x80
+}
+
+fn is_ident_continue(c: u8) -> bool {
+    c == b'_' || c.is_ascii_alphanumeric() || c >= 0x80
+}
+
+// Block comments nest in Rust.
+fn skip_block_comment(bytes: &[u8], mut i: usize) -> usize {
+    let mut depth = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            depth += 1;
+            i += 2;
+        } else if bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/') {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    i
+}
+
+// Skip to just past the closing `quote`, honouring backslash escapes.
+fn skip_quoted(bytes: &[u8], mut i: usize, quote: u8) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+// Returns the end of a character literal starting at `i`, or `None` if this
+// is a lifetime.
+fn char_literal_end(bytes: &[u8], i: usize) -> Option<usize> {
+    match bytes.get(i + 1)? {
+        b'\\' => Some(skip_quoted(bytes, i + 1, b'\'')),
+        _ => {
+            // Find the end of one (possibly multi-byte) character.
+            let len = match bytes[i + 1] {
+                b if b < 0x80 => 1,
+                b if b >= 0xf0 => 4,
+                b if b >= 0xe0 => 3,
+                _ => 2,
+            };
+            (bytes.get(i + 1 + len) == Some(&b'\'')).then_some(i + 2 + len)
+        }
+    }
+}
+
+// Literals with a letter prefix: `b"..."`, `b'.'`, `c"..."`, `r"..."`,
+// `r#"..."#` and `br#"..."#`.
+fn prefixed_literal_end(bytes: &[u8], i: usize) -> Option<usize> {
+    let mut j = i;
+    if matches!(bytes[j], b'b' | b'c') {
+        j += 1;
+    }
+    if bytes.get(j) == Some(&b'r') {
+        j += 1;
+        let hashes_start = j;
+        while bytes.get(j) == Some(&b'#') {
+            j += 1;
+        }
+        let hashes = j - hashes_start;
+        if bytes.get(j) != Some(&b'"') {
+            return None;
+        }
+        j += 1;
+        while j < bytes.len() {
+            if bytes[j] == b'"' && bytes[j + 1..].iter().take(hashes).filter(|&&b| b == b'#').count() == hashes {
+                return Some(j + 1 + hashes);
+            }
+            j += 1;
+        }
+        return Some(bytes.len());
+    }
+    match bytes.get(j) {
+        Some(b'"') if j > i => Some(skip_quoted(bytes, j + 1, b'"')),
+        Some(b'\'') if bytes[i] == b'b' && j == i + 1 => Some(skip_quoted(bytes, j + 1, b'\'')),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex(src: &str) -> Vec<(TokenKind, &str)> {
+        tokenize(src).into_iter().map(|t| (t.kind.clone(), t.text(src))).collect()
+    }
+
+    fn texts(src: &str) -> Vec<&str> {
+        lex(src).into_iter().map(|(_, text)| text).collect()
+    }
+
+    #[test]
+    fn idents_and_punct() {
+        assert_eq!(lex("pub mod a;"), vec![
+            (TokenKind::Ident, "pub"),
+            (TokenKind::Ident, "mod"),
+            (TokenKind::Ident, "a"),
+            (TokenKind::Punct(';'), ";"),
+        ]);
+    }
+
+    #[test]
+    fn comments_are_skipped() {
+        assert_eq!(texts("a // mod b;\nc /* mod /* nested */ d; */ e"), vec!["a", "c", "e"]);
+    }
+
+    #[test]
+    fn strings_hide_braces() {
+        assert_eq!(texts(r#"f("{ \" }") }"#), vec!["f", "(", r#""{ \" }""#, ")", "}"]);
+        assert_eq!(texts(r###"r#"a "} b"# x"###), vec![r###"r#"a "} b"#"###, "x"]);
+        assert_eq!(texts(r#"b"}" br"}" c"}""#), vec![r#"b"}""#, r#"br"}""#, r#"c"}""#]);
+    }
+
+    #[test]
+    fn chars_and_lifetimes() {
+        assert_eq!(lex("'{' '\\'' &'a x"), vec![
+            (TokenKind::Literal, "'{'"),
+            (TokenKind::Literal, "'\\''"),
+            (TokenKind::Punct('&'), "&"),
+            (TokenKind::Lifetime, "'a"),
+            (TokenKind::Ident, "x"),
+        ]);
+        assert_eq!(texts("'é' b'x'"), vec!["'é'", "b'x'"]);
+    }
+
+    #[test]
+    fn raw_idents_and_numbers() {
+        assert_eq!(texts("r#type 1.5 0x1f 1..2"), vec!["r#type", "1.5", "0x1f", "1", ".", ".", "2"]);
+    }
+
+    #[test]
+    fn unterminated_input() {
+        assert_eq!(texts("a \"never closed"), vec!["a", "\"never closed"]);
+        assert_eq!(texts("a /* never closed"), vec!["a"]);
+    }
+}
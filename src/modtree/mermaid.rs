@@ -0,0 +1,92 @@
+// Prints a `ModuleTree` as a Mermaid flowchart in a fenced code block, ready
+// to paste into Markdown on GitHub and other sites that render Mermaid.
+//
+// As in the DOT output, solid arrows go from parent to child module and
+// dotted arrows show `use` imports between modules.
+
+use std::io::{self, Write};
+
+use super::{ModuleId, ModuleTree, Visibility};
+
+pub fn write(tree: &ModuleTree, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "```mermaid")?;
+    writeln!(out, "graph TD")?;
+
+    for id in tree.ids() {
+        let module = tree.module(id);
+        let mut lines = Vec::new();
+        lines.extend(module.attrs.iter().filter(|a| a.starts_with("cfg")).map(|a| format!("#[{a}]")));
+        lines.push(match &module.vis {
+            _ if id == tree.root() => "crate".to_string(),
+            Visibility::Private => format!("mod {}", module.name),
+            vis => format!("{vis} mod {}", module.name),
+        });
+        match module.file {
+            Some(_) if module.inline => {}
+            Some(file) => lines.push(tree.display_path(&tree.file(file).path)),
+            None => lines.push("(file not found)".to_string()),
+        }
+
+        let label = lines.iter().map(|l| escape(l)).collect::<Vec<_>>().join("<br/>");
+        // Inline modules get rounded corners.
+        let (open, close) = if module.inline { ("(", ")") } else { ("[", "]") };
+        writeln!(out, "    {}{open}\"{label}\"{close}", node(id))?;
+    }
+
+    for id in tree.ids() {
+        for &child in &tree.module(id).children {
+            writeln!(out, "    {} --> {}", node(id), node(child))?;
+        }
+    }
+
+    for (from, to) in tree.use_edges() {
+        writeln!(out, "    {} -.-> {}", node(from), node(to))?;
+    }
+
+    writeln!(out, "```")
+}
+
+fn node(id: ModuleId) -> String {
+    format!("m{}", id.0)
+}
+
+// Mermaid labels use `#code;` entities for characters with special meaning.
+fn escape(s: &str) -> String {
+    s.replace('#', "#35;")
+     .replace('"', "#quot;")
+     .replace('<', "#lt;")
+     .replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    #[test]
+    fn graph() {
+        let dir = TestDir::new("mermaid_graph", &[
+            ("src/lib.rs", "pub mod a; #[cfg(test)] mod tests { use super::a::f; }"),
+            ("src/a.rs", "pub fn f() {}"),
+        ]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+
+        let mut out = Vec::new();
+        write(&tree, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), r##"```mermaid
+graph TD
+    m0["crate<br/>src/lib.rs"]
+    m1["pub mod a<br/>src/a.rs"]
+    m2("#35;[cfg(test)]<br/>mod tests")
+    m0 --> m1
+    m0 --> m2
+    m2 -.-> m1
+```
+"##);
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(escape("#[path = \"<x>\"]"), "#35;[path = #quot;#lt;x#gt;#quot;]");
+    }
+}
@@ -0,0 +1,432 @@
+// Moving an item from one module to another, for the `refactor move-item`
+// command, to show how paths change when code moves.
+
+// The item's text, with the comments just above it, is cut from the module
+// it's in and added to the end of the other, before any `tests` module. In
+// its place goes a `use` of its new path, with the visibility the item had,
+// so `pub fn f` becomes `pub use crate::other::f;`. That keeps every old
+// path working: code in the old module still finds `f` in scope, and paths
+// from elsewhere like `crate::old::f` go through the re-export. Rewriting
+// them to the new path is a job for `use` and `pub use`, one at a time.
+//
+// The item may need a wider visibility in its new module, to still be
+// visible everywhere it was: a private `f` moved from `a::b` to `a::c` has
+// to be `pub(super)`, since it's still used in `a::b`. A re-export can't be
+// more visible than the item, as `effective.rs` explains, so this also
+// makes the `use` left behind valid.
+//
+// Paths in the item itself are resolved from its new module, so names it
+// used from the old one may be missing, and `self::` and `super::` paths
+// mean something else. Those are reported rather than changed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+
+use super::edit::{self, Change};
+use super::extract;
+use super::lexer::{self, TokenKind};
+use super::resolve::{self, Target};
+use super::{FileId, Import, Item, ItemKind, ModuleId, ModuleTree, Visibility};
+
+// The changes to move `item`, in `from`, to `to`, and notes on anything in
+// the item that may need fixing by hand.
+pub fn move_item(tree: &ModuleTree, from: ModuleId, item: &Item, to: ModuleId)
+                 -> Result<(Vec<Change>, Vec<String>), String> {
+    let (from_path, to_path) = (tree.path_string(from), tree.path_string(to));
+    if matches!(item.kind, ItemKind::Macro | ItemKind::ExternCrate) {
+        return Err(format!("`{}` is {}, which can't be moved with a `use` left behind",
+                           item.name, match item.kind {
+                               ItemKind::Macro => "a `macro_rules!` macro",
+                               _ => "an `extern crate`",
+                           }));
+    }
+    if from == to {
+        return Err(format!("`{}` is already in `{to_path}`", item.name));
+    }
+    if names(tree, to).contains(item.name.as_str()) {
+        return Err(format!("`{to_path}` already has something named `{}`", item.name));
+    }
+    let to_module: Import = to_path.parse()?;
+    if !resolve::resolve(tree, from, &to_module).accessible() {
+        return Err(format!("`{to_path}` isn't visible from `{from_path}`, so `{from_path}` \
+                            couldn't use the item from there"));
+    }
+
+    let span = item.span;
+    let text = &tree.file(span.file).text;
+    let begin = comments_above(text, span.start);
+    let start = edit::line_start(text, begin);
+    let cut = if text[start..begin].trim().is_empty() { &text[start..span.end] } else { &text[begin..span.end] };
+    let mut moved = edit::reindent(cut, edit::common_indent(cut), "");
+
+    let mut notes = Vec::new();
+    let vis = visibility(tree, &item.vis, from, to)?;
+    if tree.visible_within(&item.vis, to) != tree.visible_within(&vis, to) {
+        moved = set_visibility(&moved, &vis);
+        notes.push(format!("`{}` is now {} in `{to_path}`, so it's visible everywhere it was before.",
+                           item.name, match &vis {
+                               Visibility::Private => "private".to_string(),
+                               vis => format!("`{vis}`"),
+                           }));
+    }
+    notes.extend(check(tree, &moved, item, from, to));
+
+    let import = match &item.vis {
+        Visibility::Private => format!("use {to_path}::{};", item.name),
+        vis => format!("{vis} use {to_path}::{};", item.name),
+    };
+    let mut edits = vec![(span.file, begin, span.end, import)];
+    edits.push(insertion(tree, to, &moved)?);
+    Ok((changes(tree, edits), notes))
+}
+
+// The start of the `//` comments on the lines just above `pos`, which are
+// taken to be about the item there, or `pos` if there are none.
+pub(super) fn comments_above(text: &str, pos: usize) -> usize {
+    let mut begin = pos;
+    loop {
+        let start = edit::line_start(text, begin);
+        if start == 0 || !text[start..begin].trim().is_empty() {
+            return begin;
+        }
+        let above = edit::line_start(text, start - 1);
+        let line = &text[above..start - 1];
+        if !line.trim_start().starts_with("//") {
+            return begin;
+        }
+        begin = above + edit::indent(text, above).len();
+    }
+}
+
+// The visibility `item`, declared in `from` with `vis`, needs in `to`: the
+// narrowest that's still visible in `to` and everywhere it was before.
+fn visibility(tree: &ModuleTree, vis: &Visibility, from: ModuleId, to: ModuleId) -> Result<Visibility, String> {
+    if *vis == Visibility::Public {
+        return Ok(Visibility::Public);
+    }
+    let within = tree.visible_within(vis, from)
+        .ok_or(format!("`{vis}` isn't a valid visibility in `{}`", tree.path_string(from)))?;
+    let scope = std::iter::successors(Some(within), |&m| tree.module(m).parent)
+        .find(|&m| tree.is_within(to, m))
+        .expect("the crate root contains every module");
+    Ok(if scope == to {
+        Visibility::Private
+    } else if scope == tree.root() {
+        Visibility::Crate
+    } else if Some(scope) == tree.module(to).parent {
+        Visibility::Super
+    } else {
+        Visibility::In(tree.path(scope).iter().map(|s| s.to_string()).collect())
+    })
+}
+
+// `item`'s text with its visibility replaced by `vis`.
+pub(super) fn set_visibility(item: &str, vis: &Visibility) -> String {
+    let tokens = lexer::tokenize(item);
+    let mut i = 0;
+    // Skip the attributes.
+    while tokens.get(i).is_some_and(|t| t.is_punct('#')) && tokens.get(i + 1).is_some_and(|t| t.is_punct('[')) {
+        let mut depth = 0;
+        i += 1;
+        while let Some(token) = tokens.get(i) {
+            match token.kind {
+                TokenKind::Punct('[') => depth += 1,
+                TokenKind::Punct(']') => depth -= 1,
+                _ => {}
+            }
+            i += 1;
+            if depth == 0 {
+                break;
+            }
+        }
+    }
+    let Some(first) = tokens.get(i) else {
+        return item.to_string();
+    };
+    let mut rest = i;
+    if first.kind == TokenKind::Ident && first.text(item) == "pub" {
+        rest += 1;
+        if tokens.get(rest).is_some_and(|t| t.is_punct('(')) {
+            rest += tokens[rest..].iter().position(|t| t.is_punct(')')).map_or(0, |p| p + 1);
+        }
+    }
+    let end = tokens.get(rest).map_or(item.len(), |t| t.start);
+    let vis = match vis {
+        Visibility::Private => String::new(),
+        vis => format!("{vis} "),
+    };
+    format!("{}{vis}{}", &item[..first.start], &item[end..])
+}
+
+// The names declared or imported in `module`.
+fn names(tree: &ModuleTree, module: ModuleId) -> BTreeSet<&str> {
+    let m = tree.module(module);
+    m.items.iter().map(|i| i.name.as_str())
+        .chain(m.children.iter().map(|&c| tree.module(c).name.as_str()))
+        .chain(m.uses.iter().flat_map(|u| &u.imports).filter_map(Import::name))
+        .collect()
+}
+
+// Notes on the paths in `moved` that may mean something else in `to`: names
+// in scope in `from` but not in `to`, and paths starting `self` or `super`.
+fn check(tree: &ModuleTree, moved: &str, item: &Item, from: ModuleId, to: ModuleId) -> Vec<String> {
+    let (in_from, in_to) = (names(tree, from), names(tree, to));
+    let tokens = lexer::tokenize(moved);
+    let mut missing = BTreeSet::new();
+    let mut relative = false;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.kind != TokenKind::Ident {
+            continue;
+        }
+        let name = token.text(moved);
+        let after_path = i >= 2 && tokens[i - 1].is_punct(':') && tokens[i - 2].is_punct(':');
+        let after_dot = i >= 1 && tokens[i - 1].is_punct('.');
+        let before_path = tokens.get(i + 1).is_some_and(|t| t.is_punct(':'))
+            && tokens.get(i + 2).is_some_and(|t| t.is_punct(':'));
+        if name == "super" || name == "self" && before_path {
+            relative = true;
+        } else if !after_path && !after_dot && name != item.name && in_from.contains(name) && !in_to.contains(name) {
+            missing.insert(name);
+        }
+    }
+
+    let (from_path, to_path) = (tree.path_string(from), tree.path_string(to));
+    let mut notes: Vec<_> = missing.into_iter()
+        .map(|name| format!("`{name}` is in scope in `{from_path}` but not in `{to_path}`, \
+                             so the moved item may need a `use` for it."))
+        .collect();
+    if relative {
+        notes.push(format!("Paths in the moved item starting with `self` or `super` are now relative \
+                            to `{to_path}`."));
+    }
+    notes
+}
+
+// Where the moved item goes in `to`, as an edit: at the end of its body, but
+// before a `#[cfg(test)]` module, which conventionally comes last.
+fn insertion(tree: &ModuleTree, to: ModuleId, item: &str) -> Result<(FileId, usize, usize, String), String> {
+    let m = tree.module(to);
+    let path = tree.path_string(to);
+    let file = m.file.ok_or(format!("`{path}` has no file to add the item to"))?;
+    let text = &tree.file(file).text;
+
+    let tests = m.children.iter().filter_map(|&c| tree.module(c).decl.filter(|_| {
+        tree.module(c).attrs.iter().any(|a| a == "cfg(test)")
+    })).next();
+    if let Some(decl) = tests {
+        let pos = edit::line_start(text, comments_above(text, decl.start));
+        let item = edit::reindent(item, 0, edit::indent(text, decl.start));
+        return Ok((file, pos, pos, format!("{item}\n\n")));
+    }
+
+    match m.decl {
+        Some(decl) if m.inline => {
+            let indent = edit::indent(text, decl.start);
+            let item = edit::reindent(item, 0, &format!("{indent}    "));
+            let open = decl.start + extract::body_start(&text[decl.start..decl.end])
+                .ok_or(format!("can't find the body of `{path}`"))?;
+            let close = decl.end - 1;
+            if text[open + 1..close].trim().is_empty() {
+                Ok((file, open + 1, close, format!("\n{item}\n{indent}")))
+            } else {
+                Ok((file, text[..close].trim_end().len(), close, format!("\n\n{item}\n{indent}")))
+            }
+        }
+        _ => {
+            let end = text.trim_end().len();
+            let item = if end == 0 { format!("{item}\n") } else { format!("\n\n{item}\n") };
+            Ok((file, end, text.len(), item))
+        }
+    }
+}
+
+// Each file's new text, with `edits` made: byte ranges and what replaces them.
+fn changes(tree: &ModuleTree, edits: Vec<(FileId, usize, usize, String)>) -> Vec<Change> {
+    let mut by_file: BTreeMap<usize, Vec<(usize, usize, String)>> = BTreeMap::new();
+    for (file, start, end, replacement) in edits {
+        by_file.entry(file.0).or_default().push((start, end, replacement));
+    }
+    by_file.into_iter()
+        .map(|(file, mut edits)| {
+            edits.sort_by_key(|&(start, _, _)| std::cmp::Reverse(start));
+            let file = &tree.files[file];
+            let mut text = file.text.clone();
+            for (start, end, replacement) in edits {
+                text.replace_range(start..end, &replacement);
+            }
+            Change::Modify { path: file.path.clone(), text }
+        })
+        .collect()
+}
+
+pub fn write(tree: &ModuleTree, item_path: &str, to_path: &str, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let path: Import = item_path.parse().map_err(invalid)?;
+    let resolution = resolve::resolve(tree, tree.root(), &path);
+    if let Some(error) = &resolution.error {
+        return Err(invalid(format!("`{path}` doesn't resolve: {error}")));
+    }
+    let Some(Target::Item(from, item)) = resolution.target() else {
+        return Err(invalid(format!("`{path}` isn't an item in this crate")));
+    };
+    let to = tree.find(to_path).ok_or_else(|| invalid(format!("no module `{to_path}` in this crate")))?;
+
+    let (changes, notes) = move_item(tree, from, item, to).map_err(invalid)?;
+    edit::apply(tree, &changes, out)?;
+    for note in notes {
+        writeln!(out, "{note}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::find_crate_root;
+    use crate::modtree::test_dir::TestDir;
+    use std::fs;
+
+    fn load(dir: &TestDir) -> ModuleTree {
+        ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn between_files() {
+        let dir = TestDir::new("move_item_between_files", &[
+            ("src/lib.rs", "mod a;\nmod b;\n\npub struct Helper;\n"),
+            ("src/a.rs", "\
+use crate::Helper;
+
+// Adds one.
+fn inc(x: u32) -> u32 {
+    x + Helper::ONE
+}
+
+pub fn two() -> u32 {
+    inc(1)
+}
+"),
+            ("src/b.rs", "\
+#[cfg(test)]
+mod tests {}
+"),
+        ]);
+        let mut out = Vec::new();
+        write(&load(&dir), "crate::a::inc", "crate::b", &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+Updated src/a.rs
+Updated src/b.rs
+`inc` is now `pub(crate)` in `crate::b`, so it's visible everywhere it was before.
+`Helper` is in scope in `crate::a` but not in `crate::b`, so the moved item may need a `use` for it.
+");
+        assert_eq!(fs::read_to_string(dir.path().join("src/a.rs")).unwrap(), "\
+use crate::Helper;
+
+use crate::b::inc;
+
+pub fn two() -> u32 {
+    inc(1)
+}
+");
+        assert_eq!(fs::read_to_string(dir.path().join("src/b.rs")).unwrap(), "\
+// Adds one.
+pub(crate) fn inc(x: u32) -> u32 {
+    x + Helper::ONE
+}
+
+#[cfg(test)]
+mod tests {}
+");
+    }
+
+    #[test]
+    fn within_a_file() {
+        let dir = TestDir::new("move_item_within_a_file", &[("src/lib.rs", "\
+pub mod a {
+    pub mod b {
+        #[derive(Debug)]
+        pub(super) struct S(pub(crate) u8);
+    }
+    pub mod c {}
+    pub mod d {
+        pub fn f() {}
+    }
+}
+")]);
+        let tree = load(&dir);
+        write(&tree, "crate::a::b::S", "crate::a::c", &mut Vec::new()).unwrap();
+        write(&load(&dir), "crate::a::d::f", "crate::a", &mut Vec::new()).unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(), "\
+pub mod a {
+    pub mod b {
+        pub(super) use crate::a::c::S;
+    }
+    pub mod c {
+        #[derive(Debug)]
+        pub(super) struct S(pub(crate) u8);
+    }
+    pub mod d {
+        pub use crate::a::f;
+    }
+
+    pub fn f() {}
+}
+");
+    }
+
+    #[test]
+    fn visibilities() {
+        let dir = TestDir::new("move_item_visibilities", &[
+            ("src/lib.rs", "mod a { mod b { mod c {} } mod d {} }"),
+        ]);
+        let tree = load(&dir);
+        let [a, b, c, d] = ["crate::a", "crate::a::b", "crate::a::b::c", "crate::a::d"].map(|p| tree.find(p).unwrap());
+        let vis = |vis, from, to| visibility(&tree, &vis, from, to).unwrap();
+        assert_eq!(vis(Visibility::Private, b, a), Visibility::Private);
+        assert_eq!(vis(Visibility::Private, a, c), Visibility::In(vec!["crate".to_string(), "a".to_string()]));
+        assert_eq!(vis(Visibility::Private, b, d), Visibility::Super);
+        assert_eq!(vis(Visibility::Super, d, tree.root()), Visibility::Private);
+        assert_eq!(vis(Visibility::Private, tree.root(), d), Visibility::Crate);
+        assert_eq!(vis(Visibility::Public, c, d), Visibility::Public);
+
+        assert_eq!(set_visibility("/// Doc.\n#[inline]\nfn f() {}", &Visibility::Crate),
+                   "/// Doc.\n#[inline]\npub(crate) fn f() {}");
+        assert_eq!(set_visibility("pub(in crate::a) const X: u8 = 1;", &Visibility::Private), "const X: u8 = 1;");
+    }
+
+    #[test]
+    fn notes() {
+        let dir = TestDir::new("move_item_notes", &[("src/lib.rs", "\
+mod a {
+    use std::fmt::Display;
+    pub struct Thing;
+    pub fn show(x: impl Display) -> String { let _ = super::b::X; format!(\"{x}\") }
+}
+mod b { pub const X: u8 = 0; }
+")]);
+        let tree = load(&dir);
+        let mut out = Vec::new();
+        write(&tree, "crate::a::show", "crate::b", &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("`Display` is in scope in `crate::a` but not in `crate::b`"));
+        assert!(out.contains("starting with `self` or `super` are now relative to `crate::b`"));
+        assert!(!out.contains("`X`"));
+    }
+
+    #[test]
+    fn errors() {
+        let dir = TestDir::new("move_item_errors", &[("src/lib.rs", "\
+mod a { pub fn f() {} macro_rules! m { () => {} } }
+mod b { pub fn f() {} mod hidden {} }
+mod c {}
+")]);
+        let tree = load(&dir);
+        let move_item = |item, to| write(&tree, item, to, &mut Vec::new()).map_err(|e| e.to_string());
+        assert_eq!(move_item("crate::a::f", "crate::b"), Err("`crate::b` already has something named `f`".to_string()));
+        assert_eq!(move_item("crate::a::f", "crate::a"), Err("`f` is already in `crate::a`".to_string()));
+        assert!(move_item("crate::a::f", "crate::b::hidden").unwrap_err().contains("isn't visible from"));
+        assert!(move_item("crate::a::m", "crate::c").is_err());
+        assert!(move_item("crate::a", "crate::c").is_err());
+        assert!(move_item("crate::a::f", "crate::nope").is_err());
+    }
+}
@@ -0,0 +1,164 @@
+// Source files that no crate in the package compiles, for the
+// `analyze orphans` command.
+
+// A `.rs` file only becomes part of a crate when a `mod` declaration names
+// it, as `./lib.rs` explains: just having it in `src/` does nothing. So a
+// file whose `mod` line was forgotten, or deleted, is never compiled, and
+// its mistakes are never reported. This finds such files.
+
+// Every crate root Cargo would find in the package is loaded, so a file
+// used only by the binary in `src/main.rs`, or one in `src/bin/`, isn't an
+// orphan. Roots set with `path = "..."` in `Cargo.toml` are found too, by
+// looking for those lines rather than fully parsing it.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::ModuleTree;
+
+// The orphaned files in the same directory as `tree`'s crate root, or below
+// it, usually `src/`.
+pub fn orphans(tree: &ModuleTree) -> io::Result<Vec<PathBuf>> {
+    let root = &tree.file(tree.module(tree.root()).file.expect("the root file was read")).path;
+    let src = root.parent().unwrap_or(Path::new("."));
+
+    let mut reached: Vec<PathBuf> = tree.files.iter().map(|f| canonical(&f.path)).collect();
+    for other_root in crate_roots(&tree.base, src)? {
+        if let Ok(other) = ModuleTree::load(&other_root) {
+            reached.extend(other.files.iter().map(|f| canonical(&f.path)));
+        }
+    }
+
+    let mut orphans = Vec::new();
+    for file in rust_files(src)? {
+        if !reached.contains(&canonical(&file)) {
+            orphans.push(file);
+        }
+    }
+    orphans.sort();
+    Ok(orphans)
+}
+
+pub fn write(tree: &ModuleTree, out: &mut dyn Write) -> io::Result<()> {
+    let orphans = orphans(tree)?;
+    for file in &orphans {
+        writeln!(out, "{}", tree.display_path(file))?;
+    }
+
+    if !orphans.is_empty() {
+        writeln!(out)?;
+    }
+    match orphans.len() {
+        0 => writeln!(out, "Every .rs file is part of a crate."),
+        1 => writeln!(out, "1 .rs file isn't part of any crate: no `mod` declaration loads it."),
+        n => writeln!(out, "{n} .rs files aren't part of any crate: no `mod` declaration loads them."),
+    }
+}
+
+// The crate roots Cargo finds by convention, and those `Cargo.toml` names.
+fn crate_roots(package: &Path, src: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut roots = vec![src.join("lib.rs"), src.join("main.rs")];
+
+    // Each `src/bin/NAME.rs`, or `src/bin/NAME/main.rs` for a binary with
+    // child modules.
+    if let Ok(entries) = fs::read_dir(src.join("bin")) {
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                roots.push(path.join("main.rs"));
+            } else if path.extension().is_some_and(|e| e == "rs") {
+                roots.push(path);
+            }
+        }
+    }
+
+    if let Ok(manifest) = fs::read_to_string(package.join("Cargo.toml")) {
+        for line in manifest.lines() {
+            let value = line.trim().strip_prefix("path").map(|v| v.trim_start())
+                .and_then(|v| v.strip_prefix('=')).map(|v| v.trim());
+            if let Some(path) = value.and_then(|v| v.strip_prefix('"')?.strip_suffix('"')) {
+                if path.ends_with(".rs") {
+                    roots.push(package.join(path));
+                }
+            }
+        }
+    }
+
+    roots.retain(|root| root.is_file());
+    Ok(roots)
+}
+
+fn rust_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(rust_files(&path)?);
+        } else if path.extension().is_some_and(|e| e == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+// Paths are compared canonicalized, as `#[path]` can reach the same file
+// through `..`.
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::find_crate_root;
+    use crate::modtree::test_dir::TestDir;
+
+    fn output(dir: &TestDir) -> String {
+        let tree = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        let mut out = Vec::new();
+        write(&tree, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn finds_orphans() {
+        let dir = TestDir::new("orphans_finds_orphans", &[
+            ("src/lib.rs", "mod a; #[path = \"renamed.rs\"] mod b;"),
+            ("src/a.rs", ""),
+            ("src/renamed.rs", ""),
+            ("src/b.rs", ""),
+            ("src/a/forgotten.rs", ""),
+            ("src/notes.txt", ""),
+        ]);
+        assert_eq!(output(&dir), "\
+src/a/forgotten.rs
+src/b.rs
+
+2 .rs files aren't part of any crate: no `mod` declaration loads them.
+");
+    }
+
+    #[test]
+    fn other_crate_roots() {
+        let dir = TestDir::new("orphans_other_crate_roots", &[
+            ("Cargo.toml", "[[bin]]\nname = \"tool\"\npath = \"src/tools/entry.rs\"\n"),
+            ("src/lib.rs", ""),
+            ("src/main.rs", "mod cli;"),
+            ("src/cli.rs", ""),
+            ("src/bin/small.rs", ""),
+            ("src/bin/big/main.rs", "mod helper;"),
+            ("src/bin/big/helper.rs", ""),
+            ("src/tools/entry.rs", "mod summary;"),
+            ("src/tools/summary.rs", ""),
+        ]);
+        assert_eq!(output(&dir), "Every .rs file is part of a crate.\n");
+    }
+
+    #[test]
+    fn this_package() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs");
+        let tree = ModuleTree::load(&root).unwrap();
+        assert_eq!(orphans(&tree).unwrap(), Vec::<PathBuf>::new());
+    }
+}
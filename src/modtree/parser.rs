@@ -0,0 +1,470 @@
+// Parses the items in a source file just far enough to build a `ModuleTree`:
+// module declarations, `use` declarations, and the names, kinds and
+// visibilities of other items. Function bodies and the like are skipped.
+
+use super::lexer::{Token, TokenKind};
+use super::{Import, ItemKind, Visibility};
+
+// One item found at the top level of a file or inline module body.
+#[derive(Debug, PartialEq)]
+pub struct Entry {
+    // Text of each outer attribute, without the `#[` and `]`.
+    pub attrs: Vec<String>,
+    pub vis: Visibility,
+    pub kind: EntryKind,
+    // Byte offsets of the whole item, including attributes.
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EntryKind {
+    // `mod name;` has no body, `mod name { ... }` does.
+    Mod { name: String, body: Option<Vec<Entry>> },
+    Use(Vec<Import>),
+    Item { name: String, kind: ItemKind },
+    // Anything else: `impl` blocks, macro invocations, `extern` blocks and
+    // anything the parser doesn't understand.
+    Other,
+}
+
+pub fn parse(src: &str, tokens: &[Token]) -> Vec<Entry> {
+    Parser { src, tokens, pos: 0 }.items()
+}
+
+// Parse the tree of a single `use` declaration, without the `use` or `;`.
+// Any tokens after the tree are ignored.
+pub fn use_tree(src: &str, tokens: &[Token]) -> Vec<Import> {
+    let mut imports = Vec::new();
+    Parser { src, tokens, pos: 0 }.use_tree(Vec::new(), &mut imports);
+    imports
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self, ahead: usize) -> Option<&'a Token> {
+        self.tokens.get(self.pos + ahead)
+    }
+
+    fn text(&self, ahead: usize) -> &'a str {
+        self.peek(ahead).map_or("", |t| t.text(self.src))
+    }
+
+    fn is_ident(&self, ahead: usize, ident: &str) -> bool {
+        self.peek(ahead).is_some_and(|t| t.kind == TokenKind::Ident) && self.text(ahead) == ident
+    }
+
+    fn is_punct(&self, ahead: usize, c: char) -> bool {
+        self.peek(ahead).is_some_and(|t| t.is_punct(c))
+    }
+
+    fn ident(&mut self) -> Option<String> {
+        let t = self.peek(0).filter(|t| t.kind == TokenKind::Ident)?;
+        self.pos += 1;
+        Some(t.text(self.src).trim_start_matches("r#").to_string())
+    }
+
+    // The byte offset just past the previous token.
+    fn prev_end(&self) -> usize {
+        self.tokens[self.pos - 1].end
+    }
+
+    // With `pos` on an opening bracket, the index just past its matching close.
+    fn group_end(&self, open: usize) -> usize {
+        let mut depth = 0;
+        for (i, t) in self.tokens.iter().enumerate().skip(open) {
+            match t.kind {
+                TokenKind::Punct('(' | '[' | '{') => depth += 1,
+                TokenKind::Punct(')' | ']' | '}') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return i + 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.tokens.len()
+    }
+
+    fn is_open(&self, ahead: usize) -> bool {
+        self.is_punct(ahead, '(') || self.is_punct(ahead, '[') || self.is_punct(ahead, '{')
+    }
+
+    fn items(&mut self) -> Vec<Entry> {
+        let mut entries = Vec::new();
+        while self.pos < self.tokens.len() {
+            // A stray closing bracket, e.g. in a half-edited file.
+            if self.is_punct(0, '}') || self.is_punct(0, ')') || self.is_punct(0, ']') {
+                self.pos += 1;
+                continue;
+            }
+            if let Some(entry) = self.item() {
+                entries.push(entry);
+            }
+        }
+        entries
+    }
+
+    fn item(&mut self) -> Option<Entry> {
+        // Inner attributes like `#![allow(dead_code)]` apply to the enclosing module.
+        if self.is_punct(0, '#') && self.is_punct(1, '!') && self.is_punct(2, '[') {
+            self.pos = self.group_end(self.pos + 2);
+            return None;
+        }
+
+        let start = self.peek(0)?.start;
+
+        let mut attrs = Vec::new();
+        while self.is_punct(0, '#') && self.is_punct(1, '[') {
+            let end = self.group_end(self.pos + 1);
+            let inner = &self.tokens[self.pos + 2..end - 1];
+            attrs.push(match (inner.first(), inner.last()) {
+                (Some(first), Some(last)) => collapse_whitespace(&self.src[first.start..last.end]),
+                _ => String::new(),
+            });
+            self.pos = end;
+        }
+
+        let vis = self.visibility();
+
+        // Qualifiers that may come before the item keyword.
+        loop {
+            if self.is_ident(0, "extern") && self.peek(1).is_some_and(|t| t.kind == TokenKind::Literal) {
+                self.pos += 2;
+            } else if ["async", "unsafe", "default", "auto"].iter().any(|q| self.is_ident(0, q))
+                || self.is_ident(0, "extern") && self.is_ident(1, "fn")
+                || self.is_ident(0, "const") && ["fn", "unsafe", "async", "extern"].iter().any(|k| self.is_ident(1, k)) {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+
+        let keyword = self.text(0);
+        let kind = match keyword {
+            "mod" => {
+                self.pos += 1;
+                let name = self.ident().unwrap_or_default();
+                if self.is_punct(0, '{') {
+                    let end = self.group_end(self.pos);
+                    let body = parse(self.src, &self.tokens[self.pos + 1..end - 1]);
+                    self.pos = end;
+                    EntryKind::Mod { name, body: Some(body) }
+                } else {
+                    self.skip_past(';');
+                    EntryKind::Mod { name, body: None }
+                }
+            }
+            "use" => {
+                self.pos += 1;
+                let mut imports = Vec::new();
+                self.use_tree(Vec::new(), &mut imports);
+                self.skip_past(';');
+                EntryKind::Use(imports)
+            }
+            "macro_rules" if self.is_punct(1, '!') => {
+                self.pos += 2;
+                let name = self.ident().unwrap_or_default();
+                self.skip_item();
+                EntryKind::Item { name, kind: ItemKind::Macro }
+            }
+            "extern" if self.is_ident(1, "crate") => {
+                self.pos += 2;
+                let name = self.ident().unwrap_or_default();
+                let name = if self.is_ident(0, "as") {
+                    self.pos += 1;
+                    self.ident().unwrap_or(name)
+                } else {
+                    name
+                };
+                self.skip_past(';');
+                EntryKind::Item { name, kind: ItemKind::ExternCrate }
+            }
+            "const" | "static" => {
+                self.pos += 1;
+                if self.is_ident(0, "mut") {
+                    self.pos += 1;
+                }
+                let name = self.ident().unwrap_or_default();
+                self.skip_past(';');
+                let kind = if keyword == "const" { ItemKind::Const } else { ItemKind::Static };
+                EntryKind::Item { name, kind }
+            }
+            "type" => {
+                self.pos += 1;
+                let name = self.ident().unwrap_or_default();
+                self.skip_past(';');
+                EntryKind::Item { name, kind: ItemKind::Type }
+            }
+            // `union` is only a keyword when followed by a name.
+            "fn" | "struct" | "enum" | "trait" | "union"
+                if self.peek(1).is_some_and(|t| t.kind == TokenKind::Ident) =>
+            {
+                self.pos += 1;
+                let name = self.ident().unwrap_or_default();
+                self.skip_item();
+                let kind = match keyword {
+                    "fn" => ItemKind::Fn,
+                    "struct" => ItemKind::Struct,
+                    "enum" => ItemKind::Enum,
+                    "trait" => ItemKind::Trait,
+                    _ => ItemKind::Union,
+                };
+                EntryKind::Item { name, kind }
+            }
+            _ => {
+                self.skip_item();
+                EntryKind::Other
+            }
+        };
+
+        Some(Entry { attrs, vis, kind, start, end: self.prev_end() })
+    }
+
+    fn visibility(&mut self) -> Visibility {
+        if !self.is_ident(0, "pub") {
+            return Visibility::Private;
+        }
+        self.pos += 1;
+        if !self.is_punct(0, '(') {
+            return Visibility::Public;
+        }
+
+        let vis = match self.text(1) {
+            "crate" if self.is_punct(2, ')') => Visibility::Crate,
+            "super" if self.is_punct(2, ')') => Visibility::Super,
+            "self" if self.is_punct(2, ')') => Visibility::SelfOnly,
+            "in" => {
+                let end = self.group_end(self.pos);
+                let path = self.tokens[self.pos + 2..end - 1].iter()
+                    .filter(|t| t.kind == TokenKind::Ident)
+                    .map(|t| t.text(self.src).to_string())
+                    .collect();
+                self.pos = end;
+                return Visibility::In(path);
+            }
+            // Not a visibility restriction, e.g. the field list in `pub (u8, u8)`.
+            _ => return Visibility::Public,
+        };
+        self.pos += 3;
+        vis
+    }
+
+    // Parse a use tree like `a::{b, c::*}`, adding each import to `out`.
+    fn use_tree(&mut self, mut prefix: Vec<String>, out: &mut Vec<Import>) {
+        if self.is_punct(0, ':') && self.is_punct(1, ':') {
+            prefix.push("::".to_string());
+            self.pos += 2;
+        }
+
+        loop {
+            if self.is_punct(0, '*') {
+                self.pos += 1;
+                out.push(Import { path: prefix, alias: None, glob: true });
+                return;
+            }
+
+            if self.is_punct(0, '{') {
+                let end = self.group_end(self.pos);
+                self.pos += 1;
+                while self.pos < end - 1 {
+                    if self.is_punct(0, ',') {
+                        self.pos += 1;
+                        continue;
+                    }
+                    let before = self.pos;
+                    self.use_tree(prefix.clone(), out);
+                    if self.pos == before {
+                        // Skip a token we don't understand rather than looping forever.
+                        self.pos += 1;
+                    }
+                }
+                self.pos = end;
+                return;
+            }
+
+            let Some(segment) = self.ident() else { return };
+            if segment == "self" && !prefix.is_empty() {
+                // `a::{self}` imports `a` itself.
+            } else {
+                prefix.push(segment);
+            }
+
+            if self.is_punct(0, ':') && self.is_punct(1, ':') {
+                self.pos += 2;
+                continue;
+            }
+
+            let alias = if self.is_ident(0, "as") {
+                self.pos += 1;
+                if self.text(0) == "_" {
+                    self.pos += 1;
+                    Some("_".to_string())
+                } else {
+                    self.ident()
+                }
+            } else {
+                None
+            };
+            out.push(Import { path: prefix, alias, glob: false });
+            return;
+        }
+    }
+
+    fn skip_past(&mut self, c: char) {
+        while self.pos < self.tokens.len() {
+            if self.is_open(0) {
+                self.pos = self.group_end(self.pos);
+            } else {
+                self.pos += 1;
+                if self.tokens[self.pos - 1].is_punct(c) {
+                    return;
+                }
+            }
+        }
+    }
+
+    // Skip to the end of an item that ends with either a `{ ... }` block or a `;`.
+    fn skip_item(&mut self) {
+        // Always move forward, even over a token that can't start an item.
+        if !self.is_open(0) && !self.is_punct(0, ';') {
+            self.pos += 1;
+        }
+        while self.pos < self.tokens.len() {
+            if self.is_punct(0, '{') {
+                self.pos = self.group_end(self.pos);
+                // A macro invocation like `m! { ... };` may still have a trailing `;`.
+                return;
+            } else if self.is_open(0) {
+                self.pos = self.group_end(self.pos);
+            } else {
+                self.pos += 1;
+                if self.tokens[self.pos - 1].is_punct(';') {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::lexer::tokenize;
+
+    fn parse_str(src: &str) -> Vec<Entry> {
+        parse(src, &tokenize(src))
+    }
+
+    // A compact summary of the entries, for easy comparison.
+    fn summary(entries: &[Entry]) -> Vec<String> {
+        entries.iter().map(|e| {
+            let attrs: String = e.attrs.iter().map(|a| format!("#[{a}] ")).collect();
+            let vis = match &e.vis {
+                Visibility::Private => String::new(),
+                vis => format!("{vis} "),
+            };
+            let kind = match &e.kind {
+                EntryKind::Mod { name, body: None } => format!("mod {name};"),
+                EntryKind::Mod { name, body: Some(body) } =>
+                    format!("mod {name} {{ {} }}", summary(body).join(" ")),
+                EntryKind::Use(imports) => format!("use {}",
+                    imports.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(", ")),
+                EntryKind::Item { name, kind } => format!("{kind} {name}"),
+                EntryKind::Other => "other".to_string(),
+            };
+            format!("{attrs}{vis}{kind}")
+        }).collect()
+    }
+
+    #[test]
+    fn modules() {
+        let src = "
+            #![allow(dead_code)]
+            mod a;
+            #[cfg(unix)]
+            #[path = \"unix.rs\"]
+            pub mod platform;
+            mod inline { pub fn f() {} mod deeper; }
+        ";
+        assert_eq!(summary(&parse_str(src)), vec![
+            "mod a;",
+            "#[cfg(unix)] #[path = \"unix.rs\"] pub mod platform;",
+            "mod inline { pub fn f mod deeper; }",
+        ]);
+    }
+
+    #[test]
+    fn spans_include_attributes() {
+        let src = "fn f() {}\n#[cfg(test)]\nmod tests { }\n";
+        let entries = parse_str(src);
+        assert_eq!(&src[entries[1].start..entries[1].end], "#[cfg(test)]\nmod tests { }");
+    }
+
+    #[test]
+    fn visibilities() {
+        let src = "pub(crate) fn a() {} pub(super) fn b() {} pub(self) fn c() {}
+                   pub(in crate::x) fn d() {} pub fn e() {} fn f() {}";
+        assert_eq!(summary(&parse_str(src)), vec![
+            "pub(crate) fn a", "pub(super) fn b", "pub(self) fn c",
+            "pub(in crate::x) fn d", "pub fn e", "fn f",
+        ]);
+    }
+
+    #[test]
+    fn item_kinds() {
+        let src = r#"
+            pub const fn cf() -> u8 { 1 }
+            async unsafe fn af() {}
+            extern "C" fn ef() {}
+            struct Unit;
+            pub struct Tuple(pub u8, String);
+            struct Named<T> where T: Copy { t: T }
+            enum E { A { x: u8 }, B }
+            pub trait T { fn m(&self) {} }
+            unsafe auto trait AT {}
+            union U { a: u8 }
+            type Alias = u8;
+            const C: Named<u8> = Named { t: 1 };
+            static mut S: &str = "}";
+            macro_rules! m { () => {} }
+            extern crate alloc as a;
+            impl T for Unit { fn m(&self) {} }
+            m! { mod not_a_mod; }
+            m!(fn not_a_fn() {});
+            let union = 1;
+        "#;
+        assert_eq!(summary(&parse_str(src)), vec![
+            "pub fn cf", "fn af", "fn ef", "struct Unit", "pub struct Tuple",
+            "struct Named", "enum E", "pub trait T", "trait AT", "union U",
+            "type Alias", "const C", "static S", "macro_rules! m", "extern crate a",
+            "other", "other", "other", "other",
+        ]);
+    }
+
+    #[test]
+    fn use_trees() {
+        let src = "
+            use a::b;
+            pub use a::{self, b::c as d, e::*};
+            use ::std::fmt::{Debug, Display as _};
+            use super::*;
+        ";
+        assert_eq!(summary(&parse_str(src)), vec![
+            "use a::b",
+            "pub use a, a::b::c as d, a::e::*",
+            "use ::std::fmt::Debug, ::std::fmt::Display as _",
+            "use super::*",
+        ]);
+    }
+}
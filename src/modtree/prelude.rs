@@ -0,0 +1,231 @@
+// Proposing a prelude, for the `scaffold prelude` command: a `prelude`
+// module re-exporting the crate's most used public items, so code can
+// bring them all into scope with `use crate::prelude::*;`.
+
+// The standard library does this with `std::prelude`, which every module
+// imports implicitly, and many crates copy the idea for their own common
+// types and traits. Which items belong there is a judgement call, so this
+// counts how often each public item is named in the crate's own code, by
+// resolving every path the way `rename.rs` does, and proposes the most
+// named. Each is re-exported by its shortest public path, as listed by
+// `analyze api`. Uses from other crates can't be counted, so the counts are
+// only a starting point.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+
+use super::edit::{self, Change};
+use super::lexer::{self, TokenKind};
+use super::rename::{paths, scope_at};
+use super::resolve::{self, Target};
+use super::{api, scaffold, FileId, Import, ModuleTree};
+
+#[derive(Debug, PartialEq)]
+pub struct Candidate {
+    // The shortest public path to the item, e.g. `crate::Color`.
+    pub path: String,
+    pub kind: String,
+    pub references: usize,
+}
+
+// The crate's public items, most referenced first, leaving out those that
+// aren't referenced at all.
+pub fn candidates(tree: &ModuleTree) -> Vec<Candidate> {
+    // Each item, by the module declaring it and its name, with its shortest
+    // public path and kind.
+    let mut items: BTreeMap<(usize, String), (String, String)> = BTreeMap::new();
+    for entry in api::api(tree) {
+        if ["mod", "use", "macro_rules!", "extern crate"].contains(&entry.kind.as_str()) {
+            continue;
+        }
+        let declared = entry.reexport_of.as_deref().unwrap_or(&entry.path);
+        let Some((module, name)) = declared.rsplit_once("::") else { continue };
+        let Some(module) = tree.find(module) else { continue };
+        let shortest = |path: &str| (path.matches("::").count(), path.to_string());
+        let key = (module.0, name.to_string());
+        if items.get(&key).is_none_or(|(path, _)| shortest(&entry.path) < shortest(path)) {
+            items.insert(key, (entry.path, entry.kind));
+        }
+    }
+    let names: BTreeSet<&str> = items.keys().map(|(_, name)| name.as_str()).collect();
+
+    let mut references: BTreeMap<(usize, String), usize> = BTreeMap::new();
+    for (index, file) in tree.files.iter().enumerate() {
+        let file_id = FileId(index);
+        let text = &file.text;
+        let tokens = lexer::tokenize(text);
+        for (segments, at) in paths(tree, file_id, &tokens, true) {
+            if !segments.iter().any(|t| names.contains(t.text(text))) {
+                continue;
+            }
+            // An item's name where it's declared, like `fn area`, isn't a
+            // reference to it.
+            let first = tokens.partition_point(|t| t.start < segments[0].start);
+            let declaration = first > 0 && tokens[first - 1].kind == TokenKind::Ident
+                && ["fn", "struct", "enum", "union", "trait", "type", "const", "static"]
+                    .contains(&tokens[first - 1].text(text));
+            if declaration {
+                continue;
+            }
+            let import = Import {
+                path: segments.iter().map(|t| t.text(text).to_string()).collect(),
+                alias: None,
+                glob: false,
+            };
+            // Every step, as in `Color::Red` resolution stops inside `Color`.
+            for step in resolve::resolve(tree, scope_at(tree, file_id, at), &import).steps {
+                if let Target::Item(module, item) = step.target {
+                    *references.entry((module.0, item.name.clone())).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    let mut candidates: Vec<_> = items.into_iter()
+        .filter_map(|(key, (path, kind))| {
+            let references = references.get(&key).copied().unwrap_or(0);
+            (references > 0).then_some(Candidate { path, kind, references })
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.references.cmp(&a.references).then_with(|| a.path.cmp(&b.path)));
+    candidates
+}
+
+// The changes that add a `prelude` module re-exporting `chosen`.
+pub fn prelude(tree: &ModuleTree, chosen: &[Candidate]) -> Result<Vec<Change>, String> {
+    let root = tree.root();
+    if tree.child(root, "prelude").is_some() {
+        return Err("the crate already has a `prelude` module".to_string());
+    }
+    let path = tree.module(root).child_dir.join("prelude.rs");
+    if path.exists() {
+        return Err(format!("{} already exists", tree.display_path(&path)));
+    }
+    let mut lines: Vec<_> = chosen.iter().map(|c| format!("pub use {};", c.path)).collect();
+    lines.sort();
+    let text = format!("// The crate's most used items, to bring them all into scope with\n\
+                        // `use crate::prelude::*;`.\n\n{}\n", lines.join("\n"));
+    let root_file = tree.file(tree.module(root).file.ok_or("the crate root's file wasn't found")?);
+    Ok(vec![
+        Change::Create { path, text },
+        Change::Modify { path: root_file.path.clone(),
+                         text: scaffold::declare(tree, root, &root_file.text, "pub mod prelude;") },
+    ])
+}
+
+// Proposes a prelude of the `count` most referenced items, and unless
+// `dry_run` is set, writes it.
+pub fn write(tree: &ModuleTree, count: usize, dry_run: bool, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    if count == 0 {
+        return Err(invalid("a prelude needs at least one item".to_string()));
+    }
+    let candidates = candidates(tree);
+    if candidates.is_empty() {
+        return Err(invalid("none of the crate's public items are used in the crate".to_string()));
+    }
+    let chosen = &candidates[..count.min(candidates.len())];
+    let changes = prelude(tree, chosen).map_err(invalid)?;
+    if !dry_run {
+        edit::apply(tree, &changes, out)?;
+    }
+
+    writeln!(out, "{} `crate::prelude` with the {} most referenced of {} used public items:",
+             if dry_run { "Proposed" } else { "Added" }, chosen.len(), candidates.len())?;
+    let width = chosen.iter().map(|c| c.path.len()).max().unwrap_or(0) + "pub use ;".len();
+    for candidate in chosen {
+        let line = format!("pub use {};", candidate.path);
+        writeln!(out, "  {line:width$}  // {}, {}", candidate.kind, match candidate.references {
+            1 => "1 reference".to_string(),
+            n => format!("{n} references"),
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::find_crate_root;
+    use crate::modtree::test_dir::TestDir;
+    use std::fs;
+
+    fn files() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("src/lib.rs", "\
+pub mod shapes;
+mod hidden;
+
+pub use shapes::round::Circle;
+
+pub fn unused() {}
+
+pub fn describe(c: &Circle) -> String {
+    shapes::area(c).to_string()
+}
+"),
+            ("src/shapes.rs", "\
+pub mod round;
+
+use round::Circle;
+
+pub fn area(c: &Circle) -> f64 {
+    3.0 * c.r * c.r
+}
+"),
+            ("src/shapes/round.rs", "pub struct Circle { pub r: f64 }\n\npub fn unit() -> Circle { Circle { r: 1.0 } }\n"),
+            ("src/hidden.rs", "pub fn f() { crate::shapes::round::unit(); }\n"),
+        ]
+    }
+
+    fn load(dir: &TestDir) -> ModuleTree {
+        ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn counts() {
+        let dir = TestDir::new("prelude_counts", &files());
+        let candidate = |path: &str, kind: &str, references| {
+            Candidate { path: path.to_string(), kind: kind.to_string(), references }
+        };
+        assert_eq!(candidates(&load(&dir)), vec![
+            candidate("crate::Circle", "struct", 6),
+            candidate("crate::shapes::area", "fn", 1),
+            candidate("crate::shapes::round::unit", "fn", 1),
+        ]);
+    }
+
+    #[test]
+    fn dry_run_and_write() {
+        let dir = TestDir::new("prelude_dry_run_and_write", &files());
+        let mut out = Vec::new();
+        write(&load(&dir), 2, true, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+Proposed `crate::prelude` with the 2 most referenced of 3 used public items:
+  pub use crate::Circle;        // struct, 6 references
+  pub use crate::shapes::area;  // fn, 1 reference
+");
+        assert!(!dir.path().join("src/prelude.rs").exists());
+
+        let mut out = Vec::new();
+        write(&load(&dir), 10, false, &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().starts_with("\
+Created src/prelude.rs
+Updated src/lib.rs
+Added `crate::prelude` with the 3 most referenced of 3 used public items:
+"));
+        assert_eq!(fs::read_to_string(dir.path().join("src/prelude.rs")).unwrap(), "\
+// The crate's most used items, to bring them all into scope with
+// `use crate::prelude::*;`.
+
+pub use crate::Circle;
+pub use crate::shapes::area;
+pub use crate::shapes::round::unit;
+");
+        let tree = load(&dir);
+        assert_eq!(tree.problems, Vec::<String>::new());
+        assert!(tree.find("crate::prelude").is_some());
+        assert!(write(&tree, 10, true, &mut Vec::new()).is_err());
+        assert!(write(&tree, 0, true, &mut Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,297 @@
+// Generating a starter crate, for the `scaffold project` command: a small
+// task list library and a binary using it, with its modules organized in
+// one of three common ways.
+
+// Every layout has the same three pieces: a `Task` type, `parse` reading
+// tasks from text, and `summary` reporting on them. What changes is where
+// they live:
+//
+// - `flat` puts each in its own file directly in `src/`, all declared in
+//   `lib.rs`. Fine for a small crate, and the easiest to find your way in.
+// - `layered` groups them by technical role, in `domain`, `service` and
+//   `interface` modules, each using only those below it.
+// - `feature-sliced` groups them by what the user can do, one module per
+//   feature under `features`, with anything they share in `shared`.
+//
+// In each, `lib.rs` re-exports the three with `pub use`, so `main.rs` is
+// the same whichever layout the crate has: how a crate organizes its
+// modules needn't leak into its public API.
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use super::edit::is_identifier;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    Flat,
+    Layered,
+    FeatureSliced,
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Layout, String> {
+        match s {
+            "flat" => Ok(Layout::Flat),
+            "layered" => Ok(Layout::Layered),
+            "feature-sliced" => Ok(Layout::FeatureSliced),
+            _ => Err(format!("unknown layout '{s}', expected flat, layered or feature-sliced")),
+        }
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Layout::Flat => "flat",
+            Layout::Layered => "layered",
+            Layout::FeatureSliced => "feature-sliced",
+        })
+    }
+}
+
+const TASK: &str = "\
+#[derive(Debug, PartialEq)]
+pub struct Task {
+    pub title: String,
+    pub done: bool,
+}
+";
+
+// `parse`, using `Task` from `task_path`.
+fn parse(task_path: &str) -> String {
+    format!("\
+// Reading tasks from text, one per line, with done ones starting `x `.
+
+use {task_path};
+
+pub fn parse(text: &str) -> Vec<Task> {{
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.strip_prefix(\"x \") {{
+            Some(title) => Task {{ title: title.to_string(), done: true }},
+            None => Task {{ title: line.to_string(), done: false }},
+        }})
+        .collect()
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn done_and_not() {{
+        assert_eq!(parse(\"x write\\n\\nread\\n\"), vec![
+            Task {{ title: \"write\".to_string(), done: true }},
+            Task {{ title: \"read\".to_string(), done: false }},
+        ]);
+    }}
+}}
+")
+}
+
+// `summary`, using `Task` from `task_path`.
+fn summary(task_path: &str) -> String {
+    format!("\
+// Summing up a list of tasks.
+
+use {task_path};
+
+pub fn summary(tasks: &[Task]) -> String {{
+    let done = tasks.iter().filter(|task| task.done).count();
+    format!(\"{{done}} of {{}} tasks done\", tasks.len())
+}}
+")
+}
+
+// The files of a new crate `name` with `layout`, as paths relative to its
+// directory and their text.
+pub fn project(name: &str, layout: Layout) -> Result<Vec<(String, String)>, String> {
+    // Cargo names the library after the package, with `-` as `_`. That has
+    // to be an identifier, and in snake case, as `non_snake_case` warns
+    // about any other: lowercase, with words apart by a single `_`.
+    let lib = name.replace('-', "_");
+    let snake_case = name.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        && !lib.contains("__")
+        && !lib.ends_with('_');
+    if !snake_case || !is_identifier(&lib) {
+        return Err(format!("'{name}' isn't a good crate name, which needs lowercase letters and digits, \
+                            starting with a letter, in words apart by single `-` or `_`, and isn't a keyword"));
+    }
+    // Cargo won't make a package `test`, and the others would hide the
+    // crates Rust comes with.
+    if ["test", "std", "core", "alloc", "proc_macro"].contains(&lib.as_str()) {
+        return Err(format!("'{name}' is the name of a crate Rust comes with"));
+    }
+
+    let file = |path: &str, text: &str| (path.to_string(), text.to_string());
+    let mut files = vec![
+        file("Cargo.toml", &format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+                                     [dependencies]\n")),
+        file(".gitignore", "/target\n"),
+        file("src/main.rs", &format!("\
+fn main() {{
+    let tasks = {lib}::parse(\"x sketch the modules\\nfill them in\\n\");
+    println!(\"{{}}\", {lib}::summary(&tasks));
+}}
+")),
+    ];
+    match layout {
+        Layout::Flat => files.extend([
+            file("src/lib.rs", &format!("\
+// The `{name}` library. Each module is a file directly in `src/`, declared
+// here.
+
+mod parse;
+mod report;
+mod task;
+
+pub use parse::parse;
+pub use report::summary;
+pub use task::Task;
+")),
+            file("src/parse.rs", &parse("crate::task::Task")),
+            file("src/report.rs", &summary("crate::task::Task")),
+            file("src/task.rs", &format!("// A task on the list.\n\n{TASK}")),
+        ]),
+        Layout::Layered => files.extend([
+            file("src/lib.rs", &format!("\
+// The `{name}` library, in layers. Each uses only those below it:
+// `interface` on top, then `service`, then `domain`.
+
+pub mod domain;
+pub mod interface;
+pub mod service;
+
+pub use domain::task::Task;
+pub use interface::report::summary;
+pub use service::parse::parse;
+")),
+            file("src/domain.rs", "// The types the rest of the crate works with.\n\npub mod task;\n"),
+            file("src/domain/task.rs", &format!("// A task on the list.\n\n{TASK}")),
+            file("src/interface.rs", "// Presenting results to the user.\n\npub mod report;\n"),
+            file("src/interface/report.rs", &summary("crate::domain::task::Task")),
+            file("src/service.rs", "// Operations on the domain's types.\n\npub mod parse;\n"),
+            file("src/service/parse.rs", &parse("crate::domain::task::Task")),
+        ]),
+        Layout::FeatureSliced => files.extend([
+            file("src/lib.rs", &format!("\
+// The `{name}` library, a module per feature under `features`, with what
+// more than one feature needs in `shared`.
+
+pub mod features;
+pub mod shared;
+
+pub use features::import::parse;
+pub use features::summary::summary;
+pub use shared::task::Task;
+")),
+            file("src/features.rs", "// One module for each thing the crate can do.\n\n\
+                                     pub mod import;\npub mod summary;\n"),
+            file("src/features/import.rs", &parse("crate::shared::task::Task")),
+            file("src/features/summary.rs", &summary("crate::shared::task::Task")),
+            file("src/shared.rs", "// What more than one feature uses.\n\npub mod task;\n"),
+            file("src/shared/task.rs", &format!("// A task on the list, which every feature works with.\n\n{TASK}")),
+        ]),
+    }
+    Ok(files)
+}
+
+// Creates crate `name` with `layout` in a new directory `name` in `dir`.
+pub fn write(dir: &Path, name: &str, layout: Layout, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let files = project(name, layout).map_err(invalid)?;
+    let root = dir.join(name);
+    if root.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", root.display())));
+    }
+    for (path, text) in &files {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().expect("every file is in the crate's directory"))?;
+        fs::write(path, text)?;
+    }
+    for (path, _) in &files {
+        writeln!(out, "Created {name}/{path}")?;
+    }
+    writeln!(out, "Build and run it with `cd {name} && cargo run`.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+    use crate::modtree::ModuleTree;
+
+    // The modules of a new crate with `layout`, after checking they load
+    // without problems.
+    fn modules(test: &str, layout: Layout) -> Vec<String> {
+        let dir = TestDir::new(test, &[]);
+        write(dir.path(), "my-tasks", layout, &mut Vec::new()).unwrap();
+        let tree = ModuleTree::load(&dir.path().join("my-tasks/src/lib.rs")).unwrap();
+        assert_eq!(tree.problems, Vec::<String>::new());
+        tree.ids().map(|id| tree.path_string(id)).collect()
+    }
+
+    #[test]
+    fn layouts() {
+        assert_eq!(modules("project_flat", Layout::Flat),
+                   ["crate", "crate::parse", "crate::parse::tests", "crate::report", "crate::task"]);
+        assert_eq!(modules("project_layered", Layout::Layered),
+                   ["crate", "crate::domain", "crate::domain::task", "crate::interface", "crate::interface::report",
+                    "crate::service", "crate::service::parse", "crate::service::parse::tests"]);
+        assert_eq!(modules("project_feature_sliced", Layout::FeatureSliced),
+                   ["crate", "crate::features", "crate::features::import", "crate::features::import::tests",
+                    "crate::features::summary", "crate::shared", "crate::shared::task"]);
+    }
+
+    #[test]
+    fn files() {
+        let files = project("my-tasks", Layout::Flat).unwrap();
+        let main = &files.iter().find(|(path, _)| path == "src/main.rs").unwrap().1;
+        assert!(main.contains("my_tasks::summary(&tasks)"), "{main}");
+        assert_eq!(files[0].1, "[package]\nname = \"my-tasks\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+                                [dependencies]\n");
+
+        let dir = TestDir::new("project_files", &[]);
+        let mut out = Vec::new();
+        write(dir.path(), "t", Layout::Layered, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+Created t/Cargo.toml
+Created t/.gitignore
+Created t/src/main.rs
+Created t/src/lib.rs
+Created t/src/domain.rs
+Created t/src/domain/task.rs
+Created t/src/interface.rs
+Created t/src/interface/report.rs
+Created t/src/service.rs
+Created t/src/service/parse.rs
+Build and run it with `cd t && cargo run`.
+");
+        assert!(write(dir.path(), "t", Layout::Flat, &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn errors() {
+        assert!(project("1st", Layout::Flat).is_err());
+        assert!(project("my crate", Layout::Flat).is_err());
+        assert!(project("", Layout::Flat).is_err());
+        // These would build with warnings, or not at all.
+        for name in ["Bad-Name", "a--b", "tasks_", "fn", "self", "test", "proc-macro"] {
+            assert!(project(name, Layout::Flat).is_err(), "{name}");
+        }
+        assert_eq!(project("a b", Layout::Flat).unwrap_err(),
+                   "'a b' isn't a good crate name, which needs lowercase letters and digits, starting with a letter, \
+                    in words apart by single `-` or `_`, and isn't a keyword");
+        assert!(project("tasks2-v_3", Layout::Flat).is_ok());
+        assert_eq!("feature-sliced".parse(), Ok(Layout::FeatureSliced));
+        assert!("hexagonal".parse::<Layout>().is_err());
+    }
+}
@@ -0,0 +1,331 @@
+// Renaming a module, for the `refactor rename` command: its files, its
+// `mod` declaration, and every path in the crate that names it.
+
+// Finding those paths is name resolution, the same as in `./resolve.rs`:
+// a `round` in one module can be the module being renamed while a `round`
+// somewhere else is something else entirely. So each path that mentions
+// the old name is resolved from the module it's written in, and only the
+// segments that resolve to the renamed module change. That covers `use`
+// declarations, including groups like `use a::{b, c::d}`, and paths in code
+// and in `pub(in path)`. Comments, strings and doc tests aren't changed, nor
+// are paths inside macros that this crate's parser doesn't understand.
+//
+// A `use` of the module without `as` brings in the new name instead of the
+// old one, so paths through it change too, and the new name could clash
+// with something already in scope there.
+//
+// Paths from other crates can't be found, so renaming a `pub` module in a
+// library can still break its users.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use super::convert_style::{self, has_path_attribute};
+use super::edit::{self, Change};
+use super::lexer::{self, Token, TokenKind};
+use super::resolve::{self, Target};
+use super::scaffold::Style;
+use super::{FileId, Import, ModuleId, ModuleTree, Span};
+
+pub fn rename(tree: &ModuleTree, module: ModuleId, new_name: &str) -> Result<Vec<Change>, String> {
+    let m = tree.module(module);
+    let path = tree.path_string(module);
+    let (Some(parent), Some(decl)) = (m.parent, m.decl) else {
+        return Err("the crate root can't be renamed".to_string());
+    };
+    if !edit::is_identifier(new_name) {
+        return Err(format!("'{new_name}' isn't a valid module name"));
+    }
+    if tree.child(parent, new_name).is_some() {
+        return Err(format!("`{}` already has a module `{new_name}`", tree.path_string(parent)));
+    }
+
+    // The byte ranges to replace in each file, by index.
+    let mut edits: BTreeMap<usize, Vec<(usize, usize)>> = BTreeMap::new();
+    let name_in_decl = mod_name(&tree.file(decl.file).text, decl)
+        .ok_or(format!("can't find the name in `{path}`'s declaration"))?;
+    edits.entry(decl.file.0).or_default().push(name_in_decl);
+    for (file, start, end) in references(tree, module) {
+        edits.entry(file.0).or_default().push((start, end));
+    }
+
+    let mut changes = Vec::new();
+    for (file, mut ranges) in edits {
+        ranges.sort();
+        ranges.dedup();
+        let file = &tree.files[file];
+        let mut text = file.text.clone();
+        for &(start, end) in ranges.iter().rev() {
+            text.replace_range(start..end, new_name);
+        }
+        changes.push(Change::Modify { path: file.path.clone(), text });
+    }
+
+    // Files move last, so the edits above are to files where they are now.
+    // A module's children are in a directory named after it, whatever its
+    // style, except under `#[path]`, where they're next to its file.
+    let dir = &tree.module(parent).child_dir;
+    let mut moves = Vec::new();
+    if !has_path_attribute(m) {
+        if let Some(Style::Edition2018) = convert_style::style(tree, module) {
+            moves.push((dir.join(format!("{}.rs", m.name)), dir.join(format!("{new_name}.rs"))));
+        }
+        if dir.join(&m.name).is_dir() {
+            moves.push((dir.join(&m.name), dir.join(new_name)));
+        }
+    }
+    for (from, to) in moves {
+        if to.exists() {
+            return Err(format!("{} already exists", tree.display_path(&to)));
+        }
+        changes.push(Change::Move { from, to });
+    }
+    Ok(changes)
+}
+
+// The byte range of the name in a `mod name;` or `mod name { ... }`
+// declaration, after any attributes.
+fn mod_name(text: &str, decl: Span) -> Option<(usize, usize)> {
+    let tokens = lexer::tokenize(&text[decl.start..decl.end]);
+    let keyword = keyword_index(&text[decl.start..decl.end], &tokens, "mod")?;
+    let name = tokens.get(keyword + 1).filter(|t| t.kind == TokenKind::Ident)?;
+    Some((decl.start + name.start, decl.start + name.end))
+}
+
+// The index of the first `keyword` token outside attributes' brackets.
+fn keyword_index(src: &str, tokens: &[Token], keyword: &str) -> Option<usize> {
+    let mut depth = 0;
+    tokens.iter().position(|t| {
+        match t.kind {
+            TokenKind::Punct('[') => depth += 1,
+            TokenKind::Punct(']') => depth -= 1,
+            _ => {}
+        }
+        depth == 0 && t.kind == TokenKind::Ident && t.text(src) == keyword
+    })
+}
+
+// Every identifier in the crate that names `module` as a path segment, as
+// its file and byte range.
+fn references(tree: &ModuleTree, module: ModuleId) -> Vec<(FileId, usize, usize)> {
+    let name = &tree.module(module).name;
+    let mut found = Vec::new();
+
+    for (index, file) in tree.files.iter().enumerate() {
+        let file_id = FileId(index);
+        let text = &file.text;
+        let tokens = lexer::tokenize(text);
+        // A lone identifier outside a `use` is almost always a variable or
+        // an item rather than a module, so those aren't resolved.
+        for (segments, at) in paths(tree, file_id, &tokens, false) {
+            if !segments.iter().any(|t| t.text(text) == name) {
+                continue;
+            }
+            let import = Import {
+                path: segments.iter().map(|t| t.text(text).to_string()).collect(),
+                alias: None,
+                glob: false,
+            };
+            let resolution = resolve::resolve(tree, scope_at(tree, file_id, at), &import);
+            for (step, token) in resolution.steps.iter().zip(&segments) {
+                if step.target == Target::Module(module) && step.segment == *name {
+                    found.push((file_id, token.start, token.end));
+                }
+            }
+        }
+    }
+    found
+}
+
+// The paths in `file`, whose tokens are `tokens`, each as the tokens of its
+// segments and where it's written: those in `use` declarations, whose
+// groups mean a path's segments aren't all next to each other, then the
+// others, `a::b::c`. With `single`, that includes lone identifiers.
+pub(super) fn paths<'t>(tree: &ModuleTree, file: FileId, tokens: &'t [Token], single: bool)
+                        -> Vec<(Vec<&'t Token>, usize)> {
+    let text = &tree.file(file).text;
+    let mut paths = Vec::new();
+    let mut in_use = vec![false; tokens.len()];
+    for u in tree.ids().flat_map(|id| &tree.module(id).uses).filter(|u| u.span.file == file) {
+        let first = tokens.partition_point(|t| t.start < u.span.start);
+        let last = tokens.partition_point(|t| t.start < u.span.end);
+        let Some(keyword) = keyword_index(text, &tokens[first..last], "use") else { continue };
+        in_use[first + keyword..last].iter_mut().for_each(|t| *t = true);
+        use_paths(text, &tokens[first + keyword + 1..last], &mut paths);
+    }
+
+    // Paths not following a `::` themselves.
+    let mut i = 0;
+    while i < tokens.len() {
+        let starts_path = !in_use[i] && tokens[i].kind == TokenKind::Ident
+            && !(i >= 2 && is_path_separator(tokens, i - 2));
+        if !starts_path {
+            i += 1;
+            continue;
+        }
+        let mut segments = vec![&tokens[i]];
+        while is_path_separator(tokens, i + 1) && tokens.get(i + 3).is_some_and(|t| t.kind == TokenKind::Ident) {
+            i += 3;
+            segments.push(&tokens[i]);
+        }
+        if single || segments.len() > 1 {
+            paths.push((segments, tokens[i].start));
+        }
+        i += 1;
+    }
+    paths
+}
+
+// Whether `tokens[i]` and `tokens[i + 1]` are a `::`.
+fn is_path_separator(tokens: &[Token], i: usize) -> bool {
+    match (tokens.get(i), tokens.get(i + 1)) {
+        (Some(a), Some(b)) => a.is_punct(':') && b.is_punct(':') && a.end == b.start,
+        _ => false,
+    }
+}
+
+// The paths in a use tree, the tokens after `use`, each with the tokens of
+// its segments. `a::{b, c::d}` gives `a`, `a::b`, `a::c` and `a::c::d`, so
+// every segment is resolved with its prefix.
+fn use_paths<'t>(text: &str, tokens: &'t [Token], paths: &mut Vec<(Vec<&'t Token>, usize)>) {
+    let mut prefixes: Vec<Vec<&Token>> = Vec::new();
+    let mut current: Vec<&Token> = Vec::new();
+    let mut alias = false;
+    for token in tokens {
+        match token.kind {
+            TokenKind::Ident if alias => alias = false,
+            TokenKind::Ident if token.text(text) == "as" => alias = true,
+            // `self` in a group names the prefix, which is already a path.
+            TokenKind::Ident if token.text(text) == "self" && !current.is_empty() => {}
+            TokenKind::Ident => {
+                current.push(token);
+                paths.push((current.clone(), token.start));
+            }
+            TokenKind::Punct('{') => prefixes.push(current.clone()),
+            TokenKind::Punct(',') => current = prefixes.last().cloned().unwrap_or_default(),
+            TokenKind::Punct('}') => {
+                prefixes.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+// The module that code at `offset` in `file` is in: the innermost inline
+// module around it, or the file's own module.
+pub(super) fn scope_at(tree: &ModuleTree, file: FileId, offset: usize) -> ModuleId {
+    tree.ids()
+        .filter(|&id| tree.module(id).file == Some(file))
+        .filter_map(|id| match tree.module(id) {
+            m if !m.inline => Some((0, id)),
+            m => m.decl.filter(|d| d.start <= offset && offset < d.end).map(|d| (d.start + 1, id)),
+        })
+        .max_by_key(|&(start, _)| start)
+        .map_or(tree.root(), |(_, id)| id)
+}
+
+pub fn write(tree: &ModuleTree, module_path: &str, new_name: &str, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let module = tree.find(module_path)
+        .ok_or_else(|| invalid(format!("no module `{module_path}` in this crate")))?;
+    let changes = rename(tree, module, new_name).map_err(invalid)?;
+    edit::apply(tree, &changes, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::find_crate_root;
+    use crate::modtree::test_dir::TestDir;
+    use std::fs;
+
+    fn load(dir: &TestDir) -> ModuleTree {
+        ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn references() {
+        let dir = TestDir::new("rename_references", &[
+            ("src/lib.rs", "\
+pub mod shapes;
+use shapes::{round::{self, area}, square};
+use crate::shapes::round as r;
+
+mod other {
+    // A different `round`, which stays.
+    pub mod round { pub fn area() {} }
+    fn f() { round::area(); super::shapes::round::area(); }
+}
+
+// Imported as `round`, which is now `circle` too.
+pub fn g() -> f64 { round::area() + r::area() + shapes::round::area() + \"round::area\".len() as f64 }
+"),
+            ("src/shapes.rs", "#[cfg(test)]\npub mod round;\npub mod square;\nuse self::round::*;\n"),
+            ("src/shapes/round.rs", "pub(in crate::shapes::round) fn helper() {}\npub fn area() -> f64 { 1.0 }\n"),
+            // Not declared, but in the module's directory, so it moves too.
+            ("src/shapes/round/inner.rs", ""),
+            ("src/shapes/square.rs", "use super::round;\nfn f() { round::area(); }\n"),
+        ]);
+        let mut out = Vec::new();
+        write(&load(&dir), "crate::shapes::round", "circle", &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+Updated src/lib.rs
+Updated src/shapes.rs
+Updated src/shapes/round.rs
+Updated src/shapes/square.rs
+Moved src/shapes/round.rs to src/shapes/circle.rs
+Moved src/shapes/round to src/shapes/circle
+");
+
+        let read = |path: &str| fs::read_to_string(dir.path().join(path)).unwrap();
+        assert_eq!(read("src/lib.rs"), "\
+pub mod shapes;
+use shapes::{circle::{self, area}, square};
+use crate::shapes::circle as r;
+
+mod other {
+    // A different `round`, which stays.
+    pub mod round { pub fn area() {} }
+    fn f() { round::area(); super::shapes::circle::area(); }
+}
+
+// Imported as `round`, which is now `circle` too.
+pub fn g() -> f64 { circle::area() + r::area() + shapes::circle::area() + \"round::area\".len() as f64 }
+");
+        assert_eq!(read("src/shapes.rs"), "#[cfg(test)]\npub mod circle;\npub mod square;\nuse self::circle::*;\n");
+        assert_eq!(read("src/shapes/circle.rs"),
+                   "pub(in crate::shapes::circle) fn helper() {}\npub fn area() -> f64 { 1.0 }\n");
+        assert_eq!(read("src/shapes/square.rs"), "use super::circle;\nfn f() { circle::area(); }\n");
+        assert_eq!(load(&dir).problems, Vec::<String>::new());
+    }
+
+    #[test]
+    fn files() {
+        let dir = TestDir::new("rename_files", &[
+            ("src/lib.rs", "mod a; mod b { mod c; } #[path = \"elsewhere.rs\"] mod d;"),
+            ("src/a/mod.rs", "mod x;"),
+            ("src/a/x.rs", ""),
+            ("src/b/c.rs", ""),
+            ("src/elsewhere.rs", ""),
+        ]);
+        for (module, new_name) in [("crate::a", "a2"), ("crate::b", "b2"), ("crate::d", "d2")] {
+            write(&load(&dir), module, new_name, &mut Vec::new()).unwrap();
+        }
+        let tree = load(&dir);
+        assert_eq!(tree.problems, Vec::<String>::new());
+        assert_eq!(fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(),
+                   "mod a2; mod b2 { mod c; } #[path = \"elsewhere.rs\"] mod d2;");
+        let paths: Vec<_> = tree.files.iter().map(|f| tree.display_path(&f.path)).collect();
+        assert_eq!(paths, ["src/lib.rs", "src/a2/mod.rs", "src/a2/x.rs", "src/b2/c.rs", "src/elsewhere.rs"]);
+    }
+
+    #[test]
+    fn errors() {
+        let dir = TestDir::new("rename_errors", &[("src/lib.rs", "mod a {} mod b {}")]);
+        let tree = load(&dir);
+        let rename = |module, new_name| rename(&tree, tree.find(module).unwrap(), new_name);
+        assert_eq!(rename("crate", "x"), Err("the crate root can't be renamed".to_string()));
+        assert_eq!(rename("crate::a", "b"), Err("`crate` already has a module `b`".to_string()));
+        assert_eq!(rename("crate::a", "self"), Err("'self' isn't a valid module name".to_string()));
+    }
+}
@@ -0,0 +1,521 @@
+// Follows a path the way the compiler resolves it, for the `resolve`
+// command: segment by segment from a starting module, through child
+// modules, items and imports, checking at each step that the name is
+// visible from where the path is written.
+
+// This is the 2018 edition's rules, simplified:
+//
+// * A path starts at `crate`, `self`, `super`, or a name in scope in the
+//   module it's written in: a child module, an item, or an import. Any other
+//   name is taken to be an external crate, like `std`, which isn't followed.
+// * Each following segment is looked up inside the module reached so far,
+//   where only its child modules, items and imports count. `super` can
+//   follow `self` and other `super`s.
+// * A name is usable if its visibility allows it in the starting module,
+//   see `ModuleTree::visible_within`. Modules are checked like any item.
+//
+// Macros, generics, and anything inside an item, like enum variants or
+// associated functions, aren't modelled. `#[cfg]` isn't evaluated either, so
+// the first of several same-named modules is used.
+
+use std::io::{self, Write};
+
+use super::{Import, Item, ItemKind, ModuleId, ModuleTree, Visibility};
+
+// What a path, or a prefix of one, refers to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Target<'a> {
+    Module(ModuleId),
+    // An item and the module that declares it.
+    Item(ModuleId, &'a Item),
+    // A name outside this crate, such as `std`.
+    External,
+}
+
+// One segment of a path, and what it resolved to.
+#[derive(Debug)]
+pub struct Step<'a> {
+    pub segment: String,
+    pub target: Target<'a>,
+    // The module the name was found in, and its visibility there. The
+    // visibility of `crate`, `self` and `super` is always `pub`.
+    pub found_in: ModuleId,
+    pub vis: Visibility,
+    // `Some` if the name was brought into `found_in` by an import, with the
+    // import as written.
+    pub via: Option<String>,
+    pub accessible: bool,
+}
+
+#[derive(Debug)]
+pub struct Resolution<'a> {
+    pub steps: Vec<Step<'a>>,
+    // Why resolution stopped before the end of the path, if it did.
+    pub error: Option<String>,
+}
+
+impl<'a> Resolution<'a> {
+    // What the whole path refers to, if it resolved.
+    pub fn target(&self) -> Option<Target<'a>> {
+        match self.error {
+            None => self.steps.last().map(|step| step.target),
+            Some(_) => None,
+        }
+    }
+
+    pub fn accessible(&self) -> bool {
+        self.steps.iter().all(|step| step.accessible)
+    }
+}
+
+// Imports can refer to each other in a loop, so following them stops here.
+const MAX_DEPTH: usize = 16;
+
+pub fn resolve<'a>(tree: &'a ModuleTree, from: ModuleId, import: &Import) -> Resolution<'a> {
+    resolve_path(tree, from, from, &import.path, 0)
+}
+
+// Resolve `path` as written in module `at`, checking visibility from `from`.
+fn resolve_path<'a>(tree: &'a ModuleTree, at: ModuleId, from: ModuleId, path: &[String],
+                    depth: usize) -> Resolution<'a> {
+    let mut resolution = Resolution { steps: Vec::new(), error: None };
+    if depth > MAX_DEPTH {
+        resolution.error = Some("too many imports to follow, they may form a loop".to_string());
+        return resolution;
+    }
+
+    let mut current: Option<ModuleId> = None;
+    for (i, segment) in path.iter().enumerate() {
+        let keyword_step = |target| Step {
+            segment: segment.clone(),
+            target: Target::Module(target),
+            found_in: current.unwrap_or(at),
+            vis: Visibility::Public,
+            via: None,
+            accessible: true,
+        };
+        let only_keywords_so_far = resolution.steps.iter()
+            .all(|s| matches!(s.segment.as_str(), "self" | "super"));
+
+        let step = match segment.as_str() {
+            "::" if i == 0 => {
+                resolution.steps.push(Step {
+                    segment: path[1..].first().cloned().unwrap_or_default(),
+                    target: Target::External,
+                    found_in: at,
+                    vis: Visibility::Public,
+                    via: None,
+                    accessible: true,
+                });
+                return resolution;
+            }
+            "crate" if i == 0 => keyword_step(tree.root()),
+            "self" if i == 0 => keyword_step(at),
+            "super" if only_keywords_so_far => {
+                let module = current.unwrap_or(at);
+                match tree.module(module).parent {
+                    Some(parent) => keyword_step(parent),
+                    None => {
+                        resolution.error = Some("`super` at the crate root, which has no parent".to_string());
+                        return resolution;
+                    }
+                }
+            }
+            "crate" | "self" | "super" => {
+                resolution.error = Some(format!("`{segment}` can't come after other segments"));
+                return resolution;
+            }
+            name => {
+                let module = current.unwrap_or(at);
+                match lookup(tree, module, from, name, depth) {
+                    Ok(Some(step)) => step,
+                    Ok(None) if i == 0 => Step {
+                        segment: name.to_string(),
+                        target: Target::External,
+                        found_in: at,
+                        vis: Visibility::Public,
+                        via: None,
+                        accessible: true,
+                    },
+                    Ok(None) => {
+                        resolution.error = Some(format!(
+                            "no `{name}` in `{}`", tree.path_string(module)));
+                        return resolution;
+                    }
+                    Err(e) => {
+                        resolution.error = Some(e);
+                        return resolution;
+                    }
+                }
+            }
+        };
+
+        let target = step.target;
+        resolution.steps.push(step);
+        match target {
+            Target::Module(id) => current = Some(id),
+            Target::External => return resolution,
+            Target::Item(_, item) if i + 1 < path.len() => {
+                resolution.error = Some(format!(
+                    "`{}` is inside {} `{}`, which this doesn't look into",
+                    path[i + 1..].join("::"), item.kind, item.name));
+                return resolution;
+            }
+            Target::Item(..) => {}
+        }
+    }
+    resolution
+}
+
+// Find `name` in `module`: a child module, an item, or an import, in that
+// order. `Ok(None)` if there's nothing by that name.
+fn lookup<'a>(tree: &'a ModuleTree, module: ModuleId, from: ModuleId, name: &str,
+              depth: usize) -> Result<Option<Step<'a>>, String> {
+    let step = |target, vis: &Visibility, via| Step {
+        segment: name.to_string(),
+        target,
+        found_in: module,
+        vis: vis.clone(),
+        via,
+        accessible: accessible(tree, vis, module, from),
+    };
+
+    if let Some(child) = tree.child(module, name) {
+        return Ok(Some(step(Target::Module(child), &tree.module(child).vis, None)));
+    }
+
+    if let Some(item) = tree.module(module).items.iter().find(|item| item.name == name) {
+        let target = if item.kind == ItemKind::ExternCrate {
+            Target::External
+        } else {
+            Target::Item(module, item)
+        };
+        return Ok(Some(step(target, &item.vis, None)));
+    }
+
+    // Named imports first, then globs, as a named import shadows a glob.
+    let uses = &tree.module(module).uses;
+    let named = uses.iter().flat_map(|u| u.imports.iter().map(move |i| (u, i)))
+        .find(|(_, import)| import.name() == Some(name));
+    if let Some((declaration, import)) = named {
+        let inner = resolve_path(tree, module, module, &import.path, depth + 1);
+        if let Some(error) = inner.error {
+            return Err(format!("the import `use {import};` in `{}` doesn't resolve: {error}",
+                               tree.path_string(module)));
+        }
+        let target = inner.target().expect("no error, so there's a target");
+        let mut found = step(target, &declaration.vis, Some(import.to_string()));
+        found.accessible &= inner.accessible();
+        return Ok(Some(found));
+    }
+
+    for (declaration, import) in uses.iter().flat_map(|u| u.imports.iter().map(move |i| (u, i)))
+        .filter(|(_, import)| import.glob)
+    {
+        let inner = resolve_path(tree, module, module, &import.path, depth + 1);
+        let Some(Target::Module(glob_module)) = inner.target() else { continue };
+        // A glob only imports names visible where the `use` is written.
+        if let Ok(Some(mut found)) = lookup(tree, glob_module, module, name, depth + 1) {
+            if found.accessible {
+                found.found_in = module;
+                found.vis = declaration.vis.clone();
+                found.via = Some(import.to_string());
+                found.accessible = accessible(tree, &declaration.vis, module, from);
+                return Ok(Some(found));
+            }
+        }
+    }
+    Ok(None)
+}
+
+// Whether something declared in `owner` with visibility `vis` can be named in `from`.
+fn accessible(tree: &ModuleTree, vis: &Visibility, owner: ModuleId, from: ModuleId) -> bool {
+    tree.visible_within(vis, owner).is_some_and(|scope| tree.is_within(from, scope))
+}
+
+pub fn write(tree: &ModuleTree, from: ModuleId, import: &Import, out: &mut dyn Write) -> io::Result<()> {
+    let from_path = tree.path_string(from);
+    writeln!(out, "Resolving `{import}` from `{from_path}`:")?;
+
+    let resolution = resolve(tree, from, import);
+    let width = resolution.steps.iter().map(|s| s.segment.len()).max().unwrap_or(0) + 2;
+    for step in &resolution.steps {
+        let segment = format!("`{}`", step.segment);
+        write!(out, "  {segment:width$}  {}", describe(tree, step.target))?;
+        if let Some(via) = &step.via {
+            write!(out, ", imported into `{}` by `use {via};`", tree.path_string(step.found_in))?;
+        }
+        match (&step.vis, step.target) {
+            (_, Target::External) => {}
+            (vis, _) if step.segment == "crate" || step.segment == "self" || step.segment == "super"
+                         || *vis == Visibility::Public && step.via.is_none() && step.accessible => {}
+            (vis, _) => {
+                let vis = match vis {
+                    Visibility::Private => "private".to_string(),
+                    vis => vis.to_string(),
+                };
+                let scope = tree.visible_within(&step.vis, step.found_in)
+                    .map_or("nowhere".to_string(), |m| format!("inside `{}`", tree.path_string(m)));
+                write!(out, ", {vis} so visible {scope}")?;
+            }
+        }
+        writeln!(out)?;
+    }
+
+    match (&resolution.error, resolution.target()) {
+        (Some(error), _) => writeln!(out, "Doesn't resolve: {error}."),
+        (None, Some(Target::External)) => writeln!(out,
+            "Leaves this crate: `{}` would have to be an external crate, which isn't followed.",
+            resolution.steps.last().map_or("", |s| s.segment.as_str())),
+        (None, Some(target)) if resolution.accessible() => writeln!(out,
+            "Resolves to {}, and `{from_path}` can use it.", describe(tree, target)),
+        (None, Some(target)) => {
+            let blocked = resolution.steps.iter().find(|s| !s.accessible).expect("not accessible");
+            writeln!(out, "Resolves to {}, but `{from_path}` can't use it: `{}` isn't visible there, \
+                           error E0603.", describe(tree, target), blocked.segment)
+        }
+        (None, None) => writeln!(out, "The path is empty."),
+    }
+}
+
+fn describe(tree: &ModuleTree, target: Target) -> String {
+    match target {
+        Target::Module(id) => format!("module `{}`", tree.path_string(id)),
+        Target::Item(module, item) => {
+            let file = tree.file(item.span.file);
+            format!("{} `{}::{}` ({}:{})", item.kind, tree.path_string(module), item.name,
+                    tree.display_path(&file.path), tree.line(item.span))
+        }
+        Target::External => "an external crate".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+    use proptest::prelude::*;
+    use proptest::test_runner::{Config, RngAlgorithm, TestRng, TestRunner};
+    use std::cell::Cell;
+
+    const LIB_RS: &str = "
+        fn f() {}
+        pub mod a {
+            pub mod b { pub fn g() {} fn hidden() {} pub(super) fn for_a() {} pub(in crate::a) fn in_a() {} }
+            pub use self::b::g as reexported;
+            use self::b::hidden;
+            pub use crate::c::*;
+        }
+        mod c { pub fn h() {} pub enum E { V } }
+        mod d { use super::f; use std::io; }
+    ";
+
+    fn tree(name: &str) -> (TestDir, ModuleTree) {
+        let dir = TestDir::new(name, &[("src/lib.rs", LIB_RS)]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+        (dir, tree)
+    }
+
+    // The target's description, and whether it's accessible, or the error.
+    fn summary(tree: &ModuleTree, from: &str, path: &str) -> String {
+        let from = tree.find(from).unwrap();
+        let resolution = resolve(tree, from, &path.parse().unwrap());
+        match (&resolution.error, resolution.target()) {
+            (Some(error), _) => format!("error: {error}"),
+            (None, Some(Target::Item(module, item))) =>
+                format!("{} {}::{} {}", item.kind, tree.path_string(module), item.name,
+                        if resolution.accessible() { "ok" } else { "private" }),
+            (None, Some(Target::Module(id))) =>
+                format!("mod {} {}", tree.path_string(id),
+                        if resolution.accessible() { "ok" } else { "private" }),
+            (None, target) => format!("{target:?}"),
+        }
+    }
+
+    #[test]
+    fn paths() {
+        let (_dir, tree) = tree("resolve_paths");
+        let check = |from, path, expected: &str| assert_eq!(summary(&tree, from, path), expected,
+                                                             "{path} from {from}");
+
+        check("crate::c", "super::f", "fn crate::f ok");
+        check("crate", "a::b::g", "fn crate::a::b::g ok");
+        check("crate::c", "crate::a::b::g", "fn crate::a::b::g ok");
+        check("crate::a::b", "self::g", "fn crate::a::b::g ok");
+        check("crate::a::b", "super::super::f", "fn crate::f ok");
+        check("crate", "a", "mod crate::a ok");
+        check("crate::a::b", "crate", "mod crate ok");
+
+        // Through imports, including a glob.
+        check("crate", "a::reexported", "fn crate::a::b::g ok");
+        check("crate", "a::h", "fn crate::c::h ok");
+        check("crate::d", "f", "fn crate::f ok");
+
+        // Visibility.
+        check("crate", "a::b::hidden", "fn crate::a::b::hidden private");
+        check("crate::a", "b::for_a", "fn crate::a::b::for_a ok");
+        check("crate", "a::b::for_a", "fn crate::a::b::for_a private");
+        check("crate", "a::hidden", "fn crate::a::b::hidden private");
+        check("crate::a", "b::in_a", "fn crate::a::b::in_a ok");
+        check("crate::c", "crate::a::b::in_a", "fn crate::a::b::in_a private");
+
+        // Outside the crate, or not there at all.
+        check("crate", "std::io", "Some(External)");
+        check("crate::d", "io::Write", "Some(External)");
+        check("crate", "::std::io", "Some(External)");
+        check("crate", "a::nope", "error: no `nope` in `crate::a`");
+        check("crate", "super::f", "error: `super` at the crate root, which has no parent");
+        check("crate", "a::super::f", "error: `super` can't come after other segments");
+        check("crate", "c::E::V", "error: `V` is inside enum `E`, which this doesn't look into");
+    }
+
+    #[test]
+    fn import_loops() {
+        let dir = TestDir::new("resolve_import_loops", &[
+            ("src/lib.rs", "mod a { pub use crate::b::x; } mod b { pub use crate::a::x; }"),
+        ]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+        assert!(summary(&tree, "crate", "a::x").starts_with("error: "));
+    }
+
+    #[test]
+    fn output() {
+        let (_dir, tree) = tree("resolve_output");
+        let mut out = Vec::new();
+        write(&tree, tree.find("crate").unwrap(), &"a::hidden".parse().unwrap(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+Resolving `a::hidden` from `crate`:
+  `a`       module `crate::a`
+  `hidden`  fn `crate::a::b::hidden` (src/lib.rs:4), imported into `crate::a` by `use self::b::hidden;`, private so visible inside `crate::a`
+Resolves to fn `crate::a::b::hidden` (src/lib.rs:4), but `crate` can't use it: `hidden` isn't visible there, error E0603.
+");
+    }
+
+    // Property tests: invariants checked over many generated module trees,
+    // with `proptest`. The runner is seeded the same way every time, so
+    // failures repeat, and it shrinks a failing crate to a small one.
+
+    fn runner() -> TestRunner {
+        let config = Config { cases: 32, failure_persistence: None, ..Config::default() };
+        TestRunner::new_with_rng(config, TestRng::deterministic_rng(RngAlgorithm::ChaCha))
+    }
+
+    fn visibility() -> impl Strategy<Value = &'static str> {
+        prop::sample::select(&["", "pub ", "pub(crate) ", "pub(super) ", "pub(self) "][..])
+    }
+
+    // One to three functions, `f0` and so on.
+    fn functions() -> impl Strategy<Value = String> {
+        prop::collection::vec(visibility(), 1..=3).prop_map(|visibilities| {
+            visibilities.iter().enumerate().map(|(i, vis)| format!("{vis}fn f{i}() {{}} ")).collect()
+        })
+    }
+
+    // A random tree of inline modules, each with a function or two and some
+    // random visibilities, written as the source of a `lib.rs`.
+    fn random_crate() -> impl Strategy<Value = String> {
+        functions().prop_recursive(3, 30, 3, |inner| {
+            (functions(), prop::collection::vec((visibility(), inner), 0..3)).prop_map(|(mut src, modules)| {
+                for (i, (vis, body)) in modules.iter().enumerate() {
+                    src.push_str(&format!("{vis}mod m{i} {{ {body} }} "));
+                }
+                src
+            })
+        })
+    }
+
+    // Every item's path, from the crate root.
+    fn item_paths(tree: &ModuleTree) -> Vec<String> {
+        tree.ids().flat_map(|id| tree.module(id).items.iter()
+            .map(move |item| format!("{}::{}", tree.path_string(id), item.name)))
+            .collect()
+    }
+
+    #[test]
+    fn properties() {
+        let inaccessible = Cell::new(0);
+        runner().run(&random_crate(), |src| {
+            let dir = TestDir::new("resolve_properties", &[("src/lib.rs", &src)]);
+            let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+
+            for path in item_paths(&tree) {
+                let import: Import = path.parse().unwrap();
+                let from_root = resolve(&tree, tree.root(), &import);
+                let declared_in = tree.find(path.rsplit_once("::").unwrap().0).unwrap();
+
+                for from in tree.ids() {
+                    let resolution = resolve(&tree, from, &import);
+                    inaccessible.set(inaccessible.get() + usize::from(!resolution.accessible()));
+
+                    // `crate::x` means the same item from anywhere.
+                    prop_assert_eq!(resolution.target(), from_root.target(), "{} from {:?}", path, from);
+
+                    // Resolving is deterministic.
+                    let again = resolve(&tree, from, &import);
+                    prop_assert_eq!(again.target(), resolution.target());
+                    prop_assert_eq!(again.accessible(), resolution.accessible());
+
+                    // A module can always use its own items, whatever their
+                    // visibility, and `self::x` is the same as the full path.
+                    if from == declared_in {
+                        prop_assert!(resolution.accessible(), "{} from its own module", path);
+
+                        let name = path.rsplit("::").next().unwrap();
+                        let relative = resolve(&tree, from, &format!("self::{name}").parse().unwrap());
+                        prop_assert_eq!(relative.target(), resolution.target());
+                        prop_assert!(relative.accessible());
+                    }
+                }
+            }
+            Ok(())
+        }).unwrap();
+        // The generated trees do exercise visibility.
+        assert!(inaccessible.get() > 0);
+    }
+
+    // A random relative path of names the generated crates use, and some
+    // they don't, so that some paths don't resolve.
+    fn random_path() -> impl Strategy<Value = String> {
+        let name = prop::sample::select(&["m0", "m1", "m2", "f0", "f1", "nope"][..]);
+        prop::collection::vec(name, 1..=3).prop_map(|segments| segments.join("::"))
+    }
+
+    #[test]
+    fn random_paths() {
+        let (resolved, unresolved) = (Cell::new(0), Cell::new(0));
+        let cases = (random_crate(), prop::collection::vec(random_path(), 20));
+        runner().run(&cases, |(src, paths)| {
+            let dir = TestDir::new("resolve_random_paths", &[("src/lib.rs", &src)]);
+            let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+
+            for path in paths {
+                let from_root = resolve(&tree, tree.root(), &path.parse().unwrap());
+                let absolute: Import = format!("crate::{path}").parse().unwrap();
+                // A first name the root doesn't have is an external crate,
+                // where `crate::` would be an error.
+                let in_crate = from_root.steps.first().is_some_and(|step| step.target != Target::External);
+
+                for from in tree.ids() {
+                    let resolution = resolve(&tree, from, &absolute);
+                    let count = if resolution.target().is_some() { &resolved } else { &unresolved };
+                    count.set(count.get() + 1);
+
+                    // Resolving is deterministic, failures included.
+                    let again = resolve(&tree, from, &absolute);
+                    prop_assert_eq!(again.target(), resolution.target());
+                    prop_assert_eq!(again.accessible(), resolution.accessible());
+                    prop_assert_eq!(&again.error, &resolution.error);
+
+                    // `crate::x` from anywhere is `x` looked up in the root.
+                    if in_crate {
+                        prop_assert_eq!(resolution.target(), from_root.target(), "crate::{} from {:?}", path, from);
+                    }
+                }
+            }
+            Ok(())
+        }).unwrap();
+        // The paths are a mix of both.
+        let (resolved, unresolved) = (resolved.get(), unresolved.get());
+        assert!(resolved > 0 && unresolved > 0, "{resolved} resolved, {unresolved} didn't");
+    }
+}
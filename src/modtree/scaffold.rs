@@ -0,0 +1,228 @@
+// Creating a new module, for the `scaffold new-module` command: the file,
+// in either layout style, and the `mod` declaration in its parent.
+
+// A new module needs both halves, as `../lib.rs` explains: a file on its
+// own is ignored, and `mod name;` on its own is error E0583. Where the
+// file goes depends on the parent. A child of `src/a.rs` or of
+// `src/a/mod.rs` goes in `src/a/`, and it's `name.rs` in the 2018 style,
+// or `name/mod.rs` in the original style.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use super::edit::{self, Change};
+use super::{ModuleId, ModuleTree};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    // `name/mod.rs`.
+    ModRs,
+    // `name.rs`, with any children in `name/`.
+    Edition2018,
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Style, String> {
+        match s {
+            "mod-rs" => Ok(Style::ModRs),
+            "2018" => Ok(Style::Edition2018),
+            _ => Err(format!("unknown style '{s}', expected mod-rs or 2018")),
+        }
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Style::ModRs => "mod-rs",
+            Style::Edition2018 => "2018",
+        })
+    }
+}
+
+const TESTS: &str = "
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {}
+}
+";
+
+// The changes that add a module `name` to `parent`, with a `tests` module
+// stub in it if `tests` is set.
+pub fn new_module(tree: &ModuleTree, parent: ModuleId, name: &str, style: Style, tests: bool)
+                  -> Result<Vec<Change>, String> {
+    let parent_path = tree.path_string(parent);
+    if !edit::is_identifier(name) {
+        return Err(format!("'{name}' isn't a valid module name"));
+    }
+    if tree.child(parent, name).is_some() {
+        return Err(format!("`{parent_path}` already has a module `{name}`"));
+    }
+    let module = tree.module(parent);
+    let file = module.file.ok_or(format!("the file for `{parent_path}` wasn't found"))?;
+    let file_rs = module.child_dir.join(format!("{name}.rs"));
+    let mod_rs = module.child_dir.join(name).join("mod.rs");
+    if let Some(existing) = [&file_rs, &mod_rs].into_iter().find(|p| p.exists()) {
+        return Err(format!("{} already exists, so only `mod {name};` is needed",
+                           tree.display_path(existing)));
+    }
+
+    let mut text = format!("// The `{parent_path}::{name}` module.\n");
+    if tests {
+        text.push_str(TESTS);
+    }
+    let path = match style {
+        Style::ModRs => mod_rs,
+        Style::Edition2018 => file_rs,
+    };
+
+    let parent_file = tree.file(file);
+    Ok(vec![
+        Change::Create { path, text },
+        Change::Modify { path: parent_file.path.clone(),
+                         text: declare(tree, parent, &parent_file.text, &format!("mod {name};")) },
+    ])
+}
+
+// `text`, the source of `parent`'s file, with `declaration` added to
+// `parent`: after its other `mod` declarations, but before a `tests` module.
+pub(super) fn declare(tree: &ModuleTree, parent: ModuleId, text: &str, declaration: &str) -> String {
+    let module = tree.module(parent);
+    let children = module.children.iter().map(|&id| tree.module(id));
+    let (tests, others): (Vec<_>, Vec<_>) = children.partition(|m| m.attrs.iter().any(|a| a == "cfg(test)"));
+
+    let (pos, insert) = if let Some(last) = others.last().and_then(|m| m.decl) {
+        (last.end, format!("\n{}{declaration}", edit::indent(text, last.start)))
+    } else if let Some(first) = tests.first().and_then(|m| m.decl) {
+        (edit::line_start(text, first.start), format!("{}{declaration}\n\n", edit::indent(text, first.start)))
+    } else if let Some(decl) = module.decl.filter(|_| module.inline) {
+        // Just inside the closing brace of `mod parent { ... }`.
+        let close = decl.end - 1;
+        let indent = edit::indent(text, decl.start);
+        let line_start = edit::line_start(text, close);
+        if text[line_start..close].trim().is_empty() {
+            (line_start, format!("{indent}    {declaration}\n"))
+        } else {
+            (close, format!("\n{indent}    {declaration}\n{indent}"))
+        }
+    } else if text.trim().is_empty() {
+        (0, format!("{declaration}\n"))
+    } else if text.ends_with('\n') {
+        (text.len(), format!("\n{declaration}\n"))
+    } else {
+        (text.len(), format!("\n\n{declaration}\n"))
+    };
+
+    let mut text = text.to_string();
+    text.insert_str(pos, &insert);
+    text
+}
+
+pub fn write(tree: &ModuleTree, parent_path: &str, name: &str, style: Style, tests: bool,
+             out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let parent = tree.find(parent_path)
+        .ok_or_else(|| invalid(format!("no module `{parent_path}` in this crate")))?;
+    let changes = new_module(tree, parent, name, style, tests).map_err(invalid)?;
+    edit::apply(tree, &changes, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::find_crate_root;
+    use crate::modtree::test_dir::TestDir;
+    use std::fs;
+
+    // Adds `name` to `parent` in a crate made of `files`, and returns the
+    // new crate's files.
+    fn scaffold(test: &str, files: &[(&str, &str)], parent: &str, name: &str, style: Style,
+                tests: bool) -> Vec<(String, String)> {
+        let dir = TestDir::new(test, files);
+        let tree = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        write(&tree, parent, name, style, tests, &mut Vec::new()).unwrap();
+
+        // The new module should load without problems.
+        let tree = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        assert_eq!(tree.problems, Vec::<String>::new());
+        assert!(tree.find(&format!("{parent}::{name}")).is_some());
+        tree.files.iter()
+            .map(|f| (tree.display_path(&f.path), fs::read_to_string(&f.path).unwrap()))
+            .collect()
+    }
+
+    fn file(path: &str, text: &str) -> (String, String) {
+        (path.to_string(), text.to_string())
+    }
+
+    #[test]
+    fn edition_2018() {
+        assert_eq!(scaffold("scaffold_2018", &[
+            ("src/lib.rs", "// The root.\nmod a;\n\n#[cfg(test)]\nmod tests {}\n"),
+            ("src/a.rs", "pub fn f() {}"),
+        ], "crate::a", "b", Style::Edition2018, false), vec![
+            file("src/lib.rs", "// The root.\nmod a;\n\n#[cfg(test)]\nmod tests {}\n"),
+            file("src/a.rs", "pub fn f() {}\n\nmod b;\n"),
+            file("src/a/b.rs", "// The `crate::a::b` module.\n"),
+        ]);
+    }
+
+    #[test]
+    fn mod_rs() {
+        assert_eq!(scaffold("scaffold_mod_rs", &[
+            ("src/lib.rs", "mod a;\n\n#[cfg(test)]\nmod tests {}\n"),
+            ("src/a.rs", ""),
+        ], "crate", "b", Style::ModRs, true), vec![
+            file("src/lib.rs", "mod a;\nmod b;\n\n#[cfg(test)]\nmod tests {}\n"),
+            file("src/a.rs", ""),
+            file("src/b/mod.rs", &format!("// The `crate::b` module.\n{TESTS}")),
+        ]);
+    }
+
+    #[test]
+    fn before_tests() {
+        assert_eq!(scaffold("scaffold_before_tests", &[
+            ("src/main.rs", "fn main() {}\n\n    #[cfg(test)]\n    mod tests {}\n"),
+        ], "crate", "new", Style::Edition2018, false)[0],
+            file("src/main.rs", "fn main() {}\n\n    mod new;\n\n    #[cfg(test)]\n    mod tests {}\n"));
+    }
+
+    #[test]
+    fn inline_parent() {
+        assert_eq!(scaffold("scaffold_inline_parent", &[
+            ("src/lib.rs", "mod a {}\nmod b {\n    fn f() {}\n}\n"),
+        ], "crate::a", "c", Style::Edition2018, false), vec![
+            file("src/lib.rs", "mod a {\n    mod c;\n}\nmod b {\n    fn f() {}\n}\n"),
+            file("src/a/c.rs", "// The `crate::a::c` module.\n"),
+        ]);
+        assert_eq!(scaffold("scaffold_inline_parent_lines", &[
+            ("src/lib.rs", "mod b {\n    fn f() {}\n}\n"),
+        ], "crate::b", "c", Style::ModRs, false)[0],
+            file("src/lib.rs", "mod b {\n    fn f() {}\n    mod c;\n}\n"));
+    }
+
+    #[test]
+    fn errors() {
+        let dir = TestDir::new("scaffold_errors", &[("src/lib.rs", "mod a; mod gone;"), ("src/a.rs", ""),
+                                                    ("src/b.rs", "")]);
+        let tree = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        let new_module = |parent, name| {
+            new_module(&tree, tree.find(parent).unwrap(), name, Style::Edition2018, false)
+        };
+        assert_eq!(new_module("crate", "a"), Err("`crate` already has a module `a`".to_string()));
+        assert_eq!(new_module("crate", "b"),
+                   Err("src/b.rs already exists, so only `mod b;` is needed".to_string()));
+        assert_eq!(new_module("crate", "fn"), Err("'fn' isn't a valid module name".to_string()));
+        assert_eq!(new_module("crate::gone", "c"),
+                   Err("the file for `crate::gone` wasn't found".to_string()));
+        assert!(write(&tree, "crate::nope", "c", Style::ModRs, false, &mut Vec::new()).is_err());
+        assert_eq!("mod-rs".parse(), Ok(Style::ModRs));
+        assert!("2015".parse::<Style>().is_err());
+    }
+}
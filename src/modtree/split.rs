@@ -0,0 +1,648 @@
+// Splitting a big module into children by kind of item, for the
+// `refactor split` command: its structs, enums and type aliases go to
+// `types.rs`, its functions to `fns.rs`, and its traits to `traits.rs`.
+
+// Nothing outside the module notices, which is the point. In the module's
+// place each moved item gets a re-export with the visibility it had, so
+// `pub struct Circle` becomes `pub use types::Circle;` and every path to it
+// still works, the way `move_item.rs` leaves a `use` behind. The new
+// modules themselves are private, as only their items are needed outside.
+// Each starts with `use super::*;`, which brings in everything the old
+// module could name, including its imports and the items re-exported from
+// the other new modules.
+//
+// Privacy is by module, so the visibilities inside the moved items have to
+// change to mean the same thing one level down. Private, or `pub(self)`,
+// meant visible in the old module, which from a child is `pub(super)`, and
+// `pub(super)` becomes `pub(in <grandparent>)`. That applies to the items,
+// to their fields, and to the methods in `impl` blocks, which move along
+// with their type so they can still see its private fields.
+//
+// Constants, statics, macros, `use`s and child modules stay where they are.
+// Paths written with `self::` or `super::` inside the moved items are
+// reported, as they now start from the new module.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use super::edit::{self, Change};
+use super::extract;
+use super::lexer::{self, Token, TokenKind};
+use super::move_item::{comments_above, set_visibility};
+use super::{ItemKind, ModuleId, ModuleTree, Visibility};
+
+// The new modules, in the order they're declared.
+const GROUPS: [&str; 3] = ["types", "fns", "traits"];
+
+fn group(kind: ItemKind) -> Option<&'static str> {
+    match kind {
+        ItemKind::Struct | ItemKind::Enum | ItemKind::Union | ItemKind::Type => Some("types"),
+        ItemKind::Fn => Some("fns"),
+        ItemKind::Trait => Some("traits"),
+        _ => None,
+    }
+}
+
+// Something to move: the bytes to cut from the module's file, and the text
+// to put in the new module, with its visibilities changed.
+struct Piece {
+    group: &'static str,
+    cut: (usize, usize),
+    text: String,
+    // The item's name and visibility, to re-export it. `None` for an `impl`.
+    export: Option<(String, Visibility)>,
+}
+
+pub struct Split {
+    pub changes: Vec<Change>,
+    pub notes: Vec<String>,
+}
+
+pub fn split(tree: &ModuleTree, module: ModuleId) -> Result<Split, String> {
+    let m = tree.module(module);
+    let path = tree.path_string(module);
+    let file = m.file.ok_or(format!("the file for `{path}` wasn't found"))?;
+    let text = &tree.file(file).text;
+    let (region_start, region_end) = match m.decl {
+        Some(decl) if m.inline => {
+            let open = extract::body_start(&text[decl.start..decl.end])
+                .ok_or(format!("can't find the body of `{path}`"))?;
+            (decl.start + open + 1, decl.end - 1)
+        }
+        _ => (0, text.len()),
+    };
+
+    let mut pieces = Vec::new();
+    let mut widened = false;
+    for item in &m.items {
+        let Some(group) = group(item.kind) else { continue };
+        let begin = comments_above(text, item.span.start);
+        let mut moved = unindented(text, begin, item.span.end);
+        if matches!(item.kind, ItemKind::Struct | ItemKind::Union) {
+            let (fields, changed) = widen_members(tree, module, &moved, false)?;
+            moved = fields;
+            widened |= changed;
+        }
+        if let Some(vis) = in_child(tree, &item.vis, module)? {
+            moved = set_visibility(&moved, &vis);
+        }
+        pieces.push(Piece { group, cut: cut(text, begin, item.span.end), text: moved,
+                            export: Some((item.name.clone(), item.vis.clone())) });
+    }
+    if pieces.is_empty() {
+        return Err(format!("`{path}` has no types, functions or traits to split out"));
+    }
+
+    // `impl` blocks go with their type.
+    let types: Vec<_> = pieces.iter()
+        .filter(|p| p.group == "types")
+        .filter_map(|p| p.export.as_ref().map(|(name, _)| name.clone()))
+        .collect();
+    for (start, end, self_type, inherent) in impls(text, region_start, region_end) {
+        if !types.contains(&self_type) {
+            continue;
+        }
+        let begin = comments_above(text, start);
+        let mut moved = unindented(text, begin, end);
+        if inherent {
+            let (members, changed) = widen_members(tree, module, &moved, true)?;
+            moved = members;
+            widened |= changed;
+        }
+        pieces.push(Piece { group: "types", cut: cut(text, begin, end), text: moved, export: None });
+    }
+    pieces.sort_by_key(|p| p.cut);
+
+    let mut notes = Vec::new();
+    if widened {
+        notes.push(format!("Private fields and methods are now `pub(super)`, so `{path}` can still use them."));
+    }
+    let relative = pieces.iter().any(|p| {
+        let tokens = lexer::tokenize(&p.text);
+        tokens.iter().enumerate().any(|(i, t)| {
+            t.kind == TokenKind::Ident && ["self", "super"].contains(&t.text(&p.text))
+                && tokens.get(i + 1).is_some_and(|n| n.is_punct(':'))
+        })
+    });
+    if relative {
+        notes.push("Paths in the moved items starting with `self` or `super` are now relative to the new \
+                    modules.".to_string());
+    }
+
+    // The new files, and the declarations and re-exports that replace the
+    // items in the module.
+    let mut changes = Vec::new();
+    let mut declarations = Vec::new();
+    let mut reexports = Vec::new();
+    for group in GROUPS {
+        let texts: Vec<_> = pieces.iter().filter(|p| p.group == group).map(|p| p.text.as_str()).collect();
+        if texts.is_empty() {
+            continue;
+        }
+        if tree.child(module, group).is_some() || m.items.iter().any(|i| i.name == group) {
+            return Err(format!("`{path}` already has something named `{group}`"));
+        }
+        let new_path: PathBuf = m.child_dir.join(format!("{group}.rs"));
+        if new_path.exists() {
+            return Err(format!("{} already exists", tree.display_path(&new_path)));
+        }
+        changes.push(Change::Create { path: new_path, text: format!("use super::*;\n\n{}\n", texts.join("\n\n")) });
+        declarations.push(format!("mod {group};"));
+
+        let mut by_vis: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+        for (name, vis) in pieces.iter().filter(|p| p.group == group).filter_map(|p| p.export.as_ref()) {
+            by_vis.entry(vis.to_string()).or_default().push(name);
+        }
+        for (vis, mut names) in by_vis {
+            names.sort();
+            let names = match names.as_slice() {
+                [name] => name.to_string(),
+                names => format!("{{{}}}", names.join(", ")),
+            };
+            let vis = if vis.is_empty() { vis } else { format!("{vis} ") };
+            reexports.push(format!("{vis}use {group}::{names};"));
+        }
+    }
+
+    // The block goes where the first piece was. It's followed by a blank
+    // line, unless the pieces ran to the end of the module.
+    let first = pieces[0].cut.0;
+    let mut run_end = pieces[0].cut.1;
+    for piece in &pieces[1..] {
+        if piece.cut.0 == run_end {
+            run_end = piece.cut.1;
+        }
+    }
+    let at_end = run_end >= region_end || text[run_end..].trim_start().starts_with('}');
+    let indent = edit::indent(text, first);
+    let block = declarations.iter().chain([&String::new()]).chain(&reexports)
+        .map(|line| if line.is_empty() { String::new() } else { format!("{indent}{line}") })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut new_text = text.clone();
+    for (i, piece) in pieces.iter().enumerate().rev() {
+        let (start, end) = piece.cut;
+        let replacement = match (i, at_end) {
+            (0, true) => format!("{block}\n"),
+            (0, false) => format!("{block}\n\n"),
+            _ => String::new(),
+        };
+        new_text.replace_range(start..end, &replacement);
+    }
+    if !m.inline {
+        new_text = format!("{}\n", new_text.trim_end());
+    }
+    changes.push(Change::Modify { path: tree.file(file).path.clone(), text: new_text });
+    Ok(Split { changes, notes })
+}
+
+// The text from `begin` to `end`, with the indentation of `begin`'s line
+// taken off every line.
+fn unindented(text: &str, begin: usize, end: usize) -> String {
+    let start = edit::line_start(text, begin);
+    let cut = if text[start..begin].trim().is_empty() { &text[start..end] } else { &text[begin..end] };
+    edit::reindent(cut, edit::common_indent(cut), "")
+}
+
+// The bytes to remove for something from `begin` to `end`: from the start
+// of its line, when that's only indentation, through the blank lines after.
+fn cut(text: &str, begin: usize, end: usize) -> (usize, usize) {
+    let line_start = edit::line_start(text, begin);
+    let start = if text[line_start..begin].trim().is_empty() { line_start } else { begin };
+    let mut stop = end;
+    loop {
+        let line_end = text[stop..].find('\n').map_or(text.len(), |i| stop + i + 1);
+        if !text[stop..line_end].trim().is_empty() || stop == line_end {
+            return (start, stop);
+        }
+        stop = line_end;
+    }
+}
+
+// What `vis`, on something declared in `module`, must become to mean the
+// same in a new child of `module`, or `None` if it already does.
+fn in_child(tree: &ModuleTree, vis: &Visibility, module: ModuleId) -> Result<Option<Visibility>, String> {
+    match vis {
+        Visibility::Public | Visibility::Crate => return Ok(None),
+        Visibility::In(path) if path.first().is_some_and(|s| s == "crate") => return Ok(None),
+        _ => {}
+    }
+    let scope = tree.visible_within(vis, module)
+        .ok_or(format!("`{vis}` isn't a valid visibility in `{}`", tree.path_string(module)))?;
+    Ok(Some(if scope == tree.root() {
+        Visibility::Crate
+    } else if scope == module {
+        Visibility::Super
+    } else {
+        Visibility::In(tree.path(scope).iter().map(|s| s.to_string()).collect())
+    }))
+}
+
+// `item`, a struct or an inherent `impl`, with the visibility of each of its
+// fields or associated items changed as `in_child` says, and whether any
+// changed.
+fn widen_members(tree: &ModuleTree, module: ModuleId, item: &str, is_impl: bool) -> Result<(String, bool), String> {
+    let tokens = lexer::tokenize(item);
+    let mut edits = Vec::new();
+    for (start, end, vis) in members(item, &tokens, is_impl) {
+        if let Some(new) = in_child(tree, &vis, module)? {
+            let new = match new {
+                Visibility::Private => String::new(),
+                new if start == end => format!("{new} "),
+                new => new.to_string(),
+            };
+            edits.push((start, end, new));
+        }
+    }
+    let mut text = item.to_string();
+    for (start, end, new) in edits.iter().rev() {
+        text.replace_range(*start..*end, new);
+    }
+    Ok((text, !edits.is_empty()))
+}
+
+// The visibility of each field of a struct, or each associated item of an
+// `impl`, with its byte range, which is empty if there's no `pub`.
+fn members(text: &str, tokens: &[Token], is_impl: bool) -> Vec<(usize, usize, Visibility)> {
+    // The body: an `impl`'s braces, or a struct's braces or parentheses,
+    // after the keyword and any generics.
+    let mut nesting = 0;
+    let Some(keyword) = tokens.iter().position(|t| {
+        match t.kind {
+            TokenKind::Punct('[') => nesting += 1,
+            TokenKind::Punct(']') => nesting -= 1,
+            _ => {}
+        }
+        nesting == 0 && ["struct", "union", "impl"].contains(&t.text(text))
+    }) else {
+        return Vec::new();
+    };
+    let mut angle = 0;
+    let Some(open) = tokens[keyword..].iter().position(|t| {
+        match t.kind {
+            TokenKind::Punct('<') => angle += 1,
+            TokenKind::Punct('>') => angle -= 1,
+            _ => {}
+        }
+        angle == 0 && (t.is_punct('{') || !is_impl && t.is_punct('('))
+    }).map(|p| keyword + p) else {
+        return Vec::new();
+    };
+
+    let mut found = Vec::new();
+    let mut depth = 0;
+    let mut at_start = true;
+    let mut k = open + 1;
+    while let Some(token) = tokens.get(k) {
+        if depth == 0 && at_start && !matches!(token.kind, TokenKind::Punct(')' | '}')) {
+            at_start = false;
+            while tokens.get(k).is_some_and(|t| t.is_punct('#')) && tokens.get(k + 1).is_some_and(|t| t.is_punct('[')) {
+                k += tokens[k..].iter().position(|t| t.is_punct(']')).map_or(tokens.len(), |p| p + 1);
+            }
+            let (vis, end) = visibility(text, tokens, k);
+            let keyword = tokens.get(end).map_or("", |t| t.text(text));
+            let is_member = !is_impl || ["fn", "const", "type", "async", "unsafe", "extern"].contains(&keyword);
+            if let (Some(first), true) = (tokens.get(k), is_member) {
+                let range_end = if end > k { tokens[end - 1].end } else { first.start };
+                found.push((first.start, range_end, vis));
+            }
+            k = end;
+            continue;
+        }
+        match token.kind {
+            TokenKind::Punct('(' | '[' | '{') => depth += 1,
+            TokenKind::Punct('<') if !is_impl => depth += 1,
+            TokenKind::Punct('>') if !(is_impl || k > 0 && tokens[k - 1].is_punct('-')) => depth -= 1,
+            TokenKind::Punct(')' | ']' | '}') => {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+                at_start |= depth == 0 && is_impl && token.is_punct('}');
+            }
+            TokenKind::Punct(',') if depth == 0 && !is_impl => at_start = true,
+            TokenKind::Punct(';') if depth == 0 && is_impl => at_start = true,
+            _ => {}
+        }
+        k += 1;
+    }
+    found
+}
+
+// The visibility starting at `tokens[i]`, and the index of the token after it.
+fn visibility(text: &str, tokens: &[Token], i: usize) -> (Visibility, usize) {
+    let ident = |j: usize| tokens.get(j).filter(|t| t.kind == TokenKind::Ident).map(|t| t.text(text));
+    if ident(i) != Some("pub") {
+        return (Visibility::Private, i);
+    }
+    if !tokens.get(i + 1).is_some_and(|t| t.is_punct('(')) {
+        return (Visibility::Public, i + 1);
+    }
+    let close = tokens[i..].iter().position(|t| t.is_punct(')')).map_or(tokens.len(), |p| i + p + 1);
+    let vis = match ident(i + 2) {
+        Some("crate") => Visibility::Crate,
+        Some("super") => Visibility::Super,
+        Some("self") => Visibility::SelfOnly,
+        Some("in") => Visibility::In((i + 3..close).filter_map(ident).map(str::to_string).collect()),
+        // A tuple struct field's type, as in `pub (u8, u8)`.
+        _ => return (Visibility::Public, i + 1),
+    };
+    (vis, close)
+}
+
+// The `impl` blocks directly inside the bytes from `start` to `end` of
+// `text`, each as its byte range, the name of the type it's for, and
+// whether it's inherent rather than a trait's.
+fn impls(text: &str, start: usize, end: usize) -> Vec<(usize, usize, String, bool)> {
+    let tokens: Vec<_> = lexer::tokenize(text).into_iter().filter(|t| t.start >= start && t.end <= end).collect();
+    let mut found = Vec::new();
+    let mut depth = 0;
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = &tokens[i];
+        match token.kind {
+            TokenKind::Punct('{') => depth += 1,
+            TokenKind::Punct('}') => depth -= 1,
+            _ => {}
+        }
+        // `impl` starts an item only where an item can start, not in
+        // `fn f(x: impl Trait)`.
+        let item_start = i == 0 || matches!(tokens[i - 1].kind, TokenKind::Punct('}' | ';' | ']'))
+            || tokens[i - 1].text(text) == "unsafe";
+        if depth != 0 || token.kind != TokenKind::Ident || token.text(text) != "impl" || !item_start {
+            i += 1;
+            continue;
+        }
+
+        let mut first = i;
+        if first > 0 && tokens[first - 1].text(text) == "unsafe" {
+            first -= 1;
+        }
+        // Attributes, backwards from their closing `]`.
+        while first >= 2 && tokens[first - 1].is_punct(']') {
+            let mut nesting = 0;
+            let Some(open) = (0..first).rev().find(|&j| {
+                match tokens[j].kind {
+                    TokenKind::Punct(']') => nesting += 1,
+                    TokenKind::Punct('[') => nesting -= 1,
+                    _ => {}
+                }
+                nesting == 0
+            }) else { break };
+            if open == 0 || !tokens[open - 1].is_punct('#') {
+                break;
+            }
+            first = open - 1;
+        }
+
+        // The type: the last name outside `<>` before the body, after `for`
+        // if there is one.
+        let mut angle = 0;
+        let mut self_type = String::new();
+        let mut inherent = true;
+        let mut j = i + 1;
+        while let Some(t) = tokens.get(j) {
+            match t.kind {
+                TokenKind::Punct('<') => angle += 1,
+                TokenKind::Punct('>') if !tokens[j - 1].is_punct('-') => angle -= 1,
+                TokenKind::Punct('{') if angle == 0 => break,
+                TokenKind::Ident if angle == 0 => match t.text(text) {
+                    "for" => {
+                        inherent = false;
+                        self_type.clear();
+                    }
+                    "where" => {
+                        while tokens.get(j).is_some_and(|t| !t.is_punct('{')) {
+                            j += 1;
+                        }
+                        break;
+                    }
+                    "dyn" | "mut" => {}
+                    name => self_type = name.to_string(),
+                },
+                _ => {}
+            }
+            j += 1;
+        }
+        // The matching `}`.
+        let mut nesting = 0;
+        let close = (j..tokens.len()).find(|&k| {
+            match tokens[k].kind {
+                TokenKind::Punct('{') => nesting += 1,
+                TokenKind::Punct('}') => nesting -= 1,
+                _ => {}
+            }
+            nesting == 0
+        });
+        let Some(close) = close else { break };
+        found.push((tokens[first].start, tokens[close].end, self_type, inherent));
+        i = close + 1;
+    }
+    found
+}
+
+pub fn write(tree: &ModuleTree, module_path: &str, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let module = tree.find(module_path)
+        .ok_or_else(|| invalid(format!("no module `{module_path}` in this crate")))?;
+    let split = split(tree, module).map_err(invalid)?;
+    edit::apply(tree, &split.changes, out)?;
+    for note in split.notes {
+        writeln!(out, "{note}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::resolve::{self, Target};
+    use crate::modtree::find_crate_root;
+    use crate::modtree::test_dir::TestDir;
+    use std::fs;
+
+    fn load(dir: &TestDir) -> ModuleTree {
+        ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap()
+    }
+
+    const LIB_RS: &str = "\
+pub mod shapes {
+    use std::fmt;
+
+    pub const SIDES: u32 = 0;
+
+    // A circle.
+    #[derive(Debug)]
+    pub struct Circle {
+        r: f64,
+        pub(crate) name: Vec<(u8, u8)>,
+    }
+
+    impl Circle {
+        fn new(r: f64) -> Circle {
+            Circle { r, name: Vec::new() }
+        }
+
+        pub fn area(&self) -> f64 {
+            3.0 * self.r * self.r
+        }
+    }
+
+    impl fmt::Display for Circle {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, \"{}\", self.r)
+        }
+    }
+
+    struct Point(pub(super) u8, u8);
+
+    pub trait Shape {
+        fn area(&self) -> f64;
+    }
+
+    pub fn unit() -> Circle {
+        Circle::new(1.0)
+    }
+
+    fn origin() -> Point {
+        Point(0, 0)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+    }
+}
+";
+
+    #[test]
+    fn by_kind() {
+        let dir = TestDir::new("split_by_kind", &[("src/lib.rs", LIB_RS)]);
+        let before = load(&dir);
+        let mut out = Vec::new();
+        write(&before, "crate::shapes", &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+Created src/shapes/types.rs
+Created src/shapes/fns.rs
+Created src/shapes/traits.rs
+Updated src/lib.rs
+Private fields and methods are now `pub(super)`, so `crate::shapes` can still use them.
+");
+        assert_eq!(fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(), "\
+pub mod shapes {
+    use std::fmt;
+
+    pub const SIDES: u32 = 0;
+
+    mod types;
+    mod fns;
+    mod traits;
+
+    use types::Point;
+    pub use types::Circle;
+    use fns::origin;
+    pub use fns::unit;
+    pub use traits::Shape;
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+    }
+}
+");
+        assert_eq!(fs::read_to_string(dir.path().join("src/shapes/types.rs")).unwrap(), "\
+use super::*;
+
+// A circle.
+#[derive(Debug)]
+pub struct Circle {
+    pub(super) r: f64,
+    pub(crate) name: Vec<(u8, u8)>,
+}
+
+impl Circle {
+    pub(super) fn new(r: f64) -> Circle {
+        Circle { r, name: Vec::new() }
+    }
+
+    pub fn area(&self) -> f64 {
+        3.0 * self.r * self.r
+    }
+}
+
+impl fmt::Display for Circle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, \"{}\", self.r)
+    }
+}
+
+pub(super) struct Point(pub(crate) u8, pub(super) u8);
+");
+        assert_eq!(fs::read_to_string(dir.path().join("src/shapes/fns.rs")).unwrap(), "\
+use super::*;
+
+pub fn unit() -> Circle {
+    Circle::new(1.0)
+}
+
+pub(super) fn origin() -> Point {
+    Point(0, 0)
+}
+");
+
+        // The same paths, though the items are now declared elsewhere.
+        let after = load(&dir);
+        assert_eq!(after.problems, Vec::<String>::new());
+        for path in ["crate::shapes::Circle", "crate::shapes::Point", "crate::shapes::Shape", "crate::shapes::unit"] {
+            let resolution = resolve::resolve(&after, after.root(), &path.parse().unwrap());
+            assert!(matches!(resolution.target(), Some(Target::Item(..))), "{path}");
+        }
+        assert!(after.find("crate::shapes::traits").is_some());
+    }
+
+    #[test]
+    fn visibilities() {
+        let dir = TestDir::new("split_visibilities", &[("src/lib.rs", "mod a { mod b {} }")]);
+        let tree = load(&dir);
+        let b = tree.find("crate::a::b").unwrap();
+        let a = tree.find("crate::a").unwrap();
+        let in_b = |vis| in_child(&tree, &vis, b).unwrap();
+        assert_eq!(in_b(Visibility::Private), Some(Visibility::Super));
+        assert_eq!(in_b(Visibility::SelfOnly), Some(Visibility::Super));
+        assert_eq!(in_b(Visibility::Super), Some(Visibility::In(vec!["crate".to_string(), "a".to_string()])));
+        assert_eq!(in_b(Visibility::Crate), None);
+        assert_eq!(in_b(Visibility::In(vec!["crate".to_string(), "a".to_string()])), None);
+        assert_eq!(in_child(&tree, &Visibility::Super, a).unwrap(), Some(Visibility::Crate));
+    }
+
+    #[test]
+    fn relative_paths() {
+        let dir = TestDir::new("split_relative_paths", &[
+            ("src/lib.rs", "fn g() {}\nmod a {\n    fn f() {\n        super::g()\n    }\n}\n"),
+        ]);
+        let tree = load(&dir);
+        let split = split(&tree, tree.find("crate::a").unwrap()).unwrap();
+        assert_eq!(split.notes, ["Paths in the moved items starting with `self` or `super` are now relative to \
+                                  the new modules."]);
+        assert_eq!(split.changes[1], Change::Modify {
+            path: dir.path().join("src/lib.rs"),
+            text: "fn g() {}\nmod a {\n    mod fns;\n\n    use fns::f;\n}\n".to_string(),
+        });
+    }
+
+    #[test]
+    fn errors() {
+        let dir = TestDir::new("split_errors", &[
+            ("src/lib.rs", "mod consts { const X: u8 = 1; } mod taken { fn f() {} mod fns {} } mod file;"),
+            ("src/file.rs", "pub struct S;"),
+            ("src/file/types.rs", ""),
+        ]);
+        let tree = load(&dir);
+        let split = |module| split(&tree, tree.find(module).unwrap()).err();
+        assert_eq!(split("crate::consts"),
+                   Some("`crate::consts` has no types, functions or traits to split out".to_string()));
+        assert_eq!(split("crate::taken"), Some("`crate::taken` already has something named `fns`".to_string()));
+        assert_eq!(split("crate::file"), Some("src/file/types.rs already exists".to_string()));
+    }
+}
@@ -0,0 +1,76 @@
+// Prints a `ModuleTree` as an indented tree, like the `tree` shell command.
+
+use std::io::{self, Write};
+
+use super::{Module, ModuleId, ModuleTree, Visibility};
+
+pub fn write(tree: &ModuleTree, out: &mut dyn Write) -> io::Result<()> {
+    let root = tree.module(tree.root());
+    writeln!(out, "crate  {}", location(tree, root))?;
+    write_children(tree, tree.root(), "", out)
+}
+
+fn write_children(tree: &ModuleTree, id: ModuleId, indent: &str,
+                  out: &mut dyn Write) -> io::Result<()> {
+    let children = &tree.module(id).children;
+    for (i, &child) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let (branch, next_indent) = if last { ("└── ", "    ") } else { ("├── ", "│   ") };
+        writeln!(out, "{indent}{branch}{}  {}", label(tree.module(child)),
+                 location(tree, tree.module(child)))?;
+        write_children(tree, child, &format!("{indent}{next_indent}"), out)?;
+    }
+    Ok(())
+}
+
+// E.g. `#[cfg(unix)] pub mod platform`. Only `cfg` attributes are shown, as
+// they decide whether the module exists at all.
+fn label(module: &Module) -> String {
+    let mut label = String::new();
+    for attr in module.attrs.iter().filter(|a| a.starts_with("cfg")) {
+        label.push_str(&format!("#[{attr}] "));
+    }
+    if module.vis != Visibility::Private {
+        label.push_str(&format!("{} ", module.vis));
+    }
+    label.push_str("mod ");
+    label.push_str(&module.name);
+    label
+}
+
+fn location(tree: &ModuleTree, module: &Module) -> String {
+    match module.file {
+        Some(file) if module.inline => {
+            format!("(inline in {})", tree.display_path(&tree.file(file).path))
+        }
+        Some(file) => tree.display_path(&tree.file(file).path),
+        None => "(file not found)".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    #[test]
+    fn tree_drawing() {
+        let dir = TestDir::new("tree_drawing", &[
+            ("src/lib.rs", "pub mod a; #[cfg(test)] mod tests {} mod missing;"),
+            ("src/a.rs", "pub(crate) mod b { mod c {} } mod d {}"),
+        ]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+
+        let mut out = Vec::new();
+        write(&tree, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+crate  src/lib.rs
+├── pub mod a  src/a.rs
+│   ├── pub(crate) mod b  (inline in src/a.rs)
+│   │   └── mod c  (inline in src/a.rs)
+│   └── mod d  (inline in src/a.rs)
+├── #[cfg(test)] mod tests  (inline in src/lib.rs)
+└── mod missing  (file not found)
+");
+    }
+}
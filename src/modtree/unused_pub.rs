@@ -0,0 +1,103 @@
+// Visible items that nothing else in the crate refers to, for the
+// `analyze unused-pub` command.
+
+// The compiler warns about an unused private item, or an unused
+// `pub(crate)` one, as nothing outside the crate can use it. It can't warn
+// about an unused `pub` item in a library, as other crates might. So
+// everything marked `pub` "just in case" stays, and becomes part of the API
+// that can't be changed without breaking someone. This lists them, to
+// check each is meant to be exported.
+
+// References are found by name: an item counts as used if its name appears
+// anywhere in the crate, in a `use` or in code, more often than items of
+// that name are declared. That's quick and never misses a real use, but an
+// item can look used because of an unrelated item or local variable with
+// the same name. It also can't see uses from other crates, including the
+// crate's own binaries and tests.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use super::lexer::{self, TokenKind};
+use super::{Item, ModuleId, ModuleTree, Visibility};
+
+pub fn unused(tree: &ModuleTree) -> Vec<(ModuleId, &Item)> {
+    let mut mentions: HashMap<&str, usize> = HashMap::new();
+    for file in &tree.files {
+        for token in lexer::tokenize(&file.text) {
+            if token.kind == TokenKind::Ident {
+                let name = token.text(&file.text).trim_start_matches("r#");
+                *mentions.entry(name).or_default() += 1;
+            }
+        }
+    }
+
+    // Each declaration mentions its own name once.
+    let mut declared: HashMap<&str, usize> = HashMap::new();
+    for id in tree.ids() {
+        let module = tree.module(id);
+        *declared.entry(&module.name).or_default() += 1;
+        for item in &module.items {
+            *declared.entry(&item.name).or_default() += 1;
+        }
+    }
+
+    tree.ids()
+        .flat_map(|id| tree.module(id).items.iter().map(move |item| (id, item)))
+        .filter(|(_, item)| item.vis != Visibility::Private)
+        .filter(|(_, item)| mentions.get(item.name.as_str()) <= declared.get(item.name.as_str()))
+        .collect()
+}
+
+pub fn write(tree: &ModuleTree, out: &mut dyn Write) -> io::Result<()> {
+    let unused = unused(tree);
+    for &(id, item) in &unused {
+        let path = &tree.file(item.span.file).path;
+        writeln!(out, "{}:{}: {} {} {}::{}", tree.display_path(path), tree.line(item.span),
+                 item.vis, item.kind, tree.path_string(id), item.name)?;
+    }
+
+    writeln!(out)?;
+    match unused.len() {
+        0 => writeln!(out, "Every visible item is referred to in the crate."),
+        1 => writeln!(out, "1 visible item isn't referred to in the crate."),
+        n => writeln!(out, "{n} visible items aren't referred to in the crate."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    fn output(name: &str, files: &[(&str, &str)]) -> String {
+        let dir = TestDir::new(name, files);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+        let mut out = Vec::new();
+        write(&tree, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn finds_unused() {
+        assert_eq!(output("unused_pub_finds_unused", &[
+            ("src/lib.rs", "pub mod shapes;\nuse shapes::area;\n\npub fn run() { area(); }\n"),
+            ("src/shapes.rs", "pub fn area() {}\n\n#[inline]\npub(crate) fn perimeter() {}\n\
+                               pub struct Square;\nfn private() {}\n"),
+        ]), "\
+src/lib.rs:4: pub fn crate::run
+src/shapes.rs:3: pub(crate) fn crate::shapes::perimeter
+src/shapes.rs:5: pub struct crate::shapes::Square
+
+3 visible items aren't referred to in the crate.
+");
+    }
+
+    #[test]
+    fn references_in_code() {
+        assert_eq!(output("unused_pub_references_in_code", &[
+            ("src/lib.rs", "pub const N: u8 = 1; pub struct S; impl S { fn f() -> u8 { crate::N } }\n\
+                            pub fn g() -> S { S }"),
+        ]), "src/lib.rs:2: pub fn crate::g\n\n1 visible item isn't referred to in the crate.\n");
+    }
+}
@@ -0,0 +1,116 @@
+// Every module that can name an item by its path, for the
+// `analyze visible-from` command.
+
+// An item's own visibility is only half of it: each module on its path must
+// be visible too, so a `pub fn` in a private module is only nameable where
+// that module is. This resolves the item's full path from every module in
+// the tree, with `resolve`, and lists those where every segment is visible.
+// Re-exports can make an item visible in more places, under other paths;
+// those aren't counted here.
+
+use std::io::{self, Write};
+
+use super::resolve::{self, Target};
+use super::{Import, ModuleId, ModuleTree};
+
+// The modules that can name `path`, written as if from the crate root.
+pub fn visible_from(tree: &ModuleTree, path: &Import) -> Result<Vec<ModuleId>, String> {
+    let path = absolute(path);
+    let resolution = resolve::resolve(tree, tree.root(), &path);
+    if let Some(error) = resolution.error {
+        return Err(format!("`{path}` doesn't resolve: {error}"));
+    }
+    if resolution.target() == Some(Target::External) {
+        return Err(format!("`{path}` is outside this crate"));
+    }
+    Ok(tree.ids().filter(|&from| resolve::resolve(tree, from, &path).accessible()).collect())
+}
+
+// `path` with `crate::` in front, unless it already starts there, so it
+// means the same from every module.
+fn absolute(path: &Import) -> Import {
+    let mut path = path.clone();
+    if path.path.first().map(String::as_str) != Some("crate") {
+        path.path.insert(0, "crate".to_string());
+    }
+    path
+}
+
+pub fn write(tree: &ModuleTree, item_path: &str, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let path: Import = item_path.parse().map_err(invalid)?;
+    let visible = visible_from(tree, &path).map_err(invalid)?;
+    let path = absolute(&path);
+
+    // Each step's own visibility, to explain the result.
+    let resolution = resolve::resolve(tree, tree.root(), &path);
+    for step in &resolution.steps[1..] {
+        let scope = tree.visible_within(&step.vis, step.found_in)
+            .map_or("nowhere".to_string(), |m| format!("inside `{}`", tree.path_string(m)));
+        let vis = match step.vis.to_string() {
+            vis if vis.is_empty() => "private".to_string(),
+            vis => vis,
+        };
+        writeln!(out, "`{}` is {vis}, so visible {scope}.", step.segment)?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, "`{path}` can be named in {} of {} modules:", visible.len(), tree.modules.len())?;
+    for id in visible {
+        writeln!(out, "  {}", tree.path_string(id))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    fn tree(name: &str) -> (TestDir, ModuleTree) {
+        let dir = TestDir::new(name, &[("src/lib.rs", "
+            mod a {
+                pub mod b { pub(super) fn g() {} pub fn h() {} }
+                mod c {}
+            }
+            pub mod d { pub(crate) fn i() {} }
+        ")]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+        (dir, tree)
+    }
+
+    fn visible(tree: &ModuleTree, path: &str) -> Vec<String> {
+        visible_from(tree, &path.parse().unwrap()).unwrap().into_iter()
+            .map(|id| tree.path_string(id))
+            .collect()
+    }
+
+    #[test]
+    fn modules() {
+        let (_dir, tree) = tree("visible_from_modules");
+        assert_eq!(visible(&tree, "crate::a::b::g"), vec!["crate::a", "crate::a::b", "crate::a::c"]);
+        // `pub`, inside a private module at the root, which the whole crate can see.
+        assert_eq!(visible(&tree, "crate::a::b::h"), visible(&tree, "crate::a"));
+        assert_eq!(visible(&tree, "crate::a").len(), tree.modules.len());
+        assert_eq!(visible(&tree, "d::i").len(), tree.modules.len());
+        assert!(visible_from(&tree, &"crate::nope".parse().unwrap()).is_err());
+        assert!(visible_from(&tree, &"::std::io".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn output() {
+        let (_dir, tree) = tree("visible_from_output");
+        let mut out = Vec::new();
+        write(&tree, "crate::a::b::g", &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+`a` is private, so visible inside `crate`.
+`b` is pub, so visible inside `crate`.
+`g` is pub(super), so visible inside `crate::a`.
+
+`crate::a::b::g` can be named in 3 of 5 modules:
+  crate::a
+  crate::a::b
+  crate::a::c
+");
+    }
+}
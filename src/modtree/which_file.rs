@@ -0,0 +1,133 @@
+// Which file a module path is loaded from, for the `which-file` command.
+
+// `mod name;` looks in the declaring module's directory for either
+// `name.rs`, the 2018 style, or `name/mod.rs`, the original style, as
+// `../lib.rs` explains. This prints both candidates for a module and which
+// exists, or where an undeclared module's file would have to go.
+
+use std::io::{self, Write};
+
+use super::{path_attribute, ModuleTree};
+
+pub fn write(tree: &ModuleTree, module_path: &str, out: &mut dyn Write) -> io::Result<()> {
+    let not_found = |message: String| io::Error::new(io::ErrorKind::NotFound, message);
+
+    if module_path != "crate" && !module_path.starts_with("crate::") {
+        return Err(not_found(format!("module paths start with `crate::`, found '{module_path}'")));
+    }
+    let Some((parent_path, name)) = module_path.rsplit_once("::") else {
+        let root = tree.module(tree.root());
+        let file = root.file.map_or("(file not found)".to_string(),
+                                    |f| tree.display_path(&tree.file(f).path));
+        return writeln!(out, "`crate` is the crate root, {file}.");
+    };
+    let parent = tree.find(parent_path)
+        .ok_or_else(|| not_found(format!("no module `{parent_path}` in this crate")))?;
+    let parent_file = tree.module(parent).file
+        .map_or("(file not found)".to_string(), |f| tree.display_path(&tree.file(f).path));
+
+    // Several declarations can share a name when `#[cfg]` picks between them.
+    let declared: Vec<_> = tree.module(parent).children.iter()
+        .map(|&id| tree.module(id))
+        .filter(|m| m.name == name)
+        .collect();
+
+    let mut file_declared = false;
+    for module in &declared {
+        let cfgs: String = module.attrs.iter().filter(|a| a.starts_with("cfg"))
+            .map(|a| format!("#[{a}] ")).collect();
+        if module.inline {
+            writeln!(out, "`{module_path}` is an inline module, `{cfgs}mod {name} {{ ... }}` in \
+                           {parent_file}, so it has no file of its own.")?;
+        } else if let Some(path) = module.attrs.iter().find_map(|a| path_attribute(a)) {
+            let file = module.file.map_or(path.to_string(), |f| tree.display_path(&tree.file(f).path));
+            writeln!(out, "`{module_path}` is declared with `{cfgs}#[path = \"{path}\"] mod {name};` \
+                           in {parent_file}, so it's loaded from {file}.")?;
+        } else {
+            file_declared = true;
+        }
+    }
+    if !declared.is_empty() && !file_declared {
+        return Ok(());
+    }
+
+    if declared.is_empty() {
+        writeln!(out, "`{module_path}` isn't declared. `mod {name};` in {parent_file} would load it \
+                       from one of:")?;
+    } else {
+        writeln!(out, "`mod {name};` in {parent_file} loads `{module_path}` from one of:")?;
+    }
+
+    let dir = &tree.module(parent).child_dir;
+    let candidates = [
+        (dir.join(format!("{name}.rs")), "2018 style"),
+        (dir.join(name).join("mod.rs"), "mod.rs style"),
+    ];
+    let width = candidates.iter().map(|(path, _)| tree.display_path(path).len()).max().unwrap_or(0);
+    for (path, style) in &candidates {
+        let found = if path.is_file() { "found" } else { "not found" };
+        writeln!(out, "  {:width$}  {style:12}  {found}", tree.display_path(path))?;
+    }
+    if candidates.iter().all(|(path, _)| path.is_file()) {
+        writeln!(out, "Both exist, which is error E0761.")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::find_crate_root;
+    use crate::modtree::test_dir::TestDir;
+
+    fn output(tree: &ModuleTree, module_path: &str) -> io::Result<String> {
+        let mut out = Vec::new();
+        write(tree, module_path, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn candidates() {
+        let dir = TestDir::new("which_file_candidates", &[
+            ("src/lib.rs", "mod a; mod b; mod inline { mod c; } #[cfg(unix)] #[path = \"os.rs\"] mod os;"),
+            ("src/a.rs", ""),
+            ("src/b/mod.rs", ""),
+            ("src/inline/c.rs", ""),
+            ("src/os.rs", ""),
+        ]);
+        let tree = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        let output = |path| output(&tree, path).unwrap();
+
+        assert_eq!(output("crate"), "`crate` is the crate root, src/lib.rs.\n");
+        assert_eq!(output("crate::a"), "\
+`mod a;` in src/lib.rs loads `crate::a` from one of:
+  src/a.rs      2018 style    found
+  src/a/mod.rs  mod.rs style  not found
+");
+        assert_eq!(output("crate::b"), "\
+`mod b;` in src/lib.rs loads `crate::b` from one of:
+  src/b.rs      2018 style    not found
+  src/b/mod.rs  mod.rs style  found
+");
+        assert_eq!(output("crate::a::new"), "\
+`crate::a::new` isn't declared. `mod new;` in src/a.rs would load it from one of:
+  src/a/new.rs      2018 style    not found
+  src/a/new/mod.rs  mod.rs style  not found
+");
+        assert_eq!(output("crate::inline"),
+                   "`crate::inline` is an inline module, `mod inline { ... }` in src/lib.rs, \
+                    so it has no file of its own.\n");
+        assert!(output("crate::inline::c").contains("  src/inline/c.rs      2018 style    found\n"));
+        assert_eq!(output("crate::os"),
+                   "`crate::os` is declared with `#[cfg(unix)] #[path = \"os.rs\"] mod os;` in \
+                    src/lib.rs, so it's loaded from src/os.rs.\n");
+    }
+
+    #[test]
+    fn errors() {
+        let dir = TestDir::new("which_file_errors", &[("src/lib.rs", "")]);
+        let tree = ModuleTree::load(&find_crate_root(dir.path()).unwrap()).unwrap();
+        assert!(output(&tree, "a::b").is_err());
+        assert!(output(&tree, "crate::missing::b").is_err());
+    }
+}
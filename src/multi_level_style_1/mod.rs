@@ -1 +1,28 @@
 mod child;
+
+use crate::example::{self, Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "multi_level_style_1" }
+
+    fn description(&self) -> &'static str {
+        "A module with children using `foo/mod.rs` layout"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["multi-level", "files"] }
+
+    fn topic(&self) -> Topic { Topic::MultiLevel }
+
+    fn source(&self) -> &'static str {
+        example::section(example::LIB_RS, "Multi-level modules")
+    }
+
+    fn run(&self) {
+        println!("This code lives in `./multi_level_style_1/mod.rs`.");
+        println!("It declares `mod child;`, loaded from `./multi_level_style_1/child.rs`.");
+    }
+}
@@ -0,0 +1,87 @@
+// # A `no_std` core behind a `std` feature
+
+// Libraries that might run without an operating system split their code by
+// what it needs: logic that only uses `core` in modules that are always
+// compiled, and anything needing `std` in modules behind a `std` feature
+// that's on by default. See `../crates/core_logic/`, whose `lib.rs` starts
+// with:
+//
+//     #![cfg_attr(not(feature = "std"), no_std)]
+//
+//     pub mod path;
+//
+//     #[cfg(feature = "std")]
+//     pub mod owned;
+
+// This crate uses `std` anyway, so it depends on `core_logic` with its
+// default features, and gets both modules.
+use core_logic::{owned, path};
+
+pub fn describe(module_path: &str) -> String {
+    match owned::segments(module_path) {
+        Ok(segments) => format!("{module_path}: {} segments, {segments:?}, inside `crate::a`: {}",
+                                path::depth(module_path), path::is_within(module_path, "crate::a")),
+        // `PathError` implements `std::error::Error` too, with the `std` feature.
+        Err(e) => format!("{module_path:?}: {e}"),
+    }
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "no_std_core" }
+
+    fn description(&self) -> &'static str {
+        "A dependency with a `no_std` core and `std`-only modules behind a feature"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["cfg", "features", "dependencies"] }
+
+    fn topic(&self) -> Topic { Topic::Cfg }
+
+    fn source(&self) -> &'static str {
+        concat!(include_str!("no_std_core.rs"), "\n",
+                include_str!("../crates/core_logic/src/lib.rs"))
+    }
+
+    fn run(&self) {
+        for module_path in ["crate::a::b", "crate::c", "crate::::d"] {
+            println!("{}", describe(module_path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::process::Command;
+
+    #[test]
+    fn describe_paths() {
+        assert_eq!(super::describe("crate::a::b"),
+                   r#"crate::a::b: 3 segments, ["crate", "a", "b"], inside `crate::a`: true"#);
+        assert_eq!(super::describe(""), r#""": empty path"#);
+    }
+
+    // Tests always have `std`, so the `no_std` configuration is checked by
+    // building `core_logic` without its default features.
+    #[test]
+    fn core_logic_builds_without_std() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let status = Command::new(cargo)
+            .args(["build", "--quiet", "--offline", "--no-default-features", "--manifest-path"])
+            .arg(root.join("crates/core_logic/Cargo.toml"))
+            // A separate target directory, so this doesn't wait for the lock
+            // on the one running the tests.
+            .arg("--target-dir")
+            .arg(root.join("target/no_std_check"))
+            .status()
+            .expect("running cargo");
+        assert!(status.success());
+    }
+}
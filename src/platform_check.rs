@@ -0,0 +1,36 @@
+// Compiles each platform file under its own name, on whatever host runs the
+// tests, so `cargo test` on Linux still catches a `windows.rs` that has
+// fallen out of step with the others.
+
+// Usually only one of these is compiled, as `crate::platform`. The files
+// don't refer to themselves as `platform`, so loading them again under other
+// names works. Clippy warns about loading one file twice, because it's
+// normally a mistake, but here it's the point.
+#![allow(clippy::duplicate_mod)]
+#[path = "linux.rs"]
+mod linux;
+
+#[path = "macos.rs"]
+mod macos;
+
+#[path = "windows.rs"]
+mod windows;
+
+// `wasm.rs` can't be checked this way: it calls `core::arch::wasm32`, which
+// only exists when building for wasm32. `unsupported.rs` is a deliberate
+// compile error.
+
+use crate::platform_api::PlatformApi;
+
+// Generic over the trait, so instantiating it with a type only compiles if
+// that type implements all of `PlatformApi`.
+fn describe<P: PlatformApi>() -> String {
+    format!("{} {:?} {:?} {:?}", P::FAMILY, P::EXE_SUFFIX, P::DLL_SUFFIX, P::path_separator())
+}
+
+#[test]
+fn every_platform_implements_the_api() {
+    assert_eq!(describe::<linux::Platform>(), "unix \"\" \".so\" '/'");
+    assert_eq!(describe::<macos::Platform>(), "unix \"\" \".dylib\" '/'");
+    assert_eq!(describe::<windows::Platform>(), "windows \".exe\" \".dll\" '\\\\'");
+}
@@ -0,0 +1,21 @@
+// # The crate prelude
+
+// Every Rust module starts with the standard library's prelude already
+// imported, which is why `Vec`, `Option` and `String` work without a `use`.
+// It's an ordinary module, `std::prelude::rust_2021`, that re-exports the
+// most commonly used items from all over `std`.
+
+// Libraries often copy the idea: a `prelude` module that re-exports what
+// almost every user needs, so they can write one glob import,
+// `use some_crate::prelude::*;`, instead of a long list. `tokio`, `bevy`
+// and `diesel` all have one.
+
+// Only re-exports belong here, no new items, so it stays a shortcut and
+// never becomes the only place to find something. It should also stay short:
+// everything in it lands in the glob importer's scope.
+
+// The trait every example implements, and its topics.
+pub use crate::example::{Example, Topic};
+
+// The parsed module tree, and the IDs used to look things up in it.
+pub use crate::modtree::{ModuleId, ModuleTree};
@@ -0,0 +1,63 @@
+// # Using a prelude
+
+// `./prelude.rs` re-exports the crate's most commonly used items. This
+// module imports all of them with a single glob, the same way every module
+// implicitly gets `use std::prelude::rust_2021::*;`:
+use crate::prelude::*;
+
+// `Example`, `Topic` and `ModuleTree` are all in scope now, even though
+// they're defined in three different modules.
+
+// Globs lose to explicit imports and local items (see `use_examples` in
+// `lib.rs`), so a prelude never stops a module from defining its own
+// `Topic`, say. It would just shadow the prelude's.
+
+// The number of modules in the tree parsed from `src`, using `ModuleTree`
+// from the prelude.
+pub fn count_modules(tree: &ModuleTree) -> usize {
+    tree.ids().count()
+}
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "prelude" }
+
+    fn description(&self) -> &'static str {
+        "A `prelude` module of re-exports, imported with one glob"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["use", "re-export", "glob"] }
+
+    fn topic(&self) -> Topic { Topic::Use }
+
+    fn source(&self) -> &'static str {
+        concat!(include_str!("prelude.rs"), "\n", include_str!("prelude_consumer.rs"))
+    }
+
+    fn run(&self) {
+        println!("`Example`, `Topic` and `ModuleTree` all came from `use crate::prelude::*;`.");
+        println!("This example's topic is {:?}.", self.topic());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::modtree::test_dir::TestDir;
+    use crate::prelude::*;
+
+    #[test]
+    fn prelude_items_in_scope() {
+        let demo: &dyn Example = &super::Demo;
+        assert_eq!(demo.topic(), Topic::Use);
+    }
+
+    #[test]
+    fn count_modules() {
+        let dir = TestDir::new("prelude_count", &[("src/lib.rs", "mod a {}\nmod b {}\n")]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+        assert_eq!(super::count_modules(&tree), 3);
+    }
+}
@@ -0,0 +1,253 @@
+// A learner's progress, saved between runs: which examples they've run,
+// which exercises compile, and which quiz questions they've answered
+// right. The `progress` command sums it up.
+
+// It's kept in `$XDG_DATA_HOME/rust-module-example/progress.toml`, which
+// is usually under `~/.local/share/`. Setting `RUST_MODULE_EXAMPLE_PROGRESS`
+// to a file's path uses that file instead, which is how the tests keep out
+// of the real one.
+//
+// The file is a small subset of TOML, like `../spec/examples.toml`: a
+// comment, then one `key = ["string", ...]` line for each list. Exercise
+// names are the names of directories, which can have anything in them, so
+// the strings are escaped as TOML's basic strings are.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Progress {
+    // Example names, as in `run <EXAMPLE>`.
+    pub examples: BTreeSet<String>,
+    // Exercise directory names, e.g. `01_private_function`.
+    pub exercises: BTreeSet<String>,
+    // `quiz::Question::id`s.
+    pub questions: BTreeSet<String>,
+}
+
+// Where progress is saved, or `None` if there's no home directory to save it
+// in.
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("RUST_MODULE_EXAMPLE_PROGRESS") {
+        return Some(path.into());
+    }
+    let data = std::env::var_os("XDG_DATA_HOME").map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))?;
+    Some(data.join("rust-module-example/progress.toml"))
+}
+
+impl Progress {
+    pub fn parse(text: &str) -> Result<Progress, String> {
+        let mut progress = Progress::default();
+        for (i, line) in text.lines().enumerate() {
+            let error = |message: &str| format!("line {}: {message}", i + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| error("expected `key = [...]`"))?;
+            let list = match key.trim() {
+                "examples" => &mut progress.examples,
+                "exercises" => &mut progress.exercises,
+                "questions" => &mut progress.questions,
+                other => return Err(error(&format!("unknown key `{other}`"))),
+            };
+            let value = value.trim().strip_prefix('[').and_then(|v| v.strip_suffix(']'))
+                .ok_or_else(|| error("expected a list in square brackets"))?;
+            list.extend(strings(value).map_err(|e| error(&e))?);
+        }
+        Ok(progress)
+    }
+
+    // The progress saved at `path`, or none yet if there's no file.
+    pub fn load(path: &Path) -> io::Result<Progress> {
+        match fs::read_to_string(path) {
+            Ok(text) => Progress::parse(&text).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display()))
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Progress::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_toml())
+    }
+
+    pub fn to_toml(&self) -> String {
+        let list = |set: &BTreeSet<String>| {
+            set.iter().map(|s| format!("\"{}\"", escape(s))).collect::<Vec<_>>().join(", ")
+        };
+        format!("# Progress through rust-module-example, updated as you go. See `cargo run -- progress`.\n\n\
+                 examples = [{}]\nexercises = [{}]\nquestions = [{}]\n",
+                list(&self.examples), list(&self.exercises), list(&self.questions))
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::new();
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// The strings in a list's `"a", "b\"c"`, between its square brackets.
+fn strings(list: &str) -> Result<Vec<String>, String> {
+    let mut strings = Vec::new();
+    let mut chars = list.trim_start().chars().peekable();
+    while chars.peek().is_some() {
+        if chars.next() != Some('"') {
+            return Err("expected strings in double quotes".to_string());
+        }
+        let mut string = String::new();
+        loop {
+            match chars.next().ok_or("a string has no closing `\"`")? {
+                '"' => break,
+                '\\' => string.push(match chars.next() {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some('u') => {
+                        let hex: String = chars.by_ref().take(4).collect();
+                        u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)
+                            .ok_or(format!("bad escape `\\u{hex}`"))?
+                    }
+                    Some(other) => return Err(format!("unknown escape `\\{other}`")),
+                    None => return Err("a string has no closing `\"`".to_string()),
+                }),
+                c => string.push(c),
+            }
+        }
+        strings.push(string);
+        // Then a comma, unless it's the last.
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.next() {
+            Some(',') => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+            Some(c) => return Err(format!("expected `,` between strings, not `{c}`")),
+            None => {}
+        }
+    }
+    Ok(strings)
+}
+
+// Adds to the saved progress with `update`. Progress is a nicety, so
+// failing to save it is only a warning.
+pub fn record(update: impl FnOnce(&mut Progress)) {
+    let Some(path) = path() else { return };
+    let result = Progress::load(&path).and_then(|mut progress| {
+        update(&mut progress);
+        progress.save(&path)
+    });
+    if let Err(e) = result {
+        eprintln!("Warning: couldn't save progress to {}: {e}", path.display());
+    }
+}
+
+// Sums up `progress` out of the totals of each: `(examples, exercises)`,
+// and the ids of the quiz questions counted. Questions answered from other
+// banks are saved in `progress` too, but only those in `questions` count, so
+// the score can't pass the total. `next` is the first example in the tour
+// not run yet.
+pub fn write(progress: &Progress, totals: (usize, usize), questions: &BTreeSet<String>, next: Option<&str>,
+             out: &mut dyn Write) -> io::Result<()> {
+    let (examples, exercises) = totals;
+    let rows = [
+        ("Examples run", progress.examples.len(), examples),
+        ("Exercises compiling", progress.exercises.len(), exercises),
+        ("Quiz questions answered right", progress.questions.intersection(questions).count(), questions.len()),
+    ];
+    for (label, done, total) in rows {
+        let percent = (done * 100).checked_div(total).unwrap_or(0);
+        writeln!(out, "{:30} {done:>3} of {total:<3} {percent:>3}%", format!("{label}:"))?;
+    }
+    if let Some(next) = next {
+        writeln!(out, "\nNext, try `run {next}`.")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    #[test]
+    fn round_trip() {
+        let mut progress = Progress::default();
+        progress.examples.extend(["a".to_string(), "inline".to_string()]);
+        progress.questions.insert("0123abcd".to_string());
+        let text = progress.to_toml();
+        assert!(text.ends_with("examples = [\"a\", \"inline\"]\nexercises = []\nquestions = [\"0123abcd\"]\n"));
+        assert_eq!(Progress::parse(&text), Ok(progress));
+    }
+
+    #[test]
+    fn odd_names() {
+        // Exercises are named after directories, which can be called
+        // anything.
+        let mut progress = Progress::default();
+        progress.exercises.extend(["a, \"b\"".to_string(), "c\\d\ne\u{7}".to_string(), "".to_string()]);
+        let text = progress.to_toml();
+        assert!(text.contains("exercises = [\"\", \"a, \\\"b\\\"\", \"c\\\\d\\ne\\u0007\"]\n"), "{text}");
+        assert_eq!(Progress::parse(&text), Ok(progress));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Progress::parse("examples = [\"a\"]\nlessons = []\n"),
+                   Err("line 2: unknown key `lessons`".to_string()));
+        assert!(Progress::parse("examples = \"a\"").is_err());
+        assert!(Progress::parse("examples = [a]").is_err());
+        assert!(Progress::parse("examples").is_err());
+        assert_eq!(Progress::parse("examples = [\"a\" \"b\"]"),
+                   Err("line 1: expected `,` between strings, not `\"`".to_string()));
+        assert_eq!(Progress::parse("examples = [\"a\\q\"]"), Err("line 1: unknown escape `\\q`".to_string()));
+        assert_eq!(Progress::parse("examples = [\"a]"), Err("line 1: a string has no closing `\"`".to_string()));
+    }
+
+    #[test]
+    fn load_and_save() {
+        let dir = TestDir::new("progress_load_and_save", &[("bad.toml", "nonsense")]);
+        let path = dir.path().join("new/progress.toml");
+        assert_eq!(Progress::load(&path).unwrap(), Progress::default());
+        let mut progress = Progress::default();
+        progress.exercises.insert("01_private_function".to_string());
+        progress.save(&path).unwrap();
+        assert_eq!(Progress::load(&path).unwrap(), progress);
+        assert!(Progress::load(&dir.path().join("bad.toml")).unwrap_err().to_string().ends_with("bad.toml: line 1: \
+                                                                                                 expected `key = [...]`"));
+    }
+
+    #[test]
+    fn summary() {
+        let mut progress = Progress::default();
+        progress.examples.insert("a".to_string());
+        // One from the set counted, and one from another bank.
+        progress.questions.extend(["q1".to_string(), "elsewhere".to_string()]);
+        let questions = (0..10).map(|i| format!("q{i}")).collect();
+        let mut out = Vec::new();
+        write(&progress, (4, 0), &questions, Some("inline"), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+Examples run:                    1 of 4    25%
+Exercises compiling:             0 of 0     0%
+Quiz questions answered right:   1 of 10   10%
+
+Next, try `run inline`.
+");
+    }
+}
@@ -0,0 +1,350 @@
+// The visibility quiz, for the `quiz` command: shows a small crate and asks
+// whether one of its modules can call a function in another, then explains
+// the answer.
+
+// The answers aren't stored, they're worked out by following the path with
+// `modtree::resolve`, as the `resolve` command does. So the hand-written
+// questions below only need a crate, a module and a path, plus an
+// explanation in words, and there are many more generated from one crate
+// with every combination of a few visibilities, explained step by step.
+// Instructors can add more in TOML files, see `bank`.
+//
+// Each question has a topic, so `report` can show which areas a learner
+// finds hardest.
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+// Loading more questions from TOML files, in `./quiz/bank.rs`.
+pub mod bank;
+// Saving and showing the results of a quiz, in `./quiz/report.rs`.
+pub mod report;
+// Writing the questions as flashcards, in `./quiz/export.rs`.
+pub mod export;
+
+use crate::modtree::resolve::{self, Resolution};
+use crate::modtree::{Import, ModuleTree, Visibility};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Question {
+    // The source of a `lib.rs` of inline modules.
+    pub snippet: String,
+    // The module the call is in, and the path it calls, both from `crate`.
+    pub from: String,
+    pub item: String,
+    pub answer: bool,
+    pub explanation: String,
+    // One of `TOPICS`.
+    pub topic: &'static str,
+}
+
+// What questions can be about: whether an item is visible, which paths
+// reach it, and how `#[cfg]` and macros change what's there.
+pub const TOPICS: &[&str] = &["visibility", "paths", "cfg", "macros"];
+
+impl Question {
+    // A question about `snippet`, answered and explained from how `item`
+    // resolves in `from`. Its topic is visibility until it's set otherwise.
+    pub fn new(snippet: &str, from: &str, item: &str) -> Result<Question, String> {
+        let tree = ModuleTree::from_source(Path::new("src/lib.rs"), snippet.to_string());
+        // `mod name;` would be loaded from whatever `src/` is in the current
+        // directory, so a question could be answered from some crate's files
+        // rather than its snippet.
+        if let Some(module) = tree.ids().skip(1).map(|id| tree.module(id)).find(|module| !module.inline) {
+            return Err(format!("the snippet's `mod {0};` would load a file, so its modules must be inline, \
+                                like `mod {0} {{ ... }}`", module.name));
+        }
+        if let Some(problem) = tree.problems.first() {
+            return Err(problem.clone());
+        }
+        let from_id = tree.find(from).ok_or(format!("no module `{from}` in the snippet"))?;
+        let import: Import = item.parse()?;
+        let resolution = resolve::resolve(&tree, from_id, &import);
+        if let Some(error) = &resolution.error {
+            return Err(format!("`{item}` doesn't resolve from `{from}`: {error}"));
+        }
+        Ok(Question {
+            snippet: snippet.to_string(),
+            from: from.to_string(),
+            item: item.to_string(),
+            answer: resolution.accessible(),
+            explanation: explain(&tree, from, &resolution),
+            topic: "visibility",
+        })
+    }
+
+    // A short name for the question, the same from one run to the next, to
+    // save progress with: an FNV-1a hash of the snippet, module and path.
+    pub fn id(&self) -> String {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in [&self.snippet, "\0", &self.from, "\0", &self.item].concat().bytes() {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3);
+        }
+        format!("{hash:016x}")
+    }
+
+    pub fn prompt(&self) -> String {
+        format!("Can code in `{}` call `{}()`?", self.from, self.item)
+    }
+}
+
+// Why `from` can or can't use what `resolution` reached: the first step it
+// can't see, or else each step with a visibility other than `pub`.
+fn explain(tree: &ModuleTree, from: &str, resolution: &Resolution) -> String {
+    let step = |step: &resolve::Step| {
+        let vis = match &step.vis {
+            Visibility::Private => "private".to_string(),
+            vis => format!("`{vis}`"),
+        };
+        let via = step.via.as_ref().map_or(String::new(), |via| format!(", by `use {via};`"));
+        let scope = tree.visible_within(&step.vis, step.found_in)
+            .map_or("nowhere".to_string(), |m| format!("inside `{}`", tree.path_string(m)));
+        format!("`{}` is {vis} in `{}`{via}, so it's visible {scope}", step.segment,
+                tree.path_string(step.found_in))
+    };
+    if let Some(blocked) = resolution.steps.iter().find(|s| !s.accessible) {
+        return format!("{}, and `{from}` isn't.", step(blocked));
+    }
+    let restricted: Vec<_> = resolution.steps.iter()
+        .filter(|s| !["crate", "self", "super"].contains(&s.segment.as_str()))
+        .filter(|s| s.vis != Visibility::Public)
+        .map(step)
+        .collect();
+    match restricted.len() {
+        0 => "Every module and function on the path is `pub`.".to_string(),
+        1 => format!("{}, which includes `{from}`.", restricted[0]),
+        _ => format!("{}. `{from}` is inside all of these.", restricted.join(". ")),
+    }
+}
+
+// Each question's topic, snippet, the module asking, the path it calls,
+// and why the answer is what it is.
+const HAND_WRITTEN: &[(&str, &str, &str, &str, &str)] = &[
+    ("visibility", "mod kitchen {\n    fn cook() {}\n}\nmod dining {}\n", "crate::dining", "crate::kitchen::cook",
+     "Without `pub`, `cook` is private to `kitchen`: only `kitchen` and the modules inside it can name it."),
+    ("visibility",
+     "mod kitchen {\n    fn cook() {}\n    mod oven {}\n}\n", "crate::kitchen::oven", "crate::kitchen::cook",
+     "Private items are visible in the module they're declared in and all its descendants, so `oven` can \
+      call its parent's private `cook`."),
+    ("visibility", "mod kitchen {\n    pub fn cook() {}\n}\nmod dining {}\n", "crate::dining", "crate::kitchen::cook",
+     "`kitchen` is private, but that makes it visible inside its parent, `crate`, which `dining` is in too. \
+      And `cook` is `pub`."),
+    ("visibility",
+     "mod kitchen {\n    mod oven {\n        pub fn bake() {}\n    }\n}\nmod dining {}\n", "crate::dining",
+     "crate::kitchen::oven::bake",
+     "`bake` is `pub`, but the `oven` module is private to `kitchen`, and a path is only usable if every \
+      module along it is visible."),
+    ("paths",
+     "mod kitchen {\n    mod oven {\n        pub fn bake() {}\n    }\n    pub use oven::bake;\n}\nmod dining {}\n",
+     "crate::dining", "crate::kitchen::bake",
+     "`oven` is private, but `pub use oven::bake;` re-exports `bake` from `kitchen`, and this path only goes \
+      through `kitchen`, which is visible to the whole crate."),
+    ("paths",
+     "mod kitchen {\n    pub mod oven {\n        pub fn bake() {}\n    }\n    use oven::bake;\n}\nmod dining {}\n",
+     "crate::dining", "crate::kitchen::bake",
+     "A `use` without `pub` is private like any other item, so only `kitchen` and its descendants can call \
+      it `crate::kitchen::bake`. `dining` can call `crate::kitchen::oven::bake()` instead."),
+    ("visibility", "mod kitchen {\n    mod oven {\n        pub(super) fn bake() {}\n    }\n}\n", "crate::kitchen",
+     "crate::kitchen::oven::bake",
+     "`pub(super)` makes `bake` visible in `oven`'s parent, `kitchen`, which can also see its own private \
+      child `oven`."),
+    ("visibility",
+     "mod a {\n    pub mod b {\n        pub(in crate::a) fn f() {}\n    }\n    pub mod c {}\n}\n", "crate::a::c",
+     "crate::a::b::f",
+     "`pub(in crate::a)` makes `f` visible anywhere inside `crate::a`, including its child `c`."),
+];
+
+pub fn hand_written() -> Vec<Question> {
+    HAND_WRITTEN.iter()
+        .map(|&(topic, snippet, from, item, explanation)| Question {
+            explanation: explanation.to_string(),
+            topic,
+            ..Question::new(snippet, from, item).expect("hand-written questions resolve")
+        })
+        .collect()
+}
+
+// One crate with each combination of visibilities for the module `inner`
+// and its function `f`, asked from each of the other modules.
+pub fn generated() -> Vec<Question> {
+    const VISIBILITIES: &[&str] = &["", "pub ", "pub(crate) ", "pub(super) ", "pub(in crate::outer) "];
+    const FROM: &[&str] = &["crate", "crate::outer", "crate::outer::sibling", "crate::other"];
+    let mut questions = Vec::new();
+    for module_vis in VISIBILITIES {
+        for fn_vis in VISIBILITIES {
+            let snippet = format!("mod outer {{\n    {module_vis}mod inner {{\n        {fn_vis}fn f() {{}}\n    }}\n\
+                                   \n    mod sibling {{}}\n}}\n\nmod other {{}}\n");
+            for from in FROM {
+                questions.push(Question::new(&snippet, from, "crate::outer::inner::f")
+                    .expect("generated questions resolve"));
+            }
+        }
+    }
+    questions
+}
+
+// A small xorshift generator, so the quiz can shuffle its questions without
+// a dependency. It must start from a seed other than zero, which it never
+// leaves.
+pub(crate) struct Rng(pub(crate) u64);
+
+impl Rng {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+// The built-in questions, hand-written then generated.
+pub fn built_in() -> Vec<Question> {
+    let mut questions = hand_written();
+    questions.extend(generated());
+    questions
+}
+
+// `count` of `questions`, shuffled by `seed`.
+pub fn pick(mut questions: Vec<Question>, count: usize, seed: u64) -> Vec<Question> {
+    let mut rng = Rng(seed.max(1));
+    for i in (1..questions.len()).rev() {
+        questions.swap(i, rng.below(i + 1));
+    }
+    questions.truncate(count);
+    questions
+}
+
+fn parse_answer(line: &str) -> Option<Option<bool>> {
+    match line.trim().to_lowercase().as_str() {
+        // Just pressing enter skips the question.
+        "" => Some(None),
+        "y" | "yes" => Some(Some(true)),
+        "n" | "no" => Some(Some(false)),
+        _ => None,
+    }
+}
+
+// Whether `answer` to `question` is right, with the explanation. `None`
+// is a question skipped.
+pub fn verdict(question: &Question, answer: Option<bool>) -> String {
+    let yes_no = if question.answer { "yes" } else { "no" };
+    match answer {
+        Some(answer) if answer == question.answer => format!("Right, {yes_no}. {}", question.explanation),
+        Some(_) => format!("Not quite, the answer is {yes_no}. {}", question.explanation),
+        None => format!("The answer is {yes_no}. {}", question.explanation),
+    }
+}
+
+// Ask `question` until `input` has an answer, then give the verdict.
+// Returns the answer, where `Some(None)` is skipping the question, or
+// `None` if `input` ended first.
+pub fn ask(question: &Question, input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<Option<Option<bool>>> {
+    let answer = loop {
+        write!(out, "{} [y/n] > ", question.prompt())?;
+        out.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(out)?;
+            return Ok(None);
+        }
+        match parse_answer(&line) {
+            Some(answer) => break answer,
+            None => writeln!(out, "Please answer y or n, or press Enter to skip.")?,
+        }
+    };
+    writeln!(out, "{}\n", verdict(question, answer))?;
+    Ok(Some(answer))
+}
+
+// Ask each question, reading answers from `input` until they run out or
+// `input` ends, and return whether each one asked was answered right.
+pub fn run(questions: &[Question], input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<Vec<bool>> {
+    let mut results = Vec::new();
+    for (i, question) in questions.iter().enumerate() {
+        writeln!(out, "=== Question {} of {}\n\n{}", i + 1, questions.len(), question.snippet)?;
+        let Some(answer) = ask(question, input, out)? else {
+            writeln!(out, "You got {} out of {i}.", results.iter().filter(|&&r| r).count())?;
+            return Ok(results);
+        };
+        results.push(answer == Some(question.answer));
+    }
+    writeln!(out, "You got {} out of {}.", results.iter().filter(|&&r| r).count(), questions.len())?;
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hand_written_answers() {
+        let answers: Vec<_> = hand_written().iter().map(|q| q.answer).collect();
+        assert_eq!(answers, [false, true, true, false, true, false, true, true]);
+    }
+
+    #[test]
+    fn generated_explanations() {
+        let questions = generated();
+        assert_eq!(questions.len(), 100);
+        let find = |module_vis: &str, fn_vis: &str, from: &str| {
+            questions.iter()
+                .find(|q| q.from == from && q.snippet.contains(&format!("    {module_vis}mod inner"))
+                          && q.snippet.contains(&format!("        {fn_vis}fn f")))
+                .unwrap()
+        };
+        let question = find("pub ", "pub(super) ", "crate::other");
+        assert!(!question.answer);
+        assert_eq!(question.explanation, "`f` is `pub(super)` in `crate::outer::inner`, so it's visible inside \
+                                          `crate::outer`, and `crate::other` isn't.");
+        let question = find("", "pub(in crate::outer) ", "crate::outer::sibling");
+        assert!(question.answer);
+        assert_eq!(question.explanation, "\
+`outer` is private in `crate`, so it's visible inside `crate`. \
+`inner` is private in `crate::outer`, so it's visible inside `crate::outer`. \
+`f` is `pub(in crate::outer)` in `crate::outer::inner`, so it's visible inside `crate::outer`. \
+`crate::outer::sibling` is inside all of these.");
+        let question = find("pub ", "pub ", "crate");
+        assert_eq!(question.explanation, "`outer` is private in `crate`, so it's visible inside `crate`, \
+                                          which includes `crate`.");
+    }
+
+    #[test]
+    fn ids() {
+        let questions = built_in();
+        let ids: std::collections::BTreeSet<_> = questions.iter().map(Question::id).collect();
+        assert_eq!(ids.len(), questions.len());
+        assert_eq!(questions[0].id(), hand_written()[0].id());
+    }
+
+    #[test]
+    fn pick_is_repeatable() {
+        assert_eq!(pick(built_in(), 5, 42), pick(built_in(), 5, 42));
+        assert_eq!(pick(built_in(), 5, 42).len(), 5);
+        assert_ne!(pick(built_in(), 20, 1), pick(built_in(), 20, 2));
+        assert_eq!(pick(built_in(), 1000, 3).len(), HAND_WRITTEN.len() + 100);
+    }
+
+    #[test]
+    fn session() {
+        let questions = &hand_written()[..3];
+        let mut out = Vec::new();
+        let results = run(questions, &mut "maybe\nn\ny\n".as_bytes(), &mut out).unwrap();
+        assert_eq!(results, [true, true]);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\
+=== Question 1 of 3
+
+mod kitchen {
+    fn cook() {}
+}
+mod dining {}
+
+Can code in `crate::dining` call `crate::kitchen::cook()`? [y/n] > Please answer y or n, or press Enter to skip.
+Can code in `crate::dining` call `crate::kitchen::cook()`? [y/n] > Right, no. Without `pub`"), "{out}");
+        assert!(out.ends_with("[y/n] > \nYou got 2 out of 2.\n"), "{out}");
+    }
+}
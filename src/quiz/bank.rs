@@ -0,0 +1,229 @@
+// Question banks: extra quiz questions in TOML files, like those in
+// `../../questions/`, for instructors to add their own. A child module of
+// `../quiz.rs`.
+
+// A bank is a list of `[[question]]` tables, see `questions/workshop.toml`
+// for one with comments. Like `../../build/spec.rs`, this reads only the
+// subset of TOML banks need, rather than depending on the `toml` crate:
+// comments, `[[question]]` headers, and `key = "string"` lines, where the
+// string may be in triple quotes to span lines. Anything else is an error
+// saying which line it's on, as is a question that doesn't make sense for
+// its snippet, or whose `answer` is wrong.
+
+use std::fs;
+use std::path::Path;
+
+use super::{Question, TOPICS};
+
+const KEYS: &[&str] = &["snippet", "from", "item", "answer", "explanation", "topic"];
+
+// A `[[question]]` table: the line its header is on, and its keys.
+struct Table {
+    line: usize,
+    values: Vec<(String, String)>,
+}
+
+impl Table {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.values.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+pub fn parse(text: &str) -> Result<Vec<Question>, String> {
+    let mut tables: Vec<Table> = Vec::new();
+    let mut lines = text.lines().enumerate();
+    while let Some((i, line)) = lines.next() {
+        let error = |message: &str| format!("line {}: {message}", i + 1);
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line == "[[question]]" {
+            tables.push(Table { line: i + 1, values: Vec::new() });
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(|| error("expected `key = \"value\"`"))?;
+        let key = key.trim();
+        if !KEYS.contains(&key) {
+            return Err(error(&format!("unknown key `{key}`, expected one of {}", KEYS.join(", "))));
+        }
+        let value = value.trim();
+        let value = match value.strip_prefix("\"\"\"") {
+            Some(first) => {
+                // A newline straight after the opening quotes isn't part of
+                // the string.
+                let mut string = String::new();
+                let mut part = first;
+                let mut first_line = true;
+                loop {
+                    if let Some(end) = part.find("\"\"\"") {
+                        if !part[end + 3..].trim().is_empty() {
+                            return Err(error("unexpected text after the closing `\"\"\"`"));
+                        }
+                        string.push_str(&part[..end]);
+                        break;
+                    }
+                    if !(first_line && part.is_empty()) {
+                        string.push_str(part);
+                        string.push('\n');
+                    }
+                    first_line = false;
+                    part = match lines.next() {
+                        Some((_, line)) => line,
+                        None => return Err(error("this string's closing `\"\"\"` is missing")),
+                    };
+                }
+                unescape(&string).map_err(|e| error(&e))?
+            }
+            None => {
+                let inner = value.strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+                    .ok_or_else(|| error("expected a string in double quotes"))?;
+                unescape(inner).map_err(|e| error(&e))?
+            }
+        };
+        let table = tables.last_mut().ok_or_else(|| error("expected `[[question]]` first"))?;
+        if table.get(key).is_some() {
+            return Err(error(&format!("`{key}` is given twice")));
+        }
+        table.values.push((key.to_string(), value));
+    }
+
+    tables.iter().map(question).collect()
+}
+
+// The escapes TOML's basic strings have that snippets might need.
+fn unescape(s: &str) -> Result<String, String> {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some(other) => return Err(format!("unknown escape `\\{other}`")),
+            None => return Err("a string can't end with `\\`".to_string()),
+        });
+    }
+    Ok(out)
+}
+
+fn question(table: &Table) -> Result<Question, String> {
+    let error = |message: String| format!("question at line {}: {message}", table.line);
+    let required = |key| table.get(key).ok_or_else(|| error(format!("`{key}` is missing")));
+    let mut question = Question::new(required("snippet")?, required("from")?, required("item")?)
+        .map_err(error)?;
+    if let Some(answer) = table.get("answer") {
+        let answer = match answer {
+            "yes" => true,
+            "no" => false,
+            _ => return Err(error(format!("`answer` must be \"yes\" or \"no\", not \"{answer}\""))),
+        };
+        if answer != question.answer {
+            return Err(error(format!("`answer` is \"{}\", but it's \"{}\": {}", if answer { "yes" } else { "no" },
+                                     if question.answer { "yes" } else { "no" }, question.explanation)));
+        }
+    }
+    if let Some(explanation) = table.get("explanation") {
+        question.explanation = explanation.to_string();
+    }
+    if let Some(topic) = table.get("topic") {
+        question.topic = TOPICS.iter().find(|&&t| t == topic)
+            .ok_or_else(|| error(format!("unknown topic \"{topic}\", expected one of {}", TOPICS.join(", "))))?;
+    }
+    Ok(question)
+}
+
+// The questions in the bank at `path`.
+pub fn load(path: &Path) -> Result<Vec<Question>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    parse(&text).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+// The questions in every `.toml` bank in `dir`, in order of file name. No
+// directory means no questions.
+pub fn load_dir(dir: &Path) -> Result<Vec<Question>, String> {
+    let Ok(entries) = fs::read_dir(dir) else { return Ok(Vec::new()) };
+    let mut paths: Vec<_> = entries.filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|e| e == "toml"))
+        .collect();
+    paths.sort();
+    let mut questions = Vec::new();
+    for path in paths {
+        questions.extend(load(&path)?);
+    }
+    Ok(questions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables() {
+        let questions = parse("\
+# A comment.
+
+[[question]]
+snippet = \"\"\"
+mod a {
+    fn f() {}
+}
+\"\"\"
+from = \"crate\"
+item = \"crate::a::f\"
+answer = \"no\"
+
+[[question]]
+snippet = \"mod a { pub fn f() {} }\\nmod b {}\\n\"
+from = \"crate::b\"
+item = \"crate::a::f\"
+explanation = \"Because \\\"pub\\\".\"
+topic = \"paths\"
+").unwrap();
+        assert_eq!(questions.len(), 2);
+        assert_eq!(questions[0].snippet, "mod a {\n    fn f() {}\n}\n");
+        assert!(!questions[0].answer);
+        assert!(questions[0].explanation.starts_with("`f` is private in `crate::a`"), "{}", questions[0].explanation);
+        assert_eq!(questions[1].snippet, "mod a { pub fn f() {} }\nmod b {}\n");
+        assert!(questions[1].answer);
+        assert_eq!(questions[1].explanation, "Because \"pub\".");
+        assert_eq!((questions[0].topic, questions[1].topic), ("visibility", "paths"));
+    }
+
+    #[test]
+    fn errors() {
+        let error = |text: &str| parse(text).unwrap_err();
+        assert_eq!(error("snippet = \"\""), "line 1: expected `[[question]]` first");
+        assert_eq!(error("[[question]]\nanwser = \"no\""),
+                   "line 2: unknown key `anwser`, expected one of snippet, from, item, answer, explanation, topic");
+        assert_eq!(error("[[question]]\nfrom = crate"), "line 2: expected a string in double quotes");
+        assert_eq!(error("[[question]]\nsnippet = \"\"\"\nmod a {}\n"),
+                   "line 2: this string's closing `\"\"\"` is missing");
+        assert_eq!(error("[[question]]\nfrom = \"a\"\nfrom = \"b\""), "line 3: `from` is given twice");
+        assert_eq!(error("[[question]]\nfrom = \"\\q\""), "line 2: unknown escape `\\q`");
+        assert_eq!(error("\n[[question]]\nsnippet = \"mod a {}\"\nfrom = \"crate\""),
+                   "question at line 2: `item` is missing");
+        assert_eq!(error("[[question]]\nsnippet = \"mod a {}\"\nfrom = \"crate\"\nitem = \"crate::a::f\""),
+                   "question at line 1: `crate::a::f` doesn't resolve from `crate`: no `f` in `crate::a`");
+        assert_eq!(error("[[question]]\nsnippet = \"mod a { fn f() {} }\"\nfrom = \"crate\"\nitem = \"crate::a::f\"\n\
+                          answer = \"yes\""),
+                   "question at line 1: `answer` is \"yes\", but it's \"no\": `f` is private in `crate::a`, so it's \
+                    visible inside `crate::a`, and `crate` isn't.");
+        assert!(error("[[question]]\nsnippet = \"mod a { fn f() {} }\"\nfrom = \"crate\"\nitem = \"crate::a::f\"\n\
+                       answer = \"maybe\"").contains("`answer` must be \"yes\" or \"no\""));
+        assert_eq!(error("[[question]]\nsnippet = \"mod a { pub fn f() {} }\"\nfrom = \"crate\"\nitem = \"crate::a::f\"\n\
+                          topic = \"traits\""),
+                   "question at line 1: unknown topic \"traits\", expected one of visibility, paths, cfg, macros");
+        // The tests run in this crate's directory, where `src/cli.rs` is
+        // real, but a snippet's modules don't come from files.
+        assert_eq!(error("[[question]]\nsnippet = \"pub mod cli;\"\nfrom = \"crate\"\nitem = \"crate::cli::parse\""),
+                   "question at line 1: the snippet's `mod cli;` would load a file, so its modules must be inline, \
+                    like `mod cli { ... }`");
+    }
+}
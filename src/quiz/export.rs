@@ -0,0 +1,164 @@
+// Exporting the quiz's questions as a deck of flashcards, for `quiz
+// export`, so learners can review the rules with spaced repetition. A child
+// module of `../quiz.rs`.
+
+// Each card's front is a question's snippet and prompt, and its back the
+// answer and explanation, tagged with its topic. There are two formats:
+//
+// - `anki` is the tab-separated text Anki imports with File > Import. The
+//   `#` lines at the top tell it the separator, that fields are HTML, and
+//   which column has the tags, so it needs no settings changed. Fields are
+//   HTML so the snippet keeps its indentation in a `<pre>` block, with
+//   `<br>` for newlines, as a newline would end the card.
+// - `csv` is plain text, for other flashcard programs and spreadsheets,
+//   quoting any field with a comma, quote or newline as RFC 4180 says.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use super::Question;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeckFormat {
+    Anki,
+    Csv,
+}
+
+impl FromStr for DeckFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<DeckFormat, String> {
+        match s {
+            "anki" => Ok(DeckFormat::Anki),
+            "csv" => Ok(DeckFormat::Csv),
+            _ => Err(format!("unknown deck format '{s}', expected anki or csv")),
+        }
+    }
+}
+
+impl fmt::Display for DeckFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DeckFormat::Anki => "anki",
+            DeckFormat::Csv => "csv",
+        })
+    }
+}
+
+fn answer(question: &Question) -> &'static str {
+    if question.answer { "Yes." } else { "No." }
+}
+
+pub(crate) fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// `text` as HTML, with `code` in backticks in `<code>` tags.
+pub(crate) fn html(text: &str) -> String {
+    let mut out = String::new();
+    for (i, part) in escape_html(text).split('`').enumerate() {
+        if i % 2 == 1 {
+            out.push_str(&format!("<code>{part}</code>"));
+        } else {
+            out.push_str(part);
+        }
+    }
+    out
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// An Anki field's HTML, on one line: a tab would start the next field and a
+// newline the next card.
+fn one_line(html: &str) -> String {
+    html.replace('\t', "    ").replace("\r\n", "<br>").replace(['\n', '\r'], "<br>")
+}
+
+// A card for each of `questions`, in `format`.
+pub fn write(questions: &[Question], format: DeckFormat, out: &mut dyn Write) -> io::Result<()> {
+    match format {
+        DeckFormat::Anki => {
+            writeln!(out, "#separator:tab\n#html:true\n#tags column:3")?;
+            for question in questions {
+                let front = format!("<pre>{}</pre>{}", escape_html(question.snippet.trim_end()),
+                                    html(&question.prompt()));
+                let back = format!("<b>{}</b> {}", answer(question), html(question.explanation.trim_end()));
+                writeln!(out, "{}\t{}\trust-modules::{}", one_line(&front), one_line(&back), question.topic)?;
+            }
+        }
+        DeckFormat::Csv => {
+            writeln!(out, "front,back,topic")?;
+            for question in questions {
+                let front = format!("{}\n\n{}", question.snippet.trim_end(), question.prompt());
+                let back = format!("{} {}", answer(question), question.explanation);
+                writeln!(out, "{},{},{}", csv_field(&front), csv_field(&back), question.topic)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quiz::hand_written;
+
+    #[test]
+    fn anki() {
+        let mut out = Vec::new();
+        write(&hand_written()[..1], DeckFormat::Anki, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+#separator:tab
+#html:true
+#tags column:3
+<pre>mod kitchen {<br>    fn cook() {}<br>}<br>mod dining {}</pre>Can code in <code>crate::dining</code> call \
+<code>crate::kitchen::cook()</code>?\t<b>No.</b> Without <code>pub</code>, <code>cook</code> is private to \
+<code>kitchen</code>: only <code>kitchen</code> and the modules inside it can name it.\trust-modules::visibility
+");
+    }
+
+    #[test]
+    fn anki_multi_line() {
+        // Bank explanations can be over several lines.
+        let mut question = hand_written()[0].clone();
+        question.explanation = "First,\n\tthen\r\nlast.\n".to_string();
+        let mut out = Vec::new();
+        write(&[question], DeckFormat::Anki, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let card = out.lines().nth(3).unwrap();
+        assert_eq!(out.lines().count(), 4, "{out}");
+        assert_eq!(card.split('\t').count(), 3, "{card}");
+        assert!(card.contains("\t<b>No.</b> First,<br>    then<br>last.\t"), "{card}");
+    }
+
+    #[test]
+    fn csv() {
+        let mut question = hand_written()[2].clone();
+        question.explanation = "Said \"yes\", twice.".to_string();
+        let mut out = Vec::new();
+        write(&[question], DeckFormat::Csv, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+front,back,topic
+\"mod kitchen {
+    pub fn cook() {}
+}
+mod dining {}
+
+Can code in `crate::dining` call `crate::kitchen::cook()`?\",\"Yes. Said \"\"yes\"\", twice.\",visibility
+");
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(html("`a<b>` & \"c\""), "<code>a&lt;b&gt;</code> &amp; &quot;c&quot;");
+        assert_eq!("anki".parse(), Ok(DeckFormat::Anki));
+        assert!("apkg".parse::<DeckFormat>().is_err());
+    }
+}
@@ -0,0 +1,230 @@
+// The results of a quiz, saved as JSON after each one so learners and
+// instructors can see which topics need more work, and the table the
+// `report` command makes of them. A child module of `../quiz.rs`.
+
+// The file has a score for each of `TOPICS`, and each question asked with
+// whether it was answered right, as well as what's needed to take the same
+// quiz again:
+//
+//     {
+//       "seed": "42",
+//       "count": 10,
+//       "banks": ["/home/me/more-questions.toml"],
+//       "asked": 2,
+//       "right": 1,
+//       "topics": {
+//         "visibility": { "asked": 1, "right": 1 },
+//         "paths": { "asked": 1, "right": 0 },
+//         ...
+//       },
+//       "questions": [
+//         {
+//           "id": "…",
+//           "topic": "visibility",
+//           "from": "crate::dining",
+//           "item": "crate::kitchen::cook",
+//           "right": true
+//         },
+//         ...
+//       ]
+//     }
+//
+// The seed is a string because JSON numbers are `f64`s, which can't hold
+// every `u64`.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::{Question, TOPICS};
+use crate::json::Value;
+use crate::progress;
+
+// Where the last quiz's results are saved: beside the progress file.
+pub fn path() -> Option<PathBuf> {
+    progress::path().map(|path| path.with_file_name("quiz-results.json"))
+}
+
+// The results of a quiz of `questions` picked with `seed` from the
+// built-in questions and those in `banks`, where `right` is what `quiz::run`
+// returned, for the questions asked.
+pub fn results(questions: &[Question], right: &[bool], seed: u64, banks: &[String]) -> Value {
+    let asked: Vec<_> = questions.iter().zip(right.iter().copied()).collect();
+    let count_right = |answers: &[(&Question, bool)]| answers.iter().filter(|&&(_, right)| right).count();
+    let topics = TOPICS.iter().map(|&topic| {
+        let answers: Vec<_> = asked.iter().copied().filter(|(q, _)| q.topic == topic).collect();
+        (topic, Value::object([
+            ("asked", Value::from(answers.len())),
+            ("right", Value::from(count_right(&answers))),
+        ]))
+    });
+    let answered = asked.iter().map(|&(question, right)| Value::object([
+        ("id", Value::from(question.id())),
+        ("topic", Value::from(question.topic)),
+        ("from", Value::from(question.from.as_str())),
+        ("item", Value::from(question.item.as_str())),
+        ("right", Value::from(right)),
+    ]));
+    Value::object([
+        ("seed", Value::from(seed.to_string())),
+        ("count", Value::from(questions.len())),
+        ("banks", Value::from(banks.to_vec())),
+        ("asked", Value::from(right.len())),
+        ("right", Value::from(count_right(&asked))),
+        ("topics", Value::object(topics)),
+        ("questions", Value::Array(answered.collect())),
+    ])
+}
+
+pub fn save(path: &Path, results: &Value) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format!("{results}\n"))
+}
+
+pub fn load(path: &Path) -> io::Result<Value> {
+    let text = fs::read_to_string(path)?;
+    text.parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display())))
+}
+
+// A topic's name, how many of its questions were asked, and how many of
+// those were answered right.
+type Score<'a> = (&'a str, usize, usize);
+
+fn scores(results: &Value) -> Result<Vec<Score<'_>>, String> {
+    let Some(Value::Object(topics)) = results.get("topics") else {
+        return Err("no `topics` object".to_string());
+    };
+    topics.iter()
+        .map(|(topic, score)| {
+            let number = |key| score.get(key).and_then(Value::as_usize)
+                .ok_or(format!("topic `{topic}` has no `{key}` count"));
+            Ok((topic.as_str(), number("asked")?, number("right")?))
+        })
+        .collect()
+}
+
+pub(crate) fn percent(right: usize, asked: usize) -> String {
+    match (right * 100).checked_div(asked) {
+        Some(percent) => format!("{percent}%"),
+        None => "-".to_string(),
+    }
+}
+
+// A table of the score for each topic in `results`, then the weakest
+// topic, and the questions answered wrong.
+pub fn write(results: &Value, out: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, format!("not quiz results: {e}"));
+    let scores = scores(results).map_err(invalid)?;
+
+    writeln!(out, "{:12} {:>5} {:>5} {:>6}", "Topic", "Asked", "Right", "Score")?;
+    for &(topic, asked, right) in &scores {
+        writeln!(out, "{topic:12} {asked:>5} {right:>5} {:>6}", percent(right, asked))?;
+    }
+    let asked: usize = scores.iter().map(|&(_, asked, _)| asked).sum();
+    let right: usize = scores.iter().map(|&(_, _, right)| right).sum();
+    writeln!(out, "{:12} {asked:>5} {right:>5} {:>6}", "Total", percent(right, asked))?;
+
+    // The lowest score, the first if there's a tie.
+    let weakest = scores.iter()
+        .filter(|&&(_, asked, right)| right < asked)
+        .min_by_key(|&&(_, asked, right)| right * 100 / asked);
+    match weakest {
+        Some(&(topic, asked, right)) => writeln!(out, "\nWeakest: {topic}, at {}.", percent(right, asked))?,
+        None if asked > 0 => writeln!(out, "\nEvery question asked was answered right.")?,
+        None => writeln!(out, "\nNo questions were answered.")?,
+    }
+
+    let wrong: Vec<_> = match results.get("questions") {
+        Some(Value::Array(questions)) => questions.iter()
+            .filter(|q| q.get("right") == Some(&Value::Bool(false)))
+            .filter_map(|q| Some((q.get("topic")?.as_str()?, q.get("from")?.as_str()?, q.get("item")?.as_str()?)))
+            .collect(),
+        _ => Vec::new(),
+    };
+    if !wrong.is_empty() {
+        writeln!(out, "\nTo go over again:")?;
+        for (topic, from, item) in wrong {
+            writeln!(out, "  {topic}: can code in `{from}` call `{item}()`?")?;
+        }
+    }
+
+    if let (Some(seed), Some(count)) = (results.get("seed").and_then(Value::as_str),
+                                        results.get("count").and_then(Value::as_usize)) {
+        // The same seed only picks the same questions from the same banks.
+        let banks: String = match results.get("banks") {
+            Some(Value::Array(banks)) => {
+                banks.iter().filter_map(Value::as_str).map(|bank| format!(" --bank {bank}")).collect()
+            }
+            _ => String::new(),
+        };
+        writeln!(out, "\nTake the same quiz again with `quiz --seed {seed} --count {count}{banks}`.")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+    use crate::quiz::hand_written;
+
+    #[test]
+    fn table() {
+        // The first is about visibility and the fifth about paths.
+        let questions = hand_written();
+        let questions = [questions[0].clone(), questions[4].clone(), questions[1].clone()];
+        let results = results(&questions, &[true, false], 42, &[]);
+        assert_eq!(results.get("topics").unwrap().get("paths"), Some(&Value::object([
+            ("asked", Value::from(1usize)),
+            ("right", Value::from(0usize)),
+        ])));
+
+        let dir = TestDir::new("report_table", &[]);
+        let path = dir.path().join("results/quiz-results.json");
+        save(&path, &results).unwrap();
+        let mut out = Vec::new();
+        write(&load(&path).unwrap(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+Topic        Asked Right  Score
+visibility       1     1   100%
+paths            1     0     0%
+cfg              0     0      -
+macros           0     0      -
+Total            2     1    50%
+
+Weakest: paths, at 0%.
+
+To go over again:
+  paths: can code in `crate::dining` call `crate::kitchen::bake()`?
+
+Take the same quiz again with `quiz --seed 42 --count 3`.
+");
+    }
+
+    #[test]
+    fn all_right() {
+        let mut out = Vec::new();
+        write(&results(&hand_written()[..1], &[true], 1, &[]), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\nEvery question asked was answered right.\n"), "{out}");
+    }
+
+    #[test]
+    fn retake_with_banks() {
+        let banks = ["/tmp/a.toml".to_string(), "/tmp/b.toml".to_string()];
+        let mut out = Vec::new();
+        write(&results(&hand_written()[..1], &[true], 7, &banks), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with("`quiz --seed 7 --count 1 --bank /tmp/a.toml --bank /tmp/b.toml`.\n"), "{out}");
+    }
+
+    #[test]
+    fn not_results() {
+        let error = |text: &str| write(&text.parse().unwrap(), &mut Vec::new()).unwrap_err().to_string();
+        assert_eq!(error("[]"), "not quiz results: no `topics` object");
+        assert_eq!(error("{\"topics\": {\"cfg\": {\"asked\": 1}}}"),
+                   "not quiz results: topic `cfg` has no `right` count");
+    }
+}
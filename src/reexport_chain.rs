@@ -0,0 +1,88 @@
+// # Chains of re-exports
+
+// A `pub use` can re-export an item that was itself re-exported, so an item
+// deep in the tree can be surfaced one level at a time, all the way up to the
+// crate root. `lib.rs` finishes the chain with `pub use reexport_chain::deep_fn;`.
+
+pub mod outer {
+    pub mod middle {
+        pub mod inner {
+            // Defined three modules deep, at `crate::reexport_chain::outer::middle::inner::deep_fn`.
+            pub fn deep_fn() -> &'static str {
+                "deep_fn"
+            }
+        }
+
+        // Now also `crate::reexport_chain::outer::middle::deep_fn`.
+        pub use inner::deep_fn;
+    }
+
+    // Now also `crate::reexport_chain::outer::deep_fn`, re-exporting the re-export.
+    pub use middle::deep_fn;
+}
+
+// And `crate::reexport_chain::deep_fn`.
+pub use outer::deep_fn;
+
+// Each `pub use` adds a new path, but never a new item: there is still only
+// one function. Even its type name, which rustc derives from where the item
+// is defined, is the same through every path.
+pub fn type_name_of<T>(_: T) -> &'static str {
+    std::any::type_name::<T>()
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "reexport_chain" }
+
+    fn description(&self) -> &'static str {
+        "An item three modules deep re-exported level by level up to the crate root"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["use", "re-export"] }
+
+    fn topic(&self) -> Topic { Topic::Use }
+
+    fn source(&self) -> &'static str {
+        include_str!("reexport_chain.rs")
+    }
+
+    fn run(&self) {
+        println!("Calling `crate::deep_fn()` from the crate root: {:?}", crate::deep_fn());
+        println!("Its type name is {:?}", type_name_of(crate::deep_fn));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{outer, type_name_of};
+
+    #[test]
+    fn all_paths_are_one_function() {
+        let defined = outer::middle::inner::deep_fn as fn() -> &'static str;
+        let paths = [
+            outer::middle::deep_fn as fn() -> &'static str,
+            outer::deep_fn,
+            super::deep_fn,
+            crate::deep_fn,
+        ];
+        for path in paths {
+            assert!(std::ptr::fn_addr_eq(path, defined));
+            assert_eq!(path(), "deep_fn");
+        }
+    }
+
+    #[test]
+    fn type_name_is_the_definition_path() {
+        let defined = type_name_of(outer::middle::inner::deep_fn);
+        assert!(defined.ends_with("reexport_chain::outer::middle::inner::deep_fn"), "{defined}");
+        assert_eq!(type_name_of(outer::middle::deep_fn), defined);
+        assert_eq!(type_name_of(outer::deep_fn), defined);
+        assert_eq!(type_name_of(crate::deep_fn), defined);
+    }
+}
@@ -0,0 +1,85 @@
+// # Re-exporting a dependency
+
+// Dependencies declared in `Cargo.toml` are in scope everywhere in the crate
+// by their crate name, here `digits`. Any module could `use digits::Buffer;`
+// directly, but then every one of those modules depends on `digits`, and
+// swapping it for another crate means editing all of them.
+
+// A common pattern instead is a facade module: one place that re-exports
+// the items the rest of the crate needs with `pub use`, and wraps the others
+// in functions with our own names and signatures.
+pub mod numbers {
+    // Re-exported as is. Callers write `numbers::Buffer`, and never name `digits`.
+    pub use digits::Buffer;
+
+    // Re-exports can rename too, to match this crate's naming.
+    pub use digits::grouped as with_separator;
+
+    // Wrapped rather than re-exported, so the signature is ours to keep stable
+    // even if the dependency's changes.
+    pub fn with_commas(n: u64) -> String {
+        digits::grouped(n, ',')
+    }
+}
+
+// The rest of the crate only ever goes through the facade:
+pub mod report {
+    use super::numbers::{self, Buffer};
+
+    pub fn line(label: &str, n: u64) -> String {
+        let mut buffer = Buffer::new();
+        format!("{label}: {} ({})", buffer.format(n), numbers::with_commas(n))
+    }
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "reexport_dependency" }
+
+    fn description(&self) -> &'static str {
+        "Hiding a dependency behind the crate's own facade with `pub use`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["use", "re-export", "dependencies"] }
+
+    fn topic(&self) -> Topic { Topic::Use }
+
+    fn source(&self) -> &'static str {
+        include_str!("reexport_dependency.rs")
+    }
+
+    fn run(&self) {
+        println!("{}", report::line("Bytes in 1 GiB", 1 << 30));
+        println!("numbers::with_separator(9876543, '_') = {:?}",
+                 numbers::with_separator(9876543, '_'));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::numbers;
+
+    #[test]
+    fn call_through_reexports() {
+        let mut buffer = numbers::Buffer::new();
+        assert_eq!(buffer.format(1024), "1024");
+        assert_eq!(numbers::with_separator(1024, '.'), "1.024");
+        assert_eq!(numbers::with_commas(1_000_000), "1,000,000");
+    }
+
+    #[test]
+    fn reexport_is_the_same_item() {
+        // A re-export is a new name for the same item, not a copy.
+        let _: digits::Buffer = numbers::Buffer::new();
+    }
+
+    #[test]
+    fn report_line() {
+        assert_eq!(super::report::line("Total", 12345), "Total: 12345 (12,345)");
+    }
+}
@@ -0,0 +1,241 @@
+// The central list of examples.
+
+// Each module exports a unit struct `Demo` implementing `Example`, which is
+// named by its full path from the crate root in `../spec/examples.toml`. The
+// build script generates this list from there, see `./catalog.rs`.
+
+// With the `inventory` or `linkme` Cargo features, each module also registers
+// its own `Demo` with `register!`, and `find` searches those registrations
+// instead, see below.
+
+use crate::example::Example;
+
+pub static EXAMPLES: &[&dyn Example] = crate::catalog::EXAMPLES;
+
+// A Cargo feature that gates example modules.
+pub struct Feature {
+    pub name: &'static str,
+    // Whether the feature was enabled for this build.
+    pub enabled: bool,
+    // Names of the examples it gates, kept even when they aren't compiled in,
+    // so `list` can say what's missing.
+    pub examples: &'static [&'static str],
+}
+
+pub static FEATURES: &[Feature] = &[
+    Feature {
+        name: "advanced",
+        enabled: cfg!(feature = "advanced"),
+        examples: &["deprecated_reexport", "api", "reexport_chain"],
+    },
+];
+
+// Distributed registration: instead of this file naming every example, each
+// example module adds itself, next to its `Demo`:
+//
+//     crate::registry::register!(Demo);
+//
+// No module needs to know about any other, so adding an example touches just
+// one file. There are two popular crates for this, which work differently,
+// and this crate can use either:
+//
+// * `inventory`, with the `inventory` feature: each registration is a small
+//   function that the platform runs before `main`, like a C++ static
+//   constructor. It pushes the example onto a linked list. What's collected
+//   has to be a type of our own, so each example is wrapped in a
+//   `Registration`.
+// * `linkme`, with the `linkme` feature: each registration is a `static` in
+//   a named linker section, and the linker lays them all out as one slice.
+//   No code runs before `main`, and the result is a plain `&'static [T]`, but
+//   it needs more help from the linker, so it works on fewer targets.
+//
+// With neither feature, `register!` expands to nothing.
+macro_rules! register {
+    ($example:expr) => {
+        crate::registry::submit!($example);
+        crate::registry::add!($example);
+    };
+}
+
+#[cfg(feature = "inventory")]
+pub struct Registration(pub &'static dyn Example);
+
+#[cfg(feature = "inventory")]
+inventory::collect!(Registration);
+
+#[cfg(feature = "inventory")]
+macro_rules! submit {
+    ($example:expr) => {
+        inventory::submit!(crate::registry::Registration(&$example));
+    };
+}
+
+#[cfg(not(feature = "inventory"))]
+macro_rules! submit {
+    ($example:expr) => {};
+}
+
+#[cfg(feature = "linkme")]
+#[linkme::distributed_slice]
+pub static SLICE: [&'static dyn Example];
+
+#[cfg(feature = "linkme")]
+macro_rules! add {
+    ($example:expr) => {
+        // Each element needs a name, which the unnamed `const` keeps apart
+        // from every other module's.
+        const _: () = {
+            #[linkme::distributed_slice(crate::registry::SLICE)]
+            static EXAMPLE: &'static dyn crate::example::Example = &$example;
+        };
+    };
+}
+
+#[cfg(not(feature = "linkme"))]
+macro_rules! add {
+    ($example:expr) => {};
+}
+
+// Make the macros usable by path, `crate::registry::register!`, from modules
+// declared before this one. See `macros/reexport.rs`.
+pub(crate) use {add, register, submit};
+
+// The examples registered with `inventory`, in no particular order: that's up
+// to the linker. `EXAMPLES` still sets the order for `list` and `tour`.
+#[cfg(feature = "inventory")]
+pub fn registered() -> impl Iterator<Item = &'static dyn Example> {
+    inventory::iter::<Registration>.into_iter().map(|registration| registration.0)
+}
+
+// The runner looks examples up here, so with a registration feature it only
+// finds the ones that registered themselves. With both, `inventory` wins.
+pub fn find(name: &str) -> Option<&'static dyn Example> {
+    candidates().find(|e| e.name() == name)
+}
+
+#[cfg(feature = "inventory")]
+fn candidates() -> impl Iterator<Item = &'static dyn Example> {
+    registered()
+}
+
+#[cfg(all(feature = "linkme", not(feature = "inventory")))]
+fn candidates() -> impl Iterator<Item = &'static dyn Example> {
+    SLICE.iter().copied()
+}
+
+#[cfg(not(any(feature = "inventory", feature = "linkme")))]
+fn candidates() -> impl Iterator<Item = &'static dyn Example> {
+    EXAMPLES.iter().copied()
+}
+
+// Cargo example targets in `examples/`, which are separate programs rather
+// than entries in `EXAMPLES`. They're listed here by hand so `list` can
+// mention them; a test checks each one exists.
+pub struct ExampleTarget {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub static EXAMPLE_TARGETS: &[ExampleTarget] = &[
+    ExampleTarget {
+        name: "mini_app",
+        description: "a small app with its own module tree",
+    },
+];
+
+// Write one line per example: name, tags, then description, in aligned columns.
+pub fn write_list(out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    let tags = |e: &dyn Example| format!("[{}]", e.tags().join(", "));
+
+    let name_width = EXAMPLES.iter().map(|e| e.name().len()).max().unwrap_or(0);
+    let tags_width = EXAMPLES.iter().map(|e| tags(*e).len()).max().unwrap_or(0);
+
+    for e in EXAMPLES {
+        writeln!(out, "{:name_width$}  {:tags_width$}  {}",
+                 e.name(), tags(*e), e.description())?;
+    }
+
+    for feature in FEATURES.iter().filter(|f| !f.enabled) {
+        writeln!(out, "(not compiled in: {}; enable with `--features {}`)",
+                 feature.examples.join(", "), feature.name)?;
+    }
+
+    for target in EXAMPLE_TARGETS {
+        writeln!(out, "(also the example target `{}`, {}: `cargo run --example {}`)",
+                 target.name, target.description, target.name)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_unique() {
+        for (i, e) in EXAMPLES.iter().enumerate() {
+            assert!(EXAMPLES[i + 1..].iter().all(|other| other.name() != e.name()),
+                    "duplicate example name '{}'", e.name());
+        }
+    }
+
+    #[test]
+    fn find_by_name() {
+        assert_eq!(find("use_examples").map(|e| e.name()), Some("use_examples"));
+        assert!(find("nope").is_none());
+    }
+
+    #[test]
+    fn feature_examples_compiled_in_when_enabled() {
+        for feature in FEATURES {
+            for name in feature.examples {
+                assert_eq!(find(name).is_some(), feature.enabled,
+                           "example '{name}' of feature '{}'", feature.name);
+            }
+        }
+    }
+
+    // Every example in the central list registers itself, and nothing else does.
+    #[cfg(feature = "inventory")]
+    #[test]
+    fn registered_matches_list() {
+        let mut registered: Vec<_> = registered().map(|e| e.name()).collect();
+        let mut listed: Vec<_> = EXAMPLES.iter().map(|e| e.name()).collect();
+        registered.sort();
+        listed.sort();
+        assert_eq!(registered, listed);
+    }
+
+    // The same for `linkme`.
+    #[cfg(feature = "linkme")]
+    #[test]
+    fn slice_matches_list() {
+        let mut in_slice: Vec<_> = SLICE.iter().map(|e| e.name()).collect();
+        let mut listed: Vec<_> = EXAMPLES.iter().map(|e| e.name()).collect();
+        in_slice.sort();
+        listed.sort();
+        assert_eq!(in_slice, listed);
+    }
+
+    #[test]
+    fn example_targets_exist() {
+        let examples = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+        for target in EXAMPLE_TARGETS {
+            assert!(examples.join(target.name).join("main.rs").exists()
+                    || examples.join(format!("{}.rs", target.name)).exists(),
+                    "no example target '{}'", target.name);
+        }
+    }
+
+    #[test]
+    fn list_has_a_line_per_example() {
+        let mut out = Vec::new();
+        write_list(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let disabled = FEATURES.iter().filter(|f| !f.enabled).count();
+        assert_eq!(out.lines().count(), EXAMPLES.len() + disabled + EXAMPLE_TARGETS.len());
+        assert!(out.lines().any(|l| l.starts_with("use_examples ")
+                                    && l.contains("[use, re-export]")));
+    }
+}
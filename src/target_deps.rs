@@ -0,0 +1,84 @@
+// # Target-specific dependencies
+
+// Modules aren't the only thing that can depend on the target. `Cargo.toml`
+// can list dependencies that are only used on some targets:
+//
+//     [target.'cfg(unix)'.dependencies]
+//     unix_extras = { path = "crates/unix_extras" }
+//
+//     [target.'cfg(windows)'.dependencies]
+//     windows_extras = { path = "crates/windows_extras" }
+//
+// On Linux, `windows_extras` isn't built at all, and doesn't exist as far as
+// this crate is concerned. So every use of it must be behind a matching
+// `#[cfg(windows)]`, and the usual place for that is a module per target, as
+// with `platform` in `lib.rs`.
+
+// Unix-like targets get `unix_extras`.
+#[cfg(unix)]
+mod imp {
+    pub fn describe() -> String {
+        format!("built with `unix_extras`: parent process ID is {}", unix_extras::parent_pid())
+    }
+}
+
+// Windows gets `windows_extras`.
+#[cfg(windows)]
+mod imp {
+    pub fn describe() -> String {
+        format!("built with `windows_extras`: \"hi\" in UTF-16 is {:?}", windows_extras::to_wide("hi"))
+    }
+}
+
+// Other targets have neither dependency.
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    pub fn describe() -> String {
+        "built without a target-specific dependency".to_string()
+    }
+}
+
+pub use imp::describe;
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "target_deps" }
+
+    fn description(&self) -> &'static str {
+        "Dependencies only built for some targets, used from `cfg` modules"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["cfg", "dependencies"] }
+
+    fn topic(&self) -> Topic { Topic::Cfg }
+
+    fn source(&self) -> &'static str {
+        include_str!("target_deps.rs")
+    }
+
+    fn run(&self) {
+        println!("This build was {}.", describe());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    #[test]
+    fn unix_dependency() {
+        assert!(super::describe().starts_with("built with `unix_extras`"));
+        // The dependency is in scope here too, as it is everywhere in the crate.
+        assert_eq!(unix_extras::parent_pid(), std::os::unix::process::parent_id());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn windows_dependency() {
+        assert!(super::describe().starts_with("built with `windows_extras`"));
+    }
+}
@@ -0,0 +1,34 @@
+// # A binary with a custom path
+
+// Cargo finds `src/main.rs` and the files in `src/bin/` by itself. Any other
+// file can be a binary's crate root too, with a `[[bin]]` section in
+// `Cargo.toml` giving its name and path:
+//
+//     [[bin]]
+//     name = "modstats"
+//     path = "src/tools/entry.rs"
+//
+// Run it with `cargo run --bin modstats -- [PATH]`.
+
+// `mod` declarations are resolved relative to the crate root file, whatever
+// it's called and wherever it is. So this loads `./summary.rs`, next to this
+// file in `src/tools/`, just as `mod cli;` in `../main.rs` loads `../cli.rs`.
+// The library's `src/lib.rs` doesn't declare `tools`, so these files aren't
+// part of the library at all, even though they're under `src/`.
+mod summary;
+
+use std::path::Path;
+
+use modules_demo::modtree::{self, ModuleTree};
+
+fn main() {
+    let path = std::env::args().nth(1).unwrap_or_else(|| env!("CARGO_MANIFEST_DIR").to_string());
+    let tree = modtree::find_crate_root(Path::new(&path)).and_then(|root| ModuleTree::load(&root));
+    match tree {
+        Ok(tree) => print!("{}", summary::Summary::of(&tree)),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    }
+}
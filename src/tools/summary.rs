@@ -0,0 +1,53 @@
+// Counts of the modules in a crate, a child of `./entry.rs`.
+
+use std::fmt;
+
+use modules_demo::modtree::{ModuleTree, Visibility};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Summary {
+    pub modules: usize,
+    // Declared with `mod name { ... }`.
+    pub inline: usize,
+    // Declared `pub`, with no restriction.
+    pub public: usize,
+    // The most modules on the path from the root to any module, not counting the root.
+    pub depth: usize,
+}
+
+impl Summary {
+    pub fn of(tree: &ModuleTree) -> Summary {
+        let mut summary = Summary::default();
+        for id in tree.ids() {
+            let module = tree.module(id);
+            summary.modules += 1;
+            summary.inline += module.inline as usize;
+            summary.public += (id != tree.root() && module.vis == Visibility::Public) as usize;
+            summary.depth = summary.depth.max(tree.path(id).len() - 1);
+        }
+        summary
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "modules: {}", self.modules)?;
+        writeln!(f, "inline:  {}", self.inline)?;
+        writeln!(f, "pub:     {}", self.public)?;
+        writeln!(f, "depth:   {}", self.depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn workspace_member() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("crates/core/src/lib.rs");
+        let tree = ModuleTree::load(&root).unwrap();
+        // `example-core` has just its root and an inline, private `tests` module.
+        assert_eq!(Summary::of(&tree), Summary { modules: 2, inline: 1, public: 0, depth: 1 });
+    }
+}
@@ -0,0 +1,206 @@
+// The guided tour: steps through the examples in teaching order, showing each
+// one's annotated source and then running it. At each step the learner can
+// also run any example, or answer a quiz question.
+//
+// Everything that happens is returned as a list of `Event`s, which
+// `session` saves to replay later, so an instructor can script a lesson.
+
+use std::io::{self, BufRead, Write};
+
+// Saving and replaying tours, in `./tour/session.rs`.
+pub mod session;
+
+use crate::example::Example;
+use crate::quiz::{self, Question};
+use crate::registry;
+
+// Examples sorted by topic. The sort is stable, so examples on the same topic
+// keep their order from the registry.
+pub fn steps() -> Vec<&'static dyn Example> {
+    let mut steps = registry::EXAMPLES.to_vec();
+    steps.sort_by_key(|e| e.topic());
+    steps
+}
+
+// What happened during a tour.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    // The tour moved to an example, showing its source and output.
+    Shown(&'static str),
+    // An example run with `run <EXAMPLE>` at the prompt.
+    Ran(&'static str),
+    // A question asked with `quiz` at the prompt, and the answer, `None` if
+    // it was skipped.
+    Answered { question: Question, answer: Option<bool> },
+}
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    Next,
+    Prev,
+    Quit,
+    Run(String),
+    Quiz,
+}
+
+fn parse_action(line: &str) -> Option<Action> {
+    match line.trim() {
+        // Just pressing enter moves on.
+        "" | "n" | "next" => Some(Action::Next),
+        "p" | "prev" => Some(Action::Prev),
+        "q" | "quit" => Some(Action::Quit),
+        "quiz" => Some(Action::Quiz),
+        line => line.strip_prefix("run ").map(|name| Action::Run(name.trim().to_string())),
+    }
+}
+
+// Step `i` of `total`: its source, then its output.
+fn show(example: &dyn Example, i: usize, total: usize, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n=== [{}/{}] {}: {}", i + 1, total, example.name(), example.description())?;
+    writeln!(out)?;
+    write!(out, "{}", example.source())?;
+    run_example(example, out)
+}
+
+fn run_example(example: &dyn Example, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "--- Output of `run {}`:", example.name())?;
+    out.flush()?;
+    example.run();
+    Ok(())
+}
+
+// Run the tour, reading commands from `input` until the learner quits,
+// moves past the last example, or `input` ends. `quiz` asks the next of
+// `questions`. Returns what happened, in order.
+//
+// Examples print their own output to stdout, so `out` should normally be stdout too.
+pub fn run(questions: &[Question], input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<Vec<Event>> {
+    let steps = steps();
+    let mut i = 0;
+    let mut events = Vec::new();
+    let mut questions = questions.iter();
+
+    loop {
+        let example = steps[i];
+        show(example, i, steps.len(), out)?;
+        events.push(Event::Shown(example.name()));
+
+        loop {
+            write!(out, "\n[n]ext, [p]rev, [q]uit, run <EXAMPLE>, quiz > ")?;
+            out.flush()?;
+
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                writeln!(out)?;
+                return Ok(events);
+            }
+
+            match parse_action(&line) {
+                Some(Action::Next) if i + 1 < steps.len() => {
+                    i += 1;
+                    break;
+                }
+                Some(Action::Next) => {
+                    writeln!(out, "That was the last example, the tour is over.")?;
+                    return Ok(events);
+                }
+                Some(Action::Prev) if i > 0 => {
+                    i -= 1;
+                    break;
+                }
+                Some(Action::Prev) => writeln!(out, "This is the first example.")?,
+                Some(Action::Quit) => return Ok(events),
+                Some(Action::Run(name)) => match registry::find(&name) {
+                    Some(example) => {
+                        run_example(example, out)?;
+                        events.push(Event::Ran(example.name()));
+                    }
+                    None => writeln!(out, "There's no example '{name}', see `list`.")?,
+                },
+                Some(Action::Quiz) => match questions.next() {
+                    Some(question) => {
+                        writeln!(out, "--- Quiz:\n\n{}", question.snippet)?;
+                        let Some(answer) = quiz::ask(question, input, out)? else { return Ok(events) };
+                        events.push(Event::Answered { question: question.clone(), answer });
+                    }
+                    None => writeln!(out, "That was the last question.")?,
+                },
+                None => writeln!(out, "Please enter n, p, q, run <EXAMPLE> or quiz.")?,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tour(input: &str) -> String {
+        let mut out = Vec::new();
+        run(&[], &mut input.as_bytes(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn headers(out: &str) -> Vec<&str> {
+        out.lines()
+           .filter(|l| l.starts_with("=== "))
+           .map(|l| l.split(' ').nth(2).unwrap().trim_end_matches(':'))
+           .collect()
+    }
+
+    #[test]
+    fn steps_are_in_topic_order() {
+        let steps = steps();
+        assert_eq!(steps.len(), registry::EXAMPLES.len());
+        assert!(steps.windows(2).all(|w| w[0].topic() <= w[1].topic()));
+    }
+
+    #[test]
+    fn every_example_has_source() {
+        for e in registry::EXAMPLES {
+            assert!(!e.source().trim().is_empty(), "{} has no source", e.name());
+        }
+    }
+
+    #[test]
+    fn navigate() {
+        let first = steps()[0].name();
+        let second = steps()[1].name();
+        assert_eq!(headers(&tour("n\np\nwat\nq\n")), vec![first, second, first]);
+        assert_eq!(run(&[], &mut "n\np\nq\n".as_bytes(), &mut Vec::new()).unwrap(),
+                   [Event::Shown(first), Event::Shown(second), Event::Shown(first)]);
+    }
+
+    #[test]
+    fn run_and_quiz() {
+        let questions = quiz::hand_written();
+        let mut out = Vec::new();
+        let events = run(&questions[..1], &mut "run inline\nrun nope\nquiz\nmaybe\ny\nquiz\nq\n".as_bytes(),
+                         &mut out).unwrap();
+        assert_eq!(events, [
+            Event::Shown(steps()[0].name()),
+            Event::Ran("inline"),
+            Event::Answered { question: questions[0].clone(), answer: Some(true) },
+        ]);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("> --- Output of `run inline`:\n"), "{out}");
+        assert!(out.contains("> There's no example 'nope', see `list`.\n"), "{out}");
+        assert!(out.contains("> --- Quiz:\n\nmod kitchen {\n"), "{out}");
+        assert!(out.contains("[y/n] > Not quite, the answer is no."), "{out}");
+        assert!(out.contains("> That was the last question.\n"), "{out}");
+    }
+
+    #[test]
+    fn prev_at_start_stays_put() {
+        let out = tour("p\nq\n");
+        assert_eq!(headers(&out).len(), 1);
+        assert!(out.contains("This is the first example."));
+    }
+
+    #[test]
+    fn next_at_end_finishes() {
+        let out = tour(&"\n".repeat(steps().len()));
+        assert_eq!(headers(&out).len(), steps().len());
+        assert!(out.contains("the tour is over"));
+    }
+}
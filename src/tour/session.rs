@@ -0,0 +1,208 @@
+// Recording a tour to play back later, for `tour --record` and `tour
+// --replay`, so an instructor can go through a lesson once and show it
+// again, or write one by hand. A child module of `../tour.rs`.
+
+// A session is a JSON file listing the tour's `Event`s in order:
+//
+//     {
+//       "events": [
+//         { "shown": "a" },
+//         { "ran": "inline" },
+//         {
+//           "quiz": {
+//             "snippet": "mod kitchen {\n    fn cook() {}\n}\nmod dining {}\n",
+//             "from": "crate::dining",
+//             "item": "crate::kitchen::cook",
+//             "explanation": "Without `pub`, ...",
+//             "topic": "visibility"
+//           },
+//           "answer": "no"
+//         }
+//       ]
+//     }
+//
+// Quiz questions are saved whole rather than by id, so a session plays back
+// the same even where the question banks are different. As with banks, the
+// right answer isn't saved but worked out again, and `answer` is what was
+// answered: "yes", "no", or "skip".
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use super::{run_example, show, steps, Event};
+use crate::json::Value;
+use crate::quiz::{self, Question, TOPICS};
+use crate::registry;
+
+pub fn to_json(events: &[Event]) -> Value {
+    let events = events.iter().map(|event| match event {
+        Event::Shown(name) => Value::object([("shown", Value::from(*name))]),
+        Event::Ran(name) => Value::object([("ran", Value::from(*name))]),
+        Event::Answered { question, answer } => Value::object([
+            ("quiz", Value::object([
+                ("snippet", Value::from(question.snippet.as_str())),
+                ("from", Value::from(question.from.as_str())),
+                ("item", Value::from(question.item.as_str())),
+                ("explanation", Value::from(question.explanation.as_str())),
+                ("topic", Value::from(question.topic)),
+            ])),
+            ("answer", Value::from(match answer {
+                Some(true) => "yes",
+                Some(false) => "no",
+                None => "skip",
+            })),
+        ]),
+    });
+    Value::object([("events", Value::Array(events.collect()))])
+}
+
+pub fn from_json(session: &Value) -> Result<Vec<Event>, String> {
+    let Some(Value::Array(events)) = session.get("events") else {
+        return Err("expected an object with an `events` list".to_string());
+    };
+    events.iter().enumerate()
+        .map(|(i, event)| event_from_json(event).map_err(|e| format!("event {}: {e}", i + 1)))
+        .collect()
+}
+
+fn event_from_json(event: &Value) -> Result<Event, String> {
+    let example = |name: &Value| {
+        let name = name.as_str().ok_or("expected an example's name")?;
+        registry::find(name).map(|example| example.name()).ok_or(format!("no example '{name}'"))
+    };
+    if let Some(name) = event.get("shown") {
+        return Ok(Event::Shown(example(name)?));
+    }
+    if let Some(name) = event.get("ran") {
+        return Ok(Event::Ran(example(name)?));
+    }
+    let Some(asked) = event.get("quiz") else {
+        return Err("expected `shown`, `ran` or `quiz`".to_string());
+    };
+    let string = |key| asked.get(key).and_then(Value::as_str).ok_or(format!("the question has no `{key}`"));
+    let mut question = Question::new(string("snippet")?, string("from")?, string("item")?)?;
+    if let Ok(explanation) = string("explanation") {
+        question.explanation = explanation.to_string();
+    }
+    if let Ok(topic) = string("topic") {
+        question.topic = TOPICS.iter().find(|&&t| t == topic).ok_or(format!("unknown topic \"{topic}\""))?;
+    }
+    let answer = match event.get("answer").and_then(Value::as_str) {
+        Some("yes") => Some(true),
+        Some("no") => Some(false),
+        Some("skip") => None,
+        _ => return Err("`answer` must be \"yes\", \"no\" or \"skip\"".to_string()),
+    };
+    Ok(Event::Answered { question, answer })
+}
+
+pub fn save(path: &Path, events: &[Event]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format!("{}\n", to_json(events)))
+}
+
+pub fn load(path: &Path) -> io::Result<Vec<Event>> {
+    let text = fs::read_to_string(path)?;
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display()));
+    from_json(&text.parse().map_err(invalid)?).map_err(invalid)
+}
+
+// Plays `events` back as the tour showed them, pausing before each until
+// Enter is pressed on `input`. Once `input` ends, the rest plays without
+// pausing.
+pub fn replay(events: &[Event], input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+    let steps = steps();
+    let mut pausing = true;
+    for (i, event) in events.iter().enumerate() {
+        if i > 0 && pausing {
+            write!(out, "\n[Enter] to go on, [q]uit > ")?;
+            out.flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                writeln!(out)?;
+                pausing = false;
+            } else if matches!(line.trim(), "q" | "quit") {
+                return Ok(());
+            }
+        }
+        match event {
+            Event::Shown(name) => {
+                let step = steps.iter().position(|e| e.name() == *name).expect("every example is a step");
+                show(steps[step], step, steps.len(), out)?;
+            }
+            Event::Ran(name) => {
+                let example = registry::find(name).expect("`Event`s only name examples");
+                writeln!(out, "\n> run {name}")?;
+                run_example(example, out)?;
+            }
+            Event::Answered { question, answer } => {
+                let typed = match answer {
+                    Some(true) => "y",
+                    Some(false) => "n",
+                    None => "",
+                };
+                writeln!(out, "\n> quiz\n--- Quiz:\n\n{}{} [y/n] > {typed}", question.snippet, question.prompt())?;
+                writeln!(out, "{}", quiz::verdict(question, *answer))?;
+            }
+        }
+    }
+    writeln!(out, "\nThat's the end of the session.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+
+    #[test]
+    fn round_trip() {
+        let mut question = quiz::hand_written()[4].clone();
+        question.explanation = "Re-exported.".to_string();
+        let events = vec![
+            Event::Shown("a"),
+            Event::Ran("inline"),
+            Event::Answered { question, answer: None },
+            Event::Answered { question: quiz::hand_written()[0].clone(), answer: Some(false) },
+        ];
+        let dir = TestDir::new("session_round_trip", &[]);
+        let path = dir.path().join("lessons/session.json");
+        save(&path, &events).unwrap();
+        assert_eq!(load(&path).unwrap(), events);
+        assert!(fs::read_to_string(&path).unwrap().starts_with("{\n  \"events\": [\n    {\n      \"shown\": \"a\"\n"));
+    }
+
+    #[test]
+    fn errors() {
+        let error = |text: &str| from_json(&text.parse().unwrap()).unwrap_err();
+        assert_eq!(error("[]"), "expected an object with an `events` list");
+        assert_eq!(error(r#"{"events": [{"shown": "a"}, {"shown": "nope"}]}"#), "event 2: no example 'nope'");
+        assert_eq!(error(r#"{"events": [{"went": "a"}]}"#), "event 1: expected `shown`, `ran` or `quiz`");
+        assert_eq!(error(r#"{"events": [{"quiz": {"snippet": "mod a {}", "from": "crate"}}]}"#),
+                   "event 1: the question has no `item`");
+        assert_eq!(error(r#"{"events": [{"quiz": {"snippet": "mod a { pub fn f() {} }", "from": "crate",
+                                                   "item": "crate::a::f"}, "answer": "maybe"}]}"#),
+                   "event 1: `answer` must be \"yes\", \"no\" or \"skip\"");
+    }
+
+    #[test]
+    fn play_back() {
+        let question = quiz::hand_written()[0].clone();
+        let events = [Event::Ran("inline"), Event::Answered { question, answer: Some(false) }, Event::Ran("a")];
+        let mut out = Vec::new();
+        replay(&events, &mut "\n".as_bytes(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\n> run inline\n--- Output of `run inline`:\n\n[Enter] to go on, [q]uit > \n\
+                                 > quiz\n--- Quiz:\n\nmod kitchen {"), "{out}");
+        assert!(out.contains("call `crate::kitchen::cook()`? [y/n] > n\nRight, no. Without `pub`"), "{out}");
+        // The input ended at the second pause, so there isn't a third.
+        assert!(out.ends_with("[Enter] to go on, [q]uit > \n\n> run a\n--- Output of `run a`:\n\n\
+                               That's the end of the session.\n"), "{out}");
+
+        let mut out = Vec::new();
+        replay(&events, &mut "q\n".as_bytes(), &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().ends_with("[Enter] to go on, [q]uit > "));
+    }
+}
@@ -0,0 +1,219 @@
+// A terminal browser for the example catalog: the module tree on the left,
+// and the source of the selected module on the right.
+
+// This is only compiled with the `tui` Cargo feature, see `lib.rs`, which
+// brings in `ratatui` to draw it. The terminal is in raw mode while it runs,
+// so each key takes effect as it's pressed.
+//
+// `Browser` holds the state, changes it for each key, and draws it into a
+// `Frame`. Only `run` touches the real terminal, so the tests draw into
+// ratatui's `TestBackend` instead.
+
+use std::io;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListState, Paragraph};
+use ratatui::Frame;
+
+use crate::modtree::{ModuleId, ModuleTree};
+use crate::registry;
+
+const HELP: &str = "↑/↓ j/k: select  PgUp/PgDn: scroll source  Enter r: run example  q: quit";
+
+pub struct Browser<'a> {
+    tree: &'a ModuleTree,
+    // Each row of the left pane: a module and its indented name.
+    rows: Vec<(ModuleId, String)>,
+    selected: usize,
+    // The first source line shown in the right pane.
+    scroll: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    Continue,
+    Run(&'static str),
+    Quit,
+}
+
+impl<'a> Browser<'a> {
+    pub fn new(tree: &'a ModuleTree) -> Browser<'a> {
+        let mut rows = Vec::new();
+        add_rows(tree, tree.root(), 0, &mut rows);
+        Browser { tree, rows, selected: 0, scroll: 0 }
+    }
+
+    pub fn selected(&self) -> ModuleId {
+        self.rows[self.selected].0
+    }
+
+    // The source shown for the selected module: the `mod` block for inline
+    // modules, or the whole file.
+    fn source(&self) -> &'a str {
+        let tree = self.tree;
+        let module = tree.module(self.selected());
+        match (module.file, module.decl) {
+            (Some(_), Some(decl)) if module.inline => tree.span_text(decl),
+            (Some(file), _) => &tree.file(file).text,
+            (None, _) => "(file not found)",
+        }
+    }
+
+    // The example with the same name as the selected module, if there is one.
+    fn example(&self) -> Option<&'static str> {
+        registry::find(&self.tree.module(self.selected()).name).map(|e| e.name())
+    }
+
+    pub fn handle(&mut self, key: KeyCode) -> Action {
+        match key {
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.rows.len() - 1);
+                self.scroll = 0;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                self.scroll = 0;
+            }
+            KeyCode::PageDown | KeyCode::Char('J') => {
+                let lines = self.source().lines().count();
+                self.scroll = (self.scroll + 10).min(lines.saturating_sub(1));
+            }
+            KeyCode::PageUp | KeyCode::Char('K') => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Enter | KeyCode::Char('r') => {
+                if let Some(name) = self.example() {
+                    return Action::Run(name);
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Action::Quit,
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    // The tree and source side by side, then a status line and the keys.
+    pub fn draw(&self, frame: &mut Frame) {
+        let [body, status, help] = Layout::vertical([Constraint::Min(0), Constraint::Length(1), Constraint::Length(1)])
+            .areas(frame.area());
+        let left_width = (body.width / 3).clamp(10, 40);
+        let [left, right] = Layout::horizontal([Constraint::Length(left_width), Constraint::Min(0)]).areas(body);
+
+        let rows = List::new(self.rows.iter().map(|(_, text)| text.as_str()))
+            .highlight_style(Style::new().reversed());
+        // The list scrolls itself to keep the selected row on screen.
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(rows, left, &mut state);
+
+        let source = self.source().replace('\t', "    ");
+        let source = Paragraph::new(source)
+            .block(Block::new().borders(ratatui::widgets::Borders::LEFT))
+            .scroll((self.scroll.try_into().unwrap_or(u16::MAX), 0));
+        frame.render_widget(source, right);
+
+        let module = self.tree.path_string(self.selected());
+        let line = match self.example() {
+            Some(name) => format!("{module} (Enter runs example `{name}`)"),
+            None => module,
+        };
+        frame.render_widget(Line::from(line).bold(), status);
+        frame.render_widget(Line::from(HELP).dim(), help);
+    }
+}
+
+fn add_rows(tree: &ModuleTree, id: ModuleId, depth: usize, rows: &mut Vec<(ModuleId, String)>) {
+    rows.push((id, format!("{:1$}{2}", "", depth * 2, tree.module(id).name)));
+    for &child in &tree.module(id).children {
+        add_rows(tree, child, depth + 1, rows);
+    }
+}
+
+// Browse until the learner quits. Running an example leaves the browser
+// for the example's output, until Enter is pressed.
+pub fn run(tree: &ModuleTree) -> io::Result<()> {
+    let mut browser = Browser::new(tree);
+    // Raw mode, on a screen of its own, until `restore`. It's restored if
+    // anything panics, too.
+    let mut terminal = ratatui::init();
+    let result = loop {
+        if let Err(e) = terminal.draw(|frame| browser.draw(frame)) {
+            break Err(e);
+        }
+        let key = match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => key.code,
+            Ok(_) => continue,
+            Err(e) => break Err(e),
+        };
+        match browser.handle(key) {
+            Action::Continue => {}
+            Action::Run(name) => {
+                ratatui::restore();
+                registry::find(name).expect("example came from the registry").run();
+                println!("\nPress Enter to return to the browser.");
+                io::stdin().read_line(&mut String::new())?;
+                terminal = ratatui::init();
+            }
+            Action::Quit => break Ok(()),
+        }
+    };
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modtree::test_dir::TestDir;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn fixture(name: &str) -> (TestDir, ModuleTree) {
+        let dir = TestDir::new(name, &[
+            ("src/lib.rs", "mod a;\nmod inline {\n    fn f() {}\n}\n"),
+            ("src/a.rs", "// In a.rs\nfn a() {}\n"),
+        ]);
+        let tree = ModuleTree::load(&dir.path().join("src/lib.rs")).unwrap();
+        (dir, tree)
+    }
+
+    // The screen's text, a line for each row.
+    fn screen(browser: &Browser, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| browser.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect::<String>().trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn render_panes() {
+        let (_dir, tree) = fixture("tui_render");
+        let mut browser = Browser::new(&tree);
+        assert_eq!(browser.handle(KeyCode::Down), Action::Continue);
+
+        assert_eq!(screen(&browser, 40, 5), vec![
+            "crate        │// In a.rs",
+            "  a          │fn a() {}",
+            "  inline     │",
+            "crate::a (Enter runs example `a`)",
+            "↑/↓ j/k: select  PgUp/PgDn: scroll sourc",
+        ]);
+    }
+
+    #[test]
+    fn inline_module_source() {
+        let (_dir, tree) = fixture("tui_inline");
+        let mut browser = Browser::new(&tree);
+        browser.handle(KeyCode::Char('j'));
+        browser.handle(KeyCode::Down);
+        browser.handle(KeyCode::Down);
+        assert_eq!(browser.source(), "mod inline {\n    fn f() {}\n}");
+        // Scrolling stops at the last line.
+        browser.handle(KeyCode::PageDown);
+        assert_eq!(screen(&browser, 40, 5)[0], "crate        │}");
+        assert_eq!(browser.handle(KeyCode::Enter), Action::Run("inline"));
+        assert_eq!(browser.handle(KeyCode::Char('q')), Action::Quit);
+    }
+}
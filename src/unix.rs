@@ -1 +0,0 @@
-pub const FAMILY: &'static str = "unix";
@@ -0,0 +1,8 @@
+// Selected when none of the other platform modules match, e.g. when building
+// for FreeBSD or Android.
+
+// Without this file there would simply be no `platform` module, and the
+// build would fail wherever it's used, with errors like "failed to resolve:
+// use of undeclared crate or module `platform`". Failing here instead gives
+// one clear message naming the actual problem.
+compile_error!("unsupported platform: this crate supports Linux, macOS, Windows and wasm32");
@@ -0,0 +1,16 @@
+// `pub` and private, shown in `name_resolution` in `lib.rs`, are the most
+// common visibilities. Rust also has restricted forms of `pub` that make an
+// item visible to only part of the crate. Each child module here
+// demonstrates one of them.
+
+pub mod crate_boundary;
+pub mod enum_variants;
+pub mod newtype_invariants;
+pub mod private_in_public;
+pub mod pub_crate;
+pub mod pub_in_path;
+pub mod pub_self;
+pub mod sealed_trait;
+pub mod struct_visibility;
+pub mod tuple_struct;
+pub mod pub_super;
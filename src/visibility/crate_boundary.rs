@@ -0,0 +1,73 @@
+// # Crate boundaries
+
+// Every example so far is about modules within this one crate. A crate is
+// also a visibility boundary: `pub(crate)` items, and anything private, can't
+// be used by any other crate, while `pub` items can be used by all of them.
+
+// This repository is a Cargo workspace, so it can show that with crates of
+// its own: see `../../crates/core/`, `../../crates/examples-extra/` and
+// `../../crates/cli/`. This crate depends on `example-core` too.
+
+// The workspace is declared in the root `Cargo.toml`:
+//
+//     [workspace]
+//     members = ["crates/*"]
+//
+// All the members share one `Cargo.lock` and one `target/` directory, and
+// `cargo test --workspace` tests them all. None of that changes visibility:
+// `example_core::shape_name` is `pub(crate)`, so calling it here is error
+// E0603, exactly as if `example-core` came from crates.io.
+
+use example_core::Shape;
+
+// Some rules only apply between crates. `Shape` is `#[non_exhaustive]`, so
+// outside `example_core` a `match` on it needs a `_` arm.
+pub fn corners(shape: &Shape) -> u32 {
+    match shape {
+        Shape::Circle { .. } => 0,
+        Shape::Square { .. } => 4,
+        _ => 0,
+    }
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "crate_boundary" }
+
+    fn description(&self) -> &'static str {
+        "Crates in a workspace only see each other's `pub` items"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["visibility", "crates", "workspace"] }
+
+    fn topic(&self) -> Topic { Topic::Visibility }
+
+    fn source(&self) -> &'static str {
+        include_str!("crate_boundary.rs")
+    }
+
+    fn run(&self) {
+        let square = Shape::Square { side: 1.5 };
+        println!("example_core::describe, a `pub` fn from another crate: {}",
+                 example_core::describe(&square));
+        println!("corners(&square) = {}, with a `_` arm for the `#[non_exhaustive]` enum",
+                 corners(&square));
+        println!("Try `cargo run -p example-cli` for a binary using the workspace's crates.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_another_crate() {
+        assert_eq!(corners(&Shape::Square { side: 1.0 }), 4);
+        assert_eq!(example_core::describe(&Shape::Square { side: 1.0 }), "square with area 1.00");
+    }
+}
@@ -0,0 +1,147 @@
+// # Enum variants
+
+// Unlike struct fields, enum variants always have the same visibility as
+// their enum, and so do the fields inside them. A visibility modifier on a
+// variant is a compile error, e.g. `pub enum Light { pub Red }`.
+
+pub mod traffic {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum Light {
+        Red,
+        Amber,
+        Green,
+    }
+
+    // The `reason` field is public too, because `Signal` is.
+    pub enum Signal {
+        Go,
+        Stop { reason: String },
+    }
+}
+
+// Variants are namespaced under their enum, so are usually written
+// `Light::Red`. They are items in their own right though, so they can be
+// imported with `use` like any other item.
+
+pub mod controller {
+    use super::traffic::Light;
+
+    // A glob import brings in every variant. This is convenient in a `match`,
+    // but beware: a misspelled variant name in a pattern like `Rde => ...`
+    // silently becomes a catch-all variable binding instead of an error.
+    // Limit glob imports like this to small scopes, such as a single function.
+    pub fn next(light: Light) -> Light {
+        use Light::*;
+
+        match light {
+            Red => Green,
+            Green => Amber,
+            Amber => Red,
+        }
+    }
+}
+
+pub mod display {
+    // `self` imports the enum, alongside one variant imported by name.
+    use super::traffic::Light::{self, Red};
+    use super::traffic::Signal;
+
+    pub fn describe(light: Light) -> &'static str {
+        match light {
+            Red => "stop",
+            Light::Amber => "get ready",
+            Light::Green => "go",
+        }
+    }
+
+    pub fn signal(light: Light) -> Signal {
+        match light {
+            Red => Signal::Stop { reason: "red light".to_string() },
+            _ => Signal::Go,
+        }
+    }
+}
+
+// A private enum's variants are private too. Outside `hidden`, naming
+// `hidden::Secret::Hidden` will not compile, error E0603: "enum `Secret` is
+// private", as this `compile_fail` doctest checks:
+
+/// ```compile_fail,E0603
+/// mod hidden {
+///     enum Secret {
+///         Hidden,
+///     }
+/// }
+///
+/// fn main() {
+///     let _ = hidden::Secret::Hidden;
+/// }
+/// ```
+mod hidden {
+    #[derive(Debug)]
+    enum Secret {
+        Hidden,
+    }
+
+    pub fn describe() -> String {
+        format!("{:?}", Secret::Hidden)
+    }
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "enum_variants" }
+
+    fn description(&self) -> &'static str {
+        "Enum variants share their enum's visibility and can be imported with `use`"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["visibility", "use", "enums"] }
+
+    fn topic(&self) -> Topic { Topic::Visibility }
+
+    fn source(&self) -> &'static str {
+        include_str!("enum_variants.rs")
+    }
+
+    fn run(&self) {
+        let mut light = traffic::Light::Red;
+        for _ in 0..3 {
+            println!("{:?}: {}", light, display::describe(light));
+            light = controller::next(light);
+        }
+        if let traffic::Signal::Stop { reason } = display::signal(traffic::Light::Red) {
+            println!("Stopping for a {reason}, read from a variant's field in another module.");
+        }
+        println!("A private enum can still be used in its own module: {}", hidden::describe());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Variants can be imported in tests too.
+    use super::traffic::Light::{Amber, Green, Red};
+    use super::traffic::Signal;
+    use super::{controller, display};
+
+    #[test]
+    fn cycle() {
+        assert_eq!(controller::next(Red), Green);
+        assert_eq!(controller::next(Green), Amber);
+        assert_eq!(controller::next(Amber), Red);
+    }
+
+    #[test]
+    fn variant_fields_are_public() {
+        match display::signal(Red) {
+            Signal::Stop { reason } => assert_eq!(reason, "red light"),
+            Signal::Go => panic!("expected Stop"),
+        }
+        assert!(matches!(display::signal(Green), Signal::Go));
+    }
+}
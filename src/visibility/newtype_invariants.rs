@@ -0,0 +1,141 @@
+// # Invariants enforced by module privacy
+
+// A newtype with a private field can promise things about its contents that
+// the compiler alone can't check, like "this string is never empty".
+// Privacy is per module, so the only code that can break the promise is in
+// the defining module, and that's the only code that needs reviewing.
+
+pub mod non_empty {
+    use std::fmt;
+
+    // The field is private, so outside this module a `NonEmptyString` can
+    // only come from `new`, and can't be changed afterwards except through
+    // the methods below.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct NonEmptyString(String);
+
+    #[derive(Debug, PartialEq)]
+    pub struct EmptyError;
+
+    impl fmt::Display for EmptyError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "string is empty")
+        }
+    }
+
+    impl NonEmptyString {
+        // The only constructor, which checks the invariant.
+        pub fn new(s: &str) -> Result<NonEmptyString, EmptyError> {
+            if s.is_empty() {
+                Err(EmptyError)
+            } else {
+                Ok(NonEmptyString(s.to_string()))
+            }
+        }
+
+        pub fn as_str(&self) -> &str {
+            &self.0
+        }
+
+        // Mutation is allowed only in ways that keep the invariant.
+        pub fn push_str(&mut self, s: &str) {
+            self.0.push_str(s);
+        }
+
+        // Always succeeds, as the string has at least one character.
+        pub fn first_char(&self) -> char {
+            self.0.chars().next().expect("NonEmptyString is never empty")
+        }
+    }
+}
+
+// Outside `non_empty`, all of these fail to compile, and the doctests on
+// `Attempts` check that they do:
+//
+// * Building one directly: `NonEmptyString(String::new())`, error E0603.
+// * Emptying one: `s.0.clear()`, error E0616.
+
+pub mod attempts {
+    /// ```compile_fail,E0603
+    /// mod non_empty {
+    ///     pub struct NonEmptyString(String);
+    /// }
+    ///
+    /// let s = non_empty::NonEmptyString(String::new());
+    /// ```
+    ///
+    /// ```compile_fail,E0616
+    /// mod non_empty {
+    ///     pub struct NonEmptyString(String);
+    ///
+    ///     pub fn new(s: &str) -> NonEmptyString {
+    ///         assert!(!s.is_empty());
+    ///         NonEmptyString(s.to_string())
+    ///     }
+    /// }
+    ///
+    /// let mut s = non_empty::new("text");
+    /// s.0.clear();
+    /// ```
+    pub struct Attempts;
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "newtype_invariants" }
+
+    fn description(&self) -> &'static str {
+        "A private newtype field lets a module enforce an invariant"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["visibility", "structs"] }
+
+    fn topic(&self) -> Topic { Topic::Visibility }
+
+    fn source(&self) -> &'static str {
+        include_str!("newtype_invariants.rs")
+    }
+
+    fn run(&self) {
+        use non_empty::NonEmptyString;
+
+        match NonEmptyString::new("") {
+            Ok(_) => unreachable!(),
+            Err(e) => println!("NonEmptyString::new(\"\") failed: {e}"),
+        }
+        let mut s = NonEmptyString::new("module").expect("not empty");
+        s.push_str("s");
+        println!("NonEmptyString::new(\"module\") then push_str(\"s\"): {:?}, first char {:?}",
+                 s.as_str(), s.first_char());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // This test module is a child of `newtype_invariants`, so a sibling of
+    // `non_empty`, and has no more access to its private field than any
+    // other module outside it.
+    use super::non_empty::{EmptyError, NonEmptyString};
+
+    #[test]
+    fn new_validates() {
+        assert_eq!(NonEmptyString::new(""), Err(EmptyError));
+        assert_eq!(NonEmptyString::new("a").unwrap().as_str(), "a");
+    }
+
+    #[test]
+    fn mutation_keeps_invariant() {
+        let mut s = NonEmptyString::new("a").unwrap();
+        s.push_str("");
+        s.push_str("b");
+        assert_eq!(s.as_str(), "ab");
+        assert_eq!(s.first_char(), 'a');
+
+        // `s.0.clear()` would not compile here; see the doctests on `Attempts`.
+    }
+}
@@ -0,0 +1,128 @@
+// # Private types in public interfaces
+
+// An item's signature mentions other types, and those types can be less
+// visible than the item itself. A `pub fn` returning a private struct can
+// be called from places that can't name what it returns.
+
+// Older versions of Rust rejected that outright with error E0446, "private
+// type in public interface". Since Rust 1.74 it's a warning instead, from
+// the `private_interfaces` lint, and E0446 remains only for cases that could
+// really leak a private type, such as a public trait's associated type.
+// Both are checked by the `compile_fail` doctests on `Tokens` below.
+
+pub mod leaky {
+    /// A `pub fn` returning a private type warns, and fails if the lint is
+    /// denied:
+    ///
+    /// ```compile_fail
+    /// #![deny(private_interfaces)]
+    ///
+    /// pub mod leaky {
+    ///     struct Token(u32);
+    ///
+    ///     pub fn issue() -> Token {
+    ///         Token(1)
+    ///     }
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// A private type as an associated type in a public trait impl is still
+    /// error E0446:
+    ///
+    /// ```compile_fail,E0446
+    /// pub trait Issuer {
+    ///     type Token;
+    /// }
+    ///
+    /// struct Token(u32);
+    ///
+    /// impl Issuer for u8 {
+    ///     type Token = Token;
+    /// }
+    /// # fn main() {}
+    /// ```
+    pub struct Tokens;
+}
+
+// The fix is to make the visibilities consistent: either make the type as
+// visible as the function, or make the function only as visible as the type.
+// Here both are `pub(crate)`, so neither can escape the crate.
+
+pub mod consistent {
+    pub(crate) struct Token(u32);
+
+    impl Token {
+        pub(crate) fn id(&self) -> u32 {
+            self.0
+        }
+    }
+
+    pub(crate) fn issue(id: u32) -> Token {
+        Token(id)
+    }
+
+    // A public function can still use the type internally, as long as it
+    // doesn't appear in the signature.
+    pub fn issue_id(id: u32) -> u32 {
+        issue(id).id()
+    }
+
+    // The associated type case works the same way: a `pub(crate)` trait
+    // may use a `pub(crate)` type.
+    pub(crate) trait Issuer {
+        type Token;
+
+        fn issue(&self) -> Self::Token;
+    }
+
+    impl Issuer for u32 {
+        type Token = Token;
+
+        fn issue(&self) -> Token {
+            Token(*self)
+        }
+    }
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "private_in_public" }
+
+    fn description(&self) -> &'static str {
+        "Types in a public signature should be at least as visible as it"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["visibility"] }
+
+    fn topic(&self) -> Topic { Topic::Visibility }
+
+    fn source(&self) -> &'static str {
+        include_str!("private_in_public.rs")
+    }
+
+    fn run(&self) {
+        use consistent::Issuer;
+
+        println!("consistent::issue(7).id() = {}", consistent::issue(7).id());
+        println!("consistent::issue_id(8) = {}", consistent::issue_id(8));
+        println!("9.issue().id() = {}", 9u32.issue().id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::consistent::{self, Issuer};
+
+    #[test]
+    fn crate_visible_types_are_usable_across_the_crate() {
+        assert_eq!(consistent::issue(1).id(), 1);
+        assert_eq!(consistent::issue_id(2), 2);
+        assert_eq!(3u32.issue().id(), 3);
+    }
+}
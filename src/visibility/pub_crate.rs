@@ -0,0 +1,106 @@
+// # `pub(crate)`
+
+// `pub(crate)` makes an item visible anywhere in the current crate, but not
+// to other crates. It suits helpers shared between distant parts of a crate
+// that shouldn't become part of its public API.
+
+// For an item to be reachable its module must be too, so `counter` is
+// `pub(crate)` as well.
+pub(crate) mod counter {
+    // Any module in the crate can call this as `crate::visibility::pub_crate::counter::next`.
+    //
+    // No other crate can, and a doctest is another crate, so it can check
+    // that. This crate's path to `next` has private modules on it, which
+    // would fail for the wrong reason, so the doctest uses
+    // `example_core::shape_name` instead: `pub(crate)`, on an all-`pub` path,
+    // in `../../crates/core/`.
+    ///
+    /// ```compile_fail,E0603
+    /// example_core::shape_name(&example_core::Shape::Square { side: 1.0 });
+    /// ```
+    pub(crate) fn next(current: u32) -> u32 {
+        current + 1
+    }
+
+    // Private, so only `counter` and its children can call this.
+    fn start() -> u32 {
+        0
+    }
+
+    pub(crate) fn first() -> u32 {
+        next(start())
+    }
+}
+
+// Another crate, like the binary in `main.rs`, can't call `counter::next`,
+// even if every module on the path to it were `pub`:
+//
+//     modules_demo::visibility::pub_crate::counter::next(1);
+//
+// The compiler reports error E0603: "function `next` is private". The
+// doctest on `next` checks that for a `pub(crate)` item in another crate.
+
+// `pub(crate)` is the same as plain `pub` inside a private module that the
+// crate never re-exports, but it says the intent explicitly and stays correct
+// if the module is later made `pub`.
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "pub_crate" }
+
+    fn description(&self) -> &'static str {
+        "`pub(crate)` items are visible across the crate but not exported"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["visibility"] }
+
+    fn topic(&self) -> Topic { Topic::Visibility }
+
+    fn source(&self) -> &'static str {
+        include_str!("pub_crate.rs")
+    }
+
+    fn run(&self) {
+        println!("counter::first() = {}, from inside `pub_crate`.", counter::first());
+        println!("{}", crate::use_pub_crate_from_root());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // These modules are scattered around the tree, but all of them are in the
+    // same crate, so all of them can use `counter::next`.
+
+    mod sibling {
+        pub fn call() -> u32 {
+            crate::visibility::pub_crate::counter::next(1)
+        }
+    }
+
+    mod deeply {
+        pub mod nested {
+            // A relative path works just as well as one from the crate root.
+            use super::super::super::counter;
+
+            pub fn call() -> u32 {
+                counter::next(2)
+            }
+        }
+    }
+
+    #[test]
+    fn visible_from_siblings() {
+        assert_eq!(sibling::call(), 2);
+        assert_eq!(deeply::nested::call(), 3);
+    }
+
+    #[test]
+    fn visible_from_crate_root() {
+        assert_eq!(crate::use_pub_crate_from_root(), "counter::next(41) = 42, from the crate root.");
+    }
+}
@@ -0,0 +1,112 @@
+// # `pub(in path)`
+
+// `pub(in path)` makes an item visible inside the module at `path`, which
+// must be an ancestor of the item's module. `pub(crate)` and `pub(super)`
+// are shorthands for two common paths.
+
+// Here `shared` lives three levels down, in `outer::middle::inner`, and is
+// visible to everything inside `outer`, but not to this module. Calling it
+// from outside `outer` will not compile, error E0603: "function `shared` is
+// private", as this `compile_fail` doctest checks:
+
+/// ```compile_fail,E0603
+/// pub mod outer {
+///     pub mod middle {
+///         pub mod inner {
+///             pub(in crate::outer) fn shared() -> &'static str {
+///                 "inner::shared"
+///             }
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     outer::middle::inner::shared();
+/// }
+/// ```
+pub mod outer {
+    pub mod middle {
+        pub mod inner {
+            pub(in crate::visibility::pub_in_path::outer) fn shared() -> &'static str {
+                "inner::shared"
+            }
+
+            #[cfg(test)]
+            mod tests {
+                #[test]
+                fn reachable_in_own_module() {
+                    assert_eq!(super::shared(), "inner::shared");
+                }
+            }
+        }
+
+        pub fn call_shared() -> &'static str {
+            inner::shared()
+        }
+
+        #[cfg(test)]
+        mod tests {
+            #[test]
+            fn reachable_in_middle() {
+                assert_eq!(super::inner::shared(), "inner::shared");
+            }
+        }
+    }
+
+    pub fn call_shared() -> &'static str {
+        middle::inner::shared()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        #[test]
+        fn reachable_in_outer() {
+            assert_eq!(super::middle::inner::shared(), "inner::shared");
+        }
+    }
+}
+
+// The path may also be relative, starting with `self` or `super`, so inside
+// `inner` the visibility above could be written `pub(in super::super)`.
+
+// Paths in `pub(in ...)` must name an ancestor module, so `pub(in crate::registry)`
+// on `shared` would be a compile error.
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "pub_in_path" }
+
+    fn description(&self) -> &'static str {
+        "`pub(in path)` items are visible inside a chosen ancestor module"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["visibility"] }
+
+    fn topic(&self) -> Topic { Topic::Visibility }
+
+    fn source(&self) -> &'static str {
+        include_str!("pub_in_path.rs")
+    }
+
+    fn run(&self) {
+        println!("outer::call_shared() = {}", outer::call_shared());
+        println!("outer::middle::call_shared() = {}", outer::middle::call_shared());
+        println!("This module is outside `outer`, so can't call `shared` directly.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn not_reachable_outside_outer() {
+        // `super::outer::middle::inner::shared()` will not compile here, see
+        // the doctest on `outer`. Only things `outer` exports are reachable from here.
+        assert_eq!(super::outer::call_shared(), "inner::shared");
+        assert_eq!(super::outer::middle::call_shared(), "inner::shared");
+    }
+}
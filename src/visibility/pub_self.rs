@@ -0,0 +1,135 @@
+// Clippy agrees that `pub(self)` is pointless and warns about every use of it
+// by default. This inner attribute turns that lint off for this whole module.
+#![allow(clippy::needless_pub_self)]
+
+// # `pub(self)`
+
+// `pub(self)` is the same as `pub(in self)`: visible in the current module
+// and its children. That's exactly what an item with no visibility modifier
+// gets, so `pub(self)` is just a long way to write private.
+
+// It mostly turns up in code generated by macros, which can take a
+// visibility as input and need a way to say "private".
+
+/// ```compile_fail,E0603
+/// mod items {
+///     pub(self) fn with_pub_self() {}
+/// }
+///
+/// fn main() {
+///     items::with_pub_self();
+/// }
+/// ```
+///
+/// ```compile_fail,E0603
+/// mod items {
+///     fn with_no_modifier() {}
+/// }
+///
+/// fn main() {
+///     items::with_no_modifier();
+/// }
+/// ```
+pub mod items {
+    pub(self) fn with_pub_self() -> &'static str {
+        "pub(self)"
+    }
+
+    fn with_no_modifier() -> &'static str {
+        "private"
+    }
+
+    // Both are visible here in `items`...
+    pub fn call_both() -> [&'static str; 2] {
+        [with_pub_self(), with_no_modifier()]
+    }
+
+    // ...and in its children.
+    pub mod child {
+        pub fn call_both() -> [&'static str; 2] {
+            [super::with_pub_self(), super::with_no_modifier()]
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        #[test]
+        fn same_visibility_in_children() {
+            assert_eq!(super::with_pub_self(), "pub(self)");
+            assert_eq!(super::with_no_modifier(), "private");
+        }
+    }
+}
+
+// Neither is visible outside `items`. Both of these fail with the same error,
+// E0603: "function `...` is private", as the doctests on `items` check:
+//
+//     items::with_pub_self();
+//     items::with_no_modifier();
+
+// A macro that declares a function with a visibility given by its caller:
+macro_rules! make_fn {
+    ($vis:vis fn $name:ident) => {
+        $vis fn $name() -> &'static str {
+            stringify!($name)
+        }
+    };
+}
+
+pub mod generated {
+    // Passing `pub(self)` is the same as passing nothing for `$vis`.
+    make_fn!(pub(self) fn explicit);
+    make_fn!(fn implicit);
+
+    pub fn call_both() -> [&'static str; 2] {
+        [explicit(), implicit()]
+    }
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "pub_self" }
+
+    fn description(&self) -> &'static str {
+        "`pub(self)` is the same as no visibility modifier"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["visibility"] }
+
+    fn topic(&self) -> Topic { Topic::Visibility }
+
+    fn source(&self) -> &'static str {
+        include_str!("pub_self.rs")
+    }
+
+    fn run(&self) {
+        println!("items::call_both() = {:?}", items::call_both());
+        println!("items::child::call_both() = {:?}", items::child::call_both());
+        println!("generated::call_both() = {:?}", generated::call_both());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generated, items};
+
+    #[test]
+    fn same_visibility_in_module() {
+        assert_eq!(items::call_both(), ["pub(self)", "private"]);
+    }
+
+    #[test]
+    fn same_visibility_in_child() {
+        assert_eq!(items::child::call_both(), ["pub(self)", "private"]);
+    }
+
+    #[test]
+    fn same_visibility_from_macro() {
+        assert_eq!(generated::call_both(), ["explicit", "implicit"]);
+    }
+}
@@ -0,0 +1,112 @@
+// # `pub(super)`
+
+// `pub(super)` makes an item visible to its module's parent, and so to
+// everything inside the parent, but nowhere else.
+
+// The modules here are all `pub`, so any failure to reach `helper` below is
+// only because of `helper`'s own visibility.
+pub mod parent {
+    pub mod child {
+        // Visible in `parent`, and anything inside `parent`.
+        pub(super) fn helper() -> &'static str {
+            "child::helper"
+        }
+    }
+
+    // The parent can call it.
+    pub fn call_helper() -> &'static str {
+        child::helper()
+    }
+
+    // So can the parent's other children, as they are inside `parent`.
+    pub mod other_child {
+        pub fn call_helper() -> &'static str {
+            super::child::helper()
+        }
+    }
+}
+
+// A sibling of the parent is outside `parent`, so it can't: calling
+// `super::parent::child::helper()` here will not compile, error E0603:
+// "function `helper` is private". This `compile_fail` doctest checks that:
+
+/// ```compile_fail,E0603
+/// pub mod parent {
+///     pub mod child {
+///         pub(super) fn helper() {}
+///     }
+/// }
+///
+/// pub mod parent_sibling {
+///     pub fn call_helper() {
+///         super::parent::child::helper();
+///     }
+/// }
+/// # fn main() {}
+/// ```
+pub mod parent_sibling {
+    pub fn call_helper() -> &'static str {
+        super::parent::call_helper()
+    }
+}
+
+// Nor can the grandparent, this module, for the same reason: calling
+// `parent::child::helper()` here is error E0603 too.
+
+/// ```compile_fail,E0603
+/// pub mod parent {
+///     pub mod child {
+///         pub(super) fn helper() {}
+///     }
+/// }
+///
+/// fn main() {
+///     parent::child::helper();
+/// }
+/// ```
+fn grandparent_call_helper() {
+    // It has to go through something `parent` exports instead:
+    parent::call_helper();
+}
+
+// `pub(super)` is handy for a helper shared between a module's children, or
+// for a child module's internals that only its parent should construct.
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "pub_super" }
+
+    fn description(&self) -> &'static str {
+        "`pub(super)` items are visible to the parent module only"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["visibility"] }
+
+    fn topic(&self) -> Topic { Topic::Visibility }
+
+    fn source(&self) -> &'static str {
+        include_str!("pub_super.rs")
+    }
+
+    fn run(&self) {
+        println!("parent::call_helper() = {}", parent::call_helper());
+        println!("parent::other_child::call_helper() = {}", parent::other_child::call_helper());
+        println!("The parent's sibling and grandparent can't call `helper`, see the source.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parent;
+
+    #[test]
+    fn visible_inside_parent() {
+        assert_eq!(parent::call_helper(), "child::helper");
+        assert_eq!(parent::other_child::call_helper(), "child::helper");
+    }
+}
@@ -0,0 +1,119 @@
+// # Sealed traits
+
+// A "sealed" trait can be used by anyone who can see it, but only
+// implemented by the module that defines it. That lets a library add
+// methods to the trait later without breaking anyone, because nobody else
+// can have implemented it.
+
+// It works by giving the public trait a supertrait that nobody else can name.
+
+pub mod shapes {
+    // `sealed` is private, so `sealed::Sealed` can only be named in `shapes`
+    // and its children, even though the trait itself is `pub`.
+    mod sealed {
+        pub trait Sealed {}
+    }
+
+    // Every implementor of `Shape` must implement `Sealed` too.
+    pub trait Shape: sealed::Sealed {
+        fn area(&self) -> f64;
+    }
+
+    pub struct Square(pub f64);
+    pub struct Circle(pub f64);
+
+    // Only `shapes` can write these `impl`s, so only these types can ever be a `Shape`.
+    impl sealed::Sealed for Square {}
+    impl sealed::Sealed for Circle {}
+
+    impl Shape for Square {
+        fn area(&self) -> f64 {
+            self.0 * self.0
+        }
+    }
+
+    impl Shape for Circle {
+        fn area(&self) -> f64 {
+            std::f64::consts::PI * self.0 * self.0
+        }
+    }
+}
+
+pub mod downstream {
+    // Code outside `shapes` can use the trait like any other...
+    use super::shapes::{Circle, Shape, Square};
+
+    pub fn total_area(shapes: &[&dyn Shape]) -> f64 {
+        shapes.iter().map(|s| s.area()).sum()
+    }
+
+    pub fn example() -> f64 {
+        total_area(&[&Square(2.0), &Circle(1.0)])
+    }
+
+    // ...but it can't implement it. The doctest on `Triangle` checks that
+    // this fails to compile with error E0277, "the trait bound
+    // `Triangle: Sealed` is not satisfied". Adding `impl Sealed for Triangle`
+    // doesn't help: naming `shapes::sealed::Sealed` here fails with error
+    // E0603, "module `sealed` is private".
+
+    /// ```compile_fail,E0277
+    /// mod shapes {
+    ///     mod sealed {
+    ///         pub trait Sealed {}
+    ///     }
+    ///
+    ///     pub trait Shape: sealed::Sealed {
+    ///         fn area(&self) -> f64;
+    ///     }
+    /// }
+    ///
+    /// struct Triangle;
+    ///
+    /// impl shapes::Shape for Triangle {
+    ///     fn area(&self) -> f64 { 0.0 }
+    /// }
+    /// ```
+    pub struct Triangle;
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "sealed_trait" }
+
+    fn description(&self) -> &'static str {
+        "A public trait that only its own module can implement"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["visibility", "traits"] }
+
+    fn topic(&self) -> Topic { Topic::Visibility }
+
+    fn source(&self) -> &'static str {
+        include_str!("sealed_trait.rs")
+    }
+
+    fn run(&self) {
+        println!("Total area of a 2x2 square and a unit circle, summed in `downstream`: {:.3}",
+                 downstream::example());
+        println!("`downstream` can't implement `Shape` for its own `Triangle`, see the source.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::shapes::{Shape, Square};
+    use super::downstream;
+
+    #[test]
+    fn downstream_can_use() {
+        assert_eq!(Square(3.0).area(), 9.0);
+        let total = downstream::example();
+        assert!((total - (4.0 + std::f64::consts::PI)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,141 @@
+// # Struct field visibility
+
+// A struct's fields each have their own visibility, separate from the
+// struct's. Fields are private by default, even in a `pub struct`.
+
+pub mod account {
+    pub struct Account {
+        // Visible wherever `Account` is.
+        pub name: String,
+        // Visible anywhere in this crate.
+        pub(crate) balance: u64,
+        // Only visible in `account` and its children.
+        pin: u16,
+    }
+
+    impl Account {
+        // With a private field, code outside `account` can't write a struct
+        // literal, so it needs a constructor like this one.
+        pub fn new(name: &str, pin: u16) -> Account {
+            Account { name: name.to_string(), balance: 0, pin }
+        }
+
+        // Methods in the defining module can see every field, so they can
+        // expose private data selectively: here only whether a PIN matches.
+        pub fn check_pin(&self, pin: u16) -> bool {
+            self.pin == pin
+        }
+
+        // A getter gives read-only access from outside this crate, where
+        // `balance` can't be seen.
+        pub fn balance(&self) -> u64 {
+            self.balance
+        }
+    }
+}
+
+pub mod elsewhere {
+    use super::account::Account;
+
+    // Writing `account.pin = 0;` here will not compile, error E0616: "field
+    // `pin` of struct `Account` is private". The doc comment below checks that
+    // with a `compile_fail` doctest.
+
+    /// ```compile_fail,E0616
+    /// mod account {
+    ///     pub struct Account {
+    ///         pub name: String,
+    ///         pin: u16,
+    ///     }
+    /// }
+    ///
+    /// fn deposit(account: &mut account::Account) {
+    ///     account.pin = 0;
+    /// }
+    /// # fn main() {}
+    /// ```
+    pub fn deposit(account: &mut Account, amount: u64) -> String {
+        // `pub` fields can be read and written.
+        account.name.push_str(" (active)");
+
+        // So can `pub(crate)` fields, from anywhere in this crate.
+        account.balance += amount;
+
+        format!("{} has balance {}", account.name, account.balance())
+    }
+
+    // A struct literal, `Account { name: "literal".to_string(), balance: 0,
+    // pin: 1234 }`, will not compile here either, error E0451: "field `pin`
+    // of struct `Account` is private", even though `pin` is given a value.
+
+    /// ```compile_fail,E0451
+    /// mod account {
+    ///     pub struct Account {
+    ///         pub name: String,
+    ///         pin: u16,
+    ///     }
+    /// }
+    ///
+    /// fn open() -> account::Account {
+    ///     account::Account { name: "literal".to_string(), pin: 1234 }
+    /// }
+    /// # fn main() {}
+    /// ```
+    pub fn open() -> Account {
+        Account::new("constructed", 1234)
+    }
+}
+
+// Another crate can only see `name`, and has to use `balance()` to read the
+// balance, and `check_pin()` to learn anything about the PIN.
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "struct_visibility" }
+
+    fn description(&self) -> &'static str {
+        "Struct fields have their own visibility, private by default"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["visibility", "structs"] }
+
+    fn topic(&self) -> Topic { Topic::Visibility }
+
+    fn source(&self) -> &'static str {
+        include_str!("struct_visibility.rs")
+    }
+
+    fn run(&self) {
+        let mut account = elsewhere::open();
+        println!("elsewhere::open() built an account with Account::new(), named '{}'.", account.name);
+        println!("{}", elsewhere::deposit(&mut account, 10));
+        println!("check_pin(1234) = {}", account.check_pin(1234));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::account::Account;
+    use super::elsewhere;
+
+    #[test]
+    fn public_and_crate_fields_are_writable() {
+        let mut account = Account::new("a", 1);
+        account.name = "b".to_string();
+        account.balance = 5;
+        assert_eq!(elsewhere::deposit(&mut account, 2), "b (active) has balance 7");
+    }
+
+    #[test]
+    fn private_field_through_methods() {
+        let account = elsewhere::open();
+        assert!(account.check_pin(1234));
+        assert!(!account.check_pin(0));
+        assert_eq!(account.balance(), 0);
+    }
+}
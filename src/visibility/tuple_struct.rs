@@ -0,0 +1,129 @@
+// # Tuple struct constructors
+
+// A tuple struct's fields have visibilities just like named fields, and
+// are private by default. The struct's name is also a function that
+// constructs it, and that constructor is only as visible as the least
+// visible field.
+
+pub mod wrapper {
+    // `Wrapper` is `pub`, but its field isn't, so outside this module:
+    //
+    // * `Wrapper("text".to_string())` fails with error E0603:
+    //   "tuple struct constructor `Wrapper` is private".
+    // * So does destructuring with a pattern like `let Wrapper(s) = w;`.
+    // * Reading `w.0` fails with error E0616: "field `0` of struct `Wrapper` is private".
+    //
+    // The doc comment below holds all three cases as `compile_fail`
+    // doctests, so `cargo test` checks that they really fail that way.
+
+    /// ```compile_fail,E0603
+    /// mod wrapper {
+    ///     pub struct Wrapper(String);
+    /// }
+    ///
+    /// let w = wrapper::Wrapper("text".to_string());
+    /// ```
+    ///
+    /// ```compile_fail,E0603
+    /// mod wrapper {
+    ///     pub struct Wrapper(String);
+    ///
+    ///     impl Wrapper {
+    ///         pub fn new(s: &str) -> Wrapper {
+    ///             Wrapper(s.to_string())
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let wrapper::Wrapper(s) = wrapper::Wrapper::new("text");
+    /// ```
+    ///
+    /// ```compile_fail,E0616
+    /// mod wrapper {
+    ///     pub struct Wrapper(String);
+    ///
+    ///     impl Wrapper {
+    ///         pub fn new(s: &str) -> Wrapper {
+    ///             Wrapper(s.to_string())
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let w = wrapper::Wrapper::new("text");
+    /// let s = &w.0;
+    /// ```
+    pub struct Wrapper(String);
+
+    impl Wrapper {
+        // The idiomatic workaround: a `new()` constructor in the defining
+        // module, which can see the field. It could also validate its input.
+        pub fn new(s: &str) -> Wrapper {
+            Wrapper(s.to_string())
+        }
+
+        pub fn as_str(&self) -> &str {
+            &self.0
+        }
+    }
+
+    // With every field `pub`, the constructor is public too.
+    pub struct Open(pub String);
+}
+
+pub mod elsewhere {
+    use super::wrapper::{Open, Wrapper};
+
+    pub fn make() -> (Wrapper, Open) {
+        // `Wrapper("text".to_string())` won't compile here, so use `new`.
+        let wrapper = Wrapper::new("wrapped");
+
+        // `Open`'s constructor is visible, and so is its field.
+        let open = Open("open".to_string());
+        (wrapper, open)
+    }
+
+    pub fn unwrap(open: Open) -> String {
+        let Open(s) = open;
+        s
+    }
+}
+
+use crate::example::{Example, Topic};
+
+pub struct Demo;
+
+crate::registry::register!(Demo);
+
+impl Example for Demo {
+    fn name(&self) -> &'static str { "tuple_struct" }
+
+    fn description(&self) -> &'static str {
+        "Tuple struct constructors are only as visible as their fields"
+    }
+
+    fn tags(&self) -> &'static [&'static str] { &["visibility", "structs"] }
+
+    fn topic(&self) -> Topic { Topic::Visibility }
+
+    fn source(&self) -> &'static str {
+        include_str!("tuple_struct.rs")
+    }
+
+    fn run(&self) {
+        let (wrapper, open) = elsewhere::make();
+        println!("Wrapper::new(\"wrapped\").as_str() = {:?}", wrapper.as_str());
+        println!("Destructured Open(s), s = {:?}", elsewhere::unwrap(open));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::elsewhere;
+
+    #[test]
+    fn construct_outside_module() {
+        let (wrapper, open) = elsewhere::make();
+        assert_eq!(wrapper.as_str(), "wrapped");
+        assert_eq!(elsewhere::unwrap(open), "open");
+    }
+}
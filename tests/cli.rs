@@ -0,0 +1,438 @@
+// End-to-end tests of the command line, running the real binary the way a
+// learner would, with `assert_cmd`, and checking its output with
+// `predicates`. They check what unit tests of `cli::parse` can't: the
+// output, and the exit codes.
+
+mod common;
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+
+use assert_cmd::assert::OutputAssertExt;
+use assert_cmd::cargo::cargo_bin;
+use predicates::prelude::*;
+use predicates::str::{contains, ends_with, is_match, starts_with};
+
+use common::{bin, bin_with_progress, committed_exercises, fixture_copy, stdout};
+
+#[test]
+fn list() {
+    bin(&["list"]).assert()
+        .success()
+        .stdout(is_match(r"(?m)^a .*\[basics, files\]").unwrap())
+        .stdout(is_match(r"(?m)^use_examples ").unwrap())
+        .stderr("");
+}
+
+#[test]
+fn list_to_closed_pipe() {
+    // As with `list | head -1`, once the reader's gone.
+    let (reader, writer) = std::io::pipe().unwrap();
+    drop(reader);
+    Command::new(cargo_bin!("rust-module-example"))
+        .arg("list")
+        .stdout(writer)
+        .stderr(Stdio::piped())
+        .assert()
+        .code(1)
+        .stderr("");
+}
+
+#[test]
+fn run_example() {
+    bin(&["run", "a"]).assert().success().stdout(contains("This code lives in `./a.rs`"));
+}
+
+#[test]
+fn run_unknown_example() {
+    bin(&["run", "nope"]).assert().code(1).stdout("").stderr("Error: unknown example 'nope'\n");
+}
+
+#[test]
+fn quiz_without_input() {
+    // Without `write_stdin`, the input ends at once, so the quiz ends at the
+    // first question.
+    bin(&["quiz", "--seed", "5"]).assert()
+        .success()
+        .stdout(contains("=== Question 1 of 10\n\nmod ").and(ends_with("? [y/n] > \nYou got 0 out of 0.\n")));
+}
+
+#[test]
+fn quiz_report() {
+    let dir = std::env::temp_dir().join(format!("rust-module-example-{}-cli-report", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let progress = dir.join("progress.toml");
+    bin_with_progress(&["report"], &progress).assert()
+        .code(1)
+        .stderr(starts_with("Error: no quiz results in "));
+
+    // Skipping the second question counts as getting it wrong.
+    let results = dir.join("quiz-results.json");
+    bin_with_progress(&["quiz", "--seed", "3", "--count", "2"], &progress)
+        .write_stdin("y\n\ny\n")
+        .assert()
+        .success()
+        .stdout(ends_with(format!("Scores by topic saved in {}, see `report`.\n", results.display())));
+    assert!(fs::read_to_string(&results).unwrap().contains("\"seed\": \"3\""));
+
+    bin_with_progress(&["report"], &progress).assert()
+        .success()
+        .stdout(starts_with("Topic        Asked Right  Score\n")
+            .and(contains("\nTotal            2 "))
+            .and(ends_with("\nTake the same quiz again with `quiz --seed 3 --count 2`.\n")));
+
+    fs::write(&results, "{\"topics\": []}").unwrap();
+    bin(&["report", results.to_str().unwrap()]).assert()
+        .code(1)
+        .stderr("Error: not quiz results: no `topics` object\n");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn quiz_export() {
+    let assert = bin(&["quiz", "export"]).assert()
+        .success()
+        .stdout(starts_with("#separator:tab\n#html:true\n#tags column:3\n<pre>mod kitchen {<br>"));
+    let out = stdout(assert.get_output());
+    // A line for each of the 108 built-in questions and 3 in `questions/`,
+    // each with three fields.
+    let cards: Vec<_> = out.lines().filter(|line| !line.starts_with('#')).collect();
+    assert_eq!(cards.len(), 111);
+    assert!(cards.iter().all(|card| card.split('\t').count() == 3), "{out}");
+    assert!(cards.iter().any(|card| card.ends_with("\trust-modules::paths")), "{out}");
+
+    bin(&["quiz", "export", "--format", "csv"]).assert()
+        .success()
+        .stdout(starts_with("front,back,topic\n\"mod kitchen {\n"));
+}
+
+#[test]
+fn serve_classroom() {
+    let mut server = Command::new(cargo_bin!("rust-module-example"))
+        .args(["serve", "--classroom", "--port", "0", "--count", "3"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut lines = BufReader::new(server.stdout.take().unwrap()).lines().map(Result::unwrap);
+    assert_eq!(lines.next().unwrap(), "Hosting a quiz of 3 questions. Press Ctrl-C to stop.");
+    lines.next();
+    let join = lines.next().unwrap();
+    let results = lines.next().unwrap();
+    // The server listens on every address, so the local one will do,
+    // whatever address on the network it says.
+    let port = join.rsplit(':').next().unwrap().trim_end_matches('/').to_string();
+    let path = results.split_once(&format!(":{port}")).unwrap().1.to_string();
+    let request = |text: &str| {
+        let mut stream = TcpStream::connect(("127.0.0.1", port.parse::<u16>().unwrap())).unwrap();
+        stream.write_all(text.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    let response = request("POST /join HTTP/1.1\r\nContent-Type: application/x-www-form-urlencoded\r\n\
+                            Content-Length: 8\r\n\r\nname=Ada");
+    assert!(response.starts_with("HTTP/1.1 303 See Other\r\n"), "{response}");
+    let student = response.split("Location: ").nth(1).unwrap().lines().next().unwrap();
+    let response = request(&format!("GET {student} HTTP/1.1\r\n\r\n"));
+    assert!(response.contains("<h1>Question 1 of 3</h1>"), "{response}");
+    let response = request(&format!("GET {path} HTTP/1.1\r\n\r\n"));
+    assert!(response.contains("<tr><td>Ada</td><td>0 of 3</td><td>0</td><td>-</td></tr>"), "{response}");
+    server.kill().unwrap();
+    server.wait().unwrap();
+}
+
+#[test]
+fn tour_record_and_replay() {
+    let dir = std::env::temp_dir().join(format!("rust-module-example-{}-cli-tour", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let progress = dir.join("progress.toml");
+    let session = dir.join("session.json");
+    bin_with_progress(&["tour", "--record", session.to_str().unwrap()], &progress)
+        .write_stdin("run inline\nquiz\ny\nn\nq\n")
+        .assert()
+        .success()
+        .stdout(ends_with(format!("Saved the session in {0}, play it back with `tour --replay {0}`.\n",
+                                  session.display())));
+    let text = fs::read_to_string(&session).unwrap();
+    assert!(text.contains("\"ran\": \"inline\""), "{text}");
+    assert!(text.contains("\"answer\": \"yes\""), "{text}");
+    assert_eq!(text.matches("\"shown\"").count(), 2, "{text}");
+
+    // Without input, it plays straight through.
+    bin(&["tour", "--replay", session.to_str().unwrap()]).assert()
+        .success()
+        .stdout(contains("\n> run inline\n--- Output of `run inline`:\n")
+            .and(contains("[y/n] > y\n"))
+            .and(contains("\n=== [2/"))
+            .and(ends_with("\nThat's the end of the session.\n")));
+
+    fs::write(&session, "{\"events\": [{\"shown\": \"nope\"}]}").unwrap();
+    bin(&["tour", "--replay", session.to_str().unwrap()]).assert()
+        .code(1)
+        .stderr(format!("Error: {}: event 1: no example 'nope'\n", session.display()));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn export_book() {
+    let dir = std::env::temp_dir().join(format!("rust-module-example-{}-cli-book", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    bin(&["export", "book", dir.to_str().unwrap()]).assert()
+        .success()
+        .stdout(starts_with(format!("Wrote 10 files to {}.\n", dir.display())));
+    let summary = fs::read_to_string(dir.join("src/SUMMARY.md")).unwrap();
+    assert!(summary.contains("- [Macros](macros.md)\n"), "{summary}");
+    let macros = fs::read_to_string(dir.join("src/macros.md")).unwrap();
+    assert!(macros.contains("\n## `macro_ordering`: "), "{macros}");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn verify_stops_at_first_exercise() {
+    // Not `exercises/` itself, which the learner may have fixed some of.
+    let Some(dir) = committed_exercises("cli-verify") else { return };
+    let assert = bin(&["verify", dir.join("exercises").to_str().unwrap()]).assert();
+    fs::remove_dir_all(dir).unwrap();
+    assert.code(1)
+        .stdout(starts_with("01_private_function doesn't compile yet:\n\nerror[E0603]")
+            .and(contains("\nFix it, starting at exercises/01_private_function/lib.rs:"))
+            .and(ends_with("\n0 of 7 exercises done. Stuck? Try `hint 01_private_function`.\n")));
+}
+
+#[test]
+fn progress() {
+    let dir = std::env::temp_dir().join(format!("rust-module-example-{}-cli-progress", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let file = dir.join("progress.toml");
+    bin_with_progress(&["run", "a"], &file).assert().success();
+    bin_with_progress(&["run", "inline"], &file).assert().success();
+    if let Some(exercises) = committed_exercises("cli-progress-exercises") {
+        bin_with_progress(&["verify", exercises.join("exercises").to_str().unwrap()], &file).assert().code(1);
+        fs::remove_dir_all(exercises).unwrap();
+    }
+    assert!(fs::read_to_string(&file).unwrap().contains("examples = [\"a\", \"inline\"]\n"));
+
+    bin_with_progress(&["progress"], &file).assert()
+        .success()
+        .stdout(starts_with("Examples run:                    2 of ")
+            .and(contains("\nExercises compiling:             0 of 7 "))
+            .and(ends_with(format!("\nSaved in {}.\n", file.display()))));
+
+    fs::write(&file, "examples = nonsense\n").unwrap();
+    bin_with_progress(&["run", "a"], &file).assert()
+        .success()
+        .stderr(starts_with("Warning: couldn't save progress to "));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn hint() {
+    bin(&["hint", "private_function"]).assert()
+        .success()
+        .stdout(starts_with("Hint 1 of 3, concept:\n  Items in a module are private by default")
+            .and(ends_with("`hint 01_private_function --level 2` tells you more.\n")));
+
+    bin(&["hint", "nope"]).assert()
+        .code(1)
+        .stderr(starts_with("Error: no exercise 'nope', expected one of 01_private_function, "));
+}
+
+#[test]
+fn quiz_banks() {
+    let dir = std::env::temp_dir().join(format!("rust-module-example-{}-cli-bank", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let bank = dir.join("bank.toml");
+    fs::write(&bank, "[[question]]\nsnippet = \"mod only {}\"\nfrom = \"crate\"\nitem = \"crate::only::f\"\n").unwrap();
+    bin(&["quiz", "--bank", bank.to_str().unwrap()]).assert()
+        .code(1)
+        .stderr(format!("Error: {}: question at line 1: `crate::only::f` doesn't resolve from `crate`: \
+                         no `f` in `crate::only`\n", bank.display()));
+
+    fs::write(&bank, "[[question]]\nsnippet = \"mod only { pub fn f() {} }\"\nfrom = \"crate\"\n\
+                      item = \"crate::only::f\"\n").unwrap();
+    // Asking for more questions than there are gives them all: the 108
+    // built-in ones, 3 from `questions/workshop.toml`, and the new one.
+    bin(&["quiz", "--count", "1000", "--bank", bank.to_str().unwrap()]).assert()
+        .success()
+        .stdout(contains("=== Question 1 of 112\n"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn tree() {
+    bin(&["tree"]).assert()
+        .success()
+        .stdout(starts_with("crate  src/lib.rs\n").and(contains("mod a  src/a.rs")));
+}
+
+#[test]
+fn tree_of_another_crate() {
+    bin(&["tree", "--format", "json", "crates/core"]).assert().success().stdout(contains("\"name\""));
+}
+
+#[test]
+fn tree_missing_path() {
+    bin(&["tree", "no/such/crate"]).assert().code(1).stderr(starts_with("Error: "));
+}
+
+#[test]
+fn tree_diff() {
+    bin(&["tree", "diff", "tests/fixtures/orphans", "tests/fixtures/snapshot_crate"]).assert()
+        .success()
+        .stdout(starts_with("- mod crate::used\n+ mod crate::renamed\n")
+            .and(ends_with("\n5 added, 1 removed, 0 moved, 0 visibility changes.\n")));
+
+    bin(&["tree", "diff", "tests/fixtures/orphans", "tests/fixtures/orphans"]).assert().stdout("No differences.\n");
+}
+
+#[test]
+fn analyze_deps() {
+    bin(&["analyze", "deps", "tests/fixtures/snapshot_crate"]).assert().success().stdout("\
+crate -> crate::shapes::round
+crate -> crate::util
+crate::tests -> crate
+
+No cycles.
+");
+}
+
+#[test]
+fn analyze_unused_pub() {
+    bin(&["analyze", "unused-pub", "tests/fixtures/snapshot_crate"]).assert().success().stdout("\
+src/other_name.rs:2: pub const crate::renamed::NAME
+
+1 visible item isn't referred to in the crate.
+");
+}
+
+#[test]
+fn analyze_orphans() {
+    bin(&["analyze", "orphans", "tests/fixtures/orphans"]).assert().success().stdout("\
+src/forgotten.rs
+src/used/helper.rs
+
+2 .rs files aren't part of any crate: no `mod` declaration loads them.
+");
+}
+
+#[test]
+fn which_file() {
+    bin(&["which-file", "crate::multi_level_style_2::child"]).assert().success().stdout("\
+`mod child;` in src/multi_level_style_2.rs loads `crate::multi_level_style_2::child` from one of:
+  src/multi_level_style_2/child.rs      2018 style    found
+  src/multi_level_style_2/child/mod.rs  mod.rs style  not found
+");
+}
+
+#[test]
+fn resolve() {
+    bin(&["resolve", "--from", "crate::use_examples", "--", "super::f"]).assert()
+        .success()
+        .stdout(starts_with("Resolving `super::f` from `crate::use_examples`:\n")
+            .and(ends_with("and `crate::use_examples` can use it.\n")));
+
+    bin(&["resolve", "crate::name_resolution::private_inner"]).assert().stdout(contains("`crate` can't use it"));
+}
+
+#[test]
+fn analyze_visible_from() {
+    bin(&["analyze", "visible-from", "crate::util::clamp", "tests/fixtures/snapshot_crate"]).assert()
+        .success()
+        .stdout("\
+`util` is private, so visible inside `crate`.
+`clamp` is pub(crate), so visible inside `crate`.
+
+`crate::util::clamp` can be named in 6 of 6 modules:
+  crate
+  crate::shapes
+  crate::shapes::round
+  crate::util
+  crate::renamed
+  crate::tests
+");
+}
+
+#[test]
+fn analyze_api() {
+    bin(&["analyze", "api", "tests/fixtures/snapshot_crate"]).assert().success().stdout("\
+pub struct crate::Circle (re-export of crate::shapes::round::Circle)
+pub mod crate::shapes
+pub mod crate::shapes::round
+pub struct crate::shapes::round::Circle
+
+4 public paths, 1 of them re-exports.
+");
+}
+
+#[test]
+fn analyze_effective_vis() {
+    bin(&["analyze", "effective-vis", "crate::shapes::round::Circle", "tests/fixtures/snapshot_crate"]).assert()
+        .success()
+        .stdout(ends_with("Through that path it's effectively `pub`.\n"));
+
+    bin(&["analyze", "effective-vis", "crate::visibility::pub_super::parent::child::helper"]).assert()
+        .stdout(ends_with("effectively `pub(in crate::visibility::pub_super::parent)`.\n"));
+}
+
+#[test]
+fn scaffold_new_module() {
+    let dir = fixture_copy("snapshot_crate", "cli-scaffold");
+    bin(&["scaffold", "new-module", "square", "--parent", "crate::shapes", "--style", "mod-rs", "--tests",
+          dir.to_str().unwrap()]).assert()
+        .success()
+        .stdout("\
+Created src/shapes/square/mod.rs
+Updated src/shapes.rs
+");
+
+    bin(&["which-file", "crate::shapes::square", dir.to_str().unwrap()]).assert()
+        .stdout(contains("src/shapes/square/mod.rs  mod.rs style  found"))
+        .stderr("");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn scaffold_prelude_dry_run() {
+    bin(&["scaffold", "prelude", "--dry-run", "--count", "3", "tests/fixtures/layouts"]).assert().success().stdout("\
+Proposed `crate::prelude` with the 3 most referenced of 5 used public items:
+  pub use crate::colors::Color;  // enum, 6 references
+  pub use crate::area;           // fn, 2 references
+  pub use crate::COUNT;          // const, 1 reference
+");
+}
+
+#[test]
+fn scaffold_project() {
+    let dir = std::env::temp_dir().join(format!("rust-module-example-{}-cli-project", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    bin(&["scaffold", "project", "tasks", "--layout", "feature-sliced", dir.to_str().unwrap()]).assert()
+        .success()
+        .stdout(contains("Created tasks/src/features/import.rs\n"));
+
+    bin(&["tree", dir.join("tasks").to_str().unwrap()]).assert()
+        .stdout(contains("mod summary  src/features/summary.rs"))
+        .stderr("");
+
+    bin(&["scaffold", "project", "tasks", dir.to_str().unwrap()]).assert()
+        .code(1)
+        .stderr(ends_with("tasks already exists\n"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn usage_errors() {
+    bin(&["frobnicate"]).assert()
+        .code(2)
+        .stderr(starts_with("Error: unknown command 'frobnicate'\n")
+            .and(contains("Usage: rust-module-example [COMMAND]")));
+}
+
+#[test]
+fn help() {
+    bin(&["help"]).assert().success().stdout(starts_with("Usage: rust-module-example [COMMAND]"));
+}
@@ -0,0 +1,164 @@
+// Helpers shared by the integration tests.
+
+// Each file directly in `tests/` is the root of its own test crate, and Cargo
+// builds and runs each one as a separate test binary. So a helper module
+// can't be shared by `use`: every test crate that wants it declares it with
+// `mod common;`, and gets its own copy compiled in.
+//
+// It lives at `tests/common/mod.rs` rather than `tests/common.rs` because
+// Cargo treats every `.rs` file directly in `tests/` as a test crate. A
+// `tests/common.rs` would be built as a test binary too, and show up in the
+// `cargo test` output as "Running tests/common.rs" with zero tests.
+// Subdirectories aren't test crates, so `mod.rs` keeps it out of the way.
+
+// A test crate that doesn't use every helper would warn that the rest are
+// unused, since each copy is compiled separately.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use modules_demo::example::Example;
+use modules_demo::modtree::ModuleTree;
+use modules_demo::registry;
+
+// The package directory, wherever `cargo test` is run from.
+pub fn package_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+pub fn package_path(relative: &str) -> PathBuf {
+    package_dir().join(relative)
+}
+
+// The module tree of this package's library, parsed from its source.
+pub fn lib_tree() -> ModuleTree {
+    ModuleTree::load(&package_path("src/lib.rs")).expect("loading src/lib.rs")
+}
+
+// Look an example up by name, failing the test with the known names if it
+// isn't there.
+pub fn example(name: &str) -> &'static dyn Example {
+    registry::find(name).unwrap_or_else(|| {
+        let names: Vec<_> = registry::EXAMPLES.iter().map(|e| e.name()).collect();
+        panic!("no example named '{name}', expected one of {names:?}")
+    })
+}
+
+// The main binary with `args`, for `assert_cmd` to run and check. Cargo
+// builds the package's binaries before its integration tests, and says where
+// they are.
+//
+// Commands like `run` save the learner's progress, so this points them at a
+// throwaway file rather than the real one.
+pub fn bin(args: &[&str]) -> assert_cmd::Command {
+    let progress = std::env::temp_dir()
+        .join(format!("rust-module-example-{}-progress.toml", std::process::id()));
+    bin_with_progress(args, &progress)
+}
+
+// Like `bin`, saving progress to `progress`. Give it input for commands like
+// `quiz` with `write_stdin`; without that, it sees its input end at once.
+pub fn bin_with_progress(args: &[&str], progress: &Path) -> assert_cmd::Command {
+    let mut command = assert_cmd::cargo::cargo_bin_cmd!("rust-module-example");
+    command.args(args)
+        .env("RUST_MODULE_EXAMPLE_PROGRESS", progress)
+        .current_dir(package_dir());
+    command
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).expect("stdout is UTF-8")
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).expect("stderr is UTF-8")
+}
+
+// A fresh copy of the fixture crate `tests/fixtures/<fixture>` in a temporary
+// directory, for commands that change files. `test` must be unique among
+// tests, as tests run concurrently.
+pub fn fixture_copy(fixture: &str, test: &str) -> PathBuf {
+    fn copy(from: &Path, to: &Path) {
+        fs::create_dir_all(to).unwrap();
+        for entry in fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            if entry.file_type().unwrap().is_dir() {
+                copy(&entry.path(), &to.join(entry.file_name()));
+            } else {
+                fs::copy(entry.path(), to.join(entry.file_name())).unwrap();
+            }
+        }
+    }
+    let dir = std::env::temp_dir()
+        .join(format!("rust-module-example-{}-{test}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    copy(&package_path(&format!("tests/fixtures/{fixture}")), &dir);
+    dir
+}
+
+// A copy of the exercises as last committed, as they were before the
+// learner started fixing those in `exercises/`, in `exercises/` in a
+// temporary directory. `None` if this isn't a git checkout. `test` must be
+// unique among tests, as with `fixture_copy`.
+pub fn committed_exercises(test: &str) -> Option<PathBuf> {
+    let git = |args: &[&str]| {
+        Command::new("git").args(args).current_dir(package_dir()).output().ok()
+            .filter(|output| output.status.success())
+            .map(|output| output.stdout)
+    };
+    let files = git(&["ls-tree", "-r", "--name-only", "HEAD", "--", "exercises"])?;
+    let dir = std::env::temp_dir()
+        .join(format!("rust-module-example-{}-{test}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    for file in String::from_utf8(files).unwrap().lines() {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, git(&["show", &format!("HEAD:{file}")]).unwrap()).unwrap();
+    }
+    Some(dir)
+}
+
+// A command to check that `crate_root` compiles, as a library.
+pub fn rustc(crate_root: &Path, out_dir: &Path) -> Command {
+    // `RUSTC` is set when Cargo runs this through a wrapper; otherwise use
+    // the `rustc` on the `PATH`, which is the one Cargo itself is using.
+    let mut command = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()));
+    command.args(["--edition", "2021", "--crate-type", "lib", "--emit", "metadata", "--out-dir"])
+        .arg(out_dir)
+        .arg(crate_root);
+    command
+}
+
+// Compare `actual` with the contents of the file at `relative`, a golden
+// file. With `UPDATE_SNAPSHOTS` set, save `actual` to the file instead.
+// On a difference, `actual` is written next to the file with `.new` appended,
+// for comparison.
+pub fn assert_matches_file(relative: &str, actual: &str) {
+    let path = package_path(relative);
+    let new_path = package_path(&format!("{relative}.new"));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).unwrap();
+        let _ = fs::remove_file(&new_path);
+        return;
+    }
+
+    let Ok(expected) = fs::read_to_string(&path) else {
+        fs::write(&new_path, actual).unwrap();
+        panic!("no {relative} yet: see {}, and run with UPDATE_SNAPSHOTS=1 to save it",
+               new_path.display());
+    };
+    if actual == expected {
+        let _ = fs::remove_file(&new_path);
+        return;
+    }
+
+    fs::write(&new_path, actual).unwrap();
+    let line = expected.lines().zip(actual.lines()).position(|(e, a)| e != a)
+        .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+    panic!("{relative} differs from line {}: see {}, \
+            and rerun with UPDATE_SNAPSHOTS=1 if the change is intended",
+           line + 1, new_path.display());
+}
@@ -0,0 +1,75 @@
+// Compile-fail tests: each file in `./compile_fail/` must fail to compile,
+// with the errors in the `.stderr` file next to it. Many comments in `src/`
+// say "this won't compile"; these check such claims for whole small crates,
+// where a `compile_fail` doctest would be awkward.
+
+// `trybuild` builds each file as a binary, so each has an empty `main`. The
+// files are in a subdirectory so Cargo doesn't build them as tests of their
+// own. After a change, or a new Rust version that words an error
+// differently, run with `TRYBUILD=overwrite` to save the new errors, and
+// check the diff.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::rustc;
+
+#[test]
+fn compile_fail() {
+    trybuild::TestCases::new().compile_fail("tests/compile_fail/*.rs");
+}
+
+// The exercises for the `verify` command must fail, with the error they
+// say, until the learner fixes them. The learner fixes those in
+// `exercises/`, so this checks the ones committed, as they started.
+#[test]
+fn exercises_start_broken() {
+    let Some(base) = common::committed_exercises("compile-fail-exercises") else {
+        eprintln!("Not a git checkout, so there are no exercises as they started to check.");
+        return;
+    };
+    let out_dir = base.join("out");
+
+    let exercises = modules_demo::exercises::list(&base.join("exercises")).unwrap();
+    assert!(!exercises.is_empty());
+    let mut failures = Vec::new();
+    for exercise in &exercises {
+        let code = exercise.expected_error().unwrap()
+            .unwrap_or_else(|| panic!("{} has no `// expect: CODE` line", exercise.name));
+        match exercise.compile(&base, &out_dir).unwrap() {
+            Ok(()) => failures.push(format!("{} compiled, but should fail with {code}", exercise.name)),
+            Err(stderr) if !stderr.contains(&format!("error[{code}]")) => {
+                failures.push(format!("{} should fail with {code}, but got:\n{stderr}", exercise.name));
+            }
+            Err(_) => {}
+        }
+        assert!(!exercise.task().unwrap().is_empty(), "{} doesn't say what to fix", exercise.name);
+        assert!(exercise.hints().unwrap().iter().all(|h| !h.is_empty()), "{} has an empty hint", exercise.name);
+    }
+    let _ = fs::remove_dir_all(&base);
+
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+// A module with a file in both styles, `foo.rs` and `foo/mod.rs`, which
+// `src/lib.rs` says is an error. That needs a crate with several files, so it
+// has a directory of its own in `./fixtures/`.
+#[test]
+fn module_file_conflict() {
+    let fixture = common::package_path("tests/fixtures/module_conflict");
+    let out_dir = std::env::temp_dir().join(format!("rust-module-example-conflict-{}", std::process::id()));
+
+    // Run from the fixture's directory, so the paths in the message are short.
+    let output = rustc(Path::new("src/lib.rs"), &out_dir)
+        .current_dir(&fixture)
+        .output()
+        .expect("running rustc");
+    let _ = fs::remove_dir_all(&out_dir);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains(r#"error[E0761]: file for module `foo` found at both "src/foo.rs" and "src/foo/mod.rs""#),
+            "{stderr}");
+}
@@ -0,0 +1,12 @@
+// From `name_resolution` in `src/lib.rs`: a module's items are private to it
+// unless marked `pub`, even from its parent.
+
+mod private_inner {
+    fn a() {}
+}
+
+pub fn from_parent() {
+    private_inner::a();
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+// From `src/visibility/private_in_public.rs`: a public interface can't
+// expose a private type where other modules would need to name it. For an
+// associated type in a trait impl, that's a hard error.
+
+pub mod shapes {
+    struct Secret;
+
+    pub trait Make {
+        type Output;
+    }
+
+    pub struct Maker;
+
+    impl Make for Maker {
+        type Output = Secret;
+    }
+}
+
+fn main() {}
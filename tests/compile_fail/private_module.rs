@@ -0,0 +1,15 @@
+// From `name_resolution` in `src/lib.rs`: a module is private by default, so
+// its parent can use it, but nothing further out can, even to reach its
+// `pub` items.
+
+mod name_resolution {
+    mod private_inner {
+        pub fn b() {}
+    }
+}
+
+pub fn from_root() {
+    name_resolution::private_inner::b();
+}
+
+fn main() {}
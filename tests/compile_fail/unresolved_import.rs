@@ -0,0 +1,17 @@
+// A `use` of a path that doesn't exist. In the 2018+ editions a `use` path
+// is relative to the current module, so a sibling module needs `super::` or
+// `crate::`, see `use_examples` in `src/lib.rs`.
+
+mod helpers {
+    pub fn help() {}
+}
+
+pub mod child {
+    use helpers::help;
+
+    pub fn call() {
+        help();
+    }
+}
+
+fn main() {}
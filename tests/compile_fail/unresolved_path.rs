@@ -0,0 +1,14 @@
+// The same mistake in an expression path rather than a `use`: `helpers`
+// isn't in scope in `child`, only in its parent.
+
+mod helpers {
+    pub fn help() {}
+}
+
+pub mod child {
+    pub fn call() {
+        helpers::help();
+    }
+}
+
+fn main() {}
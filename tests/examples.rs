@@ -0,0 +1,41 @@
+// The example registry, used from outside the crate as the binaries use it.
+
+// Declares `./common/mod.rs` as a child of this test crate's root module.
+mod common;
+
+use modules_demo::{registry, tour};
+
+#[test]
+fn every_example_is_found_by_name() {
+    for e in registry::EXAMPLES {
+        assert_eq!(common::example(e.name()).name(), e.name());
+    }
+}
+
+#[test]
+fn examples_describe_themselves() {
+    for e in registry::EXAMPLES {
+        assert!(!e.description().is_empty(), "{} has no description", e.name());
+        assert!(!e.tags().is_empty(), "{} has no tags", e.name());
+        assert!(!e.source().is_empty(), "{} has no source", e.name());
+    }
+}
+
+#[test]
+fn list_names_every_example() {
+    let mut out = Vec::new();
+    registry::write_list(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    for e in registry::EXAMPLES {
+        assert!(out.lines().any(|l| l.starts_with(&format!("{} ", e.name()))), "{}", e.name());
+    }
+}
+
+#[test]
+fn tour_visits_every_example_once() {
+    let mut toured: Vec<_> = tour::steps().iter().map(|e| e.name()).collect();
+    let mut listed: Vec<_> = registry::EXAMPLES.iter().map(|e| e.name()).collect();
+    toured.sort();
+    listed.sort();
+    assert_eq!(toured, listed);
+}
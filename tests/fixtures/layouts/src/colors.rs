@@ -0,0 +1,6 @@
+pub mod palette;
+
+pub enum Color {
+    Red,
+    Blue,
+}
@@ -0,0 +1,8 @@
+use super::Color;
+
+pub fn name(color: &Color) -> &'static str {
+    match color {
+        Color::Red => "red",
+        Color::Blue => "blue",
+    }
+}
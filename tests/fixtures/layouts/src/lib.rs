@@ -0,0 +1,32 @@
+// A crate with modules in both layout styles, for the tests of the
+// `refactor` commands in `tests/refactor.rs`. They change a copy of it and
+// check that it still compiles.
+
+// `./shapes/mod.rs`, and its children in `./shapes/`.
+pub mod shapes;
+
+// `./colors.rs`, and its children in `./colors/`.
+pub mod colors;
+
+// Not loaded by the usual rules, so left alone by `convert-style`.
+#[path = "special_place.rs"]
+pub mod special;
+
+// Inline modules, for `refactor extract`.
+pub mod text {
+    pub const BANNER: &str = "Shapes
+    and colors";
+
+    pub mod case {
+        pub fn upper(s: &str) -> String {
+            s.to_uppercase()
+        }
+    }
+}
+
+// Paths in a `use` group, for `refactor rename`.
+pub use shapes::{round::area, square::{self, corners::COUNT}};
+
+pub fn describe(shape: &shapes::Shape) -> String {
+    format!("{} {}", colors::palette::name(&shape.color), shapes::round::area(1.0))
+}
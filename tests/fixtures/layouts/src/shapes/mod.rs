@@ -0,0 +1,8 @@
+pub mod round;
+pub mod square;
+
+use crate::colors::Color;
+
+pub struct Shape {
+    pub color: Color,
+}
@@ -0,0 +1,7 @@
+pub(in crate::shapes::round) fn unit() -> f64 {
+    1.0
+}
+
+pub fn area(radius: f64) -> f64 {
+    3.0 * radius * radius * unit()
+}
@@ -0,0 +1 @@
+pub const COUNT: usize = 4;
@@ -0,0 +1 @@
+pub const SPECIAL: bool = true;
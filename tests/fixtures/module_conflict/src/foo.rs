@@ -0,0 +1 @@
+// One candidate for `mod foo;`, the 2018 edition style.
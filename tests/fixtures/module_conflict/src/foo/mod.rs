@@ -0,0 +1 @@
+// The other candidate for `mod foo;`, the original style.
@@ -0,0 +1,3 @@
+// A crate that doesn't compile: `mod foo;` could mean `./foo.rs` or
+// `./foo/mod.rs`, and both exist. See `tests/compile_fail.rs`.
+mod foo;
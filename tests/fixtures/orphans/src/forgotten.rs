@@ -0,0 +1 @@
+// Nothing declares `mod forgotten;`, so this file is never compiled.
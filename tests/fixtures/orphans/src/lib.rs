@@ -0,0 +1,4 @@
+// A crate with files that no `mod` declaration loads, for the
+// `analyze orphans` test in `tests/cli.rs`. It's never compiled.
+
+mod used;
@@ -0,0 +1 @@
+// Declared by `mod used;` in `lib.rs`.
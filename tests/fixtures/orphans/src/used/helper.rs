@@ -0,0 +1 @@
+// In the directory for `used`'s children, but `used.rs` has no `mod helper;`.
@@ -0,0 +1,24 @@
+// A small crate with one of each kind of module declaration, parsed by the
+// snapshot tests in `tests/snapshots.rs`. It's never compiled.
+
+pub mod shapes;
+
+mod util {
+    pub(crate) fn clamp(x: f64) -> f64 {
+        x.max(0.0)
+    }
+}
+
+#[path = "other_name.rs"]
+mod renamed;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamps() {}
+}
+
+pub use shapes::round::Circle;
+use util::clamp;
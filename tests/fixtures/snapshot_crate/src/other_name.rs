@@ -0,0 +1,2 @@
+// Loaded by `#[path = "other_name.rs"] mod renamed;` in `lib.rs`.
+pub const NAME: &str = "renamed";
@@ -0,0 +1,5 @@
+pub mod round;
+
+pub(crate) trait Area {
+    fn area(&self) -> f64;
+}
@@ -0,0 +1,9 @@
+pub struct Circle {
+    pub radius: f64,
+}
+
+impl super::Area for Circle {
+    fn area(&self) -> f64 {
+        3.14 * self.radius * self.radius
+    }
+}
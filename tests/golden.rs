@@ -0,0 +1,31 @@
+// Golden-output tests: the binary's output is compared with a file of
+// expected output in `./golden/`, so a change to what the examples print is
+// noticed. Update the files by running the tests with `UPDATE_SNAPSHOTS=1`.
+
+mod common;
+
+use common::{bin, stdout};
+
+// Replace the parts of the output that depend on how and where it was built.
+fn normalize(output: &str) -> String {
+    let mut output = output.to_string();
+    for family in ["unix", "windows", "wasm"] {
+        output = output.replace(&format!("platform family '{family}'"), "platform family '[PLATFORM]'");
+    }
+    for profile in ["debug", "release"] {
+        output = output.replace(&format!("Built with the {profile} config."), "Built with the [PROFILE] config.");
+    }
+    output
+}
+
+#[test]
+fn default_run() {
+    let assert = bin(&[]).assert().success();
+    common::assert_matches_file("tests/golden/default.stdout", &normalize(&stdout(assert.get_output())));
+}
+
+#[test]
+fn normalize_platform_and_profile() {
+    assert_eq!(normalize("on platform family 'windows'\nBuilt with the release config.\n"),
+               "on platform family '[PLATFORM]'\nBuilt with the [PROFILE] config.\n");
+}
@@ -0,0 +1,17 @@
+// The library target is renamed in `Cargo.toml` with `[lib] name`, so every
+// crate using it, including this test crate, writes `modules_demo::`, not the
+// package name `rust_module_example::`.
+
+use modules_demo::registry::Feature;
+
+#[test]
+fn paths_use_the_lib_name() {
+    assert!(std::any::type_name::<Feature>().starts_with("modules_demo::registry::"));
+}
+
+#[test]
+fn cargo_still_uses_the_package_name() {
+    // Cargo's own names are unaffected: the package, and so the main binary.
+    assert_eq!(env!("CARGO_PKG_NAME"), "rust-module-example");
+    assert!(env!("CARGO_BIN_EXE_rust-module-example").contains("rust-module-example"));
+}
@@ -0,0 +1,31 @@
+// Integration tests are separate crates that use the library like any other
+// crate would, so only exported items are visible here.
+
+// Every file directly in `tests/` is a test crate of its own. This one
+// doesn't need the helpers in `./common/mod.rs`, so it doesn't declare them.
+
+// `#[macro_export]` macros are at the root of the library crate, whatever
+// module defines them.
+use modules_demo::square;
+
+#[test]
+fn exported_macro_by_import() {
+    assert_eq!(square!(7), 49);
+}
+
+#[test]
+fn exported_macro_by_path() {
+    assert_eq!(modules_demo::square!(8), 64);
+}
+
+#[test]
+fn exported_macro_by_module_path() {
+    // Exported as `__shout!` at the root, and re-exported in its module.
+    assert_eq!(modules_demo::macros::reexport::shout!("hi"), "HI!");
+}
+
+#[test]
+fn dollar_crate_from_another_crate() {
+    // `labelled!` calls its helper through `$crate`, so it works from here.
+    assert_eq!(modules_demo::labelled!(6 * 7), "6 * 7 = 42");
+}
@@ -0,0 +1,29 @@
+// Parsing real crates with `modtree`, from outside the library.
+
+// Another test crate, with its own copy of `./common/mod.rs`.
+mod common;
+
+use modules_demo::modtree::{self, ModuleTree};
+
+#[test]
+fn this_crate_parses_cleanly() {
+    let tree = common::lib_tree();
+    assert!(tree.problems.is_empty(), "{:?}", tree.problems);
+}
+
+#[test]
+fn example_modules_are_in_the_tree() {
+    let tree = common::lib_tree();
+    for path in ["crate::a", "crate::visibility::crate_boundary", "crate::macros::attribute"] {
+        assert!(tree.find(path).is_some(), "{path} not found");
+    }
+}
+
+#[test]
+fn workspace_member_crate() {
+    // A crate root found from its package directory, as the `tree` command does.
+    let root = modtree::find_crate_root(&common::package_path("crates/core")).unwrap();
+    let tree = ModuleTree::load(&root).unwrap();
+    assert_eq!(tree.path_string(tree.root()), "crate");
+    assert!(tree.find("crate::tests").is_some());
+}
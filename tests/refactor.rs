@@ -0,0 +1,272 @@
+// Tests of the `refactor` commands on a copy of the crate in
+// `./fixtures/layouts/`, checking that it still compiles afterwards, which
+// is the point of a refactoring.
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::{bin, fixture_copy, rustc, stdout};
+
+// Run `refactor ARGS` on the crate in `dir`, which must succeed.
+fn refactor(dir: &Path, args: &[&str]) -> String {
+    let mut all = vec!["refactor"];
+    all.extend(args);
+    all.push(dir.to_str().unwrap());
+    stdout(bin(&all).assert().success().get_output())
+}
+
+fn assert_compiles(dir: &Path) {
+    let out_dir = dir.join("target");
+    let output = rustc(&dir.join("src/lib.rs"), &out_dir).output().expect("running rustc");
+    let _ = fs::remove_dir_all(&out_dir);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+// The `.rs` files under `dir/src`, relative to `dir`, sorted.
+fn files(dir: &Path) -> Vec<String> {
+    fn walk(dir: &Path, found: &mut Vec<PathBuf>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                walk(&path, found);
+            } else {
+                found.push(path);
+            }
+        }
+    }
+    let mut found = Vec::new();
+    walk(&dir.join("src"), &mut found);
+    let mut files: Vec<_> = found.iter()
+        .map(|path| path.strip_prefix(dir).unwrap().to_string_lossy().replace('\\', "/"))
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn extract() {
+    let dir = fixture_copy("layouts", "refactor-extract");
+    assert_eq!(refactor(&dir, &["extract", "crate::text"]), "Created src/text.rs\nUpdated src/lib.rs\n");
+    assert_compiles(&dir);
+    assert_eq!(refactor(&dir, &["extract", "crate::text::case", "--style", "mod-rs"]),
+               "Created src/text/case/mod.rs\nUpdated src/text.rs\n");
+    assert_compiles(&dir);
+
+    assert_eq!(fs::read_to_string(dir.join("src/text.rs")).unwrap(), "\
+pub const BANNER: &str = \"Shapes
+    and colors\";
+
+pub mod case;
+");
+    assert_eq!(fs::read_to_string(dir.join("src/text/case/mod.rs")).unwrap(), "\
+pub fn upper(s: &str) -> String {
+    s.to_uppercase()
+}
+");
+    assert!(fs::read_to_string(dir.join("src/lib.rs")).unwrap().contains("\npub mod text;\n"));
+
+    // The same modules, with the same items, just in different files.
+    bin(&["tree", "diff", "tests/fixtures/layouts", dir.to_str().unwrap()]).assert().stdout("No differences.\n");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn inline() {
+    let dir = fixture_copy("layouts", "refactor-inline");
+    let original = fs::read_to_string(dir.join("src/colors.rs")).unwrap();
+    assert_eq!(refactor(&dir, &["inline", "crate::colors"]), "Updated src/lib.rs\nDeleted src/colors.rs\n");
+    assert_compiles(&dir);
+    assert_eq!(refactor(&dir, &["inline", "crate::shapes::round"]),
+               "Updated src/shapes/mod.rs\nDeleted src/shapes/round.rs\n");
+    assert_compiles(&dir);
+    assert!(fs::read_to_string(dir.join("src/lib.rs")).unwrap().contains("\
+pub mod colors {
+    pub mod palette;
+
+    pub enum Color {
+"));
+    bin(&["tree", "diff", "tests/fixtures/layouts", dir.to_str().unwrap()]).assert().stdout("No differences.\n");
+
+    // And out again, to the same file.
+    refactor(&dir, &["extract", "crate::colors"]);
+    assert_eq!(fs::read_to_string(dir.join("src/colors.rs")).unwrap(), original);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn rename() {
+    let dir = fixture_copy("layouts", "refactor-rename");
+    assert_eq!(refactor(&dir, &["rename", "crate::shapes", "figures"]), "\
+Updated src/lib.rs
+Updated src/shapes/round.rs
+Moved src/shapes to src/figures
+");
+    assert_compiles(&dir);
+    assert_eq!(refactor(&dir, &["rename", "crate::figures::round", "circle"]), "\
+Updated src/lib.rs
+Updated src/figures/mod.rs
+Updated src/figures/round.rs
+Moved src/figures/round.rs to src/figures/circle.rs
+");
+    assert_compiles(&dir);
+    refactor(&dir, &["rename", "crate::colors", "hues"]);
+    refactor(&dir, &["rename", "crate::text::case", "letters"]);
+    assert_compiles(&dir);
+
+    let lib_rs = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+    assert!(lib_rs.contains("pub use figures::{circle::area, square::{self, corners::COUNT}};"), "{lib_rs}");
+    assert!(lib_rs.contains("hues::palette::name(&shape.color), figures::circle::area(1.0)"), "{lib_rs}");
+    assert_eq!(fs::read_to_string(dir.join("src/figures/mod.rs")).unwrap(), "\
+pub mod circle;
+pub mod square;
+
+use crate::hues::Color;
+
+pub struct Shape {
+    pub color: Color,
+}
+");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn convert_style() {
+    let dir = fixture_copy("layouts", "refactor-convert-style");
+    let original = files(&dir);
+    assert_compiles(&dir);
+
+    assert_eq!(refactor(&dir, &["convert-style", "crate", "--to", "2018"]), "\
+Moved src/shapes/mod.rs to src/shapes.rs
+Moved src/shapes/square/mod.rs to src/shapes/square.rs
+");
+    assert_compiles(&dir);
+    assert_eq!(files(&dir), [
+        "src/colors.rs", "src/colors/palette.rs", "src/lib.rs", "src/shapes.rs", "src/shapes/round.rs",
+        "src/shapes/square.rs", "src/shapes/square/corners.rs", "src/special_place.rs",
+    ]);
+
+    refactor(&dir, &["convert-style", "crate", "--to", "mod-rs"]);
+    assert_compiles(&dir);
+    assert_eq!(files(&dir), [
+        "src/colors/mod.rs", "src/colors/palette/mod.rs", "src/lib.rs", "src/shapes/mod.rs",
+        "src/shapes/round/mod.rs", "src/shapes/square/corners/mod.rs", "src/shapes/square/mod.rs",
+        "src/special_place.rs",
+    ]);
+
+    // Back to the mix it started with, one module at a time.
+    for module in ["crate::colors", "crate::colors::palette", "crate::shapes::round", "crate::shapes::square::corners"] {
+        refactor(&dir, &["convert-style", module, "--to", "2018"]);
+    }
+    assert_compiles(&dir);
+    assert_eq!(files(&dir), original);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn move_item() {
+    let dir = fixture_copy("layouts", "refactor-move-item");
+    assert_eq!(refactor(&dir, &["move-item", "crate::colors::palette::name", "--to", "crate::shapes"]),
+               "Updated src/shapes/mod.rs\nUpdated src/colors/palette.rs\n");
+    assert_compiles(&dir);
+    assert_eq!(refactor(&dir, &["move-item", "crate::shapes::round::unit", "--to", "crate::shapes"]), "\
+Updated src/shapes/mod.rs
+Updated src/shapes/round.rs
+`unit` is now private in `crate::shapes`, so it's visible everywhere it was before.
+");
+    assert_compiles(&dir);
+    refactor(&dir, &["move-item", "crate::text::case::upper", "--to", "crate::text"]);
+    assert_compiles(&dir);
+
+    assert_eq!(fs::read_to_string(dir.join("src/shapes/round.rs")).unwrap(), "\
+pub(in crate::shapes::round) use crate::shapes::unit;
+
+pub fn area(radius: f64) -> f64 {
+    3.0 * radius * radius * unit()
+}
+");
+    assert_eq!(fs::read_to_string(dir.join("src/shapes/mod.rs")).unwrap(), "\
+pub mod round;
+pub mod square;
+
+use crate::colors::Color;
+
+pub struct Shape {
+    pub color: Color,
+}
+
+pub fn name(color: &Color) -> &'static str {
+    match color {
+        Color::Red => \"red\",
+        Color::Blue => \"blue\",
+    }
+}
+
+fn unit() -> f64 {
+    1.0
+}
+");
+    // `describe` still calls `colors::palette::name`, through the `pub use`.
+    let lib_rs = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+    assert!(lib_rs.contains("colors::palette::name(&shape.color)"), "{lib_rs}");
+    assert!(lib_rs.contains("\
+    pub mod case {
+        pub use crate::text::upper;
+    }
+
+    pub fn upper(s: &str) -> String {
+        s.to_uppercase()
+    }
+}"), "{lib_rs}");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn flatten_reexports() {
+    let dir = fixture_copy("layouts", "refactor-flatten-reexports");
+    assert_eq!(refactor(&dir, &["flatten-reexports", "--facade", "api"]), "\
+Created src/api.rs
+Updated src/lib.rs
+`crate::api` re-exports 2 items from where they're defined:
+  pub use crate::shapes::round::area;
+  pub use crate::shapes::square::corners::COUNT;
+No other re-exports become redundant.
+");
+    assert_compiles(&dir);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn split() {
+    let dir = fixture_copy("layouts", "refactor-split");
+    assert_eq!(refactor(&dir, &["split", "crate::shapes::round", "--by", "kind"]),
+               "Created src/shapes/round/fns.rs\nUpdated src/shapes/round.rs\n");
+    assert_compiles(&dir);
+    refactor(&dir, &["split", "crate::colors"]);
+    refactor(&dir, &["split", "crate::shapes"]);
+    assert_compiles(&dir);
+
+    assert_eq!(fs::read_to_string(dir.join("src/shapes/round.rs")).unwrap(), "\
+mod fns;
+
+pub use fns::area;
+pub(in crate::shapes::round) use fns::unit;
+");
+    assert_eq!(fs::read_to_string(dir.join("src/colors.rs")).unwrap(), "\
+pub mod palette;
+
+mod types;
+
+pub use types::Color;
+");
+    assert_eq!(fs::read_to_string(dir.join("src/colors/types.rs")).unwrap(), "\
+use super::*;
+
+pub enum Color {
+    Red,
+    Blue,
+}
+");
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,51 @@
+// Snapshot tests of the `tree` command's output, with the `insta` crate: the
+// expected output of each case is kept in a file in `./snapshots/`, and any
+// difference fails the test.
+//
+// When a change is intended, such as adding a module to this crate, the
+// failing test leaves what it got in `NAME.snap.new`. Accept the changes
+// with `cargo insta review`, or without `cargo-insta` installed, rerun with
+//
+//     INSTA_UPDATE=always cargo test --test snapshots
+//
+// Then review the changes with `git diff` before committing them.
+
+mod common;
+
+use common::{bin, stdout};
+
+const FIXTURE: &str = "tests/fixtures/snapshot_crate";
+
+// Saved as `./snapshots/NAME.snap`, without the expression that made it,
+// which is the same for every case.
+fn assert_snapshot(name: &str, actual: &str) {
+    insta::with_settings!({ prepend_module_to_snapshot => false, omit_expression => true }, {
+        insta::assert_snapshot!(name, actual);
+    });
+}
+
+fn tree(args: &[&str]) -> String {
+    stdout(bin(&[&["tree"], args].concat()).assert().success().get_output())
+}
+
+// This crate's own modules, so adding, removing or moving one shows up here.
+#[test]
+fn this_crate_text() {
+    assert_snapshot("this_crate_text", &tree(&[]));
+}
+
+// The formats, on a small fixture crate that doesn't change.
+#[test]
+fn fixture_text() {
+    assert_snapshot("fixture_text", &tree(&[FIXTURE]));
+}
+
+#[test]
+fn fixture_json() {
+    assert_snapshot("fixture_json", &tree(&["--format", "json", FIXTURE]));
+}
+
+#[test]
+fn fixture_dot() {
+    assert_snapshot("fixture_dot", &tree(&["--format", "dot", FIXTURE]));
+}